@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ConfigReloadedEvent = { 
+/**
+ * Changed settings the session applies from the next turn, as dotted
+ * config keys (e.g. `mcp_servers.docs`), plus `AGENTS.md` when the
+ * instructions changed.
+ */
+changed: Array<string>, 
+/**
+ * Changed settings that only take effect in new sessions.
+ */
+restart_required: Array<string>, 
+/**
+ * Why the new files were rejected. The session keeps its previous
+ * settings.
+ */
+error?: string, };
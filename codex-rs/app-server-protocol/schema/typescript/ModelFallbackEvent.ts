@@ -0,0 +1,19 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ModelFallbackEvent = { from_model: string, to_model: string, 
+/**
+ * Provider serving `to_model`.
+ */
+model_provider_id: string, 
+/**
+ * Error that caused the switch. Absent when returning to the configured
+ * model after the cooldown.
+ */
+reason?: string, 
+/**
+ * Unix timestamp (seconds) after which the session returns to the
+ * configured model at the start of a turn.
+ */
+resume_primary_at?: number, };
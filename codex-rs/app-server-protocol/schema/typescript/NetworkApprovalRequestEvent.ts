@@ -0,0 +1,25 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type NetworkApprovalRequestEvent = { 
+/**
+ * Identifier to answer with `Op::NetworkApproval`.
+ */
+id: string, 
+/**
+ * Host the command tried to reach.
+ */
+host: string, 
+/**
+ * Why the proxy blocked the request (for example `not_allowed`).
+ */
+reason: string, 
+/**
+ * Protocol of the blocked request (`http`, `https_connect`, `socks5`, ...).
+ */
+protocol: string, 
+/**
+ * HTTP method of the blocked request, when known.
+ */
+method?: string, };
@@ -6,4 +6,9 @@ import type { ReviewTarget } from "./ReviewTarget";
 /**
  * Review request sent to the review session.
  */
-export type ReviewRequest = { target: ReviewTarget, user_facing_hint?: string, };
+export type ReviewRequest = { target: ReviewTarget, user_facing_hint?: string, 
+/**
+ * Restricts the review to changes under these files or directories,
+ * relative to the session's cwd. Empty reviews the whole target.
+ */
+paths?: Array<string>, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A per-thread limit configured under `[budget]` in `config.toml`.
+ */
+export type SessionBudgetKind = "turns" | "total_tokens" | "tool_calls" | "duration";
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionBudgetKind } from "./SessionBudgetKind";
+
+/**
+ * Consumption of one session budget. `limit` and `used` count turns,
+ * tokens, tool calls or seconds depending on `kind`.
+ */
+export type SessionBudgetUsage = { kind: SessionBudgetKind, limit: number, used: number, };
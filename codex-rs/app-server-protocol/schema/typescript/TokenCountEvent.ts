@@ -4,4 +4,10 @@
 import type { RateLimitSnapshot } from "./RateLimitSnapshot";
 import type { TokenUsageInfo } from "./TokenUsageInfo";
 
-export type TokenCountEvent = { info: TokenUsageInfo | null, rate_limits: RateLimitSnapshot | null, };
+export type TokenCountEvent = { info: TokenUsageInfo | null, rate_limits: RateLimitSnapshot | null, 
+/**
+ * Estimated cost of the thread so far in US dollars, from the configured
+ * model prices. Absent when any usage was on a model without a known
+ * price, so the estimate never undercounts.
+ */
+total_cost_usd?: number, };
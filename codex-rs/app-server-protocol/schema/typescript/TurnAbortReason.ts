@@ -2,4 +2,4 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type TurnAbortReason = "interrupted" | "replaced" | "review_ended";
+export type TurnAbortReason = "interrupted" | "replaced" | "review_ended" | "budget_exceeded";
@@ -1,6 +1,11 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionBudgetUsage } from "./SessionBudgetUsage";
 import type { TurnAbortReason } from "./TurnAbortReason";
 
-export type TurnAbortedEvent = { reason: TurnAbortReason, };
+export type TurnAbortedEvent = { reason: TurnAbortReason, 
+/**
+ * The budget that ran out, when `reason` is `budget_exceeded`.
+ */
+budget?: SessionBudgetUsage, };
@@ -43,6 +43,7 @@ export type { CollabWaitingBeginEvent } from "./CollabWaitingBeginEvent";
 export type { CollabWaitingEndEvent } from "./CollabWaitingEndEvent";
 export type { CollaborationMode } from "./CollaborationMode";
 export type { CollaborationModeMask } from "./CollaborationModeMask";
+export type { ConfigReloadedEvent } from "./ConfigReloadedEvent";
 export type { ContentItem } from "./ContentItem";
 export type { ContextCompactedEvent } from "./ContextCompactedEvent";
 export type { ContextCompactionItem } from "./ContextCompactionItem";
@@ -122,7 +123,9 @@ export type { McpToolCallBeginEvent } from "./McpToolCallBeginEvent";
 export type { McpToolCallEndEvent } from "./McpToolCallEndEvent";
 export type { MessagePhase } from "./MessagePhase";
 export type { ModeKind } from "./ModeKind";
+export type { ModelFallbackEvent } from "./ModelFallbackEvent";
 export type { NetworkAccess } from "./NetworkAccess";
+export type { NetworkApprovalRequestEvent } from "./NetworkApprovalRequestEvent";
 export type { NewConversationParams } from "./NewConversationParams";
 export type { NewConversationResponse } from "./NewConversationResponse";
 export type { ParsedCommand } from "./ParsedCommand";
@@ -173,6 +176,8 @@ export type { SendUserTurnParams } from "./SendUserTurnParams";
 export type { SendUserTurnResponse } from "./SendUserTurnResponse";
 export type { ServerNotification } from "./ServerNotification";
 export type { ServerRequest } from "./ServerRequest";
+export type { SessionBudgetKind } from "./SessionBudgetKind";
+export type { SessionBudgetUsage } from "./SessionBudgetUsage";
 export type { SessionConfiguredEvent } from "./SessionConfiguredEvent";
 export type { SessionConfiguredNotification } from "./SessionConfiguredNotification";
 export type { SessionSource } from "./SessionSource";
@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Sent when `config.toml` or `AGENTS.md` changed on disk while the thread
+ * was running. Accepted changes apply from the next turn.
+ */
+export type ConfigReloadedNotification = { threadId: string, 
+/**
+ * Changed settings the thread applies, e.g. `mcp_servers.docs` or `AGENTS.md`.
+ */
+changed: Array<string>, 
+/**
+ * Changed settings that only take effect in new threads.
+ */
+restartRequired: Array<string>, 
+/**
+ * Why the new files were rejected. The previous settings stay in effect.
+ */
+error?: string, };
@@ -0,0 +1,19 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ModelFallbackNotification = { threadId: string, turnId: string, fromModel: string, toModel: string, 
+/**
+ * Provider serving `to_model`.
+ */
+modelProviderId: string, 
+/**
+ * Error that caused the switch. Absent when the thread returns to its
+ * configured model.
+ */
+reason?: string, 
+/**
+ * Unix timestamp (seconds) after which the thread returns to its
+ * configured model.
+ */
+resumePrimaryAt?: number, };
@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type NetworkAccessRequestApprovalParams = { threadId: string, 
+/**
+ * Host a sandboxed command tried to reach.
+ */
+host: string, 
+/**
+ * Why the network proxy blocked the request (for example `not_allowed`).
+ */
+reason: string, 
+/**
+ * Protocol of the blocked request (`http`, `https_connect`, `socks5`, ...).
+ */
+protocol: string, 
+/**
+ * HTTP method of the blocked request, when known.
+ */
+method?: string | null, };
@@ -0,0 +1,11 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RequestId } from "../RequestId";
+
+export type ServerRequestResolvedNotification = { threadId: string, 
+/**
+ * Id of the approval or input request that a client answered. Other clients
+ * attached to the thread should dismiss their prompt for it.
+ */
+requestId: RequestId, };
@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadAttachParams = { threadId: string, 
+/**
+ * When true, this connection may answer the thread's approval requests.
+ */
+canApprove: boolean, 
+/**
+ * When true, this connection may start, steer and interrupt turns on the thread.
+ */
+canSteer: boolean, 
+/**
+ * Token from `thread/grant` on a connection holding the requested rights.
+ * Required for `canApprove` and `canSteer`.
+ */
+grant?: string | null, };
@@ -0,0 +1,19 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Thread } from "./Thread";
+
+export type ThreadAttachResponse = { 
+/**
+ * The thread with every turn recorded so far. Live notifications for the
+ * thread follow this response.
+ */
+thread: Thread, 
+/**
+ * Whether this connection may answer approval requests for the thread.
+ */
+canApprove: boolean, 
+/**
+ * Whether this connection may start, steer and interrupt turns.
+ */
+canSteer: boolean, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadGrantParams = { threadId: string, 
+/**
+ * Let the attaching connection answer the thread's approval requests.
+ */
+canApprove: boolean, 
+/**
+ * Let the attaching connection start, steer and interrupt turns.
+ */
+canSteer: boolean, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+
+export type ThreadSearchMatch = { 
+/**
+ * Id of the turn containing the match, as returned by `thread/read`.
+ */
+turnId: string, kind: ThreadSearchMatchKind, 
+/**
+ * Excerpt of the matching text around the search terms.
+ */
+snippet: string, };
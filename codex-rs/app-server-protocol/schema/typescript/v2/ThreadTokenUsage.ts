@@ -3,4 +3,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { TokenUsageBreakdown } from "./TokenUsageBreakdown";
 
-export type ThreadTokenUsage = { total: TokenUsageBreakdown, last: TokenUsageBreakdown, modelContextWindow: number | null, };
+export type ThreadTokenUsage = { total: TokenUsageBreakdown, last: TokenUsageBreakdown, modelContextWindow: number | null, 
+/**
+ * Estimated cost of the thread so far in US dollars; null when any
+ * model used has no known price.
+ */
+totalCostUsd: number | null, };
@@ -40,6 +40,7 @@ export type { ConfigLayerMetadata } from "./ConfigLayerMetadata";
 export type { ConfigLayerSource } from "./ConfigLayerSource";
 export type { ConfigReadParams } from "./ConfigReadParams";
 export type { ConfigReadResponse } from "./ConfigReadResponse";
+export type { ConfigReloadedNotification } from "./ConfigReloadedNotification";
 export type { ConfigRequirements } from "./ConfigRequirements";
 export type { ConfigRequirementsReadResponse } from "./ConfigRequirementsReadResponse";
 export type { ConfigValueWriteParams } from "./ConfigValueWriteParams";
@@ -88,9 +89,13 @@ export type { McpToolCallResult } from "./McpToolCallResult";
 export type { McpToolCallStatus } from "./McpToolCallStatus";
 export type { MergeStrategy } from "./MergeStrategy";
 export type { Model } from "./Model";
+export type { ModelFallbackNotification } from "./ModelFallbackNotification";
 export type { ModelListParams } from "./ModelListParams";
 export type { ModelListResponse } from "./ModelListResponse";
 export type { NetworkAccess } from "./NetworkAccess";
+export type { NetworkAccessApprovalDecision } from "./NetworkAccessApprovalDecision";
+export type { NetworkAccessRequestApprovalParams } from "./NetworkAccessRequestApprovalParams";
+export type { NetworkAccessRequestApprovalResponse } from "./NetworkAccessRequestApprovalResponse";
 export type { NetworkRequirements } from "./NetworkRequirements";
 export type { OverriddenMetadata } from "./OverriddenMetadata";
 export type { PatchApplyStatus } from "./PatchApplyStatus";
@@ -113,6 +118,7 @@ export type { ReviewTarget } from "./ReviewTarget";
 export type { SandboxMode } from "./SandboxMode";
 export type { SandboxPolicy } from "./SandboxPolicy";
 export type { SandboxWorkspaceWrite } from "./SandboxWorkspaceWrite";
+export type { ServerRequestResolvedNotification } from "./ServerRequestResolvedNotification";
 export type { SessionSource } from "./SessionSource";
 export type { SkillDependencies } from "./SkillDependencies";
 export type { SkillErrorInfo } from "./SkillErrorInfo";
@@ -136,10 +142,16 @@ export type { TextRange } from "./TextRange";
 export type { Thread } from "./Thread";
 export type { ThreadArchiveParams } from "./ThreadArchiveParams";
 export type { ThreadArchiveResponse } from "./ThreadArchiveResponse";
+export type { ThreadAttachParams } from "./ThreadAttachParams";
+export type { ThreadAttachResponse } from "./ThreadAttachResponse";
 export type { ThreadCompactStartParams } from "./ThreadCompactStartParams";
 export type { ThreadCompactStartResponse } from "./ThreadCompactStartResponse";
+export type { ThreadDetachParams } from "./ThreadDetachParams";
+export type { ThreadDetachResponse } from "./ThreadDetachResponse";
 export type { ThreadForkParams } from "./ThreadForkParams";
 export type { ThreadForkResponse } from "./ThreadForkResponse";
+export type { ThreadGrantParams } from "./ThreadGrantParams";
+export type { ThreadGrantResponse } from "./ThreadGrantResponse";
 export type { ThreadItem } from "./ThreadItem";
 export type { ThreadListParams } from "./ThreadListParams";
 export type { ThreadListResponse } from "./ThreadListResponse";
@@ -152,6 +164,11 @@ export type { ThreadResumeParams } from "./ThreadResumeParams";
 export type { ThreadResumeResponse } from "./ThreadResumeResponse";
 export type { ThreadRollbackParams } from "./ThreadRollbackParams";
 export type { ThreadRollbackResponse } from "./ThreadRollbackResponse";
+export type { ThreadSearchMatch } from "./ThreadSearchMatch";
+export type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+export type { ThreadSearchParams } from "./ThreadSearchParams";
+export type { ThreadSearchResponse } from "./ThreadSearchResponse";
+export type { ThreadSearchResult } from "./ThreadSearchResult";
 export type { ThreadSetNameParams } from "./ThreadSetNameParams";
 export type { ThreadSetNameResponse } from "./ThreadSetNameResponse";
 export type { ThreadSortKey } from "./ThreadSortKey";
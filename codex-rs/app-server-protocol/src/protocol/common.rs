@@ -222,6 +222,10 @@ client_request_definitions! {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
     },
+    ThreadSearch => "thread/search" {
+        params: v2::ThreadSearchParams,
+        response: v2::ThreadSearchResponse,
+    },
     ThreadLoadedList => "thread/loaded/list" {
         params: v2::ThreadLoadedListParams,
         response: v2::ThreadLoadedListResponse,
@@ -230,6 +234,18 @@ client_request_definitions! {
         params: v2::ThreadReadParams,
         response: v2::ThreadReadResponse,
     },
+    ThreadAttach => "thread/attach" {
+        params: v2::ThreadAttachParams,
+        response: v2::ThreadAttachResponse,
+    },
+    ThreadDetach => "thread/detach" {
+        params: v2::ThreadDetachParams,
+        response: v2::ThreadDetachResponse,
+    },
+    ThreadGrant => "thread/grant" {
+        params: v2::ThreadGrantParams,
+        response: v2::ThreadGrantResponse,
+    },
     SkillsList => "skills/list" {
         params: v2::SkillsListParams,
         response: v2::SkillsListResponse,
@@ -640,6 +656,13 @@ server_request_definitions! {
         response: v2::FileChangeRequestApprovalResponse,
     },
 
+    /// Sent when the network proxy blocked a host that a sandboxed command tried to reach.
+    /// Answering with an allow decision lets later connections to the host through.
+    NetworkAccessRequestApproval => "item/networkAccess/requestApproval" {
+        params: v2::NetworkAccessRequestApprovalParams,
+        response: v2::NetworkAccessRequestApprovalResponse,
+    },
+
     /// EXPERIMENTAL - Request input from the user for a tool call.
     ToolRequestUserInput => "item/tool/requestUserInput" {
         params: v2::ToolRequestUserInputParams,
@@ -703,6 +726,7 @@ server_notification_definitions! {
     ThreadStarted => "thread/started" (v2::ThreadStartedNotification),
     ThreadNameUpdated => "thread/name/updated" (v2::ThreadNameUpdatedNotification),
     ThreadTokenUsageUpdated => "thread/tokenUsage/updated" (v2::ThreadTokenUsageUpdatedNotification),
+    ServerRequestResolved => "serverRequest/resolved" (v2::ServerRequestResolvedNotification),
     TurnStarted => "turn/started" (v2::TurnStartedNotification),
     TurnCompleted => "turn/completed" (v2::TurnCompletedNotification),
     TurnDiffUpdated => "turn/diff/updated" (v2::TurnDiffUpdatedNotification),
@@ -728,6 +752,8 @@ server_notification_definitions! {
     ContextCompacted => "thread/compacted" (v2::ContextCompactedNotification),
     DeprecationNotice => "deprecationNotice" (v2::DeprecationNoticeNotification),
     ConfigWarning => "configWarning" (v2::ConfigWarningNotification),
+    ConfigReloaded => "config/reloaded" (v2::ConfigReloadedNotification),
+    ModelFallback => "model/fallback" (v2::ModelFallbackNotification),
 
     /// Notifies the user of world-writable directories on Windows, which cannot be protected by the sandbox.
     WindowsWorldWritableWarning => "windows/worldWritableWarning" (v2::WindowsWorldWritableWarningNotification),
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::RequestId;
 use crate::protocol::common::AuthMode;
 use codex_experimental_api_macros::ExperimentalApi;
 use codex_protocol::account::PlanType;
@@ -638,6 +639,20 @@ pub enum FileChangeApprovalDecision {
     Cancel,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum NetworkAccessApprovalDecision {
+    /// Allow the next connection to the host.
+    AllowOnce,
+    /// Allow the host until the network proxy restarts.
+    AllowForSession,
+    /// Allow the host and add it to `[network] allowed_domains` in config.toml.
+    AllowAlways,
+    /// Keep the host blocked and do not ask again this session.
+    Decline,
+}
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1627,6 +1642,67 @@ pub struct ThreadListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchParams {
+    /// Words to search for in messages, commands and edited file paths; every
+    /// word must match and the last one also matches as a prefix.
+    pub query: String,
+    /// Optional maximum number of threads; defaults to a reasonable server-side value.
+    #[ts(optional = nullable)]
+    pub limit: Option<u32>,
+    /// Optional provider filter; when set, only sessions recorded under these
+    /// providers are searched. When present but empty, includes all providers.
+    #[ts(optional = nullable)]
+    pub model_providers: Option<Vec<String>>,
+    /// Optional source filter; when set, only sessions from these source kinds
+    /// are searched. When omitted or empty, defaults to interactive sources.
+    #[ts(optional = nullable)]
+    pub source_kinds: Option<Vec<ThreadSourceKind>>,
+    /// Optional archived filter; when set to true, only archived threads are searched.
+    #[ts(optional = nullable)]
+    pub archived: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResponse {
+    /// Matching threads, most relevant first.
+    pub data: Vec<ThreadSearchResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResult {
+    pub thread: Thread,
+    /// The best matches within the thread, most relevant first.
+    pub matches: Vec<ThreadSearchMatch>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchMatch {
+    /// Id of the turn containing the match, as returned by `thread/read`.
+    pub turn_id: String,
+    pub kind: ThreadSearchMatchKind,
+    /// Excerpt of the matching text around the search terms.
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ThreadSearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    File,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1667,6 +1743,70 @@ pub struct ThreadReadResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadAttachParams {
+    /// Id of a thread currently loaded by this server.
+    pub thread_id: String,
+    /// When true, this connection may answer the thread's approval requests.
+    #[serde(default)]
+    pub can_approve: bool,
+    /// When true, this connection may start, steer and interrupt turns on the thread.
+    #[serde(default)]
+    pub can_steer: bool,
+    /// Token from `thread/grant` on a connection holding the requested rights.
+    /// Required for `canApprove` and `canSteer`.
+    #[ts(optional = nullable)]
+    pub grant: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadAttachResponse {
+    /// The thread with every turn recorded so far. Live notifications for the
+    /// thread follow this response.
+    pub thread: Thread,
+    /// Whether this connection may answer approval requests for the thread.
+    pub can_approve: bool,
+    /// Whether this connection may start, steer and interrupt turns.
+    pub can_steer: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadDetachParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadDetachResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadGrantParams {
+    pub thread_id: String,
+    /// Let the attaching connection answer the thread's approval requests.
+    #[serde(default)]
+    pub can_approve: bool,
+    /// Let the attaching connection start, steer and interrupt turns.
+    #[serde(default)]
+    pub can_steer: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadGrantResponse {
+    /// Single-use token to pass as `grant` to `thread/attach`.
+    pub grant: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1966,6 +2106,10 @@ pub struct ThreadTokenUsage {
     // TODO(aibrahim): make this not optional
     #[ts(type = "number | null")]
     pub model_context_window: Option<i64>,
+    /// Estimated cost of the thread so far in US dollars; null when any
+    /// model used has no known price.
+    #[serde(default)]
+    pub total_cost_usd: Option<f64>,
 }
 
 impl From<CoreTokenUsageInfo> for ThreadTokenUsage {
@@ -1974,6 +2118,7 @@ impl From<CoreTokenUsageInfo> for ThreadTokenUsage {
             total: value.total_token_usage.into(),
             last: value.last_token_usage.into(),
             model_context_window: value.model_context_window,
+            total_cost_usd: None,
         }
     }
 }
@@ -2109,6 +2254,12 @@ pub struct ReviewStartParams {
     #[serde(default)]
     #[ts(optional = nullable)]
     pub delivery: Option<ReviewDelivery>,
+
+    /// Restrict the review to changes under these files or directories,
+    /// relative to the thread's cwd.
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub paths: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -2144,6 +2295,18 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes between two revisions, e.g. a pull request's merge
+    /// base and head.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Range { from: String, to: String },
+
+    /// Review a patch file without applying it. Relative paths are resolved
+    /// against the thread's cwd.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions, equivalent to the old free-form prompt.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -2663,6 +2826,55 @@ pub struct ThreadNameUpdatedNotification {
     pub thread_name: Option<String>,
 }
 
+/// Sent when `config.toml` or `AGENTS.md` changed on disk while the thread
+/// was running. Accepted changes apply from the next turn.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigReloadedNotification {
+    pub thread_id: String,
+    /// Changed settings the thread applies, e.g. `mcp_servers.docs` or `AGENTS.md`.
+    pub changed: Vec<String>,
+    /// Changed settings that only take effect in new threads.
+    pub restart_required: Vec<String>,
+    /// Why the new files were rejected. The previous settings stay in effect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ModelFallbackNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub from_model: String,
+    pub to_model: String,
+    /// Provider serving `to_model`.
+    pub model_provider_id: String,
+    /// Error that caused the switch. Absent when the thread returns to its
+    /// configured model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub reason: Option<String>,
+    /// Unix timestamp (seconds) after which the thread returns to its
+    /// configured model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "number")]
+    pub resume_primary_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ServerRequestResolvedNotification {
+    pub thread_id: String,
+    /// Id of the approval or input request that a client answered. Other
+    /// clients attached to the thread should dismiss their prompt for it.
+    pub request_id: RequestId,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2956,6 +3168,28 @@ pub struct FileChangeRequestApprovalResponse {
     pub decision: FileChangeApprovalDecision,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct NetworkAccessRequestApprovalParams {
+    pub thread_id: String,
+    /// Host a sandboxed command tried to reach.
+    pub host: String,
+    /// Why the network proxy blocked the request (for example `not_allowed`).
+    pub reason: String,
+    /// Protocol of the blocked request (`http`, `https_connect`, `socks5`, ...).
+    pub protocol: String,
+    /// HTTP method of the blocked request, when known.
+    #[ts(optional = nullable)]
+    pub method: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[ts(export_to = "v2/")]
+pub struct NetworkAccessRequestApprovalResponse {
+    pub decision: NetworkAccessApprovalDecision,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -23,6 +23,7 @@ use codex_app_server_protocol::CommandExecutionOutputDeltaNotification;
 use codex_app_server_protocol::CommandExecutionRequestApprovalParams;
 use codex_app_server_protocol::CommandExecutionRequestApprovalResponse;
 use codex_app_server_protocol::CommandExecutionStatus;
+use codex_app_server_protocol::ConfigReloadedNotification;
 use codex_app_server_protocol::ContextCompactedNotification;
 use codex_app_server_protocol::DeprecationNoticeNotification;
 use codex_app_server_protocol::DynamicToolCallParams;
@@ -42,6 +43,10 @@ use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::McpToolCallError;
 use codex_app_server_protocol::McpToolCallResult;
 use codex_app_server_protocol::McpToolCallStatus;
+use codex_app_server_protocol::ModelFallbackNotification;
+use codex_app_server_protocol::NetworkAccessApprovalDecision;
+use codex_app_server_protocol::NetworkAccessRequestApprovalParams;
+use codex_app_server_protocol::NetworkAccessRequestApprovalResponse;
 use codex_app_server_protocol::PatchApplyStatus;
 use codex_app_server_protocol::PatchChangeKind as V2PatchChangeKind;
 use codex_app_server_protocol::PlanDeltaNotification;
@@ -81,8 +86,10 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::FileChange as CoreFileChange;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::NetworkApprovalDecision;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SessionBudgetUsage;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::review_format::format_review_findings_block;
@@ -326,6 +333,31 @@ pub(crate) async fn apply_bespoke_event_handling(
                 }
             }
         }
+        EventMsg::NetworkApprovalRequest(request) => {
+            if matches!(api_version, ApiVersion::V2) {
+                let params = NetworkAccessRequestApprovalParams {
+                    thread_id: conversation_id.to_string(),
+                    host: request.host,
+                    reason: request.reason,
+                    protocol: request.protocol,
+                    method: request.method,
+                };
+                let rx = outgoing
+                    .send_request(ServerRequestPayload::NetworkAccessRequestApproval(params))
+                    .await;
+                tokio::spawn(async move {
+                    on_network_access_request_approval_response(request.id, rx, conversation).await;
+                });
+            } else if let Err(err) = conversation
+                .submit(Op::NetworkApproval {
+                    id: request.id,
+                    decision: NetworkApprovalDecision::Deny,
+                })
+                .await
+            {
+                error!("failed to submit NetworkApproval: {err}");
+            }
+        }
         EventMsg::DynamicToolCallRequest(request) => {
             if matches!(api_version, ApiVersion::V2) {
                 let call_id = request.call_id;
@@ -1069,13 +1101,27 @@ pub(crate) async fn apply_bespoke_event_handling(
                 }
             }
 
-            handle_turn_interrupted(
-                conversation_id,
-                event_turn_id,
-                &outgoing,
-                &turn_summary_store,
-            )
-            .await;
+            match turn_aborted_event.budget {
+                Some(budget) => {
+                    handle_turn_budget_exceeded(
+                        conversation_id,
+                        event_turn_id,
+                        budget,
+                        &outgoing,
+                        &turn_summary_store,
+                    )
+                    .await;
+                }
+                None => {
+                    handle_turn_interrupted(
+                        conversation_id,
+                        event_turn_id,
+                        &outgoing,
+                        &turn_summary_store,
+                    )
+                    .await;
+                }
+            }
         }
         EventMsg::ThreadRolledBack(_rollback_event) => {
             let pending = {
@@ -1148,6 +1194,35 @@ pub(crate) async fn apply_bespoke_event_handling(
                     .await;
             }
         }
+        EventMsg::ConfigReloaded(reload_event) => {
+            if let ApiVersion::V2 = api_version {
+                let notification = ConfigReloadedNotification {
+                    thread_id: conversation_id.to_string(),
+                    changed: reload_event.changed,
+                    restart_required: reload_event.restart_required,
+                    error: reload_event.error,
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::ConfigReloaded(notification))
+                    .await;
+            }
+        }
+        EventMsg::ModelFallback(fallback_event) => {
+            if let ApiVersion::V2 = api_version {
+                let notification = ModelFallbackNotification {
+                    thread_id: conversation_id.to_string(),
+                    turn_id: event_turn_id.clone(),
+                    from_model: fallback_event.from_model,
+                    to_model: fallback_event.to_model,
+                    model_provider_id: fallback_event.model_provider_id,
+                    reason: fallback_event.reason,
+                    resume_primary_at: fallback_event.resume_primary_at,
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::ModelFallback(notification))
+                    .await;
+            }
+        }
         EventMsg::TurnDiff(turn_diff_event) => {
             handle_turn_diff(
                 conversation_id,
@@ -1365,6 +1440,30 @@ async fn handle_turn_interrupted(
     .await;
 }
 
+async fn handle_turn_budget_exceeded(
+    conversation_id: ThreadId,
+    event_turn_id: String,
+    budget: SessionBudgetUsage,
+    outgoing: &OutgoingMessageSender,
+    turn_summary_store: &TurnSummaryStore,
+) {
+    find_and_remove_turn_summary(conversation_id, turn_summary_store).await;
+
+    let error = TurnError {
+        message: format!("Turn stopped: session {budget}"),
+        codex_error_info: None,
+        additional_details: None,
+    };
+    emit_turn_completed_with_status(
+        conversation_id,
+        event_turn_id,
+        TurnStatus::Failed,
+        Some(error),
+        outgoing,
+    )
+    .await;
+}
+
 async fn handle_thread_rollback_failed(
     conversation_id: ThreadId,
     message: String,
@@ -1396,12 +1495,19 @@ async fn handle_token_count_event(
     token_count_event: TokenCountEvent,
     outgoing: &OutgoingMessageSender,
 ) {
-    let TokenCountEvent { info, rate_limits } = token_count_event;
-    if let Some(token_usage) = info.map(ThreadTokenUsage::from) {
+    let TokenCountEvent {
+        info,
+        rate_limits,
+        total_cost_usd,
+    } = token_count_event;
+    if let Some(info) = info {
         let notification = ThreadTokenUsageUpdatedNotification {
             thread_id: conversation_id.to_string(),
             turn_id,
-            token_usage,
+            token_usage: ThreadTokenUsage {
+                total_cost_usd,
+                ..ThreadTokenUsage::from(info)
+            },
         };
         outgoing
             .send_server_notification(ServerNotification::ThreadTokenUsageUpdated(notification))
@@ -1505,6 +1611,42 @@ async fn on_exec_approval_response(
     }
 }
 
+async fn on_network_access_request_approval_response(
+    approval_id: String,
+    receiver: oneshot::Receiver<JsonValue>,
+    conversation: Arc<CodexThread>,
+) {
+    let decision = match receiver.await {
+        Ok(value) => serde_json::from_value::<NetworkAccessRequestApprovalResponse>(value)
+            .map(|response| match response.decision {
+                NetworkAccessApprovalDecision::AllowOnce => NetworkApprovalDecision::AllowOnce,
+                NetworkAccessApprovalDecision::AllowForSession => {
+                    NetworkApprovalDecision::AllowForSession
+                }
+                NetworkAccessApprovalDecision::AllowAlways => NetworkApprovalDecision::AllowAlways,
+                NetworkAccessApprovalDecision::Decline => NetworkApprovalDecision::Deny,
+            })
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize NetworkAccessRequestApprovalResponse: {err}");
+                NetworkApprovalDecision::Deny
+            }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            NetworkApprovalDecision::Deny
+        }
+    };
+
+    if let Err(err) = conversation
+        .submit(Op::NetworkApproval {
+            id: approval_id,
+            decision,
+        })
+        .await
+    {
+        error!("failed to submit NetworkApproval: {err}");
+    }
+}
+
 async fn on_request_user_input_response(
     event_turn_id: String,
     receiver: oneshot::Receiver<JsonValue>,
@@ -1933,6 +2075,9 @@ mod tests {
             OutgoingEnvelope::ToConnection { connection_id, .. } => {
                 bail!("unexpected targeted message for connection {connection_id:?}")
             }
+            OutgoingEnvelope::ToThread { thread_id, .. } => {
+                bail!("unexpected thread-scoped message for thread {thread_id}")
+            }
         }
     }
 
@@ -2227,6 +2372,7 @@ mod tests {
             TokenCountEvent {
                 info: Some(info),
                 rate_limits: Some(rate_limits),
+                total_cost_usd: Some(0.5),
             },
             &outgoing,
         )
@@ -2244,6 +2390,7 @@ mod tests {
                 assert_eq!(usage.total.cached_input_tokens, 25);
                 assert_eq!(usage.last.output_tokens, 7);
                 assert_eq!(usage.model_context_window, Some(4096));
+                assert_eq!(usage.total_cost_usd, Some(0.5));
             }
             other => bail!("unexpected notification: {other:?}"),
         }
@@ -2274,6 +2421,7 @@ mod tests {
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                total_cost_usd: None,
             },
             &outgoing,
         )
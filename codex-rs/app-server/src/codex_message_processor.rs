@@ -7,6 +7,9 @@ use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::ConnectionRequestId;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
+use crate::thread_subscriptions::ThreadAction;
+use crate::thread_subscriptions::ThreadRights;
+use crate::thread_subscriptions::ThreadSubscriptions;
 use chrono::DateTime;
 use chrono::SecondsFormat;
 use chrono::Utc;
@@ -109,10 +112,16 @@ use codex_app_server_protocol::SkillsRemoteWriteResponse;
 use codex_app_server_protocol::Thread;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
+use codex_app_server_protocol::ThreadAttachParams;
+use codex_app_server_protocol::ThreadAttachResponse;
 use codex_app_server_protocol::ThreadCompactStartParams;
 use codex_app_server_protocol::ThreadCompactStartResponse;
+use codex_app_server_protocol::ThreadDetachParams;
+use codex_app_server_protocol::ThreadDetachResponse;
 use codex_app_server_protocol::ThreadForkParams;
 use codex_app_server_protocol::ThreadForkResponse;
+use codex_app_server_protocol::ThreadGrantParams;
+use codex_app_server_protocol::ThreadGrantResponse;
 use codex_app_server_protocol::ThreadItem;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadListResponse;
@@ -123,6 +132,11 @@ use codex_app_server_protocol::ThreadReadResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchMatch;
+use codex_app_server_protocol::ThreadSearchMatchKind;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadSearchResult;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
 use codex_app_server_protocol::ThreadSortKey;
@@ -198,7 +212,9 @@ use codex_core::sandboxing::SandboxPermissions;
 use codex_core::skills::remote::download_remote_skill;
 use codex_core::skills::remote::list_remote_skills;
 use codex_core::state_db::StateDbHandle;
+use codex_core::state_db::ThreadSearchMatchKind as CoreThreadSearchMatchKind;
 use codex_core::state_db::open_if_present;
+use codex_core::state_db::search_threads_db;
 use codex_core::token_data::parse_id_token;
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_feedback::CodexFeedback;
@@ -266,6 +282,7 @@ pub(crate) type TurnSummaryStore = Arc<Mutex<HashMap<ThreadId, TurnSummary>>>;
 
 const THREAD_LIST_DEFAULT_LIMIT: usize = 25;
 const THREAD_LIST_MAX_LIMIT: usize = 100;
+const THREAD_SEARCH_DEFAULT_LIMIT: usize = 20;
 
 // Duration before a ChatGPT login attempt is abandoned.
 const LOGIN_CHATGPT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
@@ -304,6 +321,7 @@ pub(crate) struct CodexMessageProcessor {
     turn_summary_store: TurnSummaryStore,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     feedback: CodexFeedback,
+    thread_subscriptions: ThreadSubscriptions,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -321,6 +339,7 @@ pub(crate) struct CodexMessageProcessorArgs {
     pub(crate) cli_overrides: Vec<(String, TomlValue)>,
     pub(crate) cloud_requirements: Arc<RwLock<CloudRequirementsLoader>>,
     pub(crate) feedback: CodexFeedback,
+    pub(crate) thread_subscriptions: ThreadSubscriptions,
 }
 
 impl CodexMessageProcessor {
@@ -347,6 +366,29 @@ impl CodexMessageProcessor {
 
         Ok((thread_id, thread))
     }
+
+    /// Like [`Self::load_thread`], but rejects connections attached to the
+    /// thread without the right to start, steer or interrupt its turns.
+    async fn load_thread_for_steering(
+        &self,
+        connection_id: ConnectionId,
+        thread_id: &str,
+    ) -> Result<(ThreadId, Arc<CodexThread>), JSONRPCErrorError> {
+        let (thread_id, thread) = self.load_thread(thread_id).await?;
+        if !self
+            .thread_subscriptions
+            .permits(thread_id, connection_id, ThreadAction::Steer)
+            .await
+        {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("connection is not allowed to steer thread {thread_id}"),
+                data: None,
+            });
+        }
+        Ok((thread_id, thread))
+    }
+
     pub fn new(args: CodexMessageProcessorArgs) -> Self {
         let CodexMessageProcessorArgs {
             auth_manager,
@@ -357,6 +399,7 @@ impl CodexMessageProcessor {
             cli_overrides,
             cloud_requirements,
             feedback,
+            thread_subscriptions,
         } = args;
         Self {
             auth_manager,
@@ -374,6 +417,7 @@ impl CodexMessageProcessor {
             turn_summary_store: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
             feedback,
+            thread_subscriptions,
         }
     }
 
@@ -421,6 +465,7 @@ impl CodexMessageProcessor {
 
     fn review_request_from_target(
         target: ApiReviewTarget,
+        paths: Option<Vec<String>>,
     ) -> Result<(ReviewRequest, String), JSONRPCErrorError> {
         fn invalid_request(message: String) -> JSONRPCErrorError {
             JSONRPCErrorError {
@@ -449,6 +494,20 @@ impl CodexMessageProcessor {
                     .filter(|t| !t.is_empty());
                 ApiReviewTarget::Commit { sha, title }
             }
+            ApiReviewTarget::Range { from, to } => {
+                let from = from.trim().to_string();
+                let to = to.trim().to_string();
+                if from.is_empty() || to.is_empty() {
+                    return Err(invalid_request("from and to must not be empty".to_string()));
+                }
+                ApiReviewTarget::Range { from, to }
+            }
+            ApiReviewTarget::PatchFile { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(invalid_request("path must not be empty".to_string()));
+                }
+                ApiReviewTarget::PatchFile { path }
+            }
             ApiReviewTarget::Custom { instructions } => {
                 let trimmed = instructions.trim().to_string();
                 if trimmed.is_empty() {
@@ -466,13 +525,22 @@ impl CodexMessageProcessor {
             ApiReviewTarget::UncommittedChanges => CoreReviewTarget::UncommittedChanges,
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
+            ApiReviewTarget::Range { from, to } => CoreReviewTarget::Range { from, to },
+            ApiReviewTarget::PatchFile { path } => CoreReviewTarget::PatchFile { path },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
         };
 
-        let hint = codex_core::review_prompts::user_facing_hint(&core_target);
+        let paths: Vec<String> = paths
+            .unwrap_or_default()
+            .into_iter()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .collect();
+        let hint = codex_core::review_prompts::user_facing_hint_with_paths(&core_target, &paths);
         let review_request = ReviewRequest {
             target: core_target,
             user_facing_hint: Some(hint.clone()),
+            paths,
         };
 
         Ok((review_request, hint))
@@ -525,6 +593,10 @@ impl CodexMessageProcessor {
                 self.thread_list(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadSearch { request_id, params } => {
+                self.thread_search(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadLoadedList { request_id, params } => {
                 self.thread_loaded_list(to_connection_request_id(request_id), params)
                     .await;
@@ -533,6 +605,18 @@ impl CodexMessageProcessor {
                 self.thread_read(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadAttach { request_id, params } => {
+                self.thread_attach(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadDetach { request_id, params } => {
+                self.thread_detach(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadGrant { request_id, params } => {
+                self.thread_grant(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::SkillsList { request_id, params } => {
                 self.skills_list(to_connection_request_id(request_id), params)
                     .await;
@@ -1655,6 +1739,7 @@ impl CodexMessageProcessor {
         let outgoing = self.outgoing.clone();
         let request_for_task = request;
         let sandbox_cwd = self.config.cwd.clone();
+        let denied_read_paths = self.config.sandbox_denied_read_paths.clone();
         let use_linux_sandbox_bwrap = self.config.features.enabled(Feature::UseLinuxSandboxBwrap);
 
         tokio::spawn(async move {
@@ -1662,6 +1747,7 @@ impl CodexMessageProcessor {
                 exec_params,
                 &effective_policy,
                 sandbox_cwd.as_path(),
+                &denied_read_paths,
                 &codex_linux_sandbox_exe,
                 use_linux_sandbox_bwrap,
                 None,
@@ -1896,6 +1982,9 @@ impl CodexMessageProcessor {
 
                 // Auto-attach a thread listener when starting a thread.
                 // Use the same behavior as the v1 API, with opt-in support for raw item events.
+                self.thread_subscriptions
+                    .subscribe(thread_id, request_id.connection_id, ThreadRights::OWNER)
+                    .await;
                 if let Err(err) = self
                     .attach_conversation_listener(
                         thread_id,
@@ -2341,6 +2430,109 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_search(&self, request_id: ConnectionRequestId, params: ThreadSearchParams) {
+        let ThreadSearchParams {
+            query,
+            limit,
+            model_providers,
+            source_kinds,
+            archived,
+        } = params;
+
+        let limit = limit
+            .map(|value| value as usize)
+            .unwrap_or(THREAD_SEARCH_DEFAULT_LIMIT)
+            .clamp(1, THREAD_LIST_MAX_LIMIT);
+        let model_provider_filter = match model_providers {
+            Some(providers) if providers.is_empty() => None,
+            Some(providers) => Some(providers),
+            None => Some(vec![self.config.model_provider_id.clone()]),
+        };
+        let (allowed_sources, source_kind_filter) = compute_source_filters(source_kinds);
+        let state_db_ctx = open_if_present(
+            &self.config.codex_home,
+            self.config.model_provider_id.as_str(),
+        )
+        .await;
+        let results = match search_threads_db(
+            state_db_ctx.as_deref(),
+            &query,
+            limit,
+            &allowed_sources,
+            model_provider_filter.as_deref(),
+            None,
+            archived.unwrap_or(false),
+        )
+        .await
+        {
+            Ok(results) => results,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("failed to search threads: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let data = results
+            .into_iter()
+            .filter_map(|result| {
+                let metadata = result.thread;
+                let summary = summary_from_state_db_metadata(
+                    metadata.id,
+                    metadata.rollout_path,
+                    metadata.first_user_message,
+                    metadata
+                        .created_at
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    metadata
+                        .updated_at
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    metadata.model_provider,
+                    metadata.cwd,
+                    metadata.cli_version,
+                    metadata.source,
+                    metadata.git_sha,
+                    metadata.git_branch,
+                    metadata.git_origin_url,
+                );
+                if let Some(filter) = source_kind_filter.as_ref()
+                    && !source_kind_matches(&summary.source, filter)
+                {
+                    return None;
+                }
+                let matches = result
+                    .matches
+                    .into_iter()
+                    .map(|search_match| ThreadSearchMatch {
+                        // Turn ids in `thread/read` are 1-based.
+                        turn_id: format!("turn-{}", search_match.turn_index + 1),
+                        kind: match search_match.kind {
+                            CoreThreadSearchMatchKind::UserMessage => {
+                                ThreadSearchMatchKind::UserMessage
+                            }
+                            CoreThreadSearchMatchKind::AgentMessage => {
+                                ThreadSearchMatchKind::AgentMessage
+                            }
+                            CoreThreadSearchMatchKind::Command => ThreadSearchMatchKind::Command,
+                            CoreThreadSearchMatchKind::File => ThreadSearchMatchKind::File,
+                        },
+                        snippet: search_match.snippet,
+                    })
+                    .collect();
+                Some(ThreadSearchResult {
+                    thread: summary_to_thread(summary),
+                    matches,
+                })
+            })
+            .collect();
+        let response = ThreadSearchResponse { data };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     async fn thread_loaded_list(
         &self,
         request_id: ConnectionRequestId,
@@ -2526,6 +2718,160 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_attach(&mut self, request_id: ConnectionRequestId, params: ThreadAttachParams) {
+        let ThreadAttachParams {
+            thread_id,
+            can_approve,
+            can_steer,
+            grant,
+        } = params;
+
+        let thread_uuid = match ThreadId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+        let Ok(loaded_thread) = self.thread_manager.get_thread(thread_uuid).await else {
+            self.send_invalid_request_error(
+                request_id,
+                format!("thread not loaded: {thread_uuid}"),
+            )
+            .await;
+            return;
+        };
+
+        // Rights beyond observing must be handed out by a connection that
+        // holds them.
+        let requested = ThreadRights {
+            approve: can_approve,
+            steer: can_steer,
+        };
+        if requested != ThreadRights::default() {
+            let granted = match grant.as_deref() {
+                Some(token) => self.thread_subscriptions.redeem(thread_uuid, token).await,
+                None => None,
+            };
+            if !granted.is_some_and(|granted| granted.covers(requested)) {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!(
+                        "canApprove and canSteer need a grant from thread/grant on thread {thread_uuid}"
+                    ),
+                )
+                .await;
+                return;
+            }
+        }
+
+        // Subscribe before reading history so no event falls between the
+        // replay and the live stream; clients de-duplicate by item id.
+        let connection_id = request_id.connection_id;
+        let rights = self
+            .thread_subscriptions
+            .subscribe(thread_uuid, connection_id, requested)
+            .await;
+        self.try_attach_thread_listener(thread_uuid).await;
+
+        let config_snapshot = loaded_thread.config_snapshot().await;
+        let rollout_path = loaded_thread.rollout_path();
+        let mut thread =
+            build_thread_from_snapshot(thread_uuid, &config_snapshot, rollout_path.clone());
+        if let Some(rollout_path) = rollout_path.as_ref() {
+            match read_event_msgs_from_rollout(rollout_path).await {
+                Ok(events) => {
+                    thread.turns = build_turns_from_event_msgs(&events);
+                }
+                // Nothing is recorded before the first user message.
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    self.thread_subscriptions
+                        .unsubscribe(thread_uuid, connection_id)
+                        .await;
+                    self.send_internal_error(
+                        request_id,
+                        format!(
+                            "failed to load rollout `{}` for thread {thread_uuid}: {err}",
+                            rollout_path.display()
+                        ),
+                    )
+                    .await;
+                    return;
+                }
+            }
+        }
+
+        let response = ThreadAttachResponse {
+            thread,
+            can_approve: rights.approve,
+            can_steer: rights.steer,
+        };
+        self.outgoing.send_response(request_id, response).await;
+        // Approvals raised before the client attached are still waiting for
+        // an answer; show them to the new client too.
+        self.outgoing
+            .replay_pending_requests(thread_uuid, connection_id)
+            .await;
+    }
+
+    async fn thread_grant(&mut self, request_id: ConnectionRequestId, params: ThreadGrantParams) {
+        let thread_id = match ThreadId::from_string(&params.thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+        let rights = ThreadRights {
+            approve: params.can_approve,
+            steer: params.can_steer,
+        };
+        let Some(grant) = self
+            .thread_subscriptions
+            .grant(thread_id, request_id.connection_id, rights)
+            .await
+        else {
+            self.send_invalid_request_error(
+                request_id,
+                format!("connection does not hold the requested rights on thread {thread_id}"),
+            )
+            .await;
+            return;
+        };
+        self.outgoing
+            .send_response(request_id, ThreadGrantResponse { grant })
+            .await;
+    }
+
+    async fn thread_detach(&mut self, request_id: ConnectionRequestId, params: ThreadDetachParams) {
+        let thread_id = match ThreadId::from_string(&params.thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+        if !self
+            .thread_subscriptions
+            .unsubscribe(thread_id, request_id.connection_id)
+            .await
+        {
+            self.send_invalid_request_error(
+                request_id,
+                format!("not attached to thread: {thread_id}"),
+            )
+            .await;
+            return;
+        }
+        self.outgoing
+            .send_response(request_id, ThreadDetachResponse {})
+            .await;
+    }
+
     pub(crate) fn thread_created_receiver(&self) -> broadcast::Receiver<ThreadId> {
         self.thread_manager.subscribe_thread_created()
     }
@@ -2641,6 +2987,25 @@ impl CodexMessageProcessor {
             }
         };
 
+        // Resuming makes the caller an owner, so a loaded thread another
+        // connection drives is joined with `thread/attach` and a grant instead.
+        if let InitialHistory::Resumed(resumed) = &thread_history
+            && self
+                .thread_subscriptions
+                .owned_by_other(resumed.conversation_id, request_id.connection_id)
+                .await
+        {
+            self.send_invalid_request_error(
+                request_id,
+                format!(
+                    "thread {} is driven by another connection; use thread/attach with a grant from thread/grant",
+                    resumed.conversation_id
+                ),
+            )
+            .await;
+            return;
+        }
+
         let history_cwd = thread_history.session_cwd();
         let typesafe_overrides = self.build_thread_config_overrides(
             model,
@@ -2702,6 +3067,9 @@ impl CodexMessageProcessor {
                     return;
                 };
                 // Auto-attach a thread listener when resuming a thread.
+                self.thread_subscriptions
+                    .subscribe(thread_id, request_id.connection_id, ThreadRights::OWNER)
+                    .await;
                 if let Err(err) = self
                     .attach_conversation_listener(thread_id, false, ApiVersion::V2)
                     .await
@@ -2910,6 +3278,9 @@ impl CodexMessageProcessor {
             return;
         };
         // Auto-attach a conversation listener when forking a thread.
+        self.thread_subscriptions
+            .subscribe(thread_id, request_id.connection_id, ThreadRights::OWNER)
+            .await;
         if let Err(err) = self
             .attach_conversation_listener(thread_id, false, ApiVersion::V2)
             .await
@@ -4155,6 +4526,7 @@ impl CodexMessageProcessor {
 
         // If the thread is active, request shutdown and wait briefly.
         if let Some(conversation) = self.thread_manager.remove_thread(&thread_id).await {
+            self.thread_subscriptions.remove_thread(thread_id).await;
             if let Some(ctx) = conversation.state_db() {
                 state_db_ctx = Some(ctx);
             }
@@ -4562,7 +4934,10 @@ impl CodexMessageProcessor {
     }
 
     async fn turn_start(&self, request_id: ConnectionRequestId, params: TurnStartParams) {
-        let (_, thread) = match self.load_thread(&params.thread_id).await {
+        let (thread_id, thread) = match self
+            .load_thread_for_steering(request_id.connection_id, &params.thread_id)
+            .await
+        {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
@@ -4633,6 +5008,7 @@ impl CodexMessageProcessor {
                     turn,
                 };
                 self.outgoing
+                    .for_thread(thread_id)
                     .send_server_notification(ServerNotification::TurnStarted(notif))
                     .await;
             }
@@ -4648,7 +5024,10 @@ impl CodexMessageProcessor {
     }
 
     async fn turn_steer(&self, request_id: ConnectionRequestId, params: TurnSteerParams) {
-        let (_, thread) = match self.load_thread(&params.thread_id).await {
+        let (_, thread) = match self
+            .load_thread_for_steering(request_id.connection_id, &params.thread_id)
+            .await
+        {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
@@ -4730,7 +5109,7 @@ impl CodexMessageProcessor {
         &self,
         request_id: &ConnectionRequestId,
         turn: Turn,
-        parent_thread_id: String,
+        parent_thread_id: ThreadId,
         review_thread_id: String,
     ) {
         let response = ReviewStartResponse {
@@ -4742,10 +5121,11 @@ impl CodexMessageProcessor {
             .await;
 
         let notif = TurnStartedNotification {
-            thread_id: parent_thread_id,
+            thread_id: parent_thread_id.to_string(),
             turn,
         };
         self.outgoing
+            .for_thread(parent_thread_id)
             .send_server_notification(ServerNotification::TurnStarted(notif))
             .await;
     }
@@ -4756,7 +5136,7 @@ impl CodexMessageProcessor {
         parent_thread: Arc<CodexThread>,
         review_request: ReviewRequest,
         display_text: &str,
-        parent_thread_id: String,
+        parent_thread_id: ThreadId,
     ) -> std::result::Result<(), JSONRPCErrorError> {
         let turn_id = parent_thread.submit(Op::Review { review_request }).await;
 
@@ -4766,8 +5146,8 @@ impl CodexMessageProcessor {
                 self.emit_review_started(
                     request_id,
                     turn,
-                    parent_thread_id.clone(),
                     parent_thread_id,
+                    parent_thread_id.to_string(),
                 )
                 .await;
                 Ok(())
@@ -4821,6 +5201,9 @@ impl CodexMessageProcessor {
                 data: None,
             })?;
 
+        self.thread_subscriptions
+            .subscribe(thread_id, request_id.connection_id, ThreadRights::OWNER)
+            .await;
         if let Err(err) = self
             .attach_conversation_listener(thread_id, false, ApiVersion::V2)
             .await
@@ -4867,8 +5250,7 @@ impl CodexMessageProcessor {
             })?;
 
         let turn = Self::build_review_turn(turn_id, display_text);
-        let review_thread_id = thread_id.to_string();
-        self.emit_review_started(request_id, turn, review_thread_id.clone(), review_thread_id)
+        self.emit_review_started(request_id, turn, thread_id, thread_id.to_string())
             .await;
 
         Ok(())
@@ -4879,8 +5261,12 @@ impl CodexMessageProcessor {
             thread_id,
             target,
             delivery,
+            paths,
         } = params;
-        let (parent_thread_id, parent_thread) = match self.load_thread(&thread_id).await {
+        let (parent_thread_id, parent_thread) = match self
+            .load_thread_for_steering(request_id.connection_id, &thread_id)
+            .await
+        {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
@@ -4888,7 +5274,7 @@ impl CodexMessageProcessor {
             }
         };
 
-        let (review_request, display_text) = match Self::review_request_from_target(target) {
+        let (review_request, display_text) = match Self::review_request_from_target(target, paths) {
             Ok(value) => value,
             Err(err) => {
                 self.outgoing.send_error(request_id, err).await;
@@ -4905,7 +5291,7 @@ impl CodexMessageProcessor {
                         parent_thread,
                         review_request,
                         display_text.as_str(),
-                        thread_id.clone(),
+                        parent_thread_id,
                     )
                     .await
                 {
@@ -4935,7 +5321,10 @@ impl CodexMessageProcessor {
     ) {
         let TurnInterruptParams { thread_id, .. } = params;
 
-        let (thread_uuid, thread) = match self.load_thread(&thread_id).await {
+        let (thread_uuid, thread) = match self
+            .load_thread_for_steering(request_id.connection_id, &thread_id)
+            .await
+        {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
@@ -4971,6 +5360,13 @@ impl CodexMessageProcessor {
             .await
         {
             Ok(subscription_id) => {
+                self.thread_subscriptions
+                    .subscribe(
+                        conversation_id,
+                        request_id.connection_id,
+                        ThreadRights::OWNER,
+                    )
+                    .await;
                 let response = AddConversationSubscriptionResponse { subscription_id };
                 self.outgoing.send_response(request_id, response).await;
             }
@@ -5034,7 +5430,7 @@ impl CodexMessageProcessor {
         self.listener_thread_ids_by_subscription
             .insert(subscription_id, conversation_id);
 
-        let outgoing_for_task = self.outgoing.clone();
+        let outgoing_for_task = Arc::new(self.outgoing.for_thread(conversation_id));
         let pending_interrupts = self.pending_interrupts.clone();
         let pending_rollbacks = self.pending_rollbacks.clone();
         let turn_summary_store = self.turn_summary_store.clone();
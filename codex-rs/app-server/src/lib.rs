@@ -19,6 +19,7 @@ use crate::message_processor::MessageProcessorArgs;
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::OutgoingEnvelope;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::thread_subscriptions::ThreadSubscriptions;
 use crate::transport::CHANNEL_CAPACITY;
 use crate::transport::ConnectionState;
 use crate::transport::TransportEvent;
@@ -26,6 +27,7 @@ use crate::transport::has_initialized_connections;
 use crate::transport::route_outgoing_envelope;
 use crate::transport::start_stdio_connection;
 use crate::transport::start_websocket_acceptor;
+use crate::websocket_security::WebSocketSecurity;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_app_server_protocol::ConfigWarningNotification;
 use codex_app_server_protocol::JSONRPCMessage;
@@ -57,9 +59,12 @@ mod fuzzy_file_search;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod thread_subscriptions;
 mod transport;
+mod websocket_security;
 
 pub use crate::transport::AppServerTransport;
+pub use crate::websocket_security::WebSocketOptions;
 
 fn config_warning_from_error(
     summary: impl Into<String>,
@@ -183,6 +188,7 @@ pub async fn run_main(
         loader_overrides,
         default_analytics_enabled,
         AppServerTransport::Stdio,
+        WebSocketOptions::default(),
     )
     .await
 }
@@ -193,6 +199,7 @@ pub async fn run_main_with_transport(
     loader_overrides: LoaderOverrides,
     default_analytics_enabled: bool,
     transport: AppServerTransport,
+    websocket_options: WebSocketOptions,
 ) -> IoResult<()> {
     let (transport_event_tx, mut transport_event_rx) =
         mpsc::channel::<TransportEvent>(CHANNEL_CAPACITY);
@@ -202,11 +209,20 @@ pub async fn run_main_with_transport(
     let mut websocket_accept_handle = None;
     match transport {
         AppServerTransport::Stdio => {
+            if !websocket_options.is_empty() {
+                return Err(std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "WebSocket security options require a ws:// or wss:// --listen URL",
+                ));
+            }
             start_stdio_connection(transport_event_tx.clone(), &mut stdio_handles).await?;
         }
-        AppServerTransport::WebSocket { bind_address } => {
-            websocket_accept_handle =
-                Some(start_websocket_acceptor(bind_address, transport_event_tx.clone()).await?);
+        AppServerTransport::WebSocket { bind_address, tls } => {
+            let security = WebSocketSecurity::from_options(&websocket_options, tls)?;
+            websocket_accept_handle = Some(
+                start_websocket_acceptor(bind_address, security, transport_event_tx.clone())
+                    .await?,
+            );
         }
     }
     let shutdown_when_no_connections = matches!(transport, AppServerTransport::Stdio);
@@ -340,6 +356,7 @@ pub async fn run_main_with_transport(
         let outgoing_message_sender = Arc::new(OutgoingMessageSender::new(outgoing_tx));
         let cli_overrides: Vec<(String, TomlValue)> = cli_kv_overrides.clone();
         let loader_overrides = loader_overrides_for_config_api;
+        let thread_subscriptions = ThreadSubscriptions::default();
         let mut processor = MessageProcessor::new(MessageProcessorArgs {
             outgoing: outgoing_message_sender,
             codex_linux_sandbox_exe,
@@ -349,6 +366,7 @@ pub async fn run_main_with_transport(
             cloud_requirements: cloud_requirements.clone(),
             feedback: feedback.clone(),
             config_warnings,
+            thread_subscriptions: thread_subscriptions.clone(),
         });
         let mut thread_created_rx = processor.thread_created_receiver();
         let mut connections = HashMap::<ConnectionId, ConnectionState>::new();
@@ -366,6 +384,7 @@ pub async fn run_main_with_transport(
                             }
                             TransportEvent::ConnectionClosed { connection_id } => {
                                 connections.remove(&connection_id);
+                                thread_subscriptions.remove_connection(connection_id).await;
                                 if shutdown_when_no_connections && connections.is_empty() {
                                     break;
                                 }
@@ -386,13 +405,13 @@ pub async fn run_main_with_transport(
                                             .await;
                                     }
                                     JSONRPCMessage::Response(response) => {
-                                        processor.process_response(response).await;
+                                        processor.process_response(connection_id, response).await;
                                     }
                                     JSONRPCMessage::Notification(notification) => {
                                         processor.process_notification(notification).await;
                                     }
                                     JSONRPCMessage::Error(err) => {
-                                        processor.process_error(err).await;
+                                        processor.process_error(connection_id, err).await;
                                     }
                                 }
                             }
@@ -402,7 +421,7 @@ pub async fn run_main_with_transport(
                         let Some(envelope) = envelope else {
                             break;
                         };
-                        route_outgoing_envelope(&mut connections, envelope).await;
+                        route_outgoing_envelope(&mut connections, &thread_subscriptions, envelope).await;
                     }
                     created = thread_created_rx.recv(), if listen_for_threads => {
                         match created {
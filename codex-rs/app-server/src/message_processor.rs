@@ -9,6 +9,8 @@ use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::ConnectionRequestId;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::thread_subscriptions::ThreadAction;
+use crate::thread_subscriptions::ThreadSubscriptions;
 use async_trait::async_trait;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshParams;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshReason;
@@ -26,8 +28,10 @@ use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestPayload;
+use codex_app_server_protocol::ServerRequestResolvedNotification;
 use codex_app_server_protocol::experimental_required_message;
 use codex_core::AuthManager;
 use codex_core::ThreadManager;
@@ -111,6 +115,7 @@ pub(crate) struct MessageProcessor {
     config_api: ConfigApi,
     config: Arc<Config>,
     config_warnings: Arc<Vec<ConfigWarningNotification>>,
+    thread_subscriptions: ThreadSubscriptions,
 }
 
 #[derive(Debug, Default)]
@@ -128,6 +133,7 @@ pub(crate) struct MessageProcessorArgs {
     pub(crate) cloud_requirements: CloudRequirementsLoader,
     pub(crate) feedback: CodexFeedback,
     pub(crate) config_warnings: Vec<ConfigWarningNotification>,
+    pub(crate) thread_subscriptions: ThreadSubscriptions,
 }
 
 impl MessageProcessor {
@@ -143,6 +149,7 @@ impl MessageProcessor {
             cloud_requirements,
             feedback,
             config_warnings,
+            thread_subscriptions,
         } = args;
         let auth_manager = AuthManager::shared(
             config.codex_home.clone(),
@@ -168,6 +175,7 @@ impl MessageProcessor {
             cli_overrides: cli_overrides.clone(),
             cloud_requirements: cloud_requirements.clone(),
             feedback,
+            thread_subscriptions: thread_subscriptions.clone(),
         });
         let config_api = ConfigApi::new(
             config.codex_home.clone(),
@@ -182,6 +190,7 @@ impl MessageProcessor {
             config_api,
             config,
             config_warnings: Arc::new(config_warnings),
+            thread_subscriptions,
         }
     }
 
@@ -387,16 +396,69 @@ impl MessageProcessor {
     }
 
     /// Handle a standalone JSON-RPC response originating from the peer.
-    pub(crate) async fn process_response(&mut self, response: JSONRPCResponse) {
+    pub(crate) async fn process_response(
+        &mut self,
+        connection_id: ConnectionId,
+        response: JSONRPCResponse,
+    ) {
         tracing::info!("<- response: {:?}", response);
         let JSONRPCResponse { id, result, .. } = response;
-        self.outgoing.notify_client_response(id, result).await
+        if !self.may_answer(connection_id, &id).await {
+            return;
+        }
+        let resolved_thread = self
+            .outgoing
+            .notify_client_response(id.clone(), result)
+            .await;
+        self.notify_request_resolved(resolved_thread, id).await;
     }
 
     /// Handle an error object received from the peer.
-    pub(crate) async fn process_error(&mut self, err: JSONRPCError) {
+    pub(crate) async fn process_error(&mut self, connection_id: ConnectionId, err: JSONRPCError) {
         tracing::error!("<- error: {:?}", err);
-        self.outgoing.notify_client_error(err.id, err.error).await;
+        if !self.may_answer(connection_id, &err.id).await {
+            return;
+        }
+        let resolved_thread = self
+            .outgoing
+            .notify_client_error(err.id.clone(), err.error)
+            .await;
+        self.notify_request_resolved(resolved_thread, err.id).await;
+    }
+
+    /// Connections attached to a thread without approval rights may observe
+    /// its requests but not answer them.
+    async fn may_answer(&self, connection_id: ConnectionId, id: &RequestId) -> bool {
+        let Some(thread_id) = self.outgoing.pending_request_thread(id).await else {
+            return true;
+        };
+        let permitted = self
+            .thread_subscriptions
+            .permits(thread_id, connection_id, ThreadAction::Approve)
+            .await;
+        if !permitted {
+            tracing::warn!(
+                "ignoring answer to {id:?} from connection {connection_id:?} without approval rights on thread {thread_id}"
+            );
+        }
+        permitted
+    }
+
+    /// The first answer to a thread's request wins; tell every client
+    /// attached to the thread so the others can dismiss their prompt.
+    async fn notify_request_resolved(&self, thread_id: Option<ThreadId>, request_id: RequestId) {
+        let Some(thread_id) = thread_id else {
+            return;
+        };
+        self.outgoing
+            .for_thread(thread_id)
+            .send_server_notification(ServerNotification::ServerRequestResolved(
+                ServerRequestResolvedNotification {
+                    thread_id: thread_id.to_string(),
+                    request_id,
+                },
+            ))
+            .await;
     }
 
     async fn handle_config_read(&self, request_id: ConnectionRequestId, params: ConfigReadParams) {
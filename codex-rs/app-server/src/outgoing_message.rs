@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 
@@ -8,6 +9,7 @@ use codex_app_server_protocol::Result;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::ServerRequestPayload;
+use codex_protocol::ThreadId;
 use serde::Serialize;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
@@ -36,24 +38,60 @@ pub(crate) enum OutgoingEnvelope {
         connection_id: ConnectionId,
         message: OutgoingMessage,
     },
+    /// Delivered to the connections subscribed to the thread, or to every
+    /// initialized connection when nobody is subscribed.
+    ToThread {
+        thread_id: ThreadId,
+        message: OutgoingMessage,
+    },
     Broadcast {
         message: OutgoingMessage,
     },
 }
 
+/// A server request still waiting for a client to answer it.
+struct PendingRequest {
+    thread_id: Option<ThreadId>,
+    request: ServerRequest,
+    callback: oneshot::Sender<Result>,
+}
+
 /// Sends messages to the client and manages request callbacks.
 pub(crate) struct OutgoingMessageSender {
-    next_server_request_id: AtomicI64,
+    next_server_request_id: Arc<AtomicI64>,
     sender: mpsc::Sender<OutgoingEnvelope>,
-    request_id_to_callback: Mutex<HashMap<RequestId, oneshot::Sender<Result>>>,
+    pending_requests: Arc<Mutex<HashMap<RequestId, PendingRequest>>>,
+    /// Thread that notifications and requests sent through this sender
+    /// belong to. See [`OutgoingMessageSender::for_thread`].
+    thread_id: Option<ThreadId>,
 }
 
 impl OutgoingMessageSender {
     pub(crate) fn new(sender: mpsc::Sender<OutgoingEnvelope>) -> Self {
         Self {
-            next_server_request_id: AtomicI64::new(0),
+            next_server_request_id: Arc::new(AtomicI64::new(0)),
             sender,
-            request_id_to_callback: Mutex::new(HashMap::new()),
+            pending_requests: Arc::new(Mutex::new(HashMap::new())),
+            thread_id: None,
+        }
+    }
+
+    /// A sender sharing this one's request ids and callbacks whose
+    /// notifications and requests are delivered only to the connections
+    /// attached to `thread_id`.
+    pub(crate) fn for_thread(&self, thread_id: ThreadId) -> Self {
+        Self {
+            next_server_request_id: Arc::clone(&self.next_server_request_id),
+            sender: self.sender.clone(),
+            pending_requests: Arc::clone(&self.pending_requests),
+            thread_id: Some(thread_id),
+        }
+    }
+
+    fn scoped_envelope(&self, message: OutgoingMessage) -> OutgoingEnvelope {
+        match self.thread_id {
+            Some(thread_id) => OutgoingEnvelope::ToThread { thread_id, message },
+            None => OutgoingEnvelope::Broadcast { message },
         }
     }
 
@@ -72,65 +110,128 @@ impl OutgoingMessageSender {
         let id = RequestId::Integer(self.next_server_request_id.fetch_add(1, Ordering::Relaxed));
         let outgoing_message_id = id.clone();
         let (tx_approve, rx_approve) = oneshot::channel();
+        let request = request.request_with_id(outgoing_message_id.clone());
         {
-            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
-            request_id_to_callback.insert(id, tx_approve);
+            let mut pending_requests = self.pending_requests.lock().await;
+            pending_requests.insert(
+                id,
+                PendingRequest {
+                    thread_id: self.thread_id,
+                    request: request.clone(),
+                    callback: tx_approve,
+                },
+            );
         }
 
-        let outgoing_message =
-            OutgoingMessage::Request(request.request_with_id(outgoing_message_id.clone()));
+        let outgoing_message = OutgoingMessage::Request(request);
         if let Err(err) = self
             .sender
-            .send(OutgoingEnvelope::Broadcast {
-                message: outgoing_message,
-            })
+            .send(self.scoped_envelope(outgoing_message))
             .await
         {
             warn!("failed to send request {outgoing_message_id:?} to client: {err:?}");
-            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
-            request_id_to_callback.remove(&outgoing_message_id);
+            let mut pending_requests = self.pending_requests.lock().await;
+            pending_requests.remove(&outgoing_message_id);
         }
         (outgoing_message_id, rx_approve)
     }
 
-    pub(crate) async fn notify_client_response(&self, id: RequestId, result: Result) {
+    /// Thread a pending server request belongs to. `None` when the request is
+    /// unknown, already answered, or not tied to a thread.
+    pub(crate) async fn pending_request_thread(&self, id: &RequestId) -> Option<ThreadId> {
+        let pending_requests = self.pending_requests.lock().await;
+        pending_requests
+            .get(id)
+            .and_then(|pending| pending.thread_id)
+    }
+
+    /// Re-send the requests of `thread_id` that are still unanswered to a
+    /// connection that just attached to the thread.
+    pub(crate) async fn replay_pending_requests(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+    ) {
+        let mut requests: Vec<(RequestId, ServerRequest)> = {
+            let pending_requests = self.pending_requests.lock().await;
+            pending_requests
+                .iter()
+                .filter(|(_, pending)| pending.thread_id == Some(thread_id))
+                .map(|(id, pending)| (id.clone(), pending.request.clone()))
+                .collect()
+        };
+        // Server request ids are issued in order, so replay oldest first.
+        requests.sort_by_key(|(id, _)| match id {
+            RequestId::Integer(id) => *id,
+            RequestId::String(_) => i64::MAX,
+        });
+        for (_, request) in requests {
+            if let Err(err) = self
+                .sender
+                .send(OutgoingEnvelope::ToConnection {
+                    connection_id,
+                    message: OutgoingMessage::Request(request),
+                })
+                .await
+            {
+                warn!("failed to replay pending request to client: {err:?}");
+            }
+        }
+    }
+
+    /// Deliver a client's answer to the waiting callback. Returns the thread
+    /// of the answered request so other attached clients can be told it is
+    /// resolved; only the first answer for a request is delivered.
+    pub(crate) async fn notify_client_response(
+        &self,
+        id: RequestId,
+        result: Result,
+    ) -> Option<ThreadId> {
         let entry = {
-            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
-            request_id_to_callback.remove_entry(&id)
+            let mut pending_requests = self.pending_requests.lock().await;
+            pending_requests.remove_entry(&id)
         };
 
         match entry {
-            Some((id, sender)) => {
-                if let Err(err) = sender.send(result) {
+            Some((id, pending)) => {
+                if let Err(err) = pending.callback.send(result) {
                     warn!("could not notify callback for {id:?} due to: {err:?}");
                 }
+                pending.thread_id
             }
             None => {
                 warn!("could not find callback for {id:?}");
+                None
             }
         }
     }
 
-    pub(crate) async fn notify_client_error(&self, id: RequestId, error: JSONRPCErrorError) {
+    pub(crate) async fn notify_client_error(
+        &self,
+        id: RequestId,
+        error: JSONRPCErrorError,
+    ) -> Option<ThreadId> {
         let entry = {
-            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
-            request_id_to_callback.remove_entry(&id)
+            let mut pending_requests = self.pending_requests.lock().await;
+            pending_requests.remove_entry(&id)
         };
 
         match entry {
-            Some((id, _sender)) => {
+            Some((id, pending)) => {
                 warn!("client responded with error for {id:?}: {error:?}");
+                pending.thread_id
             }
             None => {
                 warn!("could not find callback for {id:?}");
+                None
             }
         }
     }
 
     pub(crate) async fn cancel_request(&self, id: &RequestId) -> bool {
         let entry = {
-            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
-            request_id_to_callback.remove_entry(id)
+            let mut pending_requests = self.pending_requests.lock().await;
+            pending_requests.remove_entry(id)
         };
         entry.is_some()
     }
@@ -174,9 +275,7 @@ impl OutgoingMessageSender {
     pub(crate) async fn send_server_notification(&self, notification: ServerNotification) {
         if let Err(err) = self
             .sender
-            .send(OutgoingEnvelope::Broadcast {
-                message: OutgoingMessage::AppServerNotification(notification),
-            })
+            .send(self.scoped_envelope(OutgoingMessage::AppServerNotification(notification)))
             .await
         {
             warn!("failed to send server notification to client: {err:?}");
@@ -189,9 +288,7 @@ impl OutgoingMessageSender {
         let outgoing_message = OutgoingMessage::Notification(notification);
         if let Err(err) = self
             .sender
-            .send(OutgoingEnvelope::Broadcast {
-                message: outgoing_message,
-            })
+            .send(self.scoped_envelope(outgoing_message))
             .await
         {
             warn!("failed to send notification to client: {err:?}");
@@ -260,6 +357,8 @@ mod tests {
     use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
     use codex_app_server_protocol::AccountUpdatedNotification;
     use codex_app_server_protocol::AuthMode;
+    use codex_app_server_protocol::ChatgptAuthTokensRefreshParams;
+    use codex_app_server_protocol::ChatgptAuthTokensRefreshReason;
     use codex_app_server_protocol::ConfigWarningNotification;
     use codex_app_server_protocol::LoginChatGptCompleteNotification;
     use codex_app_server_protocol::RateLimitSnapshot;
@@ -474,4 +573,71 @@ mod tests {
             other => panic!("expected targeted error envelope, got: {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn thread_scoped_requests_replay_and_resolve_once() {
+        let (tx, mut rx) = mpsc::channel::<OutgoingEnvelope>(4);
+        let thread_id = ThreadId::new();
+        let outgoing = OutgoingMessageSender::new(tx).for_thread(thread_id);
+
+        let (request_id, callback) = outgoing
+            .send_request_with_id(ServerRequestPayload::ChatgptAuthTokensRefresh(
+                ChatgptAuthTokensRefreshParams {
+                    reason: ChatgptAuthTokensRefreshReason::Unauthorized,
+                    previous_account_id: None,
+                },
+            ))
+            .await;
+        let envelope = timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("should receive envelope before timeout")
+            .expect("channel should contain one message");
+        let OutgoingEnvelope::ToThread {
+            thread_id: routed_thread_id,
+            message: OutgoingMessage::Request(request),
+        } = envelope
+        else {
+            panic!("expected thread-scoped request, got: {envelope:?}");
+        };
+        assert_eq!(routed_thread_id, thread_id);
+        assert_eq!(
+            outgoing.pending_request_thread(&request_id).await,
+            Some(thread_id)
+        );
+
+        outgoing
+            .replay_pending_requests(thread_id, ConnectionId(5))
+            .await;
+        let envelope = timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("should receive envelope before timeout")
+            .expect("channel should contain one message");
+        let OutgoingEnvelope::ToConnection {
+            connection_id,
+            message: OutgoingMessage::Request(replayed),
+        } = envelope
+        else {
+            panic!("expected replayed request, got: {envelope:?}");
+        };
+        assert_eq!(connection_id, ConnectionId(5));
+        assert_eq!(replayed, request);
+
+        assert_eq!(
+            outgoing
+                .notify_client_response(request_id.clone(), json!({ "first": true }))
+                .await,
+            Some(thread_id)
+        );
+        assert_eq!(
+            outgoing
+                .notify_client_response(request_id.clone(), json!({ "second": true }))
+                .await,
+            None
+        );
+        assert_eq!(
+            callback.await.expect("callback should receive the answer"),
+            json!({ "first": true })
+        );
+        assert_eq!(outgoing.pending_request_thread(&request_id).await, None);
+    }
 }
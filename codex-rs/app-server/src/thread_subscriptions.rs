@@ -0,0 +1,426 @@
+//! Tracks which connections are attached to which threads.
+//!
+//! Thread-scoped notifications and server requests are routed only to the
+//! connections subscribed to the thread. The connection that started, resumed
+//! or forked a thread owns it; other connections join with `thread/attach` and
+//! only observe unless they present a grant issued with `thread/grant` by a
+//! connection holding the rights they ask for. A thread stays claimed after its
+//! last subscriber leaves, until it is unloaded, so a connection that was
+//! never given rights cannot pick them up by waiting for the others to go.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use codex_protocol::ThreadId;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::outgoing_message::ConnectionId;
+
+/// What a connection attached to a thread may do besides observing it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ThreadRights {
+    /// May answer approval and user-input requests raised by the thread.
+    pub(crate) approve: bool,
+    /// May start, steer and interrupt turns.
+    pub(crate) steer: bool,
+}
+
+impl ThreadRights {
+    /// Rights of the connection that started, resumed or forked the thread.
+    pub(crate) const OWNER: Self = Self {
+        approve: true,
+        steer: true,
+    };
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            approve: self.approve || other.approve,
+            steer: self.steer || other.steer,
+        }
+    }
+    /// Whether every right in `other` is also in `self`.
+    pub(crate) fn covers(self, other: Self) -> bool {
+        (self.approve || !other.approve) && (self.steer || !other.steer)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ThreadAction {
+    Approve,
+    Steer,
+}
+
+/// An unused `thread/grant` token.
+#[derive(Clone, Copy, Debug)]
+struct Grant {
+    thread_id: ThreadId,
+    issuer: ConnectionId,
+    rights: ThreadRights,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct ThreadSubscriptions {
+    /// Threads that were ever subscribed to, with their current subscribers.
+    /// An entry outlives its subscribers and is dropped with the thread.
+    inner: Arc<Mutex<HashMap<ThreadId, HashMap<ConnectionId, ThreadRights>>>>,
+    grants: Arc<Mutex<HashMap<String, Grant>>>,
+}
+
+impl ThreadSubscriptions {
+    /// Subscribe `connection_id` to `thread_id`. Subscribing again keeps the
+    /// rights already granted and adds the new ones.
+    pub(crate) async fn subscribe(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+        rights: ThreadRights,
+    ) -> ThreadRights {
+        let mut inner = self.inner.lock().await;
+        let entry = inner
+            .entry(thread_id)
+            .or_default()
+            .entry(connection_id)
+            .or_default();
+        *entry = entry.union(rights);
+        *entry
+    }
+
+    /// Returns whether the connection was subscribed.
+    pub(crate) async fn unsubscribe(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+    ) -> bool {
+        let mut inner = self.inner.lock().await;
+        let Some(connections) = inner.get_mut(&thread_id) else {
+            return false;
+        };
+        connections.remove(&connection_id).is_some()
+    }
+
+    /// Drop the connection's subscriptions and the grants it issued.
+    pub(crate) async fn remove_connection(&self, connection_id: ConnectionId) {
+        let mut inner = self.inner.lock().await;
+        for connections in inner.values_mut() {
+            connections.remove(&connection_id);
+        }
+        self.grants
+            .lock()
+            .await
+            .retain(|_, grant| grant.issuer != connection_id);
+    }
+
+    /// Forget everything about a thread that is no longer loaded.
+    pub(crate) async fn remove_thread(&self, thread_id: ThreadId) {
+        self.inner.lock().await.remove(&thread_id);
+        self.grants
+            .lock()
+            .await
+            .retain(|_, grant| grant.thread_id != thread_id);
+    }
+
+    /// Whether a connection other than `connection_id` currently holds owner
+    /// rights on `thread_id`.
+    pub(crate) async fn owned_by_other(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+    ) -> bool {
+        let inner = self.inner.lock().await;
+        inner.get(&thread_id).is_some_and(|connections| {
+            connections.iter().any(|(other, rights)| {
+                *other != connection_id && rights.covers(ThreadRights::OWNER)
+            })
+        })
+    }
+
+    /// Issue a single-use token another connection can pass to
+    /// `thread/attach` to receive `rights`. A connection can only hand out
+    /// rights it holds itself; returns `None` otherwise.
+    pub(crate) async fn grant(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+        rights: ThreadRights,
+    ) -> Option<String> {
+        let held = self
+            .inner
+            .lock()
+            .await
+            .get(&thread_id)
+            .and_then(|connections| connections.get(&connection_id))
+            .copied()?;
+        if !held.covers(rights) {
+            return None;
+        }
+        let token = Uuid::new_v4().to_string();
+        self.grants.lock().await.insert(
+            token.clone(),
+            Grant {
+                thread_id,
+                issuer: connection_id,
+                rights,
+            },
+        );
+        Some(token)
+    }
+
+    /// Consume a grant token, returning the rights it carries for `thread_id`.
+    pub(crate) async fn redeem(&self, thread_id: ThreadId, token: &str) -> Option<ThreadRights> {
+        let mut grants = self.grants.lock().await;
+        let grant = grants.remove(token)?;
+        (grant.thread_id == thread_id).then_some(grant.rights)
+    }
+
+    /// Connections subscribed to `thread_id`, or `None` if nobody ever
+    /// subscribed to it.
+    pub(crate) async fn subscribers(&self, thread_id: ThreadId) -> Option<Vec<ConnectionId>> {
+        let inner = self.inner.lock().await;
+        inner
+            .get(&thread_id)
+            .map(|connections| connections.keys().copied().collect())
+    }
+
+    /// Whether `connection_id` may perform `action` on `thread_id`. Threads
+    /// nobody subscribed to keep the permissive behavior clients relied on
+    /// before attaching existed; once a thread has been subscribed to, only
+    /// its current subscribers may act on it, within their rights, even after
+    /// the others detached.
+    pub(crate) async fn permits(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+        action: ThreadAction,
+    ) -> bool {
+        let inner = self.inner.lock().await;
+        let Some(connections) = inner.get(&thread_id) else {
+            return true;
+        };
+        let Some(rights) = connections.get(&connection_id) else {
+            return false;
+        };
+        match action {
+            ThreadAction::Approve => rights.approve,
+            ThreadAction::Steer => rights.steer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const OBSERVER: ThreadRights = ThreadRights {
+        approve: false,
+        steer: false,
+    };
+
+    #[tokio::test]
+    async fn rights_are_enforced_per_attached_connection() {
+        let subscriptions = ThreadSubscriptions::default();
+        let thread_id = ThreadId::new();
+        subscriptions
+            .subscribe(thread_id, ConnectionId(1), ThreadRights::OWNER)
+            .await;
+        subscriptions
+            .subscribe(thread_id, ConnectionId(2), OBSERVER)
+            .await;
+
+        assert!(
+            subscriptions
+                .permits(thread_id, ConnectionId(1), ThreadAction::Approve)
+                .await
+        );
+        assert!(
+            !subscriptions
+                .permits(thread_id, ConnectionId(2), ThreadAction::Approve)
+                .await
+        );
+        assert!(
+            !subscriptions
+                .permits(thread_id, ConnectionId(2), ThreadAction::Steer)
+                .await
+        );
+        // Connections that never attached may not act on a subscribed thread,
+        // while threads nobody subscribed to keep the legacy behavior.
+        assert!(
+            !subscriptions
+                .permits(thread_id, ConnectionId(3), ThreadAction::Steer)
+                .await
+        );
+        assert!(
+            subscriptions
+                .permits(ThreadId::new(), ConnectionId(3), ThreadAction::Steer)
+                .await
+        );
+
+        let granted = subscriptions
+            .subscribe(
+                thread_id,
+                ConnectionId(2),
+                ThreadRights {
+                    approve: true,
+                    steer: false,
+                },
+            )
+            .await;
+        assert_eq!(
+            granted,
+            ThreadRights {
+                approve: true,
+                steer: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn threads_stay_claimed_after_their_subscribers_leave() {
+        let subscriptions = ThreadSubscriptions::default();
+        let thread_id = ThreadId::new();
+        subscriptions
+            .subscribe(thread_id, ConnectionId(1), ThreadRights::OWNER)
+            .await;
+        subscriptions
+            .subscribe(thread_id, ConnectionId(2), OBSERVER)
+            .await;
+
+        assert!(subscriptions.unsubscribe(thread_id, ConnectionId(1)).await);
+        assert!(subscriptions.unsubscribe(thread_id, ConnectionId(2)).await);
+
+        for connection_id in [ConnectionId(2), ConnectionId(3)] {
+            for action in [ThreadAction::Steer, ThreadAction::Approve] {
+                assert!(
+                    !subscriptions
+                        .permits(thread_id, connection_id, action)
+                        .await,
+                    "{connection_id:?} may not {action:?} after the owner detached"
+                );
+            }
+        }
+
+        // Unloading the thread forgets the claim along with its grants.
+        subscriptions
+            .subscribe(thread_id, ConnectionId(1), ThreadRights::OWNER)
+            .await;
+        let token = subscriptions
+            .grant(thread_id, ConnectionId(1), ThreadRights::OWNER)
+            .await
+            .expect("owner grant");
+        subscriptions.remove_connection(ConnectionId(1)).await;
+        assert!(
+            !subscriptions
+                .permits(thread_id, ConnectionId(3), ThreadAction::Steer)
+                .await
+        );
+        subscriptions.remove_thread(thread_id).await;
+        assert_eq!(subscriptions.redeem(thread_id, &token).await, None);
+        assert!(
+            subscriptions
+                .permits(thread_id, ConnectionId(3), ThreadAction::Steer)
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn owned_by_other_ignores_the_caller_and_observers() {
+        let subscriptions = ThreadSubscriptions::default();
+        let thread_id = ThreadId::new();
+        subscriptions
+            .subscribe(thread_id, ConnectionId(1), ThreadRights::OWNER)
+            .await;
+        subscriptions
+            .subscribe(thread_id, ConnectionId(2), OBSERVER)
+            .await;
+
+        assert!(
+            !subscriptions
+                .owned_by_other(thread_id, ConnectionId(1))
+                .await
+        );
+        assert!(
+            subscriptions
+                .owned_by_other(thread_id, ConnectionId(2))
+                .await
+        );
+
+        subscriptions.unsubscribe(thread_id, ConnectionId(1)).await;
+        assert!(
+            !subscriptions
+                .owned_by_other(thread_id, ConnectionId(2))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn grants_carry_only_rights_the_issuer_holds_and_are_single_use() {
+        let subscriptions = ThreadSubscriptions::default();
+        let thread_id = ThreadId::new();
+        let approve = ThreadRights {
+            approve: true,
+            steer: false,
+        };
+        subscriptions
+            .subscribe(thread_id, ConnectionId(1), ThreadRights::OWNER)
+            .await;
+        subscriptions
+            .subscribe(thread_id, ConnectionId(2), OBSERVER)
+            .await;
+
+        assert_eq!(
+            subscriptions
+                .grant(thread_id, ConnectionId(2), approve)
+                .await,
+            None
+        );
+        assert_eq!(
+            subscriptions
+                .grant(thread_id, ConnectionId(3), OBSERVER)
+                .await,
+            None
+        );
+
+        let token = subscriptions
+            .grant(thread_id, ConnectionId(1), approve)
+            .await
+            .expect("owner grant");
+        assert_eq!(subscriptions.redeem(thread_id, &token).await, Some(approve));
+        assert_eq!(subscriptions.redeem(thread_id, &token).await, None);
+
+        let token = subscriptions
+            .grant(thread_id, ConnectionId(1), approve)
+            .await
+            .expect("owner grant");
+        subscriptions.remove_connection(ConnectionId(1)).await;
+        assert_eq!(subscriptions.redeem(thread_id, &token).await, None);
+    }
+
+    #[tokio::test]
+    async fn removing_a_connection_drops_its_subscriptions() {
+        let subscriptions = ThreadSubscriptions::default();
+        let first = ThreadId::new();
+        let second = ThreadId::new();
+        subscriptions
+            .subscribe(first, ConnectionId(1), ThreadRights::OWNER)
+            .await;
+        subscriptions
+            .subscribe(first, ConnectionId(2), OBSERVER)
+            .await;
+        subscriptions
+            .subscribe(second, ConnectionId(2), OBSERVER)
+            .await;
+
+        subscriptions.remove_connection(ConnectionId(2)).await;
+
+        assert_eq!(
+            subscriptions.subscribers(first).await,
+            Some(vec![ConnectionId(1)])
+        );
+        assert_eq!(subscriptions.subscribers(second).await, Some(Vec::new()));
+        assert!(!subscriptions.unsubscribe(second, ConnectionId(2)).await);
+        assert!(subscriptions.unsubscribe(first, ConnectionId(1)).await);
+        assert_eq!(subscriptions.subscribers(first).await, Some(Vec::new()));
+        assert_eq!(subscriptions.subscribers(ThreadId::new()).await, None);
+    }
+}
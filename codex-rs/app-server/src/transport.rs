@@ -2,6 +2,11 @@ use crate::message_processor::ConnectionSessionState;
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::OutgoingEnvelope;
 use crate::outgoing_message::OutgoingMessage;
+use crate::thread_subscriptions::ThreadSubscriptions;
+use crate::websocket_security::AuditEvent;
+use crate::websocket_security::AuditRecord;
+use crate::websocket_security::WebSocketSecurity;
+use crate::websocket_security::certificate_fingerprint;
 use codex_app_server_protocol::JSONRPCMessage;
 use futures::SinkExt;
 use futures::StreamExt;
@@ -16,7 +21,10 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::io::{self};
@@ -24,8 +32,11 @@ use tokio::net::TcpListener;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
-use tokio_tungstenite::accept_async;
+use tokio_tungstenite::accept_hdr_async;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+use tokio_tungstenite::tungstenite::handshake::server::Response;
+use tokio_tungstenite::tungstenite::http::header::ORIGIN;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
@@ -42,20 +53,30 @@ fn colorize(text: &str, style: Style) -> String {
 }
 
 #[allow(clippy::print_stderr)]
-fn print_websocket_startup_banner(addr: SocketAddr) {
+fn print_websocket_startup_banner(addr: SocketAddr, security: &WebSocketSecurity) {
     let title = colorize("codex app-server (WebSockets)", Style::new().bold().cyan());
     let listening_label = colorize("listening on:", Style::new().dimmed());
-    let listen_url = colorize(&format!("ws://{addr}"), Style::new().green());
+    let scheme = if security.tls_acceptor().is_some() {
+        "wss"
+    } else {
+        "ws"
+    };
+    let listen_url = colorize(&format!("{scheme}://{addr}"), Style::new().green());
     let note_label = colorize("note:", Style::new().dimmed());
     eprintln!("{title}");
     eprintln!("  {listening_label} {listen_url}");
-    if addr.ip().is_loopback() {
+    if security.requires_authentication() {
+        eprintln!(
+            "  {note_label} clients must authenticate ({})",
+            security.auth_method()
+        );
+    } else if addr.ip().is_loopback() {
         eprintln!(
             "  {note_label} binds localhost only (use SSH port-forwarding for remote access)"
         );
     } else {
         eprintln!(
-            "  {note_label} this is a raw WS server; consider running behind TLS/auth for real remote use"
+            "  {note_label} no authentication is configured; use --ws-token-file or --tls-client-ca for real remote use"
         );
     }
 }
@@ -69,7 +90,11 @@ fn print_websocket_connection(peer_addr: SocketAddr) {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AppServerTransport {
     Stdio,
-    WebSocket { bind_address: SocketAddr },
+    /// `ws://IP:PORT`, or `wss://IP:PORT` when `tls` is set.
+    WebSocket {
+        bind_address: SocketAddr,
+        tls: bool,
+    },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -83,12 +108,19 @@ impl std::fmt::Display for AppServerTransportParseError {
         match self {
             AppServerTransportParseError::UnsupportedListenUrl(listen_url) => write!(
                 f,
-                "unsupported --listen URL `{listen_url}`; expected `stdio://` or `ws://IP:PORT`"
-            ),
-            AppServerTransportParseError::InvalidWebSocketListenUrl(listen_url) => write!(
-                f,
-                "invalid websocket --listen URL `{listen_url}`; expected `ws://IP:PORT`"
+                "unsupported --listen URL `{listen_url}`; expected `stdio://`, `ws://IP:PORT` or `wss://IP:PORT`"
             ),
+            AppServerTransportParseError::InvalidWebSocketListenUrl(listen_url) => {
+                let scheme = if listen_url.starts_with("wss://") {
+                    "wss"
+                } else {
+                    "ws"
+                };
+                write!(
+                    f,
+                    "invalid websocket --listen URL `{listen_url}`; expected `{scheme}://IP:PORT`"
+                )
+            }
         }
     }
 }
@@ -103,11 +135,19 @@ impl AppServerTransport {
             return Ok(Self::Stdio);
         }
 
-        if let Some(socket_addr) = listen_url.strip_prefix("ws://") {
+        let websocket_address = listen_url
+            .strip_prefix("ws://")
+            .map(|socket_addr| (socket_addr, false))
+            .or_else(|| {
+                listen_url
+                    .strip_prefix("wss://")
+                    .map(|socket_addr| (socket_addr, true))
+            });
+        if let Some((socket_addr, tls)) = websocket_address {
             let bind_address = socket_addr.parse::<SocketAddr>().map_err(|_| {
                 AppServerTransportParseError::InvalidWebSocketListenUrl(listen_url.to_string())
             })?;
-            return Ok(Self::WebSocket { bind_address });
+            return Ok(Self::WebSocket { bind_address, tls });
         }
 
         Err(AppServerTransportParseError::UnsupportedListenUrl(
@@ -220,13 +260,18 @@ pub(crate) async fn start_stdio_connection(
 
 pub(crate) async fn start_websocket_acceptor(
     bind_address: SocketAddr,
+    security: WebSocketSecurity,
     transport_event_tx: mpsc::Sender<TransportEvent>,
 ) -> IoResult<JoinHandle<()>> {
     let listener = TcpListener::bind(bind_address).await?;
     let local_addr = listener.local_addr()?;
-    print_websocket_startup_banner(local_addr);
-    info!("app-server websocket listening on ws://{local_addr}");
+    print_websocket_startup_banner(local_addr, &security);
+    info!(
+        "app-server websocket listening on {local_addr} (auth: {})",
+        security.auth_method()
+    );
 
+    let security = Arc::new(security);
     let connection_counter = Arc::new(AtomicU64::new(1));
     Ok(tokio::spawn(async move {
         loop {
@@ -235,11 +280,14 @@ pub(crate) async fn start_websocket_acceptor(
                     print_websocket_connection(peer_addr);
                     let connection_id =
                         ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
+                    let security = Arc::clone(&security);
                     let transport_event_tx_for_connection = transport_event_tx.clone();
                     tokio::spawn(async move {
                         run_websocket_connection(
                             connection_id,
                             stream,
+                            peer_addr,
+                            security,
                             transport_event_tx_for_connection,
                         )
                         .await;
@@ -256,15 +304,84 @@ pub(crate) async fn start_websocket_acceptor(
 async fn run_websocket_connection(
     connection_id: ConnectionId,
     stream: TcpStream,
+    peer_addr: SocketAddr,
+    security: Arc<WebSocketSecurity>,
     transport_event_tx: mpsc::Sender<TransportEvent>,
 ) {
-    let websocket_stream = match accept_async(stream).await {
+    let Some(tls_acceptor) = security.tls_acceptor() else {
+        let audit = AuditRecord::new(connection_id.0, peer_addr, false);
+        serve_websocket_connection(connection_id, stream, audit, &security, transport_event_tx)
+            .await;
+        return;
+    };
+
+    let mut audit = AuditRecord::new(connection_id.0, peer_addr, true);
+    let stream = match tls_acceptor.accept(stream).await {
         Ok(stream) => stream,
         Err(err) => {
-            warn!("failed to complete websocket handshake: {err}");
+            warn!("failed to complete TLS handshake with {peer_addr}: {err}");
+            audit.reason = Some(format!("tls handshake failed: {err}"));
+            security.audit_log().record(AuditEvent::Rejected, &audit);
             return;
         }
     };
+    audit.client_certificate_sha256 = stream
+        .get_ref()
+        .1
+        .peer_certificates()
+        .and_then(<[_]>::first)
+        .map(certificate_fingerprint);
+    serve_websocket_connection(connection_id, stream, audit, &security, transport_event_tx).await;
+}
+
+async fn serve_websocket_connection<S>(
+    connection_id: ConnectionId,
+    stream: S,
+    mut audit: AuditRecord,
+    security: &WebSocketSecurity,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut origin = None;
+    let mut rejection = None;
+    let handshake = accept_hdr_async(stream, |request: &Request, response: Response| {
+        origin = request
+            .headers()
+            .get(ORIGIN)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        match security.check_handshake(request.headers()) {
+            Ok(()) => Ok(response),
+            Err(err) => {
+                rejection = Some(err);
+                Err(err.into_response())
+            }
+        }
+    })
+    .await;
+    audit.origin = origin;
+    let websocket_stream = match handshake {
+        Ok(stream) => stream,
+        Err(err) => {
+            let reason = match rejection {
+                Some(rejection) => rejection.reason.to_string(),
+                None => format!("websocket handshake failed: {err}"),
+            };
+            warn!(
+                "rejected websocket connection from {}: {reason}",
+                audit.peer
+            );
+            audit.reason = Some(reason);
+            security.audit_log().record(AuditEvent::Rejected, &audit);
+            return;
+        }
+    };
+    audit.auth = Some(security.auth_method());
+    security.audit_log().record(AuditEvent::Accepted, &audit);
+    let connected_at = Instant::now();
+    let mut messages_received = 0u64;
+    let mut messages_sent = 0u64;
 
     let (writer_tx, mut writer_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
     if transport_event_tx
@@ -291,10 +408,12 @@ async fn run_websocket_connection(
                 if websocket_writer.send(WebSocketMessage::Text(json.into())).await.is_err() {
                     break;
                 }
+                messages_sent += 1;
             }
             incoming_message = websocket_reader.next() => {
                 match incoming_message {
                     Some(Ok(WebSocketMessage::Text(text))) => {
+                        messages_received += 1;
                         if !forward_incoming_message(&transport_event_tx, connection_id, &text).await {
                             break;
                         }
@@ -322,6 +441,10 @@ async fn run_websocket_connection(
     let _ = transport_event_tx
         .send(TransportEvent::ConnectionClosed { connection_id })
         .await;
+    audit.duration_ms = Some(u64::try_from(connected_at.elapsed().as_millis()).unwrap_or(u64::MAX));
+    audit.messages_received = Some(messages_received);
+    audit.messages_sent = Some(messages_sent);
+    security.audit_log().record(AuditEvent::Closed, &audit);
 }
 
 async fn forward_incoming_message(
@@ -363,6 +486,7 @@ fn serialize_outgoing_message(outgoing_message: OutgoingMessage) -> Option<Strin
 
 pub(crate) async fn route_outgoing_envelope(
     connections: &mut HashMap<ConnectionId, ConnectionState>,
+    subscriptions: &ThreadSubscriptions,
     envelope: OutgoingEnvelope,
 ) {
     match envelope {
@@ -381,27 +505,51 @@ pub(crate) async fn route_outgoing_envelope(
                 connections.remove(&connection_id);
             }
         }
-        OutgoingEnvelope::Broadcast { message } => {
-            let target_connections: Vec<ConnectionId> = connections
-                .iter()
-                .filter_map(|(connection_id, connection_state)| {
-                    if connection_state.session.initialized {
-                        Some(*connection_id)
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-
-            for connection_id in target_connections {
+        OutgoingEnvelope::ToThread { thread_id, message } => {
+            // Threads nobody ever attached to reach every connection; once
+            // claimed, only the current subscribers hear from them.
+            let Some(subscribers) = subscriptions.subscribers(thread_id).await else {
+                broadcast(connections, message).await;
+                return;
+            };
+            for connection_id in subscribers {
                 let Some(connection_state) = connections.get(&connection_id) else {
                     continue;
                 };
+                if !connection_state.session.initialized {
+                    continue;
+                }
                 if connection_state.writer.send(message.clone()).await.is_err() {
                     connections.remove(&connection_id);
                 }
             }
         }
+        OutgoingEnvelope::Broadcast { message } => broadcast(connections, message).await,
+    }
+}
+
+async fn broadcast(
+    connections: &mut HashMap<ConnectionId, ConnectionState>,
+    message: OutgoingMessage,
+) {
+    let target_connections: Vec<ConnectionId> = connections
+        .iter()
+        .filter_map(|(connection_id, connection_state)| {
+            if connection_state.session.initialized {
+                Some(*connection_id)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    for connection_id in target_connections {
+        let Some(connection_state) = connections.get(&connection_id) else {
+            continue;
+        };
+        if connection_state.writer.send(message.clone()).await.is_err() {
+            connections.remove(&connection_id);
+        }
     }
 }
 
@@ -433,10 +581,30 @@ mod tests {
             transport,
             AppServerTransport::WebSocket {
                 bind_address: "127.0.0.1:1234".parse().expect("valid socket address"),
+                tls: false,
             }
         );
     }
 
+    #[test]
+    fn app_server_transport_parses_secure_websocket_listen_url() {
+        let transport = AppServerTransport::from_listen_url("wss://0.0.0.0:4500")
+            .expect("secure websocket listen URL should parse");
+        assert_eq!(
+            transport,
+            AppServerTransport::WebSocket {
+                bind_address: "0.0.0.0:4500".parse().expect("valid socket address"),
+                tls: true,
+            }
+        );
+        let err = AppServerTransport::from_listen_url("wss://localhost:4500")
+            .expect_err("hostname bind address should be rejected");
+        assert_eq!(
+            err.to_string(),
+            "invalid websocket --listen URL `wss://localhost:4500`; expected `wss://IP:PORT`"
+        );
+    }
+
     #[test]
     fn app_server_transport_rejects_invalid_websocket_listen_url() {
         let err = AppServerTransport::from_listen_url("ws://localhost:1234")
@@ -453,7 +621,7 @@ mod tests {
             .expect_err("unsupported scheme should fail");
         assert_eq!(
             err.to_string(),
-            "unsupported --listen URL `http://127.0.0.1:1234`; expected `stdio://` or `ws://IP:PORT`"
+            "unsupported --listen URL `http://127.0.0.1:1234`; expected `stdio://`, `ws://IP:PORT` or `wss://IP:PORT`"
         );
     }
 }
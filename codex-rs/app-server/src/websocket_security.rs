@@ -0,0 +1,497 @@
+//! Authentication, TLS and audit logging for the WebSocket transport.
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use clap::Args;
+use rustls::RootCertStore;
+use rustls::ServerConfig;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::pem::PemObject;
+use rustls::server::WebPkiClientVerifier;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse;
+use tokio_tungstenite::tungstenite::http::HeaderMap;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tokio_tungstenite::tungstenite::http::header::ORIGIN;
+use tokio_tungstenite::tungstenite::http::header::WWW_AUTHENTICATE;
+use tracing::info;
+use tracing::warn;
+
+/// Command-line options that secure the WebSocket transport. All of them are
+/// ignored by (and rejected for) the stdio transport.
+#[derive(Args, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WebSocketOptions {
+    /// File containing a token clients must send as
+    /// `Authorization: Bearer <token>` when opening the WebSocket.
+    #[arg(long = "ws-token-file", value_name = "FILE")]
+    pub token_file: Option<PathBuf>,
+
+    /// PEM certificate chain served to clients. Requires a `wss://` listen
+    /// URL and `--tls-key`.
+    #[arg(long = "tls-cert", value_name = "FILE", requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key matching `--tls-cert`.
+    #[arg(long = "tls-key", value_name = "FILE", requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+
+    /// PEM CA bundle. When set, clients must present a certificate issued by
+    /// one of these CAs (mutual TLS).
+    #[arg(long = "tls-client-ca", value_name = "FILE", requires = "tls_cert")]
+    pub tls_client_ca: Option<PathBuf>,
+
+    /// Browser origin allowed to connect, e.g. `https://ide.example.com`. May
+    /// be repeated. Handshakes carrying any other `Origin` header are
+    /// rejected; clients that send no `Origin` header are unaffected.
+    #[arg(long = "ws-allowed-origin", value_name = "ORIGIN")]
+    pub allowed_origins: Vec<String>,
+
+    /// Append one JSON line per connection event (accepted, rejected,
+    /// closed) to this file.
+    #[arg(long = "ws-audit-log", value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+}
+
+impl WebSocketOptions {
+    pub(crate) fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// The resolved form of [`WebSocketOptions`] shared by every connection.
+pub(crate) struct WebSocketSecurity {
+    auth_token: Option<String>,
+    tls_acceptor: Option<TlsAcceptor>,
+    client_certificates_required: bool,
+    allowed_origins: Vec<String>,
+    audit_log: AuditLog,
+}
+
+/// Why a WebSocket handshake was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HandshakeRejection {
+    pub(crate) status: StatusCode,
+    pub(crate) reason: &'static str,
+}
+
+impl HandshakeRejection {
+    pub(crate) fn into_response(self) -> ErrorResponse {
+        let mut response = ErrorResponse::new(Some(format!("{}\n", self.reason)));
+        *response.status_mut() = self.status;
+        if self.status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
+
+impl WebSocketSecurity {
+    pub(crate) fn from_options(options: &WebSocketOptions, tls: bool) -> IoResult<Self> {
+        let tls_acceptor = match (tls, &options.tls_cert, &options.tls_key) {
+            (true, Some(cert), Some(key)) => Some(build_tls_acceptor(
+                cert,
+                key,
+                options.tls_client_ca.as_deref(),
+            )?),
+            (true, _, _) => {
+                return Err(invalid_input(
+                    "a wss:// listen URL requires --tls-cert and --tls-key".to_string(),
+                ));
+            }
+            (false, None, None) => None,
+            (false, _, _) => {
+                return Err(invalid_input(
+                    "--tls-cert and --tls-key require a wss:// listen URL".to_string(),
+                ));
+            }
+        };
+        let auth_token = options
+            .token_file
+            .as_deref()
+            .map(read_auth_token)
+            .transpose()?;
+        Ok(Self {
+            auth_token,
+            tls_acceptor,
+            client_certificates_required: options.tls_client_ca.is_some(),
+            allowed_origins: options
+                .allowed_origins
+                .iter()
+                .map(|origin| origin.trim_end_matches('/').to_string())
+                .collect(),
+            audit_log: AuditLog::open(options.audit_log.as_deref())?,
+        })
+    }
+
+    pub(crate) fn tls_acceptor(&self) -> Option<&TlsAcceptor> {
+        self.tls_acceptor.as_ref()
+    }
+
+    pub(crate) fn audit_log(&self) -> &AuditLog {
+        &self.audit_log
+    }
+
+    /// Whether clients must prove who they are before a connection opens.
+    pub(crate) fn requires_authentication(&self) -> bool {
+        self.auth_token.is_some() || self.client_certificates_required
+    }
+
+    /// How an accepted client authenticated, for the audit log.
+    pub(crate) fn auth_method(&self) -> &'static str {
+        match (self.auth_token.is_some(), self.client_certificates_required) {
+            (true, true) => "bearer+client-certificate",
+            (true, false) => "bearer",
+            (false, true) => "client-certificate",
+            (false, false) => "none",
+        }
+    }
+
+    /// Check the origin and bearer token of an upgrade request. Client
+    /// certificates are verified earlier, during the TLS handshake.
+    pub(crate) fn check_handshake(&self, headers: &HeaderMap) -> Result<(), HandshakeRejection> {
+        if let Some(origin) = headers.get(ORIGIN) {
+            let allowed = origin.to_str().is_ok_and(|origin| {
+                let origin = origin.trim_end_matches('/');
+                self.allowed_origins
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+            });
+            if !allowed {
+                return Err(HandshakeRejection {
+                    status: StatusCode::FORBIDDEN,
+                    reason: "origin not allowed",
+                });
+            }
+        }
+
+        if let Some(expected) = &self.auth_token {
+            let provided = headers
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "));
+            match provided {
+                Some(token) if constant_time_eq(token.trim().as_bytes(), expected.as_bytes()) => {}
+                Some(_) => {
+                    return Err(HandshakeRejection {
+                        status: StatusCode::UNAUTHORIZED,
+                        reason: "invalid bearer token",
+                    });
+                }
+                None => {
+                    return Err(HandshakeRejection {
+                        status: StatusCode::UNAUTHORIZED,
+                        reason: "missing bearer token",
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_auth_token(path: &Path) -> IoResult<String> {
+    let token = std::fs::read_to_string(path).map_err(|err| {
+        std::io::Error::new(
+            err.kind(),
+            format!("failed to read --ws-token-file {}: {err}", path.display()),
+        )
+    })?;
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(invalid_input(format!(
+            "--ws-token-file {} is empty",
+            path.display()
+        )));
+    }
+    Ok(token.to_string())
+}
+
+fn build_tls_acceptor(
+    cert_path: &Path,
+    key_path: &Path,
+    client_ca_path: Option<&Path>,
+) -> IoResult<TlsAcceptor> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let certificates = read_certificates(cert_path)?;
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(|err| {
+        invalid_input(format!(
+            "failed to read private key {}: {err}",
+            key_path.display()
+        ))
+    })?;
+
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(|err| invalid_input(err.to_string()))?;
+    let builder = match client_ca_path {
+        Some(client_ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for certificate in read_certificates(client_ca_path)? {
+                roots.add(certificate).map_err(|err| {
+                    invalid_input(format!(
+                        "invalid client CA certificate in {}: {err}",
+                        client_ca_path.display()
+                    ))
+                })?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .map_err(|err| invalid_input(err.to_string()))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let mut config = builder
+        .with_single_cert(certificates, key)
+        .map_err(|err| invalid_input(format!("invalid TLS certificate or key: {err}")))?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+fn read_certificates(path: &Path) -> IoResult<Vec<CertificateDer<'static>>> {
+    let certificates = CertificateDer::pem_file_iter(path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| {
+            invalid_input(format!(
+                "failed to read certificates {}: {err}",
+                path.display()
+            ))
+        })?;
+    if certificates.is_empty() {
+        return Err(invalid_input(format!(
+            "no certificates found in {}",
+            path.display()
+        )));
+    }
+    Ok(certificates)
+}
+
+fn invalid_input(message: String) -> std::io::Error {
+    std::io::Error::new(ErrorKind::InvalidInput, message)
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    left.iter()
+        .zip(right)
+        .fold(0u8, |difference, (left, right)| difference | (left ^ right))
+        == 0
+}
+
+/// SHA-256 fingerprint of a DER certificate, as lowercase hex.
+pub(crate) fn certificate_fingerprint(certificate: &CertificateDer<'_>) -> String {
+    Sha256::digest(certificate.as_ref())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AuditEvent {
+    Accepted,
+    Rejected,
+    Closed,
+}
+
+/// One audit log entry for a WebSocket connection.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AuditRecord {
+    pub(crate) timestamp: String,
+    pub(crate) event: AuditEvent,
+    pub(crate) connection_id: u64,
+    pub(crate) peer: SocketAddr,
+    pub(crate) tls: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) client_certificate_sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) auth: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) origin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) messages_received: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) messages_sent: Option<u64>,
+}
+
+impl AuditRecord {
+    pub(crate) fn new(connection_id: u64, peer: SocketAddr, tls: bool) -> Self {
+        Self {
+            timestamp: String::new(),
+            event: AuditEvent::Accepted,
+            connection_id,
+            peer,
+            tls,
+            client_certificate_sha256: None,
+            auth: None,
+            origin: None,
+            reason: None,
+            duration_ms: None,
+            messages_received: None,
+            messages_sent: None,
+        }
+    }
+}
+
+/// Writes audit records to the tracing log and, when configured, a JSONL file.
+#[derive(Clone, Default)]
+pub(crate) struct AuditLog {
+    file: Option<Arc<Mutex<File>>>,
+}
+
+impl AuditLog {
+    fn open(path: Option<&Path>) -> IoResult<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| {
+                std::io::Error::new(
+                    err.kind(),
+                    format!("failed to open --ws-audit-log {}: {err}", path.display()),
+                )
+            })?;
+        Ok(Self {
+            file: Some(Arc::new(Mutex::new(file))),
+        })
+    }
+
+    pub(crate) fn record(&self, event: AuditEvent, record: &AuditRecord) {
+        let mut record = record.clone();
+        record.event = event;
+        record.timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let mut line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(err) => {
+                warn!("failed to serialize websocket audit record: {err}");
+                return;
+            }
+        };
+        info!(target: "codex_app_server::websocket_audit", "{line}");
+
+        let Some(file) = &self.file else {
+            return;
+        };
+        line.push('\n');
+        let result = match file.lock() {
+            Ok(mut file) => file.write_all(line.as_bytes()).and_then(|()| file.flush()),
+            Err(_) => Err(std::io::Error::other("audit log lock poisoned")),
+        };
+        if let Err(err) = result {
+            warn!("failed to write websocket audit record: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn security(auth_token: Option<&str>, allowed_origins: &[&str]) -> WebSocketSecurity {
+        WebSocketSecurity {
+            auth_token: auth_token.map(str::to_string),
+            tls_acceptor: None,
+            client_certificates_required: false,
+            allowed_origins: allowed_origins.iter().map(|s| s.to_string()).collect(),
+            audit_log: AuditLog::default(),
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn bearer_token_is_required_when_configured() {
+        let security = security(Some("s3cret"), &[]);
+        assert_eq!(
+            security.check_handshake(&headers(&[("authorization", "Bearer s3cret")])),
+            Ok(())
+        );
+        assert_eq!(
+            security
+                .check_handshake(&headers(&[("authorization", "Bearer wrong")]))
+                .map_err(|rejection| (rejection.status, rejection.reason)),
+            Err((StatusCode::UNAUTHORIZED, "invalid bearer token"))
+        );
+        assert_eq!(
+            security
+                .check_handshake(&headers(&[]))
+                .map_err(|rejection| (rejection.status, rejection.reason)),
+            Err((StatusCode::UNAUTHORIZED, "missing bearer token"))
+        );
+        assert_eq!(security.auth_method(), "bearer");
+    }
+
+    #[test]
+    fn origins_must_be_allowlisted() {
+        let security = security(None, &["https://ide.example.com"]);
+        assert_eq!(security.check_handshake(&headers(&[])), Ok(()));
+        assert_eq!(
+            security.check_handshake(&headers(&[("origin", "https://IDE.example.com")])),
+            Ok(())
+        );
+        assert_eq!(
+            security
+                .check_handshake(&headers(&[("origin", "https://evil.example.com")]))
+                .map_err(|rejection| (rejection.status, rejection.reason)),
+            Err((StatusCode::FORBIDDEN, "origin not allowed"))
+        );
+    }
+
+    #[test]
+    fn tls_options_must_match_listen_scheme() {
+        let options = WebSocketOptions {
+            tls_cert: Some(PathBuf::from("cert.pem")),
+            tls_key: Some(PathBuf::from("key.pem")),
+            ..Default::default()
+        };
+        let err = WebSocketSecurity::from_options(&options, false)
+            .err()
+            .expect("plain ws:// with TLS files should fail");
+        assert_eq!(
+            err.to_string(),
+            "--tls-cert and --tls-key require a wss:// listen URL"
+        );
+
+        let err = WebSocketSecurity::from_options(&WebSocketOptions::default(), true)
+            .err()
+            .expect("wss:// without TLS files should fail");
+        assert_eq!(
+            err.to_string(),
+            "a wss:// listen URL requires --tls-cert and --tls-key"
+        );
+    }
+}
@@ -1,20 +1,46 @@
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
+use app_test_support::create_final_assistant_message_sse_response;
+use app_test_support::create_mock_responses_server_sequence;
 use app_test_support::create_mock_responses_server_sequence_unchecked;
+use app_test_support::create_shell_command_sse_response;
+use app_test_support::to_response;
 use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::CommandExecutionApprovalDecision;
+use codex_app_server_protocol::CommandExecutionRequestApprovalResponse;
 use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ServerRequestResolvedNotification;
+use codex_app_server_protocol::ThreadAttachResponse;
+use codex_app_server_protocol::ThreadGrantResponse;
+use codex_app_server_protocol::ThreadStartResponse;
 use futures::SinkExt;
 use futures::StreamExt;
+use rcgen::BasicConstraints;
+use rcgen::CertificateParams;
+use rcgen::DnType;
+use rcgen::ExtendedKeyUsagePurpose;
+use rcgen::IsCa;
+use rcgen::Issuer;
+use rcgen::KeyPair;
+use rcgen::KeyUsagePurpose;
+use rustls::ClientConfig;
+use rustls::RootCertStore;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::PrivatePkcs8KeyDer;
 use serde_json::json;
 use std::net::SocketAddr;
 use std::path::Path;
+use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::Arc;
 use tempfile::TempDir;
 use tokio::io::AsyncBufReadExt;
 use tokio::process::Child;
@@ -23,10 +49,18 @@ use tokio::time::Duration;
 use tokio::time::Instant;
 use tokio::time::sleep;
 use tokio::time::timeout;
+use tokio_tungstenite::Connector;
 use tokio_tungstenite::MaybeTlsStream;
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::connect_async;
+use tokio_tungstenite::connect_async_tls_with_config;
+use tokio_tungstenite::tungstenite::Error as WebSocketError;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tokio_tungstenite::tungstenite::http::header::ORIGIN;
 
 const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
 
@@ -39,7 +73,11 @@ async fn websocket_transport_routes_per_connection_handshake_and_responses() ->
     create_config_toml(codex_home.path(), &server.uri(), "never")?;
 
     let bind_addr = reserve_local_addr()?;
-    let mut process = spawn_websocket_server(codex_home.path(), bind_addr).await?;
+    let mut process = spawn_websocket_server(
+        codex_home.path(),
+        &["--listen".to_string(), format!("ws://{bind_addr}")],
+    )
+    .await?;
 
     let mut ws1 = connect_websocket(bind_addr).await?;
     let mut ws2 = connect_websocket(bind_addr).await?;
@@ -78,12 +116,349 @@ async fn websocket_transport_routes_per_connection_handshake_and_responses() ->
     Ok(())
 }
 
-async fn spawn_websocket_server(codex_home: &Path, bind_addr: SocketAddr) -> Result<Child> {
+#[tokio::test]
+async fn websocket_transport_requires_bearer_token_and_allowed_origin_over_tls() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let certificates = TestCertificates::generate(codex_home.path())?;
+    let token_file = codex_home.path().join("ws-token");
+    std::fs::write(&token_file, "s3cret-token\n")?;
+    let audit_log = codex_home.path().join("ws-audit.jsonl");
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server(
+        codex_home.path(),
+        &[
+            "--listen".to_string(),
+            format!("wss://{bind_addr}"),
+            "--tls-cert".to_string(),
+            certificates.server_cert.display().to_string(),
+            "--tls-key".to_string(),
+            certificates.server_key.display().to_string(),
+            "--ws-token-file".to_string(),
+            token_file.display().to_string(),
+            "--ws-allowed-origin".to_string(),
+            "https://ide.example.com".to_string(),
+            "--ws-audit-log".to_string(),
+            audit_log.display().to_string(),
+        ],
+    )
+    .await?;
+
+    let client_config = certificates.client_config(false)?;
+    let mut ws = connect_secure_websocket(
+        bind_addr,
+        Arc::clone(&client_config),
+        Some("s3cret-token"),
+        Some("https://ide.example.com"),
+    )
+    .await?;
+    send_initialize_request(&mut ws, 1, "wss_client").await?;
+    let init = read_response_for_id(&mut ws, 1).await?;
+    assert_eq!(init.id, RequestId::Integer(1));
+
+    assert_eq!(
+        rejected_handshake_status(bind_addr, Arc::clone(&client_config), Some("wrong"), None)
+            .await?,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        rejected_handshake_status(bind_addr, Arc::clone(&client_config), None, None).await?,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        rejected_handshake_status(
+            bind_addr,
+            Arc::clone(&client_config),
+            Some("s3cret-token"),
+            Some("https://evil.example.com"),
+        )
+        .await?,
+        StatusCode::FORBIDDEN
+    );
+
+    ws.close(None).await?;
+    let records = read_audit_records(&audit_log, 5).await?;
+    let mut events: Vec<(String, String)> = records
+        .iter()
+        .map(|record| {
+            (
+                record["event"].as_str().unwrap_or_default().to_string(),
+                record["reason"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect();
+    events.sort();
+    assert_eq!(
+        events,
+        vec![
+            ("accepted".to_string(), String::new()),
+            ("closed".to_string(), String::new()),
+            ("rejected".to_string(), "invalid bearer token".to_string()),
+            ("rejected".to_string(), "missing bearer token".to_string()),
+            ("rejected".to_string(), "origin not allowed".to_string()),
+        ]
+    );
+    let accepted = records
+        .iter()
+        .find(|record| record["event"] == "accepted")
+        .context("accepted audit record")?;
+    assert_eq!(accepted["auth"], "bearer");
+    assert_eq!(accepted["tls"], true);
+    assert_eq!(accepted["origin"], "https://ide.example.com");
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn websocket_transport_requires_client_certificate_with_mutual_tls() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let certificates = TestCertificates::generate(codex_home.path())?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server(
+        codex_home.path(),
+        &[
+            "--listen".to_string(),
+            format!("wss://{bind_addr}"),
+            "--tls-cert".to_string(),
+            certificates.server_cert.display().to_string(),
+            "--tls-key".to_string(),
+            certificates.server_key.display().to_string(),
+            "--tls-client-ca".to_string(),
+            certificates.ca_cert.display().to_string(),
+        ],
+    )
+    .await?;
+
+    let mut ws =
+        connect_secure_websocket(bind_addr, certificates.client_config(true)?, None, None).await?;
+    send_initialize_request(&mut ws, 1, "mtls_client").await?;
+    let init = read_response_for_id(&mut ws, 1).await?;
+    assert_eq!(init.id, RequestId::Integer(1));
+
+    let without_certificate =
+        try_connect_secure_websocket(bind_addr, certificates.client_config(false)?, None, None)
+            .await;
+    assert!(
+        without_certificate.is_err(),
+        "connection without a client certificate should be refused"
+    );
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn websocket_clients_attached_to_a_thread_share_events_and_approvals() -> Result<()> {
+    let responses = vec![
+        create_shell_command_sse_response(
+            vec![
+                "python3".to_string(),
+                "-c".to_string(),
+                "print(42)".to_string(),
+            ],
+            None,
+            Some(5000),
+            "call-shared",
+        )?,
+        create_final_assistant_message_sse_response("done")?,
+    ];
+    let server = create_mock_responses_server_sequence(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "untrusted")?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server(
+        codex_home.path(),
+        &["--listen".to_string(), format!("ws://{bind_addr}")],
+    )
+    .await?;
+
+    let mut owner = connect_websocket(bind_addr).await?;
+    let mut observer = connect_websocket(bind_addr).await?;
+    send_initialize_request(&mut owner, 1, "ws_owner").await?;
+    read_response_for_id(&mut owner, 1).await?;
+    send_initialize_request(&mut observer, 1, "ws_observer").await?;
+    read_response_for_id(&mut observer, 1).await?;
+
+    send_request(
+        &mut owner,
+        "thread/start",
+        2,
+        Some(json!({ "model": "mock-model" })),
+    )
+    .await?;
+    let started: ThreadStartResponse = to_response(read_response_for_id(&mut owner, 2).await?)?;
+    let thread_id = started.thread.id;
+
+    // Approval rights are only handed out by a connection holding them.
+    send_request(
+        &mut observer,
+        "thread/attach",
+        2,
+        Some(json!({ "threadId": thread_id, "canApprove": true })),
+    )
+    .await?;
+    let ungranted = read_error_for_id(&mut observer, 2).await?;
+    assert_eq!(
+        ungranted.error.message,
+        format!("canApprove and canSteer need a grant from thread/grant on thread {thread_id}")
+    );
+
+    send_request(
+        &mut owner,
+        "thread/grant",
+        4,
+        Some(json!({ "threadId": thread_id, "canApprove": true })),
+    )
+    .await?;
+    let granted: ThreadGrantResponse = to_response(read_response_for_id(&mut owner, 4).await?)?;
+    send_request(
+        &mut observer,
+        "thread/attach",
+        6,
+        Some(json!({ "threadId": thread_id, "canApprove": true, "grant": granted.grant })),
+    )
+    .await?;
+    let attached: ThreadAttachResponse =
+        to_response(read_response_for_id(&mut observer, 6).await?)?;
+    assert_eq!(attached.thread.id, thread_id);
+    assert!(attached.can_approve);
+    assert!(!attached.can_steer);
+
+    // The observer was not granted steering rights.
+    let turn_input = json!({
+        "threadId": thread_id,
+        "input": [{ "type": "text", "text": "run python", "text_elements": [] }],
+    });
+    send_request(&mut observer, "turn/start", 3, Some(turn_input.clone())).await?;
+    let rejected = read_error_for_id(&mut observer, 3).await?;
+    assert_eq!(
+        rejected.error.message,
+        format!("connection is not allowed to steer thread {thread_id}")
+    );
+
+    send_request(&mut owner, "turn/start", 3, Some(turn_input.clone())).await?;
+    read_response_for_id(&mut owner, 3).await?;
+
+    // Both clients see the same approval request; the observer answers it.
+    let owner_request =
+        read_request_for_method(&mut owner, "item/commandExecution/requestApproval").await?;
+    let observer_request =
+        read_request_for_method(&mut observer, "item/commandExecution/requestApproval").await?;
+    assert_eq!(owner_request.id, observer_request.id);
+    send_jsonrpc(
+        &mut observer,
+        JSONRPCMessage::Response(JSONRPCResponse {
+            id: observer_request.id.clone(),
+            result: serde_json::to_value(CommandExecutionRequestApprovalResponse {
+                decision: CommandExecutionApprovalDecision::Decline,
+            })?,
+        }),
+    )
+    .await?;
+
+    let resolved: ServerRequestResolvedNotification = serde_json::from_value(
+        read_notification_for_method(&mut owner, "serverRequest/resolved")
+            .await?
+            .params
+            .context("serverRequest/resolved params")?,
+    )?;
+    assert_eq!(resolved.thread_id, thread_id);
+    assert_eq!(resolved.request_id, owner_request.id);
+
+    // A late answer from the owner is ignored and the turn still completes
+    // for both clients.
+    send_jsonrpc(
+        &mut owner,
+        JSONRPCMessage::Response(JSONRPCResponse {
+            id: owner_request.id,
+            result: serde_json::to_value(CommandExecutionRequestApprovalResponse {
+                decision: CommandExecutionApprovalDecision::Accept,
+            })?,
+        }),
+    )
+    .await?;
+    read_notification_for_method(&mut owner, "turn/completed").await?;
+    read_notification_for_method(&mut observer, "turn/completed").await?;
+
+    // Resuming a thread another connection drives does not take it over.
+    send_request(
+        &mut observer,
+        "thread/resume",
+        7,
+        Some(json!({ "threadId": thread_id })),
+    )
+    .await?;
+    let resume_rejected = read_error_for_id(&mut observer, 7).await?;
+    assert_eq!(
+        resume_rejected.error.message,
+        format!(
+            "thread {thread_id} is driven by another connection; use thread/attach with a grant from thread/grant"
+        )
+    );
+
+    send_request(
+        &mut observer,
+        "thread/detach",
+        4,
+        Some(json!({ "threadId": thread_id })),
+    )
+    .await?;
+    read_response_for_id(&mut observer, 4).await?;
+    send_request(
+        &mut observer,
+        "thread/detach",
+        5,
+        Some(json!({ "threadId": thread_id })),
+    )
+    .await?;
+    let not_attached = read_error_for_id(&mut observer, 5).await?;
+    assert_eq!(
+        not_attached.error.message,
+        format!("not attached to thread: {thread_id}")
+    );
+
+    // Once the owner detaches too, the thread stays closed to connections
+    // that hold no rights on it.
+    send_request(
+        &mut owner,
+        "thread/detach",
+        5,
+        Some(json!({ "threadId": thread_id })),
+    )
+    .await?;
+    read_response_for_id(&mut owner, 5).await?;
+    send_request(&mut observer, "turn/start", 8, Some(turn_input)).await?;
+    let unclaimed = read_error_for_id(&mut observer, 8).await?;
+    assert_eq!(
+        unclaimed.error.message,
+        format!("connection is not allowed to steer thread {thread_id}")
+    );
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    Ok(())
+}
+
+async fn spawn_websocket_server(codex_home: &Path, args: &[String]) -> Result<Child> {
     let program = codex_utils_cargo_bin::cargo_bin("codex-app-server")
         .context("should find app-server binary")?;
     let mut cmd = Command::new(program);
-    cmd.arg("--listen")
-        .arg(format!("ws://{bind_addr}"))
+    cmd.args(args)
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
@@ -129,6 +504,155 @@ async fn connect_websocket(bind_addr: SocketAddr) -> Result<WsClient> {
     }
 }
 
+/// A throwaway CA plus server and client certificates issued by it.
+struct TestCertificates {
+    ca_cert: PathBuf,
+    server_cert: PathBuf,
+    server_key: PathBuf,
+    ca_der: CertificateDer<'static>,
+    client_cert_der: CertificateDer<'static>,
+    client_key_der: Vec<u8>,
+}
+
+impl TestCertificates {
+    fn generate(dir: &Path) -> Result<Self> {
+        let ca_key = KeyPair::generate()?;
+        let mut ca_params = CertificateParams::new(Vec::<String>::new())?;
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "codex app-server test CA");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let ca_cert = ca_params.self_signed(&ca_key)?;
+        let issuer = Issuer::new(ca_params, ca_key);
+
+        let server_key = KeyPair::generate()?;
+        let mut server_params = CertificateParams::new(vec!["127.0.0.1".to_string()])?;
+        server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let server_cert = server_params.signed_by(&server_key, &issuer)?;
+
+        let client_key = KeyPair::generate()?;
+        let mut client_params = CertificateParams::new(Vec::<String>::new())?;
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, "codex app-server test client");
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client_cert = client_params.signed_by(&client_key, &issuer)?;
+
+        let certificates = Self {
+            ca_cert: dir.join("ca.pem"),
+            server_cert: dir.join("server.pem"),
+            server_key: dir.join("server-key.pem"),
+            ca_der: ca_cert.der().clone(),
+            client_cert_der: client_cert.der().clone(),
+            client_key_der: client_key.serialize_der(),
+        };
+        std::fs::write(&certificates.ca_cert, ca_cert.pem())?;
+        std::fs::write(&certificates.server_cert, server_cert.pem())?;
+        std::fs::write(&certificates.server_key, server_key.serialize_pem())?;
+        Ok(certificates)
+    }
+
+    fn client_config(&self, with_client_certificate: bool) -> Result<Arc<ClientConfig>> {
+        let mut roots = RootCertStore::empty();
+        roots.add(self.ca_der.clone())?;
+        let builder =
+            ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()?
+                .with_root_certificates(roots);
+        let config = if with_client_certificate {
+            builder.with_client_auth_cert(
+                vec![self.client_cert_der.clone()],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(self.client_key_der.clone())),
+            )?
+        } else {
+            builder.with_no_client_auth()
+        };
+        Ok(Arc::new(config))
+    }
+}
+
+async fn connect_secure_websocket(
+    bind_addr: SocketAddr,
+    client_config: Arc<ClientConfig>,
+    token: Option<&str>,
+    origin: Option<&str>,
+) -> Result<WsClient> {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        match try_connect_secure_websocket(bind_addr, Arc::clone(&client_config), token, origin)
+            .await
+        {
+            Ok(stream) => return Ok(stream),
+            Err(err) => {
+                if Instant::now() >= deadline {
+                    bail!("failed to connect websocket to wss://{bind_addr}: {err}");
+                }
+                sleep(Duration::from_millis(50)).await;
+            }
+        }
+    }
+}
+
+async fn try_connect_secure_websocket(
+    bind_addr: SocketAddr,
+    client_config: Arc<ClientConfig>,
+    token: Option<&str>,
+    origin: Option<&str>,
+) -> Result<WsClient, WebSocketError> {
+    let mut request = format!("wss://{bind_addr}").into_client_request()?;
+    if let Some(token) = token {
+        let value = HeaderValue::from_str(&format!("Bearer {token}"))
+            .map_err(|err| WebSocketError::HttpFormat(err.into()))?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+    if let Some(origin) = origin {
+        let value =
+            HeaderValue::from_str(origin).map_err(|err| WebSocketError::HttpFormat(err.into()))?;
+        request.headers_mut().insert(ORIGIN, value);
+    }
+    let (stream, _response) =
+        connect_async_tls_with_config(request, None, false, Some(Connector::Rustls(client_config)))
+            .await?;
+    Ok(stream)
+}
+
+async fn rejected_handshake_status(
+    bind_addr: SocketAddr,
+    client_config: Arc<ClientConfig>,
+    token: Option<&str>,
+    origin: Option<&str>,
+) -> Result<StatusCode> {
+    match try_connect_secure_websocket(bind_addr, client_config, token, origin).await {
+        Ok(_) => bail!("websocket handshake unexpectedly succeeded"),
+        Err(WebSocketError::Http(response)) => Ok(response.status()),
+        Err(err) => bail!("unexpected websocket handshake error: {err}"),
+    }
+}
+
+async fn read_audit_records(path: &Path, expected: usize) -> Result<Vec<serde_json::Value>> {
+    let deadline = Instant::now() + DEFAULT_READ_TIMEOUT;
+    loop {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        if contents.ends_with('\n') {
+            let records = contents
+                .lines()
+                .map(serde_json::from_str)
+                .collect::<Result<Vec<serde_json::Value>, _>>()?;
+            if records.len() >= expected {
+                return Ok(records);
+            }
+        }
+        if Instant::now() >= deadline {
+            bail!(
+                "timed out waiting for {expected} audit records in {}",
+                path.display()
+            );
+        }
+        sleep(Duration::from_millis(50)).await;
+    }
+}
+
 async fn send_initialize_request(stream: &mut WsClient, id: i64, client_name: &str) -> Result<()> {
     let params = InitializeParams {
         client_info: ClientInfo {
@@ -203,6 +727,31 @@ async fn read_error_for_id(stream: &mut WsClient, id: i64) -> Result<JSONRPCErro
     }
 }
 
+async fn read_request_for_method(stream: &mut WsClient, method: &str) -> Result<JSONRPCRequest> {
+    loop {
+        let message = read_jsonrpc_message(stream).await?;
+        if let JSONRPCMessage::Request(request) = message
+            && request.method == method
+        {
+            return Ok(request);
+        }
+    }
+}
+
+async fn read_notification_for_method(
+    stream: &mut WsClient,
+    method: &str,
+) -> Result<JSONRPCNotification> {
+    loop {
+        let message = read_jsonrpc_message(stream).await?;
+        if let JSONRPCMessage::Notification(notification) = message
+            && notification.method == method
+        {
+            return Ok(notification);
+        }
+    }
+}
+
 async fn read_jsonrpc_message(stream: &mut WsClient) -> Result<JSONRPCMessage> {
     loop {
         let frame = timeout(DEFAULT_READ_TIMEOUT, stream.next())
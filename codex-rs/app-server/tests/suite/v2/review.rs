@@ -63,6 +63,7 @@ async fn review_start_runs_review_turn_and_emits_code_review_item() -> Result<()
         .send_review_start_request(ReviewStartParams {
             thread_id: thread_id.clone(),
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Commit {
                 sha: "1234567deadbeef".to_string(),
                 title: Some("Tidy UI colors".to_string()),
@@ -164,6 +165,7 @@ async fn review_start_exec_approval_item_id_matches_command_execution_item() ->
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Commit {
                 sha: "1234567deadbeef".to_string(),
                 title: Some("Check review approvals".to_string()),
@@ -234,6 +236,7 @@ async fn review_start_rejects_empty_base_branch() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::BaseBranch {
                 branch: "   ".to_string(),
             },
@@ -278,6 +281,7 @@ async fn review_start_with_detached_delivery_returns_new_thread_id() -> Result<(
         .send_review_start_request(ReviewStartParams {
             thread_id: thread_id.clone(),
             delivery: Some(ReviewDelivery::Detached),
+            paths: None,
             target: ReviewTarget::Custom {
                 instructions: "detached review".to_string(),
             },
@@ -316,6 +320,7 @@ async fn review_start_rejects_empty_commit_sha() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Commit {
                 sha: "\t".to_string(),
                 title: None,
@@ -351,6 +356,7 @@ async fn review_start_rejects_empty_custom_instructions() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: None,
             target: ReviewTarget::Custom {
                 instructions: "\n\n".to_string(),
             },
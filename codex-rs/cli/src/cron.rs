@@ -0,0 +1,265 @@
+//! Minimal cron expressions for `codex schedule`.
+//!
+//! Supports the classic five fields (minute, hour, day of month, month, day of
+//! week) with `*`, lists, ranges, steps and month/weekday names, plus the
+//! `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` macros. As in
+//! Vixie cron, when both day fields are restricted a day matches if either
+//! field does.
+
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Duration;
+use chrono::LocalResult;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::TimeZone;
+use chrono::Timelike;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Years searched for a matching time before giving up, e.g. for `0 0 30 2 *`.
+const MAX_SEARCH_YEARS: i32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    pub(crate) fn parse(expression: &str) -> Result<Self> {
+        let expression = expression.trim();
+        let expanded = match expression.to_ascii_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *".to_string(),
+            "@monthly" => "0 0 1 * *".to_string(),
+            "@weekly" => "0 0 * * 0".to_string(),
+            "@daily" | "@midnight" => "0 0 * * *".to_string(),
+            "@hourly" => "0 * * * *".to_string(),
+            other if other.starts_with('@') => bail!("unknown cron macro: {expression}"),
+            _ => expression.to_string(),
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            bail!(
+                "cron expression must have five fields (minute hour day-of-month month day-of-week): {expression}"
+            );
+        };
+        let mut days_of_week = parse_field(day_of_week, 0, 7, &WEEKDAY_NAMES, 0)?;
+        // Both 0 and 7 mean Sunday.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)?,
+            days_of_month: parse_field(day_of_month, 1, 31, &[], 1)?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, 1)?,
+            days_of_week,
+            day_of_month_restricted: !day_of_month.starts_with('*'),
+            day_of_week_restricted: !day_of_week.starts_with('*'),
+        })
+    }
+
+    /// The first matching minute strictly after `after`, or `None` when the
+    /// expression never matches within the next few years.
+    pub(crate) fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut candidate = start;
+        while candidate.year() <= start.year() + MAX_SEARCH_YEARS {
+            if !has_bit(self.months, candidate.month()) {
+                candidate = first_minute_of_next_month(candidate)?;
+                continue;
+            }
+            if !self.matches_day(candidate.date()) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !has_bit(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !has_bit(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+                continue;
+            }
+            match timezone.from_local_datetime(&candidate) {
+                LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => return Some(time),
+                // The local time is skipped by a DST transition.
+                LocalResult::None => candidate += Duration::minutes(1),
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = has_bit(self.days_of_month, date.day());
+        let day_of_week = has_bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+fn has_bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn first_minute_of_next_month(time: NaiveDateTime) -> Option<NaiveDateTime> {
+    let (year, month) = if time.month() == 12 {
+        (time.year() + 1, 1)
+    } else {
+        (time.year(), time.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+/// Parse one field into a bit set over `min..=max`. `names` are accepted in
+/// place of numbers, starting at `first_name_value`.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    first_name_value: u32,
+) -> Result<u64> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid step in cron field: {part}"))?;
+                if step == 0 {
+                    bail!("cron step must be positive: {part}");
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, names, first_name_value)?,
+                parse_value(end, names, first_name_value)?,
+            )
+        } else {
+            let start = parse_value(range, names, first_name_value)?;
+            // `5/15` means every 15 starting at 5.
+            (start, if part.contains('/') { max } else { start })
+        };
+        if start < min || end > max || start > end {
+            bail!("cron field {part} is outside {min}-{max}");
+        }
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+fn parse_value(value: &str, names: &[&str], first_name_value: u32) -> Result<u32> {
+    if let Ok(number) = value.parse() {
+        return Ok(number);
+    }
+    let lower = value.to_ascii_lowercase();
+    names
+        .iter()
+        .position(|name| *name == lower)
+        .and_then(|index| u32::try_from(index).ok())
+        .map(|index| index + first_name_value)
+        .ok_or_else(|| anyhow::anyhow!("invalid value in cron field: {value}"))
+}
+
+/// Parses an RFC 3339 timestamp as UTC, for the cron and schedule tests.
+#[cfg(test)]
+pub(crate) fn utc(value: &str) -> DateTime<chrono::Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .expect("valid timestamp")
+        .with_timezone(&chrono::Utc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+
+    fn next(expression: &str, after: &str) -> Option<DateTime<Utc>> {
+        CronSchedule::parse(expression)
+            .expect("valid expression")
+            .next_after(&utc(after))
+    }
+
+    #[test]
+    fn finds_next_matching_minute() {
+        assert_eq!(
+            next("*/15 * * * *", "2026-03-01T10:07:30Z"),
+            Some(utc("2026-03-01T10:15:00Z"))
+        );
+        assert_eq!(
+            next("0 2 * * *", "2026-03-01T02:00:00Z"),
+            Some(utc("2026-03-02T02:00:00Z"))
+        );
+        assert_eq!(
+            next("30 9 * * mon-fri", "2026-03-06T10:00:00Z"),
+            Some(utc("2026-03-09T09:30:00Z"))
+        );
+        assert_eq!(
+            next("@monthly", "2026-12-15T00:00:00Z"),
+            Some(utc("2027-01-01T00:00:00Z"))
+        );
+        assert_eq!(
+            next("0 0 29 feb *", "2026-03-01T00:00:00Z"),
+            Some(utc("2028-02-29T00:00:00Z"))
+        );
+        assert_eq!(next("0 0 30 2 *", "2026-03-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // The 13th of the month or any Friday.
+        assert_eq!(
+            next("0 12 13 * 5", "2026-03-01T00:00:00Z"),
+            Some(utc("2026-03-06T12:00:00Z"))
+        );
+        assert_eq!(
+            next("0 12 13 * 5", "2026-03-10T00:00:00Z"),
+            Some(utc("2026-03-13T12:00:00Z"))
+        );
+        // Sunday may be written as 7.
+        assert_eq!(
+            next("0 0 * * 7", "2026-03-01T00:00:00Z"),
+            Some(utc("2026-03-08T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "*/0 * * * *",
+            "@often",
+            "0 0 * foo *",
+        ] {
+            assert!(
+                CronSchedule::parse(expression).is_err(),
+                "{expression} should be rejected"
+            );
+        }
+    }
+}
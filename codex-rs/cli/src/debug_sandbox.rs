@@ -221,6 +221,7 @@ async fn run_command_under_sandbox(
                 cwd,
                 config.sandbox_policy.get(),
                 sandbox_policy_cwd.as_path(),
+                &config.sandbox_denied_read_paths,
                 stdio_policy,
                 env,
             )
@@ -239,6 +240,7 @@ async fn run_command_under_sandbox(
                 cwd,
                 config.sandbox_policy.get(),
                 sandbox_policy_cwd.as_path(),
+                &config.sandbox_denied_read_paths,
                 use_bwrap_sandbox,
                 stdio_policy,
                 env,
@@ -33,13 +33,29 @@ use supports_color::Stream;
 
 #[cfg(target_os = "macos")]
 mod app_cmd;
+mod cron;
 #[cfg(target_os = "macos")]
 mod desktop_app;
 mod mcp_cmd;
+mod memory_cmd;
+mod replay_cmd;
+mod schedule_cmd;
+mod search_cmd;
+mod stats_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
 use crate::mcp_cmd::McpCli;
+use crate::memory_cmd::MemoryCli;
+use crate::memory_cmd::run_memory_command;
+use crate::replay_cmd::ReplayCommand;
+use crate::replay_cmd::run_replay_command;
+use crate::schedule_cmd::ScheduleCli;
+use crate::schedule_cmd::run_schedule_command;
+use crate::search_cmd::SearchCommand;
+use crate::search_cmd::run_search_command;
+use crate::stats_cmd::StatsCommand;
+use crate::stats_cmd::run_stats_command;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -129,6 +145,9 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Replay a recorded TUI session log without contacting a model.
+    Replay(ReplayCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -143,6 +162,18 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Report usage statistics (sessions, tokens, tool calls, approvals) from local history.
+    Stats(StatsCommand),
+
+    /// Search past sessions' messages, commands and edited files.
+    Search(SearchCommand),
+
+    /// List, add, edit or remove the memories Codex carries across sessions.
+    Memory(MemoryCli),
+
+    /// Schedule recurring headless runs and run them with `codex schedule run-daemon`.
+    Schedule(ScheduleCli),
 }
 
 #[derive(Debug, Parser)]
@@ -307,7 +338,7 @@ struct AppServerCommand {
     subcommand: Option<AppServerSubcommand>,
 
     /// Transport endpoint URL. Supported values: `stdio://` (default),
-    /// `ws://IP:PORT`.
+    /// `ws://IP:PORT`, `wss://IP:PORT`.
     #[arg(
         long = "listen",
         value_name = "URL",
@@ -315,6 +346,9 @@ struct AppServerCommand {
     )]
     listen: codex_app_server::AppServerTransport,
 
+    #[command(flatten)]
+    websocket: codex_app_server::WebSocketOptions,
+
     /// Controls whether analytics are enabled by default.
     ///
     /// Analytics are disabled by default for app-server. Users have to explicitly opt in
@@ -603,6 +637,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                     codex_core::config_loader::LoaderOverrides::default(),
                     app_server_cli.analytics_default_enabled,
                     transport,
+                    app_server_cli.websocket,
                 )
                 .await?;
             }
@@ -818,6 +853,41 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 disable_feature_in_config(&interactive, &feature).await?;
             }
         },
+        Some(Subcommand::Replay(mut replay_cli)) => {
+            prepend_config_flags(
+                &mut replay_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_replay_command(replay_cli).await?;
+        }
+        Some(Subcommand::Stats(mut stats_cli)) => {
+            prepend_config_flags(
+                &mut stats_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_stats_command(stats_cli).await?;
+        }
+        Some(Subcommand::Search(mut search_cli)) => {
+            prepend_config_flags(
+                &mut search_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_search_command(search_cli).await?;
+        }
+        Some(Subcommand::Memory(mut memory_cli)) => {
+            prepend_config_flags(
+                &mut memory_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_memory_command(memory_cli).await?;
+        }
+        Some(Subcommand::Schedule(mut schedule_cli)) => {
+            prepend_config_flags(
+                &mut schedule_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_schedule_command(schedule_cli).await?;
+        }
     }
 
     Ok(())
@@ -1361,8 +1431,52 @@ mod tests {
             app_server.listen,
             codex_app_server::AppServerTransport::WebSocket {
                 bind_address: "127.0.0.1:4500".parse().expect("valid socket address"),
+                tls: false,
+            }
+        );
+    }
+
+    #[test]
+    fn app_server_secure_websocket_options_parse() {
+        let app_server = app_server_from_args(
+            [
+                "codex",
+                "app-server",
+                "--listen",
+                "wss://0.0.0.0:4500",
+                "--tls-cert",
+                "cert.pem",
+                "--tls-key",
+                "key.pem",
+                "--ws-token-file",
+                "token",
+                "--ws-allowed-origin",
+                "https://ide.example.com",
+            ]
+            .as_ref(),
+        );
+        assert_eq!(
+            app_server.listen,
+            codex_app_server::AppServerTransport::WebSocket {
+                bind_address: "0.0.0.0:4500".parse().expect("valid socket address"),
+                tls: true,
             }
         );
+        assert_eq!(
+            app_server.websocket,
+            codex_app_server::WebSocketOptions {
+                token_file: Some(PathBuf::from("token")),
+                tls_cert: Some(PathBuf::from("cert.pem")),
+                tls_key: Some(PathBuf::from("key.pem")),
+                tls_client_ca: None,
+                allowed_origins: vec!["https://ide.example.com".to_string()],
+                audit_log: None,
+            }
+        );
+        assert!(
+            MultitoolCli::try_parse_from(["codex", "app-server", "--tls-cert", "cert.pem"])
+                .is_err()
+        );
     }
 
     #[test]
@@ -1408,6 +1522,126 @@ mod tests {
         assert_eq!(feature, "shell_tool");
     }
 
+    #[test]
+    fn stats_parses_range_and_grouping() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "stats",
+            "--since",
+            "2026-01-01",
+            "--by",
+            "model",
+            "--json",
+        ])
+        .expect("parse should succeed");
+        let Some(Subcommand::Stats(stats)) = cli.subcommand else {
+            panic!("expected stats subcommand");
+        };
+        assert_eq!(stats.since, chrono::NaiveDate::from_ymd_opt(2026, 1, 1));
+        assert_eq!(stats.until, None);
+        assert_eq!(stats.group_by, Some(stats_cmd::StatsGroupBy::Model));
+        assert_eq!(stats.top, 5);
+        assert!(stats.json);
+    }
+
+    #[test]
+    fn stats_parses_group_by_day() {
+        let cli = MultitoolCli::try_parse_from(["codex", "stats", "--by", "day"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Stats(stats)) = cli.subcommand else {
+            panic!("expected stats subcommand");
+        };
+        assert_eq!(stats.group_by, Some(stats_cmd::StatsGroupBy::Day));
+    }
+
+    #[test]
+    fn replay_parses_speed_and_step() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "replay",
+            "session.jsonl",
+            "--speed",
+            "4",
+            "--step",
+        ])
+        .expect("parse should succeed");
+        let Some(Subcommand::Replay(replay)) = cli.subcommand else {
+            panic!("expected replay subcommand");
+        };
+        assert_eq!(replay.log, PathBuf::from("session.jsonl"));
+        assert_eq!(replay.speed, 4.0);
+        assert!(replay.step);
+
+        assert!(
+            MultitoolCli::try_parse_from(["codex", "replay", "session.jsonl", "--speed", "0"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn memory_add_parses_user_scope() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex", "memory", "add", "--user", "prefers", "small", "commits",
+        ])
+        .expect("parse should succeed");
+        let Some(Subcommand::Memory(memory)) = cli.subcommand else {
+            panic!("expected memory subcommand");
+        };
+        let memory_cmd::MemorySubcommand::Add(args) = memory.subcommand else {
+            panic!("expected memory add");
+        };
+        assert!(args.user);
+        assert_eq!(args.content.join(" "), "prefers small commits");
+    }
+
+    #[test]
+    fn schedule_add_parses_job_definition() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "schedule",
+            "add",
+            "nightly",
+            "--cron",
+            "0 2 * * *",
+            "--sandbox",
+            "workspace-write",
+            "-o",
+            "triage.md",
+            "triage",
+            "new",
+            "issues",
+        ])
+        .expect("parse should succeed");
+        let Some(Subcommand::Schedule(schedule)) = cli.subcommand else {
+            panic!("expected schedule subcommand");
+        };
+        let schedule_cmd::ScheduleSubcommand::Add(args) = schedule.subcommand else {
+            panic!("expected schedule add");
+        };
+        assert_eq!(args.name, "nightly");
+        assert_eq!(args.cron, "0 2 * * *");
+        assert_matches!(
+            args.sandbox,
+            Some(codex_common::SandboxModeCliArg::WorkspaceWrite)
+        );
+        assert_eq!(args.output, Some(PathBuf::from("triage.md")));
+        assert_eq!(args.prompt.join(" "), "triage new issues");
+    }
+
+    #[test]
+    fn search_joins_query_words() {
+        let cli =
+            MultitoolCli::try_parse_from(["codex", "search", "flaky", "login", "--limit", "5"])
+                .expect("parse should succeed");
+        let Some(Subcommand::Search(search)) = cli.subcommand else {
+            panic!("expected search subcommand");
+        };
+        assert_eq!(search.query, vec!["flaky".to_string(), "login".to_string()]);
+        assert_eq!(search.limit, 5);
+        assert!(!search.archived);
+        assert!(!search.json);
+    }
+
     #[test]
     fn feature_toggles_known_features_generate_overrides() {
         let toggles = FeatureToggles {
@@ -0,0 +1,233 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::memories::memory_repo_key;
+use codex_core::state_db::Memory;
+use codex_core::state_db::MemoryScope;
+use codex_core::state_db::MemorySource;
+use codex_core::state_db::NewMemory;
+use codex_core::state_db::StateDbHandle;
+use codex_core::state_db::get_state_db;
+use serde_json::json;
+
+/// Subcommands:
+/// - `list`   — list memories for the current repository (with `--all`, `--json`)
+/// - `add`    — save a memory for the current repository or, with `--user`, everywhere
+/// - `edit`   — replace the text of a memory
+/// - `remove` — delete a memory
+#[derive(Debug, clap::Parser)]
+pub struct MemoryCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: MemorySubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum MemorySubcommand {
+    List(MemoryListArgs),
+    Add(MemoryAddArgs),
+    Edit(MemoryEditArgs),
+    #[clap(visible_alias = "rm")]
+    Remove(MemoryRemoveArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MemoryListArgs {
+    /// Include memories for every repository, not just the current one.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Output the memories as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MemoryAddArgs {
+    /// Text of the memory.
+    #[arg(value_name = "TEXT", required = true, num_args = 1..)]
+    pub content: Vec<String>,
+
+    /// Apply the memory to every repository instead of the current one.
+    #[arg(long)]
+    pub user: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MemoryEditArgs {
+    /// Id of the memory to edit, as shown by `codex memory list`.
+    pub id: i64,
+
+    /// New text of the memory.
+    #[arg(value_name = "TEXT", required = true, num_args = 1..)]
+    pub content: Vec<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MemoryRemoveArgs {
+    /// Id of the memory to delete, as shown by `codex memory list`.
+    pub id: i64,
+}
+
+pub async fn run_memory_command(cmd: MemoryCli) -> Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let Some(state_db) = get_state_db(&config, None).await else {
+        bail!(
+            "memories need the state database; enable it with `codex features enable sqlite` and run a session first"
+        );
+    };
+    let repo = memory_repo_key(&config.cwd);
+
+    match cmd.subcommand {
+        MemorySubcommand::List(args) => run_list(&state_db, &repo, args).await,
+        MemorySubcommand::Add(args) => {
+            let memory = state_db
+                .add_memory(&NewMemory {
+                    scope: if args.user {
+                        MemoryScope::User
+                    } else {
+                        MemoryScope::Repo
+                    },
+                    repo: Some(repo),
+                    content: args.content.join(" "),
+                    source: MemorySource::User,
+                    thread_id: None,
+                })
+                .await?;
+            println!("Saved memory {}.", memory.id);
+            Ok(())
+        }
+        MemorySubcommand::Edit(args) => {
+            match state_db
+                .update_memory(args.id, &args.content.join(" "))
+                .await?
+            {
+                Some(memory) => println!("Updated memory {}.", memory.id),
+                None => bail!("no memory with id {}", args.id),
+            }
+            Ok(())
+        }
+        MemorySubcommand::Remove(args) => {
+            if !state_db.delete_memory(args.id).await? {
+                bail!("no memory with id {}", args.id);
+            }
+            println!("Removed memory {}.", args.id);
+            Ok(())
+        }
+    }
+}
+
+async fn run_list(state_db: &StateDbHandle, repo: &str, args: MemoryListArgs) -> Result<()> {
+    let memories = state_db.list_memories((!args.all).then_some(repo)).await?;
+    if args.json {
+        let memories: Vec<_> = memories.iter().map(memory_to_json).collect();
+        println!("{}", serde_json::to_string_pretty(&memories)?);
+    } else {
+        print!("{}", render_memories(&memories, args.all));
+    }
+    Ok(())
+}
+
+fn memory_to_json(memory: &Memory) -> serde_json::Value {
+    json!({
+        "id": memory.id,
+        "scope": memory.scope.as_str(),
+        "repo": memory.repo,
+        "content": memory.content,
+        "source": memory.source.as_str(),
+        "thread_id": memory.thread_id.map(|thread_id| thread_id.to_string()),
+        "created_at": memory.created_at.to_rfc3339(),
+        "updated_at": memory.updated_at.to_rfc3339(),
+    })
+}
+
+fn render_memories(memories: &[Memory], show_repo: bool) -> String {
+    if memories.is_empty() {
+        return "No memories saved.\n".to_string();
+    }
+    let id_width = memories
+        .iter()
+        .map(|memory| memory.id.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for memory in memories {
+        out.push_str(&format!(
+            "{:>id_width$}  {:<4}  {}\n",
+            memory.id,
+            memory.scope.as_str(),
+            memory.content
+        ));
+        let mut provenance = match (memory.source, memory.thread_id) {
+            (MemorySource::Agent, Some(thread_id)) => format!("saved by Codex in {thread_id}"),
+            (MemorySource::Agent, None) => "saved by Codex".to_string(),
+            (MemorySource::User, _) => "added by you".to_string(),
+        };
+        provenance.push_str(&format!(", {}", memory.updated_at.format("%Y-%m-%d")));
+        if show_repo && let Some(repo) = memory.repo.as_deref() {
+            provenance.push_str(&format!(", {repo}"));
+        }
+        out.push_str(&format!("{:id_width$}        {provenance}\n", ""));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn renders_memories_with_provenance() {
+        let updated_at = Utc
+            .with_ymd_and_hms(2026, 3, 1, 12, 0, 0)
+            .single()
+            .expect("valid timestamp");
+        let thread_id = ThreadId::new();
+        let memories = vec![
+            Memory {
+                id: 12,
+                scope: MemoryScope::Repo,
+                repo: Some("/work/codex".to_string()),
+                content: "Run `just fmt` before committing.".to_string(),
+                source: MemorySource::Agent,
+                thread_id: Some(thread_id),
+                created_at: updated_at,
+                updated_at,
+            },
+            Memory {
+                id: 3,
+                scope: MemoryScope::User,
+                repo: None,
+                content: "Prefers small commits.".to_string(),
+                source: MemorySource::User,
+                thread_id: None,
+                created_at: updated_at,
+                updated_at,
+            },
+        ];
+        assert_eq!(
+            render_memories(&memories, true),
+            format!(
+                "12  repo  Run `just fmt` before committing.
+          saved by Codex in {thread_id}, 2026-03-01, /work/codex
+ 3  user  Prefers small commits.
+          added by you, 2026-03-01
+"
+            )
+        );
+    }
+}
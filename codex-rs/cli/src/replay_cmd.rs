@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_tui::ReplayOptions;
+
+/// Re-render a session log recorded with `CODEX_TUI_RECORD_SESSION=1`.
+#[derive(Debug, clap::Parser)]
+pub struct ReplayCommand {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Session log (JSONL) to replay.
+    #[arg(value_name = "SESSION_LOG")]
+    pub log: PathBuf,
+
+    /// Playback speed relative to the recording, e.g. `4` for four times faster.
+    #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+    pub speed: f64,
+
+    /// Start paused and advance one recorded step per Enter.
+    #[arg(long)]
+    pub step: bool,
+}
+
+fn parse_speed(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed > 0.0 => Ok(speed),
+        _ => Err(format!("expected a positive number, got `{value}`")),
+    }
+}
+
+pub async fn run_replay_command(cmd: ReplayCommand) -> Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    codex_tui::run_replay(
+        config,
+        &cmd.log,
+        ReplayOptions {
+            speed: cmd.speed,
+            step: cmd.step,
+        },
+    )
+    .await
+    .with_context(|| format!("failed to replay {}", cmd.log.display()))
+}
@@ -0,0 +1,727 @@
+use std::fs::File;
+use std::fs::TryLockError;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Local;
+use chrono::TimeZone;
+use chrono::Timelike;
+use chrono::Utc;
+use clap::ValueEnum;
+use codex_common::CliConfigOverrides;
+use codex_common::SandboxModeCliArg;
+use codex_core::config::Config;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::state_db::NewScheduledJob;
+use codex_core::state_db::ScheduledJob;
+use codex_core::state_db::ScheduledJobRun;
+use codex_core::state_db::ScheduledRunOutcome;
+use codex_core::state_db::ScheduledRunStatus;
+use codex_core::state_db::StateDbHandle;
+use codex_core::state_db::get_state_db;
+use codex_exec::exec_events::ThreadEvent;
+use codex_protocol::ThreadId;
+use serde_json::json;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::BufReader;
+use tokio::process::Command;
+
+use crate::cron::CronSchedule;
+
+const INTERRUPTED_RUN_ERROR: &str = "the daemon stopped before the run finished";
+const DAEMON_LOCK_FILENAME: &str = "schedule-daemon.lock";
+
+/// Subcommands:
+/// - `add`        — schedule a recurring headless run
+/// - `list`       — list scheduled jobs and when they run next
+/// - `remove`     — delete a job and its run history
+/// - `enable`     — resume a disabled job
+/// - `disable`    — stop running a job without deleting it
+/// - `history`    — show past runs with their sessions
+/// - `run-daemon` — run due jobs in the foreground until interrupted
+#[derive(Debug, clap::Parser)]
+pub struct ScheduleCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ScheduleSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ScheduleSubcommand {
+    Add(ScheduleAddArgs),
+    List(ScheduleListArgs),
+    #[clap(visible_alias = "rm")]
+    Remove(ScheduleNameArgs),
+    Enable(ScheduleNameArgs),
+    Disable(ScheduleNameArgs),
+    History(ScheduleHistoryArgs),
+    RunDaemon,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ScheduleAddArgs {
+    /// Unique name for the job.
+    pub name: String,
+
+    /// When to run, as a five-field cron expression in local time (e.g.
+    /// "0 2 * * *") or a macro such as @daily.
+    #[arg(long)]
+    pub cron: String,
+
+    /// Directory the run starts in. Defaults to the current directory.
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Configuration profile from config.toml to run with.
+    #[arg(long, short = 'p')]
+    pub profile: Option<String>,
+
+    /// Sandbox policy for model-generated shell commands.
+    #[arg(long, short = 's', value_enum)]
+    pub sandbox: Option<SandboxModeCliArg>,
+
+    /// File the last agent message of each run is written to.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Instructions for the agent.
+    #[arg(value_name = "PROMPT", required = true, num_args = 1..)]
+    pub prompt: Vec<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ScheduleListArgs {
+    /// Output the jobs as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ScheduleNameArgs {
+    /// Name of the job.
+    pub name: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ScheduleHistoryArgs {
+    /// Only show runs of this job.
+    pub name: Option<String>,
+
+    /// Maximum number of runs to show.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Output the runs as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub async fn run_schedule_command(cmd: ScheduleCli) -> Result<()> {
+    let raw_overrides = cmd.config_overrides.raw_overrides.clone();
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let Some(state_db) = get_state_db(&config, None).await else {
+        bail!(
+            "scheduled runs need the state database; enable it with `codex features enable sqlite` and run a session first"
+        );
+    };
+
+    match cmd.subcommand {
+        ScheduleSubcommand::Add(args) => {
+            CronSchedule::parse(&args.cron)?;
+            let cwd = match args.cwd {
+                Some(cwd) => config.cwd.join(cwd),
+                None => config.cwd.clone(),
+            };
+            let job = state_db
+                .add_scheduled_job(
+                    &NewScheduledJob {
+                        name: args.name,
+                        prompt: args.prompt.join(" "),
+                        cwd,
+                        profile: args.profile,
+                        sandbox: args.sandbox.and_then(|sandbox| {
+                            sandbox
+                                .to_possible_value()
+                                .map(|value| value.get_name().to_string())
+                        }),
+                        cron: args.cron,
+                        output: args.output.map(|output| config.cwd.join(output)),
+                    },
+                    Utc::now(),
+                )
+                .await?;
+            println!("Scheduled {}.", job.name);
+            if let Some(next) = next_run(&job, &Local) {
+                println!("Next run: {}.", next.format("%Y-%m-%d %H:%M"));
+            }
+            println!("Jobs only run while `codex schedule run-daemon` is running.");
+            Ok(())
+        }
+        ScheduleSubcommand::List(args) => {
+            let jobs = state_db.list_scheduled_jobs().await?;
+            if args.json {
+                let jobs: Vec<_> = jobs.iter().map(job_to_json).collect();
+                println!("{}", serde_json::to_string_pretty(&jobs)?);
+            } else {
+                print!("{}", render_jobs(&jobs, &Local));
+            }
+            Ok(())
+        }
+        ScheduleSubcommand::Remove(args) => {
+            if !state_db.delete_scheduled_job(&args.name).await? {
+                bail!("no scheduled job named {}", args.name);
+            }
+            println!("Removed {}.", args.name);
+            Ok(())
+        }
+        ScheduleSubcommand::Enable(args) => set_enabled(&state_db, &args.name, true).await,
+        ScheduleSubcommand::Disable(args) => set_enabled(&state_db, &args.name, false).await,
+        ScheduleSubcommand::History(args) => {
+            let job_id = match args.name.as_deref() {
+                Some(name) => match state_db.get_scheduled_job(name).await? {
+                    Some(job) => Some(job.id),
+                    None => bail!("no scheduled job named {name}"),
+                },
+                None => None,
+            };
+            let runs = state_db.list_scheduled_runs(job_id, args.limit).await?;
+            if args.json {
+                let runs: Vec<_> = runs.iter().map(run_to_json).collect();
+                println!("{}", serde_json::to_string_pretty(&runs)?);
+            } else {
+                print!("{}", render_runs(&runs, &Local));
+            }
+            Ok(())
+        }
+        ScheduleSubcommand::RunDaemon => {
+            let runner = ExecRunner {
+                codex_home: config.codex_home.clone(),
+                config_overrides: raw_overrides,
+            };
+            let _lock = acquire_daemon_lock(&config.codex_home)?;
+            run_daemon(&state_db, &runner).await
+        }
+    }
+}
+
+async fn set_enabled(state_db: &StateDbHandle, name: &str, enabled: bool) -> Result<()> {
+    if !state_db
+        .set_scheduled_job_enabled(name, enabled, Utc::now())
+        .await?
+    {
+        bail!("no scheduled job named {name}");
+    }
+    println!("{} {name}.", if enabled { "Enabled" } else { "Disabled" });
+    Ok(())
+}
+
+/// Source of the current time, replaced by a fake clock in tests.
+trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Executes one run of a job.
+trait JobRunner {
+    async fn run(&self, job: &ScheduledJob) -> ScheduledRunOutcome;
+}
+
+/// Runs jobs as `codex exec` child processes.
+struct ExecRunner {
+    codex_home: PathBuf,
+    config_overrides: Vec<String>,
+}
+
+impl JobRunner for ExecRunner {
+    async fn run(&self, job: &ScheduledJob) -> ScheduledRunOutcome {
+        match self.run_exec(job).await {
+            Ok(outcome) => outcome,
+            Err(err) => ScheduledRunOutcome {
+                status: ScheduledRunStatus::Failed,
+                exit_code: None,
+                thread_id: None,
+                rollout_path: None,
+                error: Some(format!("{err:#}")),
+            },
+        }
+    }
+}
+
+impl ExecRunner {
+    async fn run_exec(&self, job: &ScheduledJob) -> Result<ScheduledRunOutcome> {
+        let mut command = Command::new(std::env::current_exe()?);
+        for raw_override in &self.config_overrides {
+            command.arg("-c").arg(raw_override);
+        }
+        command
+            .arg("exec")
+            .arg("--json")
+            .arg("--skip-git-repo-check")
+            .arg("--cd")
+            .arg(&job.cwd);
+        if let Some(profile) = &job.profile {
+            command.arg("--profile").arg(profile);
+        }
+        if let Some(sandbox) = &job.sandbox {
+            command.arg("--sandbox").arg(sandbox);
+        }
+        if let Some(output) = &job.output {
+            command.arg("--output-last-message").arg(output);
+        }
+        command
+            .arg("--")
+            .arg(&job.prompt)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit());
+
+        let mut child = command.spawn().context("failed to start codex exec")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("codex exec stdout was not captured")?;
+        let mut lines = BufReader::new(stdout).lines();
+        let mut thread_id = None;
+        let mut error = None;
+        while let Some(line) = lines.next_line().await? {
+            match serde_json::from_str::<ThreadEvent>(&line) {
+                Ok(ThreadEvent::ThreadStarted(event)) => {
+                    thread_id = ThreadId::from_string(&event.thread_id).ok();
+                }
+                Ok(ThreadEvent::TurnFailed(event)) => error = Some(event.error.message),
+                Ok(ThreadEvent::Error(event)) => error = Some(event.message),
+                _ => {}
+            }
+        }
+        let exit_status = child.wait().await?;
+
+        let rollout_path = match thread_id {
+            Some(thread_id) => find_thread_path_by_id_str(&self.codex_home, &thread_id.to_string())
+                .await
+                .ok()
+                .flatten(),
+            None => None,
+        };
+        let (status, error) = if exit_status.success() {
+            (ScheduledRunStatus::Succeeded, None)
+        } else {
+            (
+                ScheduledRunStatus::Failed,
+                Some(error.unwrap_or_else(|| format!("codex exec exited with {exit_status}"))),
+            )
+        };
+        Ok(ScheduledRunOutcome {
+            status,
+            exit_code: exit_status.code(),
+            thread_id,
+            rollout_path,
+            error,
+        })
+    }
+}
+
+/// Take the exclusive daemon lock in `codex_home`, recording our pid in it.
+///
+/// The lock is held until the returned file is dropped. A second daemon would
+/// otherwise mark the first one's in-flight runs as failed and start the same
+/// jobs again, so it exits instead.
+fn acquire_daemon_lock(codex_home: &Path) -> Result<File> {
+    let lock_path = codex_home.join(DAEMON_LOCK_FILENAME);
+    let mut lock_file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+        .with_context(|| format!("failed to open {}", lock_path.display()))?;
+    match lock_file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            let pid = std::fs::read_to_string(&lock_path).unwrap_or_default();
+            let pid = pid.trim();
+            if pid.is_empty() {
+                bail!("another scheduler daemon is already running");
+            }
+            bail!("another scheduler daemon is already running (pid {pid})");
+        }
+        Err(TryLockError::Error(err)) => {
+            return Err(err).with_context(|| format!("failed to lock {}", lock_path.display()));
+        }
+    }
+    lock_file.set_len(0)?;
+    write!(lock_file, "{}", std::process::id())?;
+    Ok(lock_file)
+}
+
+async fn run_daemon(state_db: &StateDbHandle, runner: &impl JobRunner) -> Result<()> {
+    let interrupted = state_db
+        .fail_unfinished_scheduled_runs(Utc::now(), INTERRUPTED_RUN_ERROR)
+        .await?;
+    if interrupted > 0 {
+        eprintln!("Marked {interrupted} unfinished run(s) from a previous daemon as failed.");
+    }
+    eprintln!("Running scheduled jobs; press Ctrl-C to stop.");
+    let clock = SystemClock;
+    loop {
+        if let Err(err) = run_due_jobs(state_db, &clock, &Local, runner).await {
+            eprintln!("Failed to run scheduled jobs: {err:#}");
+        }
+        let until_next_minute = 60 - u64::from(clock.now().second());
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(std::time::Duration::from_secs(until_next_minute)) => {}
+        }
+    }
+}
+
+/// Run every enabled job that is due at the clock's current time, one after
+/// another, and return how many runs were started.
+async fn run_due_jobs<Tz: TimeZone>(
+    state_db: &StateDbHandle,
+    clock: &impl Clock,
+    timezone: &Tz,
+    runner: &impl JobRunner,
+) -> Result<usize> {
+    let mut started = 0;
+    for job in state_db.list_scheduled_jobs().await? {
+        if !job.enabled {
+            continue;
+        }
+        let schedule = match CronSchedule::parse(&job.cron) {
+            Ok(schedule) => schedule,
+            Err(err) => {
+                eprintln!("Skipping {}: {err}", job.name);
+                continue;
+            }
+        };
+        let Some(slot) = due_slot(&job, &schedule, clock.now(), timezone) else {
+            continue;
+        };
+        eprintln!("Starting {}.", job.name);
+        let run_id = state_db
+            .start_scheduled_run(job.id, slot, clock.now())
+            .await?;
+        started += 1;
+        let outcome = runner.run(&job).await;
+        state_db
+            .finish_scheduled_run(run_id, clock.now(), &outcome)
+            .await?;
+        match &outcome.error {
+            Some(error) => eprintln!("{} failed: {error}", job.name),
+            None => eprintln!("{} finished.", job.name),
+        }
+    }
+    Ok(started)
+}
+
+/// The schedule slot a job should run for at `now`, if any. Slots missed
+/// while the daemon was not running are coalesced into one run for the most
+/// recent slot.
+fn due_slot<Tz: TimeZone>(
+    job: &ScheduledJob,
+    schedule: &CronSchedule,
+    now: DateTime<Utc>,
+    timezone: &Tz,
+) -> Option<DateTime<Utc>> {
+    let last = job.last_fired_at.unwrap_or(job.created_at);
+    let mut slot = schedule
+        .next_after(&last.with_timezone(timezone))?
+        .with_timezone(&Utc);
+    if slot > now {
+        return None;
+    }
+    // Bound the catch-up scan for frequent schedules after a long outage.
+    let catch_up_from = now - Duration::days(1);
+    if slot < catch_up_from
+        && let Some(recent) = schedule.next_after(&catch_up_from.with_timezone(timezone))
+        && recent.with_timezone(&Utc) <= now
+    {
+        slot = recent.with_timezone(&Utc);
+    }
+    while let Some(next) = schedule.next_after(&slot.with_timezone(timezone))
+        && next.with_timezone(&Utc) <= now
+    {
+        slot = next.with_timezone(&Utc);
+    }
+    Some(slot)
+}
+
+fn next_run<Tz: TimeZone>(job: &ScheduledJob, timezone: &Tz) -> Option<DateTime<Tz>> {
+    let schedule = CronSchedule::parse(&job.cron).ok()?;
+    let base = job.last_fired_at.unwrap_or(job.created_at).max(Utc::now());
+    schedule.next_after(&base.with_timezone(timezone))
+}
+
+fn render_jobs<Tz: TimeZone>(jobs: &[ScheduledJob], timezone: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    if jobs.is_empty() {
+        return "No scheduled jobs.\n".to_string();
+    }
+    let mut out = String::new();
+    for job in jobs {
+        let next = if job.enabled {
+            next_run(job, timezone)
+                .map(|next| format!("next {}", next.format("%Y-%m-%d %H:%M")))
+                .unwrap_or_else(|| "never runs".to_string())
+        } else {
+            "disabled".to_string()
+        };
+        out.push_str(&format!("{}  {}  {next}\n", job.name, job.cron));
+        out.push_str(&format!("  in {}\n", job.cwd.display()));
+        let prompt = job.prompt.lines().next().unwrap_or_default();
+        out.push_str(&format!("  {prompt}\n"));
+    }
+    out
+}
+
+fn render_runs<Tz: TimeZone>(runs: &[ScheduledJobRun], timezone: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    if runs.is_empty() {
+        return "No scheduled runs yet.\n".to_string();
+    }
+    let mut out = String::new();
+    for run in runs {
+        let duration = run
+            .finished_at
+            .map(|finished_at| format!(" in {}s", (finished_at - run.started_at).num_seconds()))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{}  {}  {}{duration}\n",
+            run.started_at
+                .with_timezone(timezone)
+                .format("%Y-%m-%d %H:%M"),
+            run.job_name,
+            run.status.as_str()
+        ));
+        if let Some(thread_id) = run.thread_id {
+            out.push_str(&format!("  session {thread_id}\n"));
+        }
+        if let Some(rollout_path) = &run.rollout_path {
+            out.push_str(&format!("  rollout {}\n", rollout_path.display()));
+        }
+        if let Some(error) = &run.error {
+            out.push_str(&format!("  error: {error}\n"));
+        }
+    }
+    out
+}
+
+fn job_to_json(job: &ScheduledJob) -> serde_json::Value {
+    json!({
+        "name": job.name,
+        "prompt": job.prompt,
+        "cwd": job.cwd,
+        "profile": job.profile,
+        "sandbox": job.sandbox,
+        "cron": job.cron,
+        "output": job.output,
+        "enabled": job.enabled,
+        "created_at": job.created_at.to_rfc3339(),
+        "last_fired_at": job.last_fired_at.map(|time| time.to_rfc3339()),
+        "next_run_at": next_run(job, &Utc).map(|time| time.to_rfc3339()),
+    })
+}
+
+fn run_to_json(run: &ScheduledJobRun) -> serde_json::Value {
+    json!({
+        "id": run.id,
+        "job": run.job_name,
+        "scheduled_for": run.scheduled_for.to_rfc3339(),
+        "started_at": run.started_at.to_rfc3339(),
+        "finished_at": run.finished_at.map(|time| time.to_rfc3339()),
+        "status": run.status.as_str(),
+        "exit_code": run.exit_code,
+        "thread_id": run.thread_id.map(|thread_id| thread_id.to_string()),
+        "rollout_path": run.rollout_path,
+        "error": run.error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::utc;
+    use codex_state::StateRuntime;
+    use pretty_assertions::assert_eq;
+    use std::cell::Cell;
+    use std::cell::RefCell;
+
+    struct FakeClock(Cell<DateTime<Utc>>);
+
+    impl FakeClock {
+        fn set(&self, value: &str) {
+            self.0.set(utc(value));
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0.get()
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeRunner {
+        runs: RefCell<Vec<String>>,
+    }
+
+    impl JobRunner for FakeRunner {
+        async fn run(&self, job: &ScheduledJob) -> ScheduledRunOutcome {
+            self.runs.borrow_mut().push(job.name.clone());
+            ScheduledRunOutcome {
+                status: ScheduledRunStatus::Succeeded,
+                exit_code: Some(0),
+                thread_id: Some(ThreadId::new()),
+                rollout_path: None,
+                error: None,
+            }
+        }
+    }
+
+    fn new_job(name: &str, cron: &str) -> NewScheduledJob {
+        NewScheduledJob {
+            name: name.to_string(),
+            prompt: "Triage new issues".to_string(),
+            cwd: PathBuf::from("/work/codex"),
+            profile: None,
+            sandbox: None,
+            cron: cron.to_string(),
+            output: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_due_jobs_once_per_slot_and_coalesces_missed_slots() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let state_db = StateRuntime::init(
+            codex_home.path().to_path_buf(),
+            "test-provider".to_string(),
+            None,
+        )
+        .await
+        .expect("initialize state db");
+        let created_at = utc("2026-03-01T10:00:00Z");
+        state_db
+            .add_scheduled_job(&new_job("nightly", "0 2 * * *"), created_at)
+            .await
+            .expect("add nightly");
+        state_db
+            .add_scheduled_job(&new_job("weekly", "@weekly"), created_at)
+            .await
+            .expect("add weekly");
+        state_db
+            .set_scheduled_job_enabled("weekly", false, created_at)
+            .await
+            .expect("disable weekly");
+
+        let clock = FakeClock(Cell::new(created_at));
+        let runner = FakeRunner::default();
+        let run = async |now: &str| {
+            clock.set(now);
+            run_due_jobs(&state_db, &clock, &Utc, &runner)
+                .await
+                .expect("run due jobs")
+        };
+
+        assert_eq!(run("2026-03-02T01:59:00Z").await, 0);
+        assert_eq!(run("2026-03-02T02:00:30Z").await, 1);
+        assert_eq!(run("2026-03-02T02:01:00Z").await, 0);
+        // Three nightly slots were missed; only the latest one runs.
+        assert_eq!(run("2026-03-05T09:00:00Z").await, 1);
+        assert_eq!(runner.runs.take(), vec!["nightly", "nightly"]);
+
+        let runs = state_db
+            .list_scheduled_runs(None, 10)
+            .await
+            .expect("list runs");
+        assert_eq!(
+            runs.iter()
+                .map(|run| (run.scheduled_for, run.started_at, run.status))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    utc("2026-03-05T02:00:00Z"),
+                    utc("2026-03-05T09:00:00Z"),
+                    ScheduledRunStatus::Succeeded
+                ),
+                (
+                    utc("2026-03-02T02:00:00Z"),
+                    utc("2026-03-02T02:00:30Z"),
+                    ScheduledRunStatus::Succeeded
+                ),
+            ]
+        );
+        assert!(runs.iter().all(|run| run.thread_id.is_some()));
+    }
+
+    #[test]
+    fn second_daemon_lock_is_refused() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let lock = acquire_daemon_lock(codex_home.path()).expect("first lock");
+
+        let err = acquire_daemon_lock(codex_home.path()).expect_err("second lock");
+        assert!(
+            err.to_string()
+                .starts_with("another scheduler daemon is already running"),
+            "unexpected error: {err}"
+        );
+
+        drop(lock);
+        acquire_daemon_lock(codex_home.path()).expect("lock after release");
+    }
+
+    #[test]
+    fn renders_run_history() {
+        let started_at = utc("2026-03-02T02:00:00Z");
+        let thread_id = ThreadId::new();
+        let runs = vec![ScheduledJobRun {
+            id: 1,
+            job_id: 1,
+            job_name: "nightly".to_string(),
+            scheduled_for: started_at,
+            started_at,
+            finished_at: Some(started_at + Duration::seconds(95)),
+            status: ScheduledRunStatus::Failed,
+            exit_code: Some(1),
+            thread_id: Some(thread_id),
+            rollout_path: Some(PathBuf::from("/home/.codex/sessions/rollout.jsonl")),
+            error: Some("usage limit reached".to_string()),
+        }];
+        assert_eq!(
+            render_runs(&runs, &Utc),
+            format!(
+                "2026-03-02 02:00  nightly  failed in 95s
+  session {thread_id}
+  rollout /home/.codex/sessions/rollout.jsonl
+  error: usage limit reached
+"
+            )
+        );
+    }
+}
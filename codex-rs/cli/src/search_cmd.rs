@@ -0,0 +1,136 @@
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::state_db::ThreadSearchMatchKind;
+use codex_core::state_db::ThreadSearchResult;
+use codex_core::state_db::get_state_db;
+use codex_core::state_db::search_threads_db;
+use serde_json::json;
+
+/// Full-text search over past sessions' messages, commands and edited files.
+#[derive(Debug, clap::Parser)]
+pub struct SearchCommand {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Words to search for; every word must match.
+    #[arg(value_name = "QUERY", required = true, num_args = 1..)]
+    pub query: Vec<String>,
+
+    /// Maximum number of sessions to show.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Search archived sessions instead of active ones.
+    #[arg(long)]
+    pub archived: bool,
+
+    /// Output the results as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub async fn run_search_command(cmd: SearchCommand) -> Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let state_db = get_state_db(&config, None).await;
+    let results = search_threads_db(
+        state_db.as_deref(),
+        &cmd.query.join(" "),
+        cmd.limit,
+        &[],
+        None,
+        None,
+        cmd.archived,
+    )
+    .await?;
+
+    if cmd.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&results_to_json(&results))?
+        );
+    } else {
+        print!("{}", render_results(&results));
+    }
+    Ok(())
+}
+
+fn kind_label(kind: ThreadSearchMatchKind) -> &'static str {
+    match kind {
+        ThreadSearchMatchKind::UserMessage => "you",
+        ThreadSearchMatchKind::AgentMessage => "codex",
+        ThreadSearchMatchKind::Command => "ran",
+        ThreadSearchMatchKind::File => "edited",
+    }
+}
+
+fn render_results(results: &[ThreadSearchResult]) -> String {
+    if results.is_empty() {
+        return "No matching sessions.\n".to_string();
+    }
+    let mut out = String::new();
+    for result in results {
+        let thread = &result.thread;
+        let title = if thread.title.is_empty() {
+            thread.first_user_message.as_deref().unwrap_or_default()
+        } else {
+            thread.title.as_str()
+        };
+        out.push_str(&format!(
+            "{}  {}  {}\n",
+            thread.id,
+            thread.updated_at.format("%Y-%m-%d %H:%M"),
+            thread.cwd.display()
+        ));
+        if !title.is_empty() {
+            let title = title.lines().next().unwrap_or_default();
+            out.push_str(&format!("  {title}\n"));
+        }
+        for search_match in &result.matches {
+            out.push_str(&format!(
+                "    turn {} {}: {}\n",
+                search_match.turn_index + 1,
+                kind_label(search_match.kind),
+                search_match.snippet.replace('\n', " ")
+            ));
+        }
+        out.push('\n');
+    }
+    out.push_str("Resume a session with `codex resume <SESSION_ID>`.\n");
+    out
+}
+
+fn results_to_json(results: &[ThreadSearchResult]) -> serde_json::Value {
+    results
+        .iter()
+        .map(|result| {
+            json!({
+                "id": result.thread.id.to_string(),
+                "path": result.thread.rollout_path,
+                "cwd": result.thread.cwd,
+                "title": result.thread.title,
+                "first_user_message": result.thread.first_user_message,
+                "created_at": result.thread.created_at.to_rfc3339(),
+                "updated_at": result.thread.updated_at.to_rfc3339(),
+                "matches": result
+                    .matches
+                    .iter()
+                    .map(|search_match| {
+                        json!({
+                            "turn_index": search_match.turn_index,
+                            "kind": search_match.kind.as_str(),
+                            "snippet": search_match.snippet,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
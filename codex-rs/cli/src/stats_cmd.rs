@@ -0,0 +1,203 @@
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::Utc;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::state_db::UsageStats;
+use codex_core::state_db::UsageStatsGroup;
+use codex_core::state_db::UsageStatsGroupBy;
+use codex_core::state_db::UsageStatsQuery;
+use codex_core::state_db::get_state_db;
+use codex_protocol::num_format::format_with_separators;
+
+/// Report sessions, turns, tokens and tool calls recorded in the state database.
+#[derive(Debug, clap::Parser)]
+pub struct StatsCommand {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Only include sessions started on or after this date (YYYY-MM-DD, UTC).
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub since: Option<NaiveDate>,
+
+    /// Only include sessions started on or before this date (YYYY-MM-DD, UTC).
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub until: Option<NaiveDate>,
+
+    /// Group the report by repository, by model or by the day each turn started.
+    #[arg(long = "by", value_enum)]
+    pub group_by: Option<StatsGroupBy>,
+
+    /// Number of entries to show in each per-tool and per-command list.
+    #[arg(long, default_value_t = 5)]
+    pub top: usize,
+
+    /// Output the report as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatsGroupBy {
+    Repo,
+    Model,
+    Day,
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|err| format!("expected a date like 2026-01-31: {err}"))
+}
+
+fn start_of_day(date: NaiveDate) -> Option<DateTime<Utc>> {
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+pub async fn run_stats_command(cmd: StatsCommand) -> Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let Some(state_db) = get_state_db(&config, None).await else {
+        anyhow::bail!(
+            "usage statistics need the state database; enable it with `codex features enable sqlite` and run a session first"
+        );
+    };
+
+    let query = UsageStatsQuery {
+        since: cmd.since.and_then(start_of_day),
+        // `--until` is inclusive, so stop at the start of the following day.
+        until: cmd
+            .until
+            .and_then(|date| date.checked_add_days(Days::new(1)))
+            .and_then(start_of_day),
+        group_by: match cmd.group_by {
+            None => UsageStatsGroupBy::None,
+            Some(StatsGroupBy::Repo) => UsageStatsGroupBy::Repo,
+            Some(StatsGroupBy::Model) => UsageStatsGroupBy::Model,
+            Some(StatsGroupBy::Day) => UsageStatsGroupBy::Day,
+        },
+        top: cmd.top,
+    };
+    let stats = state_db.usage_stats(&query).await?;
+
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", render_stats(&stats));
+    }
+    Ok(())
+}
+
+fn render_stats(stats: &UsageStats) -> String {
+    if stats.groups.is_empty() {
+        return "No sessions found.\n".to_string();
+    }
+    let mut out = String::new();
+    for (idx, group) in stats.groups.iter().enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        if let Some(key) = group.key.as_deref() {
+            let key = if key.is_empty() { "(unknown)" } else { key };
+            out.push_str(&format!("{key}\n"));
+        }
+        render_group(&mut out, group);
+    }
+    out
+}
+
+fn render_group(out: &mut String, group: &UsageStatsGroup) {
+    let cost = match group.cost_usd {
+        Some(cost_usd) => format!("${cost_usd:.2}"),
+        None => "price unknown".to_string(),
+    };
+    out.push_str(&format!(
+        "  Sessions: {}  Turns: {}  Tokens: {}  Estimated cost: {cost}\n",
+        format_with_separators(group.sessions),
+        format_with_separators(group.turns),
+        format_with_separators(group.tokens),
+    ));
+    match group.approval_rate() {
+        Some(rate) => out.push_str(&format!(
+            "  Approvals: {} requested, {} denied ({:.0}% approved)\n",
+            group.approvals_requested,
+            group.approvals_denied,
+            rate * 100.0,
+        )),
+        None => out.push_str("  Approvals: none requested\n"),
+    }
+    for (title, entries) in [
+        ("Tool calls", &group.tool_calls),
+        ("Most denied commands", &group.denied_commands),
+        ("Most failing commands", &group.failing_commands),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("  {title}:\n"));
+        let width = entries
+            .iter()
+            .map(|entry| entry.count.to_string().len())
+            .max()
+            .unwrap_or(0);
+        for entry in entries {
+            out.push_str(&format!("    {:>width$}  {}\n", entry.count, entry.name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::state_db::NamedCount;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn renders_grouped_stats() {
+        let stats = UsageStats {
+            groups: vec![UsageStatsGroup {
+                key: Some("gpt-5.2-codex".to_string()),
+                sessions: 3,
+                turns: 12,
+                tokens: 950,
+                cost_usd: Some(1.5),
+                tool_calls: vec![
+                    NamedCount {
+                        name: "shell".to_string(),
+                        count: 10,
+                    },
+                    NamedCount {
+                        name: "apply_patch".to_string(),
+                        count: 4,
+                    },
+                ],
+                approvals_requested: 4,
+                approvals_denied: 1,
+                denied_commands: vec![NamedCount {
+                    name: "rm -rf target".to_string(),
+                    count: 1,
+                }],
+                failing_commands: Vec::new(),
+            }],
+        };
+        assert_eq!(
+            render_stats(&stats),
+            "gpt-5.2-codex
+  Sessions: 3  Turns: 12  Tokens: 950  Estimated cost: $1.50
+  Approvals: 4 requested, 1 denied (75% approved)
+  Tool calls:
+    10  shell
+     4  apply_patch
+  Most denied commands:
+    1  rm -rf target
+"
+        );
+    }
+}
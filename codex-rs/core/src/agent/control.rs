@@ -289,6 +289,7 @@ mod tests {
     async fn on_event_updates_status_from_turn_aborted() {
         let status = agent_status_from_event(&EventMsg::TurnAborted(TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }));
 
         let expected = AgentStatus::Errored("Interrupted".to_string());
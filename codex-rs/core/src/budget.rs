@@ -0,0 +1,189 @@
+//! Per-thread consumption limits configured under `[budget]`.
+//!
+//! The session checks its budget before every model request of a turn. When a
+//! budget first crosses [`BUDGET_WARNING_RATIO`] of its limit the user is
+//! warned and the model is told to wrap up; once it runs out the turn ends with
+//! [`TurnAbortReason::BudgetExceeded`](codex_protocol::protocol::TurnAbortReason).
+//!
+//! Counters live in [`SessionState`](crate::state::SessionState) and are not
+//! persisted: on resume the token total comes back with the restored token
+//! usage, but turns, tool calls and elapsed time start over. Every thread,
+//! including sub-agents, gets its own budget from its config.
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+use codex_protocol::protocol::SessionBudgetKind;
+use codex_protocol::protocol::SessionBudgetUsage;
+
+use crate::config::types::SessionBudgetConfig;
+
+/// Fraction of a limit at which the model is asked to wrap up.
+const BUDGET_WARNING_RATIO: f64 = 0.8;
+
+pub(crate) struct SessionBudget {
+    limits: SessionBudgetConfig,
+    started_at: Instant,
+    turns: i64,
+    tool_calls: i64,
+    warned: HashSet<SessionBudgetKind>,
+    exceeded: Option<SessionBudgetUsage>,
+}
+
+impl SessionBudget {
+    pub(crate) fn new(limits: SessionBudgetConfig) -> Self {
+        Self {
+            limits,
+            started_at: Instant::now(),
+            turns: 0,
+            tool_calls: 0,
+            warned: HashSet::new(),
+            exceeded: None,
+        }
+    }
+
+    pub(crate) fn record_turn(&mut self) {
+        self.turns += 1;
+    }
+
+    pub(crate) fn record_tool_call(&mut self) {
+        self.tool_calls += 1;
+    }
+
+    /// Check every configured limit against the current consumption.
+    ///
+    /// Returns the exhausted budget as an error (and remembers it for
+    /// [`Self::take_exceeded`]); otherwise returns the budgets that crossed
+    /// the warning threshold since the last check.
+    pub(crate) fn check(
+        &mut self,
+        total_tokens: i64,
+    ) -> Result<Vec<SessionBudgetUsage>, SessionBudgetUsage> {
+        let usage = self.usage(total_tokens);
+        if let Some(exhausted) = usage.iter().find(|usage| is_exhausted(usage)) {
+            self.exceeded = Some(exhausted.clone());
+            return Err(exhausted.clone());
+        }
+        Ok(usage
+            .into_iter()
+            .filter(|usage| usage.used as f64 >= usage.limit as f64 * BUDGET_WARNING_RATIO)
+            .filter(|usage| self.warned.insert(usage.kind))
+            .collect())
+    }
+
+    /// The budget that ended the current turn, if any.
+    pub(crate) fn take_exceeded(&mut self) -> Option<SessionBudgetUsage> {
+        self.exceeded.take()
+    }
+
+    fn usage(&self, total_tokens: i64) -> Vec<SessionBudgetUsage> {
+        let elapsed = i64::try_from(self.started_at.elapsed().as_secs()).unwrap_or(i64::MAX);
+        [
+            (SessionBudgetKind::Turns, self.limits.max_turns, self.turns),
+            (
+                SessionBudgetKind::TotalTokens,
+                self.limits.max_total_tokens,
+                total_tokens,
+            ),
+            (
+                SessionBudgetKind::ToolCalls,
+                self.limits.max_tool_calls,
+                self.tool_calls,
+            ),
+            (
+                SessionBudgetKind::Duration,
+                self.limits.max_duration_sec,
+                elapsed,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(kind, limit, used)| {
+            let limit = i64::try_from(limit?).unwrap_or(i64::MAX);
+            Some(SessionBudgetUsage { kind, limit, used })
+        })
+        .collect()
+    }
+}
+
+impl Default for SessionBudget {
+    fn default() -> Self {
+        Self::new(SessionBudgetConfig::default())
+    }
+}
+
+/// The turn counter includes the running turn, so it may reach its limit;
+/// every other budget is spent once it is reached.
+fn is_exhausted(usage: &SessionBudgetUsage) -> bool {
+    match usage.kind {
+        SessionBudgetKind::Turns => usage.used > usage.limit,
+        SessionBudgetKind::TotalTokens
+        | SessionBudgetKind::ToolCalls
+        | SessionBudgetKind::Duration => usage.used >= usage.limit,
+    }
+}
+
+/// Message recorded for the model when a budget nears its limit.
+pub(crate) fn budget_warning_message(usage: &SessionBudgetUsage) -> String {
+    format!(
+        "This session is close to its {usage}. Finish the current task with as few further steps as possible and summarize what remains."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn warns_once_then_reports_exhausted_budget() {
+        let mut budget = SessionBudget::new(SessionBudgetConfig {
+            max_tool_calls: Some(5),
+            max_total_tokens: Some(1_000),
+            ..Default::default()
+        });
+        assert_eq!(budget.check(100), Ok(Vec::new()));
+
+        for _ in 0..4 {
+            budget.record_tool_call();
+        }
+        assert_eq!(
+            budget.check(100),
+            Ok(vec![SessionBudgetUsage {
+                kind: SessionBudgetKind::ToolCalls,
+                limit: 5,
+                used: 4,
+            }])
+        );
+        assert_eq!(budget.check(100), Ok(Vec::new()));
+
+        let exceeded = SessionBudgetUsage {
+            kind: SessionBudgetKind::TotalTokens,
+            limit: 1_000,
+            used: 1_000,
+        };
+        assert_eq!(budget.check(1_000), Err(exceeded.clone()));
+        assert_eq!(budget.take_exceeded(), Some(exceeded));
+        assert_eq!(budget.take_exceeded(), None);
+    }
+
+    #[test]
+    fn last_allowed_turn_may_run() {
+        let mut budget = SessionBudget::new(SessionBudgetConfig {
+            max_turns: Some(2),
+            ..Default::default()
+        });
+        budget.record_turn();
+        budget.record_turn();
+        assert!(budget.check(0).is_ok());
+
+        budget.record_turn();
+        assert_eq!(
+            budget.check(0),
+            Err(SessionBudgetUsage {
+                kind: SessionBudgetKind::Turns,
+                limit: 2,
+                used: 3,
+            })
+        );
+    }
+}
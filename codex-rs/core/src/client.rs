@@ -37,6 +37,7 @@ use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
+use chrono::Utc;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::MemoriesClient as ApiMemoriesClient;
@@ -69,6 +70,7 @@ use codex_protocol::config_types::Verbosity as VerbosityConfig;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::SessionSource;
 use eventsource_stream::Event;
 use eventsource_stream::EventStreamError;
@@ -98,6 +100,10 @@ use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
+use crate::model_fallback::ModelFallbackChain;
+use crate::model_fallback::ModelFallbackSwitch;
+use crate::model_fallback::ModelFallbackTarget;
+use crate::model_fallback::incompatibility;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::tools::spec::create_tools_json_for_responses_api;
@@ -132,6 +138,7 @@ struct ModelClientState {
     include_timing_metrics: bool,
     beta_features_header: Option<String>,
     disable_websockets: AtomicBool,
+    fallback: ModelFallbackChain,
 
     preconnect: Mutex<Option<PreconnectTask>>,
 }
@@ -158,6 +165,7 @@ impl std::fmt::Debug for ModelClientState {
                 "disable_websockets",
                 &self.disable_websockets.load(Ordering::Relaxed),
             )
+            .field("fallback", &self.fallback)
             .field("preconnect", &"<opaque>")
             .finish()
     }
@@ -223,6 +231,9 @@ pub struct ModelClientSession {
     /// keep sending it unchanged between turn requests (e.g., for retries, incremental
     /// appends, or continuation requests), and must not send it between different turns.
     turn_state: Arc<OnceLock<String>>,
+    /// Session on the fallback model's client while a fallback is active, keyed by its position
+    /// in the chain so a later switch starts a fresh one.
+    fallback_session: Option<(usize, Box<ModelClientSession>)>,
 }
 
 enum WebsocketStreamOutcome {
@@ -261,11 +272,40 @@ impl ModelClient {
                 include_timing_metrics,
                 beta_features_header,
                 disable_websockets: AtomicBool::new(false),
+                fallback: ModelFallbackChain::default(),
                 preconnect: Mutex::new(None),
             }),
         }
     }
 
+    /// Configures the models to switch to when the session's model keeps failing.
+    ///
+    /// Must be called before the client is cloned; the chain is shared by all clones afterwards.
+    pub(crate) fn with_model_fallbacks(
+        mut self,
+        primary_provider_id: String,
+        targets: Vec<ModelFallbackTarget>,
+        cooldown: Duration,
+    ) -> Self {
+        if targets.is_empty() {
+            return self;
+        }
+        match Arc::get_mut(&mut self.state) {
+            Some(state) => {
+                state.fallback = ModelFallbackChain::new(primary_provider_id, targets, cooldown);
+            }
+            None => warn!("model client already shared; ignoring fallback models"),
+        }
+        self
+    }
+
+    /// Returns to the configured model once the fallback cooldown has passed.
+    ///
+    /// Returns the fallback model that was serving the session when it switches back.
+    pub(crate) fn restore_primary_model_if_due(&self) -> Option<String> {
+        self.state.fallback.restore_primary_if_due(Utc::now())
+    }
+
     /// Creates a fresh turn-scoped streaming session.
     ///
     /// This constructor does not perform network I/O itself. The returned session either adopts a
@@ -279,6 +319,7 @@ impl ModelClient {
             websocket_last_response_id: None,
             websocket_last_response_id_rx: None,
             turn_state: Arc::new(OnceLock::new()),
+            fallback_session: None,
         }
     }
 
@@ -1032,7 +1073,8 @@ impl ModelClientSession {
     /// The caller is responsible for passing per-turn settings explicitly (model selection,
     /// reasoning settings, telemetry context, and turn metadata). This method will prefer the
     /// Responses WebSocket transport when enabled and healthy, and will fall back to the HTTP
-    /// Responses API transport otherwise.
+    /// Responses API transport otherwise. While a fallback model is active and matches
+    /// `model_info`, the request goes to that model and its provider instead.
     pub async fn stream(
         &mut self,
         prompt: &Prompt,
@@ -1041,6 +1083,62 @@ impl ModelClientSession {
         effort: Option<ReasoningEffortConfig>,
         summary: ReasoningSummaryConfig,
         turn_metadata_header: Option<&str>,
+    ) -> Result<ResponseStream> {
+        if let Some((index, target)) = self.client.state.fallback.active()
+            && incompatibility(model_info, &target.model_info).is_none()
+        {
+            // The fallback model may not accept the configured reasoning effort.
+            let effort = effort
+                .filter(|effort| {
+                    target
+                        .model_info
+                        .supported_reasoning_levels
+                        .iter()
+                        .any(|preset| preset.effort == *effort)
+                })
+                .or(target.model_info.default_reasoning_level);
+            if self
+                .fallback_session
+                .as_ref()
+                .is_some_and(|(active, _)| *active != index)
+            {
+                self.fallback_session = None;
+            }
+            let (_, session) = self
+                .fallback_session
+                .get_or_insert_with(|| (index, Box::new(target.client.new_session())));
+            return session
+                .stream_with_own_provider(
+                    prompt,
+                    &target.model_info,
+                    otel_manager,
+                    effort,
+                    summary,
+                    turn_metadata_header,
+                )
+                .await;
+        }
+
+        self.stream_with_own_provider(
+            prompt,
+            model_info,
+            otel_manager,
+            effort,
+            summary,
+            turn_metadata_header,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_with_own_provider(
+        &mut self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        otel_manager: &OtelManager,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        turn_metadata_header: Option<&str>,
     ) -> Result<ResponseStream> {
         let wire_api = self.client.state.provider.wire_api;
         match wire_api {
@@ -1104,6 +1202,29 @@ impl ModelClientSession {
         }
         activated
     }
+
+    /// Moves the session to the next fallback model after `err`, if the error calls for one.
+    ///
+    /// `rate_limits` is the latest snapshot reported by the provider; the configured model is not
+    /// retried before the limits it reports as exhausted have reset.
+    pub(crate) fn try_switch_fallback_model(
+        &mut self,
+        primary: &ModelInfo,
+        err: &CodexErr,
+        rate_limits: Option<&RateLimitSnapshot>,
+    ) -> Option<ModelFallbackSwitch> {
+        let switch = self
+            .client
+            .state
+            .fallback
+            .advance(primary, err, rate_limits, Utc::now())?;
+        warn!(
+            "switching from {} to fallback model {} ({})",
+            switch.from_model, switch.to_model, switch.to_provider_id
+        );
+        self.fallback_session = None;
+        Some(switch)
+    }
 }
 
 /// Adapts the core `Prompt` type into the `codex-api` payload shape.
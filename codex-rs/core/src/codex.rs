@@ -14,6 +14,8 @@ use crate::agent::MAX_THREAD_SPAWN_DEPTH;
 use crate::agent::agent_status_from_event;
 use crate::analytics_client::AnalyticsEventsClient;
 use crate::analytics_client::build_track_events_context;
+use crate::budget::SessionBudget;
+use crate::budget::budget_warning_message;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
@@ -27,9 +29,14 @@ use crate::features::maybe_push_unstable_features_warning;
 use crate::hooks::HookEvent;
 use crate::hooks::HookEventAfterAgent;
 use crate::hooks::Hooks;
+use crate::lsp::LspManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::network_approval::NETWORK_APPROVAL_POLL_INTERVAL;
+use crate::network_approval::NetworkApprovals;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
+use crate::pricing::ThreadCost;
+use crate::pricing::estimate_cost_usd;
 use crate::rollout::session_index;
 use crate::stream_events_utils::HandleOutputCtx;
 use crate::stream_events_utils::handle_non_tool_response_item;
@@ -126,6 +133,9 @@ pub enum SteerInputError {
     ExpectedTurnMismatch { expected: String, actual: String },
     EmptyInput,
 }
+use crate::config::reload::ConfigReload;
+use crate::config::reload::refresh_global_instructions;
+use crate::config::reload::reload_config;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
 use crate::file_watcher::FileWatcher;
@@ -142,6 +152,8 @@ use crate::mentions::build_connector_slug_counts;
 use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_paths;
 use crate::mentions::collect_tool_mentions_from_messages;
+use crate::model_fallback::ModelFallbackTarget;
+use crate::model_fallback::incompatibility;
 use crate::project_doc::get_user_instructions;
 use crate::proposed_plan_parser::ProposedPlanParser;
 use crate::proposed_plan_parser::ProposedPlanSegment;
@@ -151,12 +163,14 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::ConfigReloadedEvent;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
 use crate::protocol::McpServerRefreshConfig;
+use crate::protocol::ModelFallbackEvent;
 use crate::protocol::Op;
 use crate::protocol::PlanDeltaEvent;
 use crate::protocol::RateLimitSnapshot;
@@ -212,7 +226,9 @@ use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::util::backoff;
+use crate::web_fetch::WebFetcher;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
+use crate::workspace_edits::WorkspaceEdits;
 use codex_async_utils::OrCancelExt;
 use codex_otel::OtelManager;
 use codex_otel::TelemetryAuthMode;
@@ -227,6 +243,7 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
+use codex_protocol::protocol::SessionBudgetUsage;
 use codex_protocol::user_input::UserInput;
 use codex_utils_readiness::Readiness;
 use codex_utils_readiness::ReadinessFlag;
@@ -497,11 +514,21 @@ pub(crate) struct Session {
     /// session.
     features: Features,
     pending_mcp_server_refresh_config: Mutex<Option<McpServerRefreshConfig>>,
+    pending_config_reload: Mutex<Option<PendingConfigReload>>,
     pub(crate) active_turn: Mutex<Option<ActiveTurn>>,
     pub(crate) services: SessionServices,
     next_internal_sub_id: AtomicU64,
 }
 
+/// Config and instruction changes read from disk, applied when the next turn
+/// starts.
+struct PendingConfigReload {
+    config: Arc<Config>,
+    /// New user instructions, when `AGENTS.md` changed.
+    user_instructions: Option<Option<String>>,
+    refresh_mcp_servers: bool,
+}
+
 /// The context needed for a single turn of the thread.
 #[derive(Debug)]
 pub(crate) struct TurnContext {
@@ -774,6 +801,18 @@ impl Session {
                         };
                         sess.send_event_raw(event).await;
                     }
+                    Ok(FileWatcherEvent::WorkspaceChanged { paths }) => {
+                        let Some(sess) = weak_sess.upgrade() else {
+                            break;
+                        };
+                        sess.services.workspace_edits.record_user_changes(&paths);
+                    }
+                    Ok(FileWatcherEvent::ConfigChanged { .. }) => {
+                        let Some(sess) = weak_sess.upgrade() else {
+                            break;
+                        };
+                        sess.reload_config_from_disk().await;
+                    }
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -781,6 +820,38 @@ impl Session {
         });
     }
 
+    fn start_network_approval_listener(self: &Arc<Self>) {
+        let Some(approvals) = self.services.network_approvals.clone() else {
+            return;
+        };
+        let weak_sess = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(NETWORK_APPROVAL_POLL_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let Some(sess) = weak_sess.upgrade() else {
+                    break;
+                };
+                let requests = match approvals.poll_new_requests().await {
+                    Ok(requests) => requests,
+                    Err(err) => {
+                        // The proxy may not be running yet; keep polling.
+                        debug!("failed to poll network proxy for blocked requests: {err}");
+                        continue;
+                    }
+                };
+                for request in requests {
+                    let event = Event {
+                        id: sess.next_internal_sub_id(),
+                        msg: EventMsg::NetworkApprovalRequest(request),
+                    };
+                    sess.send_event_raw(event).await;
+                }
+            }
+        });
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn make_turn_context(
         auth_manager: Option<Arc<AuthManager>>,
@@ -807,7 +878,8 @@ impl Session {
             model_info: &model_info,
             features: &per_turn_config.features,
             web_search_mode: Some(per_turn_config.web_search_mode.value()),
-        });
+        })
+        .with_custom_tools(per_turn_config.custom_tools.clone());
 
         let cwd = session_configuration.cwd.clone();
         TurnContext {
@@ -1044,7 +1116,78 @@ impl Session {
                 }
             };
         session_configuration.thread_name = thread_name.clone();
-        let state = SessionState::new(session_configuration.clone());
+        let mut state = SessionState::new(session_configuration.clone());
+        state.budget = SessionBudget::new(config.session_budget);
+
+        // Sub-agents share the parent's proxy; only the top-level session
+        // drains its blocked-request queue.
+        let network_approvals = match config.sandbox_network_proxy {
+            Some(proxy)
+                if !matches!(
+                    session_configuration.session_source,
+                    SessionSource::SubAgent(_)
+                ) =>
+            {
+                match NetworkApprovals::new(proxy.admin_addr, config.codex_home.clone()) {
+                    Ok(approvals) => Some(Arc::new(approvals)),
+                    Err(err) => {
+                        warn!("failed to set up network approvals: {err}");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let new_model_client = |provider: ModelProviderInfo| {
+            ModelClient::new(
+                Some(Arc::clone(&auth_manager)),
+                conversation_id,
+                provider,
+                session_configuration.session_source.clone(),
+                config.model_verbosity,
+                config.features.enabled(Feature::ResponsesWebsockets)
+                    || config.features.enabled(Feature::ResponsesWebsocketsV2),
+                config.features.enabled(Feature::ResponsesWebsocketsV2),
+                config.features.enabled(Feature::EnableRequestCompression),
+                config.features.enabled(Feature::RuntimeMetrics),
+                Self::build_model_client_beta_features_header(config.as_ref()),
+            )
+        };
+        let mut fallback_targets = Vec::with_capacity(config.fallback_models.len());
+        if !config.fallback_models.is_empty() {
+            let primary = models_manager
+                .get_model_info(session_configuration.collaboration_mode.model(), &config)
+                .await;
+            for fallback in &config.fallback_models {
+                let model_info = models_manager
+                    .get_model_info(&fallback.model, &config)
+                    .await;
+                if let Some(issue) = incompatibility(&primary, &model_info) {
+                    post_session_configured_events.push(Event {
+                        id: "".to_owned(),
+                        msg: EventMsg::Warning(WarningEvent {
+                            message: format!(
+                                "Ignoring fallback model {}: it does not match {} in {issue}.",
+                                model_info.slug, primary.slug
+                            ),
+                        }),
+                    });
+                    continue;
+                }
+                fallback_targets.push(ModelFallbackTarget {
+                    model_info,
+                    provider_id: fallback.model_provider_id.clone(),
+                    client: new_model_client(fallback.model_provider.clone()),
+                });
+            }
+        }
+        let model_client = new_model_client(session_configuration.provider.clone())
+            .with_model_fallbacks(
+                config.model_provider_id.clone(),
+                fallback_targets,
+                config.fallback_cooldown,
+            );
 
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
@@ -1067,19 +1210,15 @@ impl Session {
             file_watcher,
             agent_control,
             state_db: state_db_ctx.clone(),
-            model_client: ModelClient::new(
-                Some(Arc::clone(&auth_manager)),
-                conversation_id,
-                session_configuration.provider.clone(),
-                session_configuration.session_source.clone(),
-                config.model_verbosity,
-                config.features.enabled(Feature::ResponsesWebsockets)
-                    || config.features.enabled(Feature::ResponsesWebsocketsV2),
-                config.features.enabled(Feature::ResponsesWebsocketsV2),
-                config.features.enabled(Feature::EnableRequestCompression),
-                config.features.enabled(Feature::RuntimeMetrics),
-                Self::build_model_client_beta_features_header(config.as_ref()),
-            ),
+            network_approvals,
+            lsp_manager: Arc::new(LspManager::new(config.lsp_servers.clone())),
+            workspace_edits: Arc::new(if config.features.enabled(Feature::WorkspaceEdits) {
+                WorkspaceEdits::new(&session_configuration.cwd)
+            } else {
+                WorkspaceEdits::disabled()
+            }),
+            web_fetcher: WebFetcher::default(),
+            model_client,
         };
 
         let sess = Arc::new(Session {
@@ -1089,6 +1228,7 @@ impl Session {
             state: Mutex::new(state),
             features: config.features.clone(),
             pending_mcp_server_refresh_config: Mutex::new(None),
+            pending_config_reload: Mutex::new(None),
             active_turn: Mutex::new(None),
             services,
             next_internal_sub_id: AtomicU64::new(0),
@@ -1134,6 +1274,7 @@ impl Session {
 
         // Start the watcher after SessionConfigured so it cannot emit earlier events.
         sess.start_file_watcher_listener();
+        sess.start_network_approval_listener();
 
         // Construct sandbox_state before initialize() so it can be sent to each
         // MCP server immediately after it becomes ready (avoiding blocking).
@@ -1142,6 +1283,7 @@ impl Session {
             codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
             sandbox_cwd: session_configuration.cwd.clone(),
             use_linux_sandbox_bwrap: config.features.enabled(Feature::UseLinuxSandboxBwrap),
+            denied_read_paths: config.sandbox_denied_read_paths.clone(),
         };
         let mut required_mcp_servers: Vec<String> = mcp_servers
             .iter()
@@ -1235,6 +1377,49 @@ impl Session {
         state.get_total_token_usage(state.server_reasoning_included())
     }
 
+    pub(crate) async fn record_tool_call_for_budget(&self) {
+        self.state.lock().await.budget.record_tool_call();
+    }
+
+    pub(crate) async fn take_budget_exceeded(&self) -> Option<SessionBudgetUsage> {
+        self.state.lock().await.budget.take_exceeded()
+    }
+
+    /// Check the session budget before a model request, warning the user and
+    /// the model about budgets that near their limit. Returns `true` once a
+    /// budget has run out and the turn must end.
+    async fn enforce_session_budget(&self, turn_context: &TurnContext) -> bool {
+        let status = {
+            let mut state = self.state.lock().await;
+            let total_tokens = state
+                .token_info()
+                .map_or(0, |info| info.total_token_usage.total_tokens);
+            state.budget.check(total_tokens)
+        };
+        match status {
+            Ok(warnings) => {
+                for usage in warnings {
+                    self.send_event(
+                        turn_context,
+                        EventMsg::Warning(WarningEvent {
+                            message: format!("Approaching the session {usage}."),
+                        }),
+                    )
+                    .await;
+                    let message: ResponseItem =
+                        DeveloperInstructions::new(budget_warning_message(&usage)).into();
+                    self.record_conversation_items(turn_context, std::slice::from_ref(&message))
+                        .await;
+                }
+                false
+            }
+            Err(usage) => {
+                info!(turn_id = %turn_context.sub_id, "session {usage}; ending turn");
+                true
+            }
+        }
+    }
+
     async fn get_estimated_token_count(&self, turn_context: &TurnContext) -> Option<i64> {
         let state = self.state.lock().await;
         state.history.estimate_token_count(turn_context)
@@ -1299,9 +1484,14 @@ impl Session {
 
                 // Seed usage info from the recorded rollout so UIs can show token counts
                 // immediately on resume/fork.
-                if let Some(info) = Self::last_token_info_from_rollout(&rollout_items) {
+                if let Some(token_count) = Self::last_token_count_from_rollout(&rollout_items) {
                     let mut state = self.state.lock().await;
-                    state.set_token_info(Some(info));
+                    state.set_token_info(token_count.info.clone());
+                    let total_tokens = token_count
+                        .info
+                        .as_ref()
+                        .map_or(0, |info| info.total_token_usage.total_tokens);
+                    state.cost = ThreadCost::restored(token_count.total_cost_usd, total_tokens);
                 }
 
                 // Defer seeding the session's initial context until the first turn starts so
@@ -1320,9 +1510,14 @@ impl Session {
 
                 // Seed usage info from the recorded rollout so UIs can show token counts
                 // immediately on resume/fork.
-                if let Some(info) = Self::last_token_info_from_rollout(&rollout_items) {
+                if let Some(token_count) = Self::last_token_count_from_rollout(&rollout_items) {
                     let mut state = self.state.lock().await;
-                    state.set_token_info(Some(info));
+                    state.set_token_info(token_count.info.clone());
+                    let total_tokens = token_count
+                        .info
+                        .as_ref()
+                        .map_or(0, |info| info.total_token_usage.total_tokens);
+                    state.cost = ThreadCost::restored(token_count.total_cost_usd, total_tokens);
                 }
 
                 // If persisting, persist all rollout items as-is (recorder filters)
@@ -1363,9 +1558,9 @@ impl Session {
         }
     }
 
-    fn last_token_info_from_rollout(rollout_items: &[RolloutItem]) -> Option<TokenUsageInfo> {
+    fn last_token_count_from_rollout(rollout_items: &[RolloutItem]) -> Option<&TokenCountEvent> {
         rollout_items.iter().rev().find_map(|item| match item {
-            RolloutItem::EventMsg(EventMsg::TokenCount(ev)) => ev.info.clone(),
+            RolloutItem::EventMsg(EventMsg::TokenCount(ev)) if ev.info.is_some() => Some(ev),
             _ => None,
         })
     }
@@ -1449,6 +1644,7 @@ impl Session {
                 use_linux_sandbox_bwrap: per_turn_config
                     .features
                     .enabled(Feature::UseLinuxSandboxBwrap),
+                denied_read_paths: per_turn_config.sandbox_denied_read_paths.clone(),
             };
             if let Err(e) = self
                 .services
@@ -2204,6 +2400,16 @@ impl Session {
                 .into(),
             );
         }
+        if self.features.enabled(Feature::MemoryTool)
+            && let Some(memories) = crate::memories::load_memory_instructions(
+                self.state_db().as_deref(),
+                &turn_context.cwd,
+                turn_context.config.memory_max_tokens,
+            )
+            .await
+        {
+            items.push(DeveloperInstructions::new(memories).into());
+        }
         items.push(ResponseItem::from(EnvironmentContext::new(
             Some(turn_context.cwd.clone()),
             shell.as_ref().clone(),
@@ -2238,6 +2444,12 @@ impl Session {
             if let Some(token_usage) = token_usage {
                 state
                     .update_token_info_from_usage(token_usage, turn_context.model_context_window());
+                let cost_usd = turn_context
+                    .config
+                    .model_prices
+                    .get(turn_context.model_info.slug.as_str())
+                    .map(|price| estimate_cost_usd(token_usage, price));
+                state.cost.add(cost_usd);
             }
         }
         self.send_token_count_event(turn_context).await;
@@ -2317,11 +2529,16 @@ impl Session {
     }
 
     async fn send_token_count_event(&self, turn_context: &TurnContext) {
-        let (info, rate_limits) = {
+        let (info, rate_limits, total_cost_usd) = {
             let state = self.state.lock().await;
-            state.token_info_and_rate_limits()
+            let (info, rate_limits) = state.token_info_and_rate_limits();
+            (info, rate_limits, state.cost.total_usd())
         };
-        let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
+        let event = EventMsg::TokenCount(TokenCountEvent {
+            info,
+            rate_limits,
+            total_cost_usd,
+        });
         self.send_event(turn_context, event).await;
     }
 
@@ -2598,6 +2815,7 @@ impl Session {
             codex_linux_sandbox_exe: turn_context.codex_linux_sandbox_exe.clone(),
             sandbox_cwd: turn_context.cwd.clone(),
             use_linux_sandbox_bwrap: turn_context.features.enabled(Feature::UseLinuxSandboxBwrap),
+            denied_read_paths: turn_context.config.sandbox_denied_read_paths.clone(),
         };
         let cancel_token = self.reset_mcp_startup_cancellation_token().await;
 
@@ -2660,6 +2878,140 @@ impl Session {
             .await;
     }
 
+    /// Re-read `config.toml` layers and `AGENTS.md` after the file watcher saw
+    /// them change. Accepted changes are queued for the next turn; either way
+    /// clients are told what happened.
+    async fn reload_config_from_disk(&self) {
+        let pending = self
+            .pending_config_reload
+            .lock()
+            .await
+            .as_ref()
+            .map(|pending| {
+                (
+                    Arc::clone(&pending.config),
+                    pending.user_instructions.clone(),
+                )
+            });
+        let (base, previous_instructions) = {
+            let state = self.state.lock().await;
+            let configuration = &state.session_configuration;
+            match pending {
+                Some((config, Some(instructions))) => (config, instructions),
+                Some((config, None)) => (config, configuration.user_instructions.clone()),
+                None => (
+                    Arc::clone(&configuration.original_config_do_not_use),
+                    configuration.user_instructions.clone(),
+                ),
+            }
+        };
+
+        let reload = match reload_config(&base).await {
+            Ok(reload) => reload,
+            Err(err) => {
+                warn!("rejected config change: {err}");
+                self.send_event_raw(Event {
+                    id: self.next_internal_sub_id(),
+                    msg: EventMsg::ConfigReloaded(ConfigReloadedEvent {
+                        changed: Vec::new(),
+                        restart_required: Vec::new(),
+                        error: Some(err.to_string()),
+                    }),
+                })
+                .await;
+                return;
+            }
+        };
+
+        let mut config = reload
+            .as_ref()
+            .map_or_else(|| (*base).clone(), |reload| reload.config.clone());
+        refresh_global_instructions(&mut config);
+        let enabled_skills = self
+            .services
+            .skills_manager
+            .skills_for_config(&config)
+            .enabled_skills();
+        let instructions = get_user_instructions(&config, Some(&enabled_skills)).await;
+        let instructions_changed = instructions != previous_instructions;
+        let Some(reload) = reload.or_else(|| {
+            instructions_changed.then(|| ConfigReload {
+                config: config.clone(),
+                changed: Vec::new(),
+                restart_required: Vec::new(),
+                mcp_servers_changed: false,
+            })
+        }) else {
+            return;
+        };
+
+        let mut changed = reload.changed;
+        if instructions_changed {
+            changed.push("AGENTS.md".to_string());
+        }
+        {
+            let mut pending = self.pending_config_reload.lock().await;
+            let pending = pending.get_or_insert_with(|| PendingConfigReload {
+                config: Arc::clone(&base),
+                user_instructions: None,
+                refresh_mcp_servers: false,
+            });
+            pending.config = Arc::new(config);
+            if instructions_changed {
+                pending.user_instructions = Some(instructions);
+            }
+            pending.refresh_mcp_servers |= reload.mcp_servers_changed;
+        }
+
+        self.send_event_raw(Event {
+            id: self.next_internal_sub_id(),
+            msg: EventMsg::ConfigReloaded(ConfigReloadedEvent {
+                changed,
+                restart_required: reload.restart_required,
+                error: None,
+            }),
+        })
+        .await;
+    }
+
+    /// Apply config changes queued by [`Self::reload_config_from_disk`].
+    /// Returns whether the user instructions changed. Does nothing while a
+    /// turn is running, so the changes land at a turn boundary.
+    async fn apply_pending_config_reload(&self) -> bool {
+        if self.active_turn.lock().await.is_some() {
+            return false;
+        }
+        let Some(pending) = self.pending_config_reload.lock().await.take() else {
+            return false;
+        };
+        if pending.refresh_mcp_servers {
+            match (
+                serde_json::to_value(pending.config.mcp_servers.get()),
+                serde_json::to_value(pending.config.mcp_oauth_credentials_store_mode),
+            ) {
+                (Ok(mcp_servers), Ok(mcp_oauth_credentials_store_mode)) => {
+                    *self.pending_mcp_server_refresh_config.lock().await =
+                        Some(McpServerRefreshConfig {
+                            mcp_servers,
+                            mcp_oauth_credentials_store_mode,
+                        });
+                }
+                (Err(err), _) | (_, Err(err)) => {
+                    warn!("failed to serialize reloaded MCP servers: {err}");
+                }
+            }
+        }
+        let mut state = self.state.lock().await;
+        state.session_configuration.original_config_do_not_use = pending.config;
+        match pending.user_instructions {
+            Some(user_instructions) => {
+                state.session_configuration.user_instructions = user_instructions;
+                true
+            }
+            None => false,
+        }
+    }
+
     async fn mcp_startup_cancellation_token(&self) -> CancellationToken {
         self.services
             .mcp_startup_cancellation_token
@@ -2747,6 +3099,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
+            Op::NetworkApproval { id, decision } => {
+                handlers::network_approval(&sess, id, decision).await;
+            }
             Op::UserInputAnswer { id, response } => {
                 handlers::request_user_input_response(&sess, id, response).await;
             }
@@ -2860,6 +3215,7 @@ mod handlers {
     use codex_protocol::protocol::ListRemoteSkillsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
+    use codex_protocol::protocol::NetworkApprovalDecision;
     use codex_protocol::protocol::Op;
     use codex_protocol::protocol::RemoteSkillDownloadedEvent;
     use codex_protocol::protocol::RemoteSkillSummary;
@@ -2873,11 +3229,14 @@ mod handlers {
     use codex_protocol::request_user_input::RequestUserInputResponse;
 
     use crate::context_manager::is_user_turn_boundary;
+    use crate::instructions::UserInstructions;
     use codex_protocol::config_types::CollaborationMode;
     use codex_protocol::config_types::ModeKind;
     use codex_protocol::config_types::Settings;
     use codex_protocol::dynamic_tools::DynamicToolResponse;
     use codex_protocol::mcp::RequestId as ProtocolRequestId;
+    use codex_protocol::models::DeveloperInstructions;
+    use codex_protocol::models::ResponseItem;
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
@@ -2962,6 +3321,7 @@ mod handlers {
             ),
             _ => unreachable!(),
         };
+        let instructions_reloaded = sess.apply_pending_config_reload().await;
 
         let Ok(current_context) = sess.new_turn_with_sub_id(sub_id, updates).await else {
             // new_turn_with_sub_id already emits the error event.
@@ -2982,6 +3342,29 @@ mod handlers {
                 sess.record_conversation_items(&current_context, &update_items)
                     .await;
             }
+            if instructions_reloaded {
+                let item: ResponseItem = match current_context.user_instructions.as_deref() {
+                    Some(text) => UserInstructions {
+                        text: text.to_string(),
+                        directory: current_context.cwd.to_string_lossy().into_owned(),
+                    }
+                    .into(),
+                    None => DeveloperInstructions::new(
+                        "The AGENTS.md instructions given earlier in this conversation were removed and no longer apply.",
+                    )
+                    .into(),
+                };
+                sess.record_conversation_items(&current_context, &[item])
+                    .await;
+            }
+            if let Some(report) = sess
+                .services
+                .workspace_edits
+                .take_report(&current_context.cwd)
+            {
+                sess.record_conversation_items(&current_context, &[report])
+                    .await;
+            }
 
             sess.refresh_mcp_servers_if_requested(&current_context)
                 .await;
@@ -3059,6 +3442,28 @@ mod handlers {
         }
     }
 
+    /// Apply a user's answer to a network approval prompt.
+    pub async fn network_approval(
+        sess: &Arc<Session>,
+        id: String,
+        decision: NetworkApprovalDecision,
+    ) {
+        let Some(approvals) = sess.services.network_approvals.as_ref() else {
+            warn!("network approval {id} received without a network proxy");
+            return;
+        };
+        match approvals.resolve(&id, decision).await {
+            Ok(Some(_)) => {}
+            Ok(None) => warn!("no pending network approval found for id: {id}"),
+            Err(err) => {
+                let message = format!("Failed to apply network approval: {err}");
+                tracing::warn!("{message}");
+                let warning = EventMsg::Warning(WarningEvent { message });
+                sess.send_event_raw(Event { id, msg: warning }).await;
+            }
+        }
+    }
+
     /// Propagate a user's exec approval decision to the session.
     /// Also optionally applies an execpolicy amendment.
     pub async fn exec_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
@@ -3617,13 +4022,30 @@ async fn spawn_review_thread(
         // Review prompt is synthesized; no UI element ranges to preserve.
         text_elements: Vec::new(),
     }];
+    let task = if sess.features.enabled(Feature::MultiPassReview) {
+        let passes = crate::review_passes::plan_review_passes(
+            &resolved,
+            parent_turn_context.cwd.as_path(),
+            config.review_files_per_pass,
+            &config.review_lenses,
+        )
+        .await;
+        if passes.is_empty() {
+            ReviewTask::new()
+        } else {
+            ReviewTask::with_passes(passes)
+        }
+    } else {
+        ReviewTask::new()
+    };
     let tc = Arc::new(review_turn_context);
-    sess.spawn_task(tc.clone(), input, ReviewTask::new()).await;
+    sess.spawn_task(tc.clone(), input, task).await;
 
     // Announce entering review mode so UIs can switch modes.
     let review_request = ReviewRequest {
         target: resolved.target,
         user_facing_hint: Some(resolved.user_facing_hint),
+        paths: resolved.paths,
     };
     sess.send_event(&tc, EventMsg::EnteredReviewMode(review_request))
         .await;
@@ -3716,6 +4138,7 @@ pub(crate) async fn run_turn(
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
+    sess.state.lock().await.budget.record_turn();
     if total_usage_tokens >= auto_compact_limit
         && run_auto_compact(&sess, &turn_context).await.is_err()
     {
@@ -3815,12 +4238,30 @@ pub(crate) async fn run_turn(
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
 
+    if let Some(fallback_model) = sess.services.model_client.restore_primary_model_if_due() {
+        sess.send_event(
+            &turn_context,
+            EventMsg::ModelFallback(ModelFallbackEvent {
+                from_model: fallback_model,
+                to_model: turn_context.model_info.slug.clone(),
+                model_provider_id: turn_context.config.model_provider_id.clone(),
+                reason: None,
+                resume_primary_at: None,
+            }),
+        )
+        .await;
+    }
+
     let turn_metadata_header = turn_context.resolve_turn_metadata_header().await;
     // `ModelClientSession` is turn-scoped and caches WebSocket + sticky routing state, so we reuse
     // one instance across retries within this turn.
     let mut client_session = sess.services.model_client.new_session();
 
     loop {
+        if sess.enforce_session_budget(turn_context.as_ref()).await {
+            break;
+        }
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -4159,12 +4600,21 @@ async fn run_sampling_request(
                 if let Some(rate_limits) = rate_limits {
                     sess.update_rate_limits(&turn_context, rate_limits).await;
                 }
-                return Err(CodexErr::UsageLimitReached(e));
+                let err = CodexErr::UsageLimitReached(e);
+                if try_switch_fallback_model(&sess, &turn_context, client_session, &err).await {
+                    retries = 0;
+                    continue;
+                }
+                return Err(err);
             }
             Err(err) => err,
         };
 
         if !err.is_retryable() {
+            if try_switch_fallback_model(&sess, &turn_context, client_session, &err).await {
+                retries = 0;
+                continue;
+            }
             return Err(err);
         }
 
@@ -4206,12 +4656,44 @@ async fn run_sampling_request(
             .await;
 
             tokio::time::sleep(delay).await;
+        } else if try_switch_fallback_model(&sess, &turn_context, client_session, &err).await {
+            retries = 0;
         } else {
             return Err(err);
         }
     }
 }
 
+/// Switches the turn to the next configured fallback model after `err`, telling clients about
+/// the switch. Returns `false` when the error does not call for a fallback or none is left.
+async fn try_switch_fallback_model(
+    sess: &Session,
+    turn_context: &TurnContext,
+    client_session: &mut ModelClientSession,
+    err: &CodexErr,
+) -> bool {
+    let rate_limits = sess.state.lock().await.latest_rate_limits.clone();
+    let Some(switch) = client_session.try_switch_fallback_model(
+        &turn_context.model_info,
+        err,
+        rate_limits.as_ref(),
+    ) else {
+        return false;
+    };
+    sess.send_event(
+        turn_context,
+        EventMsg::ModelFallback(ModelFallbackEvent {
+            from_model: switch.from_model,
+            to_model: switch.to_model,
+            model_provider_id: switch.to_provider_id,
+            reason: Some(err.to_string()),
+            resume_primary_at: Some(switch.resume_primary_at.timestamp()),
+        }),
+    )
+    .await;
+    true
+}
+
 #[derive(Debug)]
 struct SamplingRequestResult {
     needs_follow_up: bool,
@@ -5256,24 +5738,28 @@ mod tests {
             TokenCountEvent {
                 info: Some(info1),
                 rate_limits: None,
+                total_cost_usd: None,
             },
         )));
         rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                total_cost_usd: None,
             },
         )));
         rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
             TokenCountEvent {
                 info: Some(info2.clone()),
                 rate_limits: None,
+                total_cost_usd: None,
             },
         )));
         rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                total_cost_usd: None,
             },
         )));
 
@@ -5911,6 +6397,10 @@ mod tests {
             file_watcher,
             agent_control,
             state_db: None,
+            network_approvals: None,
+            lsp_manager: Arc::new(LspManager::new(config.lsp_servers.clone())),
+            workspace_edits: Arc::new(WorkspaceEdits::disabled()),
+            web_fetcher: WebFetcher::default(),
             model_client: ModelClient::new(
                 Some(auth_manager.clone()),
                 conversation_id,
@@ -5943,6 +6433,7 @@ mod tests {
             state: Mutex::new(state),
             features: config.features.clone(),
             pending_mcp_server_refresh_config: Mutex::new(None),
+            pending_config_reload: Mutex::new(None),
             active_turn: Mutex::new(None),
             services,
             next_internal_sub_id: AtomicU64::new(0),
@@ -6043,6 +6534,10 @@ mod tests {
             file_watcher,
             agent_control,
             state_db: None,
+            network_approvals: None,
+            lsp_manager: Arc::new(LspManager::new(config.lsp_servers.clone())),
+            workspace_edits: Arc::new(WorkspaceEdits::disabled()),
+            web_fetcher: WebFetcher::default(),
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
@@ -6075,6 +6570,7 @@ mod tests {
             state: Mutex::new(state),
             features: config.features.clone(),
             pending_mcp_server_refresh_config: Mutex::new(None),
+            pending_config_reload: Mutex::new(None),
             active_turn: Mutex::new(None),
             services,
             next_internal_sub_id: AtomicU64::new(0),
@@ -475,6 +475,7 @@ mod tests {
                 id: "full".to_string(),
                 msg: EventMsg::TurnAborted(TurnAbortedEvent {
                     reason: TurnAbortReason::Interrupted,
+                    budget: None,
                 }),
             })
             .await
@@ -0,0 +1,196 @@
+//! Custom tools: local commands the model can call like function tools.
+//!
+//! Tools come from `[tools.custom.<name>]` in the merged config and from
+//! `tools/<name>.toml` files next to the user and project config layers, i.e.
+//! `$CODEX_HOME/tools/` and `<project>/.codex/tools/`. A file replaces a
+//! `[tools.custom]` entry with the same name, and files from higher-precedence
+//! layers replace those from lower ones. Invalid definitions are skipped with a
+//! startup warning.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use super::layer_files::LayerTomlFile;
+use super::layer_files::layer_toml_files;
+use super::types::CustomToolConfig;
+use super::types::CustomToolToml;
+use crate::config_loader::ConfigLayerStack;
+use crate::tools::spec::parse_tool_input_schema;
+
+const TOOLS_DIR: &str = "tools";
+
+/// Longest tool name the Responses API accepts.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+pub(crate) fn resolve_custom_tools(
+    configured: &BTreeMap<String, CustomToolToml>,
+    layer_stack: &ConfigLayerStack,
+    cwd: &Path,
+    startup_warnings: &mut Vec<String>,
+) -> Vec<CustomToolConfig> {
+    let mut tools = BTreeMap::new();
+    for (name, tool) in configured {
+        match validate_custom_tool(name, tool.clone(), cwd.to_path_buf()) {
+            Ok(tool) => {
+                tools.insert(name.clone(), tool);
+            }
+            Err(err) => {
+                startup_warnings.push(format!("Ignoring `[tools.custom.{name}]`: {err}"));
+            }
+        }
+    }
+
+    for file in layer_toml_files(layer_stack, TOOLS_DIR) {
+        let LayerTomlFile {
+            name,
+            path,
+            project_root,
+        } = file;
+        // Project tools run in their project, user tools in the session cwd.
+        let tool_cwd = project_root.unwrap_or_else(|| cwd.to_path_buf());
+        let tool = std::fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                toml::from_str::<CustomToolToml>(&contents).map_err(|err| err.to_string())
+            })
+            .and_then(|tool| validate_custom_tool(&name, tool, tool_cwd));
+        match tool {
+            Ok(tool) => {
+                tools.insert(name, tool);
+            }
+            Err(err) => {
+                startup_warnings.push(format!("Ignoring custom tool {}: {err}", path.display()));
+            }
+        }
+    }
+
+    tools.into_values().collect()
+}
+
+fn validate_custom_tool(
+    name: &str,
+    tool: CustomToolToml,
+    cwd: PathBuf,
+) -> Result<CustomToolConfig, String> {
+    if name.is_empty()
+        || name.len() > MAX_TOOL_NAME_LEN
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "tool names must be 1-{MAX_TOOL_NAME_LEN} characters of letters, digits, `_` or `-`, got `{name}`"
+        ));
+    }
+    if tool
+        .command
+        .first()
+        .is_none_or(|program| program.is_empty())
+    {
+        return Err("`command` must name a program".to_string());
+    }
+    let input_schema = tool
+        .input_schema
+        .unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+    parse_tool_input_schema(&input_schema)
+        .map_err(|err| format!("invalid `input_schema`: {err}"))?;
+
+    Ok(CustomToolConfig {
+        name: name.to_string(),
+        description: tool.description,
+        command: tool.command,
+        input_schema,
+        timeout_ms: tool.timeout_ms,
+        cwd,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn loads_tools_from_config_and_tools_dir() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            r#"
+[tools.custom.codegen]
+description = "Regenerate API bindings"
+command = ["make", "codegen"]
+
+[tools.custom."bad name"]
+description = "Not a valid tool name"
+command = ["true"]
+"#,
+        )?;
+        std::fs::create_dir(codex_home.path().join(TOOLS_DIR))?;
+        std::fs::write(
+            codex_home.path().join(TOOLS_DIR).join("query_db.toml"),
+            r#"
+description = "Run a read-only SQL query against the dev database"
+command = ["scripts/query-db"]
+timeout_ms = 5000
+
+[input_schema]
+type = "object"
+required = ["sql"]
+properties = { sql = { type = "string" } }
+"#,
+        )?;
+        std::fs::write(
+            codex_home.path().join(TOOLS_DIR).join("broken.toml"),
+            "command = []\n",
+        )?;
+
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(cwd.path().to_path_buf()))
+            .build()
+            .await?;
+
+        let names: Vec<&str> = config
+            .custom_tools
+            .iter()
+            .map(|tool| tool.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["codegen", "query_db"]);
+        let codegen = &config.custom_tools[0];
+        assert_eq!(
+            codegen.input_schema,
+            json!({ "type": "object", "properties": {} })
+        );
+        assert_eq!(config.custom_tools[1].timeout_ms, Some(5000));
+        assert_eq!(
+            config
+                .startup_warnings
+                .iter()
+                .filter(|warning| warning.contains("bad name") || warning.contains("broken.toml"))
+                .count(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_tools_without_a_program() {
+        let tool = CustomToolToml {
+            description: "nothing".to_string(),
+            command: vec![String::new()],
+            input_schema: None,
+            timeout_ms: None,
+        };
+
+        assert_eq!(
+            validate_custom_tool("noop", tool, PathBuf::from("/tmp")),
+            Err("`command` must name a program".to_string())
+        );
+    }
+}
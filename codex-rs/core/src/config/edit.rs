@@ -46,6 +46,8 @@ pub enum ConfigEdit {
     /// Set trust_level under `[projects."<path>"]`,
     /// migrating inline tables to explicit tables.
     SetProjectTrustLevel { path: PathBuf, level: TrustLevel },
+    /// Append a host pattern to `[network] allowed_domains` unless already present.
+    AddNetworkAllowedDomain(String),
     /// Set the value stored at the exact dotted path.
     SetPath {
         segments: Vec<String>,
@@ -338,6 +340,9 @@ impl ConfigDocument {
             ConfigEdit::SetSkillConfig { path, enabled } => {
                 Ok(self.set_skill_config(path.as_path(), *enabled))
             }
+            ConfigEdit::AddNetworkAllowedDomain(domain) => {
+                Ok(self.add_network_allowed_domain(domain))
+            }
             ConfigEdit::SetPath { segments, value } => Ok(self.insert(segments, value.clone())),
             ConfigEdit::ClearPath { segments } => Ok(self.clear_owned(segments)),
             ConfigEdit::SetProjectTrustLevel { path, level } => {
@@ -534,6 +539,33 @@ impl ConfigDocument {
         mutated
     }
 
+    fn add_network_allowed_domain(&mut self, domain: &str) -> bool {
+        let Some(network) = self.descend(&["network".to_string()], TraversalMode::Create) else {
+            return false;
+        };
+
+        if let Some(array) = network
+            .get_mut("allowed_domains")
+            .and_then(TomlItem::as_array_mut)
+        {
+            let already_present = array.iter().any(|entry| {
+                entry
+                    .as_str()
+                    .is_some_and(|entry| entry.eq_ignore_ascii_case(domain))
+            });
+            if already_present {
+                return false;
+            }
+            array.push(domain);
+            return true;
+        }
+
+        let mut array = toml_edit::Array::new();
+        array.push(domain);
+        network["allowed_domains"] = TomlItem::Value(array.into());
+        true
+    }
+
     fn scoped_segments(&self, scope: Scope, segments: &[&str]) -> Vec<String> {
         let resolved: Vec<String> = segments
             .iter()
@@ -819,6 +851,13 @@ impl ConfigEditsBuilder {
         self
     }
 
+    /// Append a host pattern to `[network] allowed_domains` unless it is already listed.
+    pub fn add_network_allowed_domain(mut self, domain: &str) -> Self {
+        self.edits
+            .push(ConfigEdit::AddNetworkAllowedDomain(domain.to_string()));
+        self
+    }
+
     pub fn with_edits<I>(mut self, edits: I) -> Self
     where
         I: IntoIterator<Item = ConfigEdit>,
@@ -916,6 +955,34 @@ enabled = false
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn add_network_allowed_domain_appends_once() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"[network]
+enabled = true
+allowed_domains = ["github.com"]
+"#,
+        )
+        .expect("seed config");
+
+        ConfigEditsBuilder::new(codex_home)
+            .add_network_allowed_domain("registry.npmjs.org")
+            .add_network_allowed_domain("Registry.npmjs.org")
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"[network]
+enabled = true
+allowed_domains = ["github.com", "registry.npmjs.org"]
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn set_skill_config_removes_entry_when_enabled() {
         let tmp = tempdir().expect("tmpdir");
@@ -0,0 +1,71 @@
+//! Definition files kept next to the user and project config layers, e.g.
+//! `$CODEX_HOME/tools/` and `<project>/.codex/tools/`.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_app_server_protocol::ConfigLayerSource;
+
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
+
+/// A `*.toml` file found in a layer's directory.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LayerTomlFile {
+    /// File stem, e.g. `lint` for `tools/lint.toml`.
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+    /// The project directory, for files from a project layer.
+    pub(crate) project_root: Option<PathBuf>,
+}
+
+/// `*.toml` files in `subdir` of `$CODEX_HOME` and of every project `.codex`
+/// folder, lowest-precedence layer first and sorted by name within a layer.
+pub(crate) fn layer_toml_files(layer_stack: &ConfigLayerStack, subdir: &str) -> Vec<LayerTomlFile> {
+    let mut files = Vec::new();
+    for layer in layer_stack.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false) {
+        let (dir, project_root) = match &layer.name {
+            ConfigLayerSource::User { file } => match file.as_path().parent() {
+                Some(codex_home) => (codex_home.join(subdir), None),
+                None => continue,
+            },
+            ConfigLayerSource::Project { dot_codex_folder } => {
+                let dot_codex_folder = dot_codex_folder.as_path();
+                let project_root = dot_codex_folder.parent().unwrap_or(dot_codex_folder);
+                (
+                    dot_codex_folder.join(subdir),
+                    Some(project_root.to_path_buf()),
+                )
+            }
+            _ => continue,
+        };
+        files.extend(
+            toml_files(&dir)
+                .into_iter()
+                .map(|(name, path)| LayerTomlFile {
+                    name,
+                    path,
+                    project_root: project_root.clone(),
+                }),
+        );
+    }
+    files
+}
+
+/// `*.toml` files in `dir` keyed by file stem, sorted by name.
+fn toml_files(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(String, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            Some((name, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
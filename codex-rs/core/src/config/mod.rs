@@ -2,18 +2,30 @@ use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::types::AppsConfigToml;
+use crate::config::types::CustomToolConfig;
+use crate::config::types::CustomToolToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::FallbackModelConfig;
+use crate::config::types::FallbackModelToml;
 use crate::config::types::History;
+use crate::config::types::LspServerConfig;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::ModelCatalogEntryToml;
+use crate::config::types::ModelPrice;
+use crate::config::types::NetworkProxyToml;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::ReviewConfigToml;
+use crate::config::types::ReviewLens;
+use crate::config::types::SandboxResourceLimits;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SessionBudgetConfig;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
@@ -44,6 +56,7 @@ use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::network_proxy::SandboxNetworkProxy;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
@@ -57,6 +70,7 @@ use codex_protocol::config_types::TrustLevel;
 use codex_protocol::config_types::Verbosity;
 use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::config_types::WindowsSandboxLevel;
+use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -70,6 +84,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 #[cfg(test)]
 use tempfile::tempdir;
 
@@ -78,8 +93,12 @@ use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
 
 mod constraint;
+mod custom_tools;
 pub mod edit;
+mod layer_files;
+mod model_catalog;
 pub mod profile;
+pub(crate) mod reload;
 pub mod schema;
 pub mod service;
 pub mod types;
@@ -97,9 +116,45 @@ pub use codex_git::GhostSnapshotConfig;
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 pub(crate) const DEFAULT_AGENT_MAX_THREADS: Option<usize> = Some(6);
+pub(crate) const DEFAULT_REVIEW_FILES_PER_PASS: usize = 12;
+pub(crate) const DEFAULT_MEMORY_MAX_TOKENS: usize = 1_000;
+pub(crate) const DEFAULT_FALLBACK_COOLDOWN_SECS: u64 = 300;
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
+/// Credential locations, relative to the user's home directory, that are
+/// hidden from sandboxed commands by default.
+const DEFAULT_DENIED_READ_HOME_SUBPATHS: &[&str] = &[
+    ".ssh",
+    ".aws",
+    ".azure",
+    ".gnupg",
+    ".config/gcloud",
+    ".docker/config.json",
+    ".git-credentials",
+    ".netrc",
+];
+
+/// Returns the credential locations that sandboxed commands cannot read
+/// unless the user approves running outside the sandbox.
+///
+/// Paths are returned whether or not they exist; the platform sandboxes skip
+/// entries that are missing at spawn time.
+pub fn default_sandbox_denied_read_paths(codex_home: &Path) -> Vec<AbsolutePathBuf> {
+    let mut paths = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        for subpath in DEFAULT_DENIED_READ_HOME_SUBPATHS {
+            if let Ok(path) = AbsolutePathBuf::from_absolute_path(home.join(subpath)) {
+                paths.push(path);
+            }
+        }
+    }
+    if let Ok(auth_json) = AbsolutePathBuf::from_absolute_path(codex_home.join("auth.json")) {
+        paths.push(auth_json);
+    }
+    paths
+}
+
 #[cfg(test)]
 pub(crate) fn test_config() -> Config {
     let codex_home = tempdir().expect("create temp dir");
@@ -127,6 +182,20 @@ pub struct Config {
     /// Model used specifically for review sessions.
     pub review_model: Option<String>,
 
+    /// Maximum number of changed files a single reviewer sub-agent covers when
+    /// multi-pass review is enabled.
+    pub review_files_per_pass: usize,
+
+    /// Extra review passes, each focused on one concern.
+    pub review_lenses: Vec<ReviewLens>,
+
+    /// Per-thread limits on turns, tokens, tool calls and wall-clock time.
+    pub session_budget: SessionBudgetConfig,
+
+    /// Token prices used to estimate cost, keyed by model slug. Built-in
+    /// prices merged with `[model_prices]` overrides.
+    pub model_prices: HashMap<String, ModelPrice>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -139,6 +208,19 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Models to switch to, in order, when the active model keeps failing
+    /// with rate-limit or server errors.
+    pub fallback_models: Vec<FallbackModelConfig>,
+
+    /// How long the session stays on a fallback model before returning to
+    /// the configured one.
+    pub fallback_cooldown: Duration,
+
+    /// Models described locally in `[model_catalog]` and `models/*.toml`,
+    /// sorted by priority. They take precedence over remote and built-in
+    /// metadata for the same slug.
+    pub model_catalog: Vec<ModelInfo>,
+
     /// Optionally specify the personality of the model
     pub personality: Option<Personality>,
 
@@ -147,6 +229,19 @@ pub struct Config {
 
     pub sandbox_policy: Constrained<SandboxPolicy>,
 
+    /// Paths that sandboxed commands must not be able to read, even though
+    /// the rest of the filesystem stays readable. Always includes the default
+    /// credential locations from [`default_sandbox_denied_read_paths`].
+    pub sandbox_denied_read_paths: Vec<AbsolutePathBuf>,
+
+    /// Resource limits applied to commands run for tool calls.
+    pub sandbox_resource_limits: SandboxResourceLimits,
+
+    /// Proxy listeners that sandboxed commands without network access are
+    /// routed through, from the `[network]` table. `None` keeps network
+    /// access fully blocked.
+    pub sandbox_network_proxy: Option<SandboxNetworkProxy>,
+
     /// enforce_residency means web traffic cannot be routed outside of a
     /// particular geography. HTTP clients should direct their requests
     /// using backend-specific headers or URLs to enforce this.
@@ -246,6 +341,12 @@ pub struct Config {
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: Constrained<HashMap<String, McpServerConfig>>,
 
+    /// Language servers available to the `code_intel` tool, keyed by name.
+    pub lsp_servers: BTreeMap<String, LspServerConfig>,
+
+    /// Local commands exposed to the model as function tools, sorted by name.
+    pub custom_tools: Vec<CustomToolConfig>,
+
     /// Preferred store for MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          Credentials stored in the keyring will only be readable by Codex unless the user explicitly grants access via OS-level keyring access.
@@ -266,6 +367,11 @@ pub struct Config {
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: usize,
 
+    /// Maximum number of tokens of saved memories added to the context at
+    /// session start when the `memory_tool` feature is enabled. `0` disables
+    /// injection.
+    pub memory_max_tokens: usize,
+
     /// Additional filenames to try when looking for project-level docs.
     pub project_doc_fallback_filenames: Vec<String>,
 
@@ -346,6 +452,14 @@ pub struct Config {
     /// The active profile name used to derive this `Config` (if any).
     pub active_profile: Option<String>,
 
+    /// Overrides this `Config` was loaded with, reapplied when a running
+    /// session re-reads its config files.
+    pub session_overrides: ConfigOverrides,
+
+    /// Where the config layers were read from, so a running session re-reads
+    /// the same ones.
+    pub loader_overrides: LoaderOverrides,
+
     /// The currently active project config, resolved by checking if cwd:
     /// is (1) part of a git repo, (2) a git worktree, or (3) just using the cwd
     pub active_project: ProjectConfig,
@@ -442,7 +556,7 @@ impl ConfigBuilder {
             &codex_home,
             Some(cwd),
             &cli_overrides,
-            loader_overrides,
+            loader_overrides.clone(),
             cloud_requirements,
         )
         .await?;
@@ -467,12 +581,14 @@ impl ConfigBuilder {
                 return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
             }
         };
-        Config::load_config_with_layer_stack(
+        let mut config = Config::load_config_with_layer_stack(
             config_toml,
             harness_overrides,
             codex_home,
             config_layer_stack,
-        )
+        )?;
+        config.loader_overrides = loader_overrides;
+        Ok(config)
     }
 }
 
@@ -844,9 +960,34 @@ pub struct ConfigToml {
     /// Review model override used by the `/review` feature.
     pub review_model: Option<String>,
 
+    /// Settings for the `/review` feature.
+    pub review: Option<ReviewConfigToml>,
+
+    /// Per-thread limits on turns, tokens, tool calls and wall-clock time.
+    pub budget: Option<SessionBudgetConfig>,
+
+    /// Token prices used to estimate cost, keyed by model slug. Entries
+    /// replace the built-in price for that model.
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Models to switch to, in order, when the active model keeps failing
+    /// with rate-limit or server errors after its retries.
+    #[serde(default)]
+    pub fallback_models: Vec<FallbackModelToml>,
+
+    /// Seconds to stay on a fallback model before returning to `model`.
+    /// Defaults to 300.
+    pub fallback_cooldown_secs: Option<u64>,
+
+    /// Models described locally, keyed by slug. `models/<name>.toml` files
+    /// next to `config.toml` add more.
+    #[serde(default)]
+    pub model_catalog: BTreeMap<String, ModelCatalogEntryToml>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -865,6 +1006,19 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Additional files or directories that sandboxed commands must not be
+    /// able to read. These are added to the built-in credential locations
+    /// (`~/.ssh`, `~/.aws`, `$CODEX_HOME/auth.json`, ...).
+    pub sandbox_denied_read_paths: Option<Vec<AbsolutePathBuf>>,
+
+    /// CPU time, memory, process count and file size limits applied to
+    /// commands run for tool calls.
+    pub sandbox_resource_limits: Option<SandboxResourceLimits>,
+
+    /// Settings shared with `codex-network-proxy`. When enabled, sandboxed
+    /// commands without network access are routed through the proxy instead.
+    pub network: Option<NetworkProxyToml>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
@@ -906,6 +1060,10 @@ pub struct ConfigToml {
     #[schemars(schema_with = "crate::config::schema::mcp_servers_schema")]
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Language servers available to the `code_intel` tool.
+    #[serde(default)]
+    pub lsp_servers: BTreeMap<String, LspServerConfig>,
+
     /// Preferred backend for storing MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          https://github.com/openai/codex/blob/main/codex-rs/rmcp-client/src/oauth.rs#L2
@@ -925,6 +1083,10 @@ pub struct ConfigToml {
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
+    /// Maximum number of tokens of saved memories added to the context at
+    /// session start. `0` disables injection.
+    pub memory_max_tokens: Option<usize>,
+
     /// Ordered list of fallback filenames to look for when AGENTS.md is missing.
     pub project_doc_fallback_filenames: Option<Vec<String>>,
 
@@ -1100,6 +1262,11 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Local commands exposed to the model as function tools, keyed by tool
+    /// name.
+    #[serde(default)]
+    pub custom: BTreeMap<String, CustomToolToml>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -1262,7 +1429,7 @@ impl ConfigToml {
 }
 
 /// Optional overrides for user configuration (e.g., from CLI flags).
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct ConfigOverrides {
     pub model: Option<String>,
     pub review_model: Option<String>,
@@ -1387,6 +1554,7 @@ impl Config {
         let requirements = config_layer_stack.requirements().clone();
         let user_instructions = Self::load_instructions(Some(&codex_home));
         let mut startup_warnings = Vec::new();
+        let session_overrides = overrides.clone();
 
         // Destructure ConfigOverrides fully to ensure all overrides are applied.
         let ConfigOverrides {
@@ -1532,6 +1700,40 @@ impl Config {
                 std::io::Error::new(std::io::ErrorKind::NotFound, message)
             })?
             .clone();
+        let fallback_models = cfg
+            .fallback_models
+            .iter()
+            .map(|entry| {
+                let (model, provider_id) = match entry {
+                    FallbackModelToml::Model(model) => (model, None),
+                    FallbackModelToml::WithProvider {
+                        model,
+                        model_provider,
+                    } => (model, model_provider.as_ref()),
+                };
+                let model_provider_id = provider_id.unwrap_or(&model_provider_id).clone();
+                let model_provider = model_providers
+                    .get(&model_provider_id)
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!(
+                                "Model provider `{model_provider_id}` for fallback model `{model}` not found"
+                            ),
+                        )
+                    })?
+                    .clone();
+                Ok(FallbackModelConfig {
+                    model: model.clone(),
+                    model_provider_id,
+                    model_provider,
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let fallback_cooldown = Duration::from_secs(
+            cfg.fallback_cooldown_secs
+                .unwrap_or(DEFAULT_FALLBACK_COOLDOWN_SECS),
+        );
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
@@ -1549,6 +1751,18 @@ impl Config {
             ));
         }
 
+        let review = cfg.review.clone().unwrap_or_default();
+        let review_files_per_pass = review
+            .files_per_pass
+            .unwrap_or(DEFAULT_REVIEW_FILES_PER_PASS);
+        if review_files_per_pass == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "review.files_per_pass must be at least 1",
+            ));
+        }
+        let review_lenses = review.lenses.unwrap_or_default();
+
         let ghost_snapshot = {
             let mut config = GhostSnapshotConfig::default();
             if let Some(ghost_snapshot) = cfg.ghost_snapshot.as_ref()
@@ -1678,17 +1892,55 @@ impl Config {
         let mcp_servers = constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_servers.as_ref())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
+        let mut sandbox_denied_read_paths = default_sandbox_denied_read_paths(&codex_home);
+        for path in cfg.sandbox_denied_read_paths.clone().unwrap_or_default() {
+            if !sandbox_denied_read_paths.contains(&path) {
+                sandbox_denied_read_paths.push(path);
+            }
+        }
+
+        let sandbox_network_proxy = match cfg.network.as_ref() {
+            Some(network) => SandboxNetworkProxy::from_toml(network)?,
+            None => None,
+        };
+
+        let custom_tools = custom_tools::resolve_custom_tools(
+            &cfg.tools
+                .as_ref()
+                .map(|tools| tools.custom.clone())
+                .unwrap_or_default(),
+            &config_layer_stack,
+            &resolved_cwd,
+            &mut startup_warnings,
+        );
+
+        let model_catalog = model_catalog::resolve_model_catalog(
+            &cfg.model_catalog,
+            &config_layer_stack,
+            &mut startup_warnings,
+        );
+
         let config = Self {
             model,
             review_model,
+            review_files_per_pass,
+            review_lenses,
+            session_budget: cfg.budget.unwrap_or_default(),
+            model_prices: crate::pricing::resolve_model_prices(cfg.model_prices),
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            fallback_models,
+            fallback_cooldown,
+            model_catalog,
             cwd: resolved_cwd,
             startup_warnings,
             approval_policy: constrained_approval_policy.value,
             sandbox_policy: constrained_sandbox_policy.value,
+            sandbox_denied_read_paths,
+            sandbox_resource_limits: cfg.sandbox_resource_limits.unwrap_or_default(),
+            sandbox_network_proxy,
             enforce_residency: enforce_residency.value,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
@@ -1703,12 +1955,15 @@ impl Config {
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
             mcp_servers,
+            lsp_servers: cfg.lsp_servers,
+            custom_tools,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
             mcp_oauth_callback_port: cfg.mcp_oauth_callback_port,
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
+            memory_max_tokens: cfg.memory_max_tokens.unwrap_or(DEFAULT_MEMORY_MAX_TOKENS),
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
                 .unwrap_or_default()
@@ -1761,6 +2016,8 @@ impl Config {
                 .suppress_unstable_features_warning
                 .unwrap_or(false),
             active_profile: active_profile_name,
+            session_overrides,
+            loader_overrides: LoaderOverrides::default(),
             active_project,
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
@@ -2013,6 +2270,51 @@ persistence = "none"
         );
     }
 
+    #[test]
+    fn sandbox_resource_limits_parse_partial_table() {
+        let cfg = r#"
+[sandbox_resource_limits]
+memory_mb = 2048
+max_processes = 256
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg)
+            .expect("sandbox_resource_limits table should deserialize");
+
+        assert_eq!(
+            parsed.sandbox_resource_limits,
+            Some(SandboxResourceLimits {
+                cpu_time_sec: None,
+                memory_mb: Some(2048),
+                max_processes: Some(256),
+                max_file_size_mb: None,
+            })
+        );
+    }
+
+    #[test]
+    fn network_table_accepts_proxy_only_keys() {
+        let cfg = r#"
+[network]
+enabled = true
+proxy_url = "http://127.0.0.1:3128"
+allowed_domains = ["example.com"]
+mode = "limited"
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("network table should deserialize");
+
+        assert_eq!(
+            parsed.network,
+            Some(NetworkProxyToml {
+                enabled: Some(true),
+                proxy_url: Some("http://127.0.0.1:3128".to_string()),
+                enable_socks5: None,
+                socks_url: None,
+            })
+        );
+    }
+
     #[test]
     fn tui_config_missing_notifications_field_defaults_to_enabled() {
         let cfg = r#"
@@ -2372,6 +2674,52 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn fallback_models_resolve_their_providers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+fallback_models = ["gpt-5.1-codex-mini", { model = "gpt-oss:20b", model_provider = "ollama" }]
+fallback_cooldown_secs = 60
+"#,
+        )
+        .expect("fallback_models should deserialize");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let resolved: Vec<(&str, &str)> = config
+            .fallback_models
+            .iter()
+            .map(|fallback| (fallback.model.as_str(), fallback.model_provider_id.as_str()))
+            .collect();
+        assert_eq!(
+            resolved,
+            vec![("gpt-5.1-codex-mini", "openai"), ("gpt-oss:20b", "ollama")]
+        );
+        assert_eq!(config.fallback_cooldown, Duration::from_secs(60));
+
+        let cfg = ConfigToml {
+            fallback_models: vec![FallbackModelToml::WithProvider {
+                model: "other-model".to_string(),
+                model_provider: Some("missing".to_string()),
+            }],
+            ..Default::default()
+        };
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("unknown fallback provider");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
     #[test]
     fn config_defaults_to_file_cli_auth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -3989,7 +4337,7 @@ model_verbosity = "high"
         };
         let o3_profile_config: Config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            o3_profile_overrides,
+            o3_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         assert_eq!(
@@ -4000,8 +4348,14 @@ model_verbosity = "high"
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                fallback_models: Vec::new(),
+                fallback_cooldown: Duration::from_secs(DEFAULT_FALLBACK_COOLDOWN_SECS),
+                model_catalog: Vec::new(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+                sandbox_denied_read_paths: default_sandbox_denied_read_paths(&fixture.codex_home()),
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_network_proxy: None,
                 enforce_residency: Constrained::allow_any(None),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
@@ -4011,10 +4365,17 @@ model_verbosity = "high"
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
+                lsp_servers: BTreeMap::new(),
+                custom_tools: Vec::new(),
+                review_files_per_pass: DEFAULT_REVIEW_FILES_PER_PASS,
+                review_lenses: Vec::new(),
+                session_budget: SessionBudgetConfig::default(),
+                model_prices: crate::pricing::resolve_model_prices(HashMap::new()),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 mcp_oauth_callback_port: None,
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+                memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
@@ -4046,6 +4407,8 @@ model_verbosity = "high"
                 features: Features::with_defaults(),
                 suppress_unstable_features_warning: false,
                 active_profile: Some("o3".to_string()),
+                session_overrides: o3_profile_overrides,
+                loader_overrides: LoaderOverrides::default(),
                 active_project: ProjectConfig { trust_level: None },
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
@@ -4078,7 +4441,7 @@ model_verbosity = "high"
         };
         let gpt3_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            gpt3_profile_overrides,
+            gpt3_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         let expected_gpt3_profile_config = Config {
@@ -4088,8 +4451,14 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-custom".to_string(),
             model_provider: fixture.openai_custom_provider.clone(),
+            fallback_models: Vec::new(),
+            fallback_cooldown: Duration::from_secs(DEFAULT_FALLBACK_COOLDOWN_SECS),
+            model_catalog: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_denied_read_paths: default_sandbox_denied_read_paths(&fixture.codex_home()),
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            sandbox_network_proxy: None,
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
@@ -4099,10 +4468,17 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
+            lsp_servers: BTreeMap::new(),
+            custom_tools: Vec::new(),
+            review_files_per_pass: DEFAULT_REVIEW_FILES_PER_PASS,
+            review_lenses: Vec::new(),
+            session_budget: SessionBudgetConfig::default(),
+            model_prices: crate::pricing::resolve_model_prices(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
@@ -4134,6 +4510,8 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt3".to_string()),
+            session_overrides: gpt3_profile_overrides,
+            loader_overrides: LoaderOverrides::default(),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
@@ -4162,11 +4540,18 @@ model_verbosity = "high"
 
         let default_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            default_profile_overrides,
+            default_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
 
-        assert_eq!(expected_gpt3_profile_config, default_profile_config);
+        assert_eq!(
+            Config {
+                session_overrides: default_profile_overrides,
+                loader_overrides: LoaderOverrides::default(),
+                ..expected_gpt3_profile_config
+            },
+            default_profile_config
+        );
         Ok(())
     }
 
@@ -4181,7 +4566,7 @@ model_verbosity = "high"
         };
         let zdr_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            zdr_profile_overrides,
+            zdr_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         let expected_zdr_profile_config = Config {
@@ -4191,8 +4576,14 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            fallback_models: Vec::new(),
+            fallback_cooldown: Duration::from_secs(DEFAULT_FALLBACK_COOLDOWN_SECS),
+            model_catalog: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_denied_read_paths: default_sandbox_denied_read_paths(&fixture.codex_home()),
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            sandbox_network_proxy: None,
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
@@ -4202,10 +4593,17 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
+            lsp_servers: BTreeMap::new(),
+            custom_tools: Vec::new(),
+            review_files_per_pass: DEFAULT_REVIEW_FILES_PER_PASS,
+            review_lenses: Vec::new(),
+            session_budget: SessionBudgetConfig::default(),
+            model_prices: crate::pricing::resolve_model_prices(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
@@ -4237,6 +4635,8 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("zdr".to_string()),
+            session_overrides: zdr_profile_overrides,
+            loader_overrides: LoaderOverrides::default(),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
@@ -4270,7 +4670,7 @@ model_verbosity = "high"
         };
         let gpt5_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            gpt5_profile_overrides,
+            gpt5_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         let expected_gpt5_profile_config = Config {
@@ -4280,8 +4680,14 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            fallback_models: Vec::new(),
+            fallback_cooldown: Duration::from_secs(DEFAULT_FALLBACK_COOLDOWN_SECS),
+            model_catalog: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_denied_read_paths: default_sandbox_denied_read_paths(&fixture.codex_home()),
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            sandbox_network_proxy: None,
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
@@ -4291,10 +4697,17 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
+            lsp_servers: BTreeMap::new(),
+            custom_tools: Vec::new(),
+            review_files_per_pass: DEFAULT_REVIEW_FILES_PER_PASS,
+            review_lenses: Vec::new(),
+            session_budget: SessionBudgetConfig::default(),
+            model_prices: crate::pricing::resolve_model_prices(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
@@ -4326,6 +4739,8 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt5".to_string()),
+            session_overrides: gpt5_profile_overrides,
+            loader_overrides: LoaderOverrides::default(),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
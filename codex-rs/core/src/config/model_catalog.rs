@@ -0,0 +1,289 @@
+//! Local model catalog: models described in config instead of `/models`.
+//!
+//! Entries come from `[model_catalog.<slug>]` in the merged config and from
+//! `models/<name>.toml` files next to the user and project config layers, i.e.
+//! `$CODEX_HOME/models/` and `<project>/.codex/models/`. A file replaces a
+//! `[model_catalog]` entry with the same slug, and files from higher-precedence
+//! layers replace those from lower ones. Invalid entries are skipped with a
+//! startup warning.
+
+use std::collections::BTreeMap;
+
+use codex_protocol::openai_models::InputModality;
+use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::openai_models::ModelVisibility;
+use codex_protocol::openai_models::ReasoningEffortPreset;
+
+use super::layer_files::LayerTomlFile;
+use super::layer_files::layer_toml_files;
+use super::types::ModelCatalogEntryToml;
+use crate::config_loader::ConfigLayerStack;
+use crate::models_manager::model_info::find_model_info_for_slug;
+
+const MODELS_DIR: &str = "models";
+
+pub(crate) fn resolve_model_catalog(
+    configured: &BTreeMap<String, ModelCatalogEntryToml>,
+    layer_stack: &ConfigLayerStack,
+    startup_warnings: &mut Vec<String>,
+) -> Vec<ModelInfo> {
+    let mut models = BTreeMap::new();
+    for (key, entry) in configured {
+        match validate_catalog_entry(key, entry.clone()) {
+            Ok(model) => {
+                models.insert(model.slug.clone(), model);
+            }
+            Err(err) => {
+                startup_warnings.push(format!("Ignoring `[model_catalog.{key}]`: {err}"));
+            }
+        }
+    }
+
+    for LayerTomlFile { name, path, .. } in layer_toml_files(layer_stack, MODELS_DIR) {
+        let model = std::fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                toml::from_str::<ModelCatalogEntryToml>(&contents).map_err(|err| err.to_string())
+            })
+            .and_then(|entry| validate_catalog_entry(&name, entry));
+        match model {
+            Ok(model) => {
+                models.insert(model.slug.clone(), model);
+            }
+            Err(err) => {
+                startup_warnings.push(format!("Ignoring model {}: {err}", path.display()));
+            }
+        }
+    }
+
+    let mut models: Vec<ModelInfo> = models.into_values().collect();
+    models.sort_by_key(|model| model.priority);
+    models
+}
+
+/// Builds the `ModelInfo` for an entry on top of the defaults for its slug,
+/// rejecting values the rest of core cannot work with.
+fn validate_catalog_entry(key: &str, entry: ModelCatalogEntryToml) -> Result<ModelInfo, String> {
+    let slug = entry.slug.unwrap_or_else(|| key.to_string());
+    if slug.is_empty() || slug.chars().any(char::is_whitespace) {
+        return Err(format!(
+            "model slugs must be non-empty and contain no whitespace, got `{slug}`"
+        ));
+    }
+    let Some(context_window) = entry.context_window else {
+        return Err("`context_window` is required".to_string());
+    };
+    if context_window <= 0 {
+        return Err(format!(
+            "`context_window` must be positive, got {context_window}"
+        ));
+    }
+    if let Some(limit) = entry.auto_compact_token_limit
+        && !(1..=context_window).contains(&limit)
+    {
+        return Err(format!(
+            "`auto_compact_token_limit` must be between 1 and `context_window` ({context_window}), got {limit}"
+        ));
+    }
+    if let Some(percent) = entry.effective_context_window_percent
+        && !(1..=100).contains(&percent)
+    {
+        return Err(format!(
+            "`effective_context_window_percent` must be between 1 and 100, got {percent}"
+        ));
+    }
+    if let Some(policy) = entry.truncation_policy
+        && policy.limit <= 0
+    {
+        return Err(format!(
+            "`truncation_policy.limit` must be positive, got {}",
+            policy.limit
+        ));
+    }
+    if let Some(modalities) = &entry.input_modalities
+        && !modalities.contains(&InputModality::Text)
+    {
+        return Err("`input_modalities` must include `text`".to_string());
+    }
+
+    let mut model = find_model_info_for_slug(&slug);
+    if let Some(levels) = entry.supported_reasoning_levels {
+        let defaults = std::mem::take(&mut model.supported_reasoning_levels);
+        model.supported_reasoning_levels = levels
+            .into_iter()
+            .map(|effort| ReasoningEffortPreset {
+                effort,
+                description: defaults
+                    .iter()
+                    .find(|preset| preset.effort == effort)
+                    .map(|preset| preset.description.clone())
+                    .unwrap_or_else(|| format!("{effort} reasoning effort")),
+            })
+            .collect();
+    }
+    if let Some(level) = entry.default_reasoning_level {
+        if !model
+            .supported_reasoning_levels
+            .iter()
+            .any(|preset| preset.effort == level)
+        {
+            return Err(format!(
+                "`default_reasoning_level` `{level}` is not in `supported_reasoning_levels`"
+            ));
+        }
+        model.default_reasoning_level = Some(level);
+    }
+
+    model.display_name = entry.display_name.unwrap_or_else(|| slug.clone());
+    model.slug = slug;
+    model.description = entry.description.or(model.description);
+    model.context_window = Some(context_window);
+    model.auto_compact_token_limit = entry.auto_compact_token_limit;
+    if let Some(percent) = entry.effective_context_window_percent {
+        model.effective_context_window_percent = percent;
+    }
+    if let Some(policy) = entry.truncation_policy {
+        model.truncation_policy = policy;
+    }
+    if entry.apply_patch_tool_type.is_some() {
+        model.apply_patch_tool_type = entry.apply_patch_tool_type;
+    }
+    if let Some(shell_type) = entry.shell_type {
+        model.shell_type = shell_type;
+    }
+    if let Some(modalities) = entry.input_modalities {
+        model.input_modalities = modalities;
+    }
+    if let Some(parallel) = entry.supports_parallel_tool_calls {
+        model.supports_parallel_tool_calls = parallel;
+    }
+    if let Some(summaries) = entry.supports_reasoning_summaries {
+        model.supports_reasoning_summaries = summaries;
+    }
+    if let Some(instructions) = entry.base_instructions {
+        model.base_instructions = instructions;
+        model.model_messages = None;
+    }
+    if let Some(priority) = entry.priority {
+        model.priority = priority;
+    }
+    model.visibility = if entry.hidden {
+        ModelVisibility::None
+    } else {
+        ModelVisibility::List
+    };
+    model.supported_in_api = true;
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use codex_protocol::openai_models::ApplyPatchToolType;
+    use codex_protocol::openai_models::ConfigShellToolType;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use codex_protocol::openai_models::TruncationPolicyConfig;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn loads_models_from_config_and_models_dir() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            r#"
+[model_catalog.qwen3-coder]
+display_name = "Qwen3 Coder"
+context_window = 131072
+truncation_policy = { mode = "tokens", limit = 8000 }
+apply_patch_tool_type = "function"
+shell_type = "shell_command"
+supports_parallel_tool_calls = true
+supported_reasoning_levels = []
+
+[model_catalog.broken]
+context_window = 0
+"#,
+        )?;
+        let models_dir = codex_home.path().join(MODELS_DIR);
+        std::fs::create_dir(&models_dir)?;
+        std::fs::write(
+            models_dir.join("gpt-oss-20b.toml"),
+            r#"
+slug = "gpt-oss:20b"
+context_window = 128000
+input_modalities = ["text"]
+supported_reasoning_levels = ["low", "medium", "high"]
+default_reasoning_level = "low"
+priority = 1
+"#,
+        )?;
+        std::fs::write(models_dir.join("typo.toml"), "context_windw = 1000\n")?;
+
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(cwd.path().to_path_buf()))
+            .build()
+            .await?;
+
+        let slugs: Vec<&str> = config
+            .model_catalog
+            .iter()
+            .map(|model| model.slug.as_str())
+            .collect();
+        assert_eq!(slugs, vec!["gpt-oss:20b", "qwen3-coder"]);
+
+        let gpt_oss = &config.model_catalog[0];
+        assert_eq!(gpt_oss.display_name, "gpt-oss:20b");
+        assert_eq!(gpt_oss.context_window, Some(128_000));
+        assert_eq!(gpt_oss.default_reasoning_level, Some(ReasoningEffort::Low));
+        assert_eq!(gpt_oss.visibility, ModelVisibility::List);
+
+        let qwen = &config.model_catalog[1];
+        assert_eq!(qwen.display_name, "Qwen3 Coder");
+        assert_eq!(qwen.truncation_policy, TruncationPolicyConfig::tokens(8000));
+        assert_eq!(
+            qwen.apply_patch_tool_type,
+            Some(ApplyPatchToolType::Function)
+        );
+        assert_eq!(qwen.shell_type, ConfigShellToolType::ShellCommand);
+        assert!(qwen.supports_parallel_tool_calls);
+        assert!(qwen.supported_reasoning_levels.is_empty());
+
+        assert_eq!(
+            config
+                .startup_warnings
+                .iter()
+                .filter(|warning| {
+                    warning.starts_with("Ignoring `[model_catalog.broken]`")
+                        || warning.contains("typo.toml")
+                })
+                .count(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_default_effort_outside_supported_levels() {
+        let entry = ModelCatalogEntryToml {
+            context_window: Some(32_000),
+            supported_reasoning_levels: Some(vec![ReasoningEffort::Low]),
+            default_reasoning_level: Some(ReasoningEffort::High),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_catalog_entry("local", entry),
+            Err(
+                "`default_reasoning_level` `high` is not in `supported_reasoning_levels`"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            validate_catalog_entry("local", ModelCatalogEntryToml::default()),
+            Err("`context_window` is required".to_string())
+        );
+    }
+}
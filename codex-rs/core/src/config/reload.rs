@@ -0,0 +1,444 @@
+//! Re-reading `config.toml` layers for sessions that are already running.
+//!
+//! Only settings a live session can pick up at a turn boundary are applied:
+//! MCP servers, feature flags and the project doc settings. Other changed
+//! keys, including the approval and sandbox policies, are reported as needing
+//! a new session, so editing a file can never loosen a running session's
+//! policy. Project layers cannot change the policies at all. Requirements
+//! (`requirements.toml`, MDM and cloud), `-c` overrides and the session's
+//! [`ConfigOverrides`] are kept from the original load.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use toml::Value as TomlValue;
+
+use super::Config;
+use super::ConfigOverrides;
+use super::ConfigToml;
+use crate::config_loader::CloudRequirementsLoader;
+use crate::config_loader::ConfigLayerEntry;
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
+use crate::config_loader::load_config_layers_state;
+use crate::project_doc::instruction_watch_paths;
+
+/// Top-level keys a running session applies from the next turn.
+const LIVE_KEYS: &[&str] = &[
+    "features",
+    "mcp_oauth_credentials_store",
+    "mcp_servers",
+    "project_doc_fallback_filenames",
+    "project_doc_max_bytes",
+];
+
+/// Top-level keys that decide what the agent may do without asking.
+const POLICY_KEYS: &[&str] = &["approval_policy", "sandbox_mode", "sandbox_workspace_write"];
+
+/// Settings that changed on disk, ready to be applied to a session.
+#[derive(Debug)]
+pub(crate) struct ConfigReload {
+    /// The session's config with the live settings replaced by the new ones.
+    pub(crate) config: Config,
+    /// Changed keys the session applies, e.g. `mcp_servers.docs`.
+    pub(crate) changed: Vec<String>,
+    /// Changed keys that only take effect in new sessions.
+    pub(crate) restart_required: Vec<String>,
+    pub(crate) mcp_servers_changed: bool,
+}
+
+/// Files whose changes [`reload_config`] picks up: every config layer file
+/// and every `AGENTS.md` location, whether or not it exists yet.
+pub(crate) fn config_watch_paths(config: &Config) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for layer in config
+        .config_layer_stack
+        .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+    {
+        match &layer.name {
+            ConfigLayerSource::System { file }
+            | ConfigLayerSource::User { file }
+            | ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
+                paths.push(file.to_path_buf());
+            }
+            ConfigLayerSource::Project { dot_codex_folder } => {
+                paths.push(dot_codex_folder.as_path().join("config.toml"));
+            }
+            ConfigLayerSource::Mdm { .. }
+            | ConfigLayerSource::SessionFlags
+            | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => {}
+        }
+    }
+    paths.extend(instruction_watch_paths(config));
+    paths
+}
+
+/// Re-read the config layers `current` was built from. Returns `Ok(None)`
+/// when nothing changed and an error when the new files do not parse or
+/// violate the requirements.
+pub(crate) async fn reload_config(current: &Config) -> std::io::Result<Option<ConfigReload>> {
+    let previous = &current.config_layer_stack;
+    let reloaded = load_config_layers_state(
+        &current.codex_home,
+        Some(AbsolutePathBuf::try_from(current.cwd.clone())?),
+        &[],
+        current.loader_overrides.clone(),
+        CloudRequirementsLoader::default(),
+    )
+    .await?;
+
+    let mut layers: Vec<ConfigLayerEntry> = reloaded
+        .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+        .into_iter()
+        .filter(|layer| layer.name != ConfigLayerSource::SessionFlags)
+        .map(|layer| keep_project_policy(layer, previous))
+        .collect();
+    layers.extend(
+        previous
+            .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+            .into_iter()
+            .filter(|layer| layer.name == ConfigLayerSource::SessionFlags)
+            .cloned(),
+    );
+    layers.sort_by_key(|layer| layer.name.precedence());
+    let stack = ConfigLayerStack::new(
+        layers,
+        previous.requirements().clone(),
+        previous.requirements_toml().clone(),
+    )?;
+
+    let keys = changed_keys(&previous.effective_config(), &stack.effective_config());
+    if keys.is_empty() {
+        return Ok(None);
+    }
+
+    let config_toml: ConfigToml = match stack.effective_config().try_into() {
+        Ok(config_toml) => config_toml,
+        Err(err) => {
+            if let Some(config_error) = crate::config_loader::first_layer_config_error(&stack).await
+            {
+                return Err(crate::config_loader::io_error_from_config_error(
+                    std::io::ErrorKind::InvalidData,
+                    config_error,
+                    Some(err),
+                ));
+            }
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+        }
+    };
+    let overrides = ConfigOverrides {
+        cwd: Some(current.cwd.clone()),
+        ..current.session_overrides.clone()
+    };
+    let fresh = Config::load_config_with_layer_stack(
+        config_toml,
+        overrides,
+        current.codex_home.clone(),
+        stack,
+    )?;
+
+    Ok(Some(plan_reload(current, fresh, keys)))
+}
+
+/// Re-read the global `AGENTS.md` in `CODEX_HOME` into `config`.
+pub(crate) fn refresh_global_instructions(config: &mut Config) {
+    config.user_instructions = Config::load_instructions(Some(&config.codex_home));
+}
+
+/// A project layer as re-read from disk, but with the policy keys it had
+/// when the session started.
+fn keep_project_policy(layer: &ConfigLayerEntry, previous: &ConfigLayerStack) -> ConfigLayerEntry {
+    if !matches!(layer.name, ConfigLayerSource::Project { .. }) {
+        return layer.clone();
+    }
+    let before = previous
+        .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+        .into_iter()
+        .find(|before| before.name == layer.name)
+        .and_then(|before| before.config.as_table());
+    let mut config = layer.config.clone();
+    if let Some(table) = config.as_table_mut() {
+        for key in POLICY_KEYS {
+            match before.and_then(|before| before.get(*key)) {
+                Some(value) => {
+                    table.insert((*key).to_string(), value.clone());
+                }
+                None => {
+                    table.remove(*key);
+                }
+            }
+        }
+    }
+    if config == layer.config {
+        return layer.clone();
+    }
+    match &layer.disabled_reason {
+        Some(reason) => ConfigLayerEntry::new_disabled(layer.name.clone(), config, reason),
+        None => ConfigLayerEntry::new(layer.name.clone(), config),
+    }
+}
+
+fn plan_reload(current: &Config, fresh: Config, keys: Vec<String>) -> ConfigReload {
+    let (changed, restart_required): (Vec<String>, Vec<String>) = keys
+        .into_iter()
+        .partition(|key| LIVE_KEYS.contains(&top_level_key(key)));
+    let touched = |names: &[&str]| {
+        changed
+            .iter()
+            .any(|key| names.contains(&top_level_key(key)))
+    };
+
+    let mcp_servers_changed = touched(&["mcp_servers", "mcp_oauth_credentials_store"]);
+
+    let mut config = current.clone();
+    config.config_layer_stack = fresh.config_layer_stack;
+    config.project_doc_max_bytes = fresh.project_doc_max_bytes;
+    config.project_doc_fallback_filenames = fresh.project_doc_fallback_filenames;
+    if touched(&["features"]) {
+        config.features = fresh.features;
+    }
+    if mcp_servers_changed {
+        config.mcp_servers = fresh.mcp_servers;
+        config.mcp_oauth_credentials_store_mode = fresh.mcp_oauth_credentials_store_mode;
+    }
+
+    ConfigReload {
+        config,
+        changed,
+        restart_required,
+        mcp_servers_changed,
+    }
+}
+
+fn top_level_key(key: &str) -> &str {
+    key.split_once('.').map_or(key, |(top, _)| top)
+}
+
+/// Keys whose effective value differs, sorted. Tables are compared one level
+/// deep so the result names e.g. `mcp_servers.docs` rather than all servers.
+fn changed_keys(before: &TomlValue, after: &TomlValue) -> Vec<String> {
+    let empty = toml::map::Map::new();
+    let before = before.as_table().unwrap_or(&empty);
+    let after = after.as_table().unwrap_or(&empty);
+    let mut changed = BTreeSet::new();
+    for key in before.keys().chain(after.keys()) {
+        match (before.get(key), after.get(key)) {
+            (Some(TomlValue::Table(old)), Some(TomlValue::Table(new))) => {
+                for child in old.keys().chain(new.keys()) {
+                    if old.get(child) != new.get(child) {
+                        changed.insert(format!("{key}.{child}"));
+                    }
+                }
+            }
+            (old, new) if old != new => {
+                changed.insert(key.clone());
+            }
+            _ => {}
+        }
+    }
+    changed.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use crate::config_loader::LoaderOverrides;
+    use crate::features::Feature;
+    use crate::protocol::AskForApproval;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn toml(text: &str) -> TomlValue {
+        toml::from_str(text).expect("valid toml")
+    }
+
+    #[test]
+    fn changed_keys_descend_into_tables() {
+        let before = toml(
+            r#"
+model = "gpt-5"
+approval_policy = "on-request"
+[mcp_servers.docs]
+command = "docs"
+[mcp_servers.search]
+command = "search"
+"#,
+        );
+        let after = toml(
+            r#"
+model = "gpt-5"
+approval_policy = "never"
+[mcp_servers.docs]
+command = "docs-v2"
+[mcp_servers.search]
+command = "search"
+[features]
+web_search_request = true
+"#,
+        );
+
+        assert_eq!(
+            changed_keys(&before, &after),
+            vec![
+                "approval_policy".to_string(),
+                "features".to_string(),
+                "mcp_servers.docs".to_string(),
+            ]
+        );
+        assert_eq!(changed_keys(&after, &after), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn reload_applies_live_keys_and_reports_the_rest() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            "approval_policy = \"on-request\"\nmodel = \"gpt-5\"\n",
+        )?;
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(cwd.path().to_path_buf()))
+            .build()
+            .await?;
+
+        assert!(reload_config(&config).await?.is_none());
+
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            "approval_policy = \"never\"\nmodel = \"gpt-5.1\"\nproject_doc_max_bytes = 1024\n",
+        )?;
+        let reload = reload_config(&config).await?.expect("config changed");
+
+        assert_eq!(reload.changed, vec!["project_doc_max_bytes".to_string()]);
+        assert_eq!(
+            reload.restart_required,
+            vec!["approval_policy".to_string(), "model".to_string()]
+        );
+        assert_eq!(reload.config.project_doc_max_bytes, 1024);
+        assert_eq!(
+            reload.config.approval_policy.value(),
+            AskForApproval::OnRequest
+        );
+        assert_eq!(reload.config.model, config.model);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reload_keeps_the_session_overrides() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(cwd.path().to_path_buf()))
+            .harness_overrides(ConfigOverrides {
+                include_apply_patch_tool: Some(true),
+                ..Default::default()
+            })
+            .build()
+            .await?;
+        assert!(config.features.enabled(Feature::ApplyPatchFreeform));
+
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            "[features]\nshell_snapshot = true\n",
+        )?;
+        let reload = reload_config(&config).await?.expect("config changed");
+
+        assert_eq!(reload.changed, vec!["features.shell_snapshot".to_string()]);
+        assert!(reload.config.features.enabled(Feature::ShellSnapshot));
+        assert!(reload.config.features.enabled(Feature::ApplyPatchFreeform));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reload_reads_the_layers_the_session_was_loaded_from() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let managed_config = codex_home.path().join("managed_config.toml");
+        std::fs::write(&managed_config, "project_doc_max_bytes = 2048\n")?;
+        let loader_overrides = LoaderOverrides {
+            managed_config_path: Some(managed_config),
+            ..Default::default()
+        };
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(cwd.path().to_path_buf()))
+            .loader_overrides(loader_overrides.clone())
+            .build()
+            .await?;
+        assert_eq!(config.project_doc_max_bytes, 2048);
+        assert_eq!(config.loader_overrides, loader_overrides);
+
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            "[features]\nshell_snapshot = true\n",
+        )?;
+        let reload = reload_config(&config).await?.expect("config changed");
+
+        assert_eq!(reload.changed, vec!["features.shell_snapshot".to_string()]);
+        assert_eq!(reload.config.project_doc_max_bytes, 2048);
+        assert_eq!(reload.config.loader_overrides, loader_overrides);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reload_ignores_policy_keys_from_project_layers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        std::fs::create_dir(project.path().join(".git"))?;
+        std::fs::create_dir(project.path().join(".codex"))?;
+        let project_key = TomlValue::String(project.path().display().to_string());
+        std::fs::write(
+            codex_home.path().join("config.toml"),
+            format!("[projects.{project_key}]\ntrust_level = \"trusted\"\n"),
+        )?;
+        let project_config = project.path().join(".codex").join("config.toml");
+        std::fs::write(&project_config, "approval_policy = \"on-request\"\n")?;
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(project.path().to_path_buf()))
+            .build()
+            .await?;
+        assert_eq!(config.approval_policy.value(), AskForApproval::OnRequest);
+
+        std::fs::write(
+            &project_config,
+            "approval_policy = \"never\"\nsandbox_mode = \"danger-full-access\"\n",
+        )?;
+        assert!(reload_config(&config).await?.is_none());
+
+        std::fs::write(
+            &project_config,
+            "approval_policy = \"never\"\nproject_doc_max_bytes = 1024\n",
+        )?;
+        let reload = reload_config(&config).await?.expect("config changed");
+
+        assert_eq!(reload.changed, vec!["project_doc_max_bytes".to_string()]);
+        assert_eq!(reload.restart_required, Vec::<String>::new());
+        assert_eq!(
+            reload.config.approval_policy.value(),
+            AskForApproval::OnRequest
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reload_rejects_invalid_toml() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(cwd.path().to_path_buf()))
+            .build()
+            .await?;
+
+        std::fs::write(codex_home.path().join("config.toml"), "approval_policy = [")?;
+
+        assert!(reload_config(&config).await.is_err());
+        Ok(())
+    }
+}
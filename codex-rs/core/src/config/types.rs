@@ -4,10 +4,16 @@
 // definitions that do not contain business logic.
 
 use crate::config_loader::RequirementSource;
+use crate::model_provider_info::ModelProviderInfo;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::openai_models::ApplyPatchToolType;
+use codex_protocol::openai_models::ConfigShellToolType;
+use codex_protocol::openai_models::InputModality;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::TruncationPolicyConfig;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -608,6 +614,262 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
     }
 }
 
+/// Resource limits applied to commands Codex runs on the model's behalf.
+///
+/// Unset fields leave the corresponding resource unlimited.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxResourceLimits {
+    /// CPU time, in seconds, a command may consume before it is terminated.
+    pub cpu_time_sec: Option<u64>,
+
+    /// Memory, in MiB, available to a command and its descendants.
+    pub memory_mb: Option<u64>,
+
+    /// Maximum number of processes a command may have alive at once.
+    pub max_processes: Option<u64>,
+
+    /// Largest file, in MiB, a command may write.
+    pub max_file_size_mb: Option<u64>,
+}
+
+impl SandboxResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.cpu_time_sec.is_none()
+            && self.memory_mb.is_none()
+            && self.max_processes.is_none()
+            && self.max_file_size_mb.is_none()
+    }
+}
+
+/// The subset of the `[network]` table Codex reads to route sandboxed
+/// commands through `codex-network-proxy`.
+///
+/// The remaining keys (domain lists, modes, ...) are consumed by the proxy
+/// itself, so unknown fields are accepted here.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+pub struct NetworkProxyToml {
+    /// When true, sandboxed commands without network access reach the
+    /// network only through the proxy listeners below.
+    pub enabled: Option<bool>,
+
+    /// Address of the proxy's HTTP listener. Defaults to
+    /// `http://127.0.0.1:3128`.
+    pub proxy_url: Option<String>,
+
+    /// Whether the proxy's SOCKS5 listener is running.
+    pub enable_socks5: Option<bool>,
+
+    /// Address of the proxy's SOCKS5 listener. Defaults to
+    /// `http://127.0.0.1:8081`.
+    pub socks_url: Option<String>,
+
+    /// Address of the proxy's admin API, polled for blocked requests so the
+    /// user can approve them. Defaults to `http://127.0.0.1:8080`.
+    pub admin_url: Option<String>,
+}
+
+/// A language server the `code_intel` tool can start, defined under
+/// `[lsp_servers.<name>]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LspServerConfig {
+    /// Executable that speaks LSP over stdio, e.g. `rust-analyzer`.
+    pub command: String,
+
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// File extensions (without the leading dot) routed to this server.
+    pub extensions: Vec<String>,
+
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// A local command exposed to the model as a function tool, defined under
+/// `[tools.custom.<name>]` or in `.codex/tools/<name>.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CustomToolToml {
+    /// What the tool does. Shown to the model.
+    pub description: String,
+
+    /// Program and arguments to run. The call's arguments are written to
+    /// stdin as JSON and stdout is returned to the model.
+    pub command: Vec<String>,
+
+    /// JSON schema for the arguments. Defaults to an object without
+    /// properties.
+    #[serde(default)]
+    pub input_schema: Option<serde_json::Value>,
+
+    /// Stop the command after this many milliseconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// A validated custom tool ready to be offered to the model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomToolConfig {
+    pub name: String,
+    pub description: String,
+    pub command: Vec<String>,
+    pub input_schema: serde_json::Value,
+    pub timeout_ms: Option<u64>,
+    /// Directory the command runs in: the project root for tools defined in
+    /// a project's `.codex` folder, the session cwd otherwise.
+    pub cwd: PathBuf,
+}
+
+/// An entry of `fallback_models`: a model slug, or a table naming the model
+/// and the `model_providers` entry to reach it through.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum FallbackModelToml {
+    Model(String),
+    WithProvider {
+        model: String,
+        /// Key into `model_providers`. Defaults to the session's provider.
+        #[serde(default)]
+        model_provider: Option<String>,
+    },
+}
+
+/// A fallback model with its provider resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FallbackModelConfig {
+    pub model: String,
+    pub model_provider_id: String,
+    pub model_provider: ModelProviderInfo,
+}
+
+/// A model described locally, under `[model_catalog.<slug>]` or in
+/// `models/<name>.toml`. Unset fields keep the defaults Codex uses for an
+/// unknown model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModelCatalogEntryToml {
+    /// Model slug sent to the provider. Defaults to the table key or file
+    /// name.
+    pub slug: Option<String>,
+
+    /// Name shown in `/model`. Defaults to the slug.
+    pub display_name: Option<String>,
+
+    pub description: Option<String>,
+
+    /// Size of the context window, in tokens.
+    pub context_window: Option<i64>,
+
+    /// Token usage that triggers auto-compaction. Defaults to 90% of
+    /// `context_window`.
+    pub auto_compact_token_limit: Option<i64>,
+
+    /// Percentage of the context window usable for input. Defaults to 95.
+    pub effective_context_window_percent: Option<i64>,
+
+    /// How tool output is truncated before it is sent to the model, e.g.
+    /// `{ mode = "tokens", limit = 10000 }`.
+    pub truncation_policy: Option<TruncationPolicyConfig>,
+
+    /// How the model edits files: `freeform` or `function`. Unset means the
+    /// model gets no `apply_patch` tool.
+    pub apply_patch_tool_type: Option<ApplyPatchToolType>,
+
+    /// Shell tool the model gets, e.g. `shell_command` or `unified_exec`.
+    pub shell_type: Option<ConfigShellToolType>,
+
+    /// Input the model accepts, e.g. `["text", "image"]`.
+    pub input_modalities: Option<Vec<InputModality>>,
+
+    pub supports_parallel_tool_calls: Option<bool>,
+
+    pub supports_reasoning_summaries: Option<bool>,
+
+    /// Reasoning efforts the model accepts.
+    pub supported_reasoning_levels: Option<Vec<ReasoningEffort>>,
+
+    /// Effort used when none is configured. Must be one of
+    /// `supported_reasoning_levels`.
+    pub default_reasoning_level: Option<ReasoningEffort>,
+
+    /// System instructions for the model, replacing Codex's default prompt.
+    pub base_instructions: Option<String>,
+
+    /// Position in `/model`; lower comes first.
+    pub priority: Option<i32>,
+
+    /// Hide the model from `/model` and `model/list`. It can still be
+    /// selected with `model = "<slug>"`.
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// Prices for one model in US dollars per million tokens, defined under
+/// `[model_prices.<model>]`. Used only to estimate the cost of a thread.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPrice {
+    /// Price of uncached input tokens.
+    pub input: f64,
+
+    /// Price of input tokens served from the prompt cache. Defaults to `input`.
+    pub cached_input: Option<f64>,
+
+    /// Price of output tokens.
+    pub output: f64,
+
+    /// Price of reasoning tokens, which are billed as output tokens. Defaults
+    /// to `output`.
+    pub reasoning_output: Option<f64>,
+}
+
+/// Per-thread consumption limits, defined under `[budget]`. Unset limits are
+/// not enforced.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SessionBudgetConfig {
+    /// Maximum number of turns in the thread.
+    #[schemars(range(min = 1))]
+    pub max_turns: Option<u64>,
+
+    /// Maximum number of tokens (input plus output) the thread may consume.
+    #[schemars(range(min = 1))]
+    pub max_total_tokens: Option<u64>,
+
+    /// Maximum number of tool calls the model may make in the thread.
+    #[schemars(range(min = 1))]
+    pub max_tool_calls: Option<u64>,
+
+    /// Maximum wall-clock time, in seconds, since the thread was started or
+    /// resumed.
+    #[schemars(range(min = 1))]
+    pub max_duration_sec: Option<u64>,
+}
+
+/// Settings for `/review`, defined under `[review]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ReviewConfigToml {
+    /// With the `multi_pass_review` feature, changes touching more files than
+    /// this are split into groups reviewed by parallel sub-agents.
+    #[schemars(range(min = 1))]
+    pub files_per_pass: Option<usize>,
+
+    /// Extra passes over the whole change, each focused on one concern.
+    pub lenses: Option<Vec<ReviewLens>>,
+}
+
+/// A specialized concern a dedicated review pass looks for.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewLens {
+    Security,
+    Performance,
+    ApiCompatibility,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum ShellEnvironmentPolicyInherit {
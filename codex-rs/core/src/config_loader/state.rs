@@ -14,7 +14,7 @@ use std::path::PathBuf;
 use toml::Value as TomlValue;
 
 /// LoaderOverrides overrides managed configuration inputs (primarily for tests).
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct LoaderOverrides {
     pub managed_config_path: Option<PathBuf>,
     //TODO(gt): Add a macos_ prefix to this field and remove the target_os check.
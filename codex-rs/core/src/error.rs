@@ -1,4 +1,5 @@
 use crate::exec::ExecToolCallOutput;
+use crate::sandboxing::resource_limits::ResourceLimitKind;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
 use crate::truncate::TruncationPolicy;
@@ -51,6 +52,13 @@ pub enum SandboxErr {
     #[error("command was killed by a signal")]
     Signal(i32),
 
+    /// Command hit one of the configured `sandbox_resource_limits`
+    #[error("command exceeded its {limit} limit")]
+    ResourceLimitExceeded {
+        limit: ResourceLimitKind,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Error from linux landlock
     #[error("Landlock was not able to fully enforce all sandbox rules")]
     LandlockRestrict,
@@ -658,6 +666,9 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
                 }
             }
         }
+        CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded { limit, .. }) => {
+            format!("error: command exceeded its {limit} limit")
+        }
         // Timeouts are not sandbox errors from a UX perspective; present them plainly
         CodexErr::Sandbox(SandboxErr::Timeout { output }) => {
             format!(
@@ -16,6 +16,7 @@ use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
 
+use crate::config::types::SandboxResourceLimits;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
@@ -29,9 +30,12 @@ use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
+use crate::sandboxing::resource_limits::ResourceLimitKind;
+use crate::sandboxing::resource_limits::ResourceLimitScope;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_pty::process_group::kill_child_process_group;
 
 pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
@@ -40,7 +44,7 @@ pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
 // for these.
 const SIGKILL_CODE: i32 = 9;
 const TIMEOUT_CODE: i32 = 64;
-const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
+pub(crate) const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
 const EXEC_TIMEOUT_EXIT_CODE: i32 = 124; // conventional timeout exit code
 
 // I/O buffer sizing
@@ -150,6 +154,7 @@ pub async fn process_exec_tool_call(
     params: ExecParams,
     sandbox_policy: &SandboxPolicy,
     sandbox_cwd: &Path,
+    denied_read_paths: &[AbsolutePathBuf],
     codex_linux_sandbox_exe: &Option<PathBuf>,
     use_linux_sandbox_bwrap: bool,
     stdout_stream: Option<StdoutStream>,
@@ -201,6 +206,9 @@ pub async fn process_exec_tool_call(
             policy: sandbox_policy,
             sandbox: sandbox_type,
             sandbox_policy_cwd: sandbox_cwd,
+            denied_read_paths,
+            resource_limits: SandboxResourceLimits::default(),
+            network_proxy: None,
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
@@ -226,6 +234,7 @@ pub(crate) async fn execute_exec_env(
         sandbox_permissions,
         justification,
         arg0,
+        resource_limits,
     } = env;
 
     let params = ExecParams {
@@ -240,7 +249,14 @@ pub(crate) async fn execute_exec_env(
     };
 
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(
+        params,
+        sandbox,
+        sandbox_policy,
+        resource_limits,
+        stdout_stream,
+    )
+    .await;
     let duration = start.elapsed();
     finalize_exec_result(raw_output_result, sandbox, duration)
 }
@@ -416,6 +432,7 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        exceeded_resource_limit: None,
     })
 }
 
@@ -428,19 +445,20 @@ fn finalize_exec_result(
         Ok(raw_output) => {
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
+            let exceeded_resource_limit = raw_output.exceeded_resource_limit;
 
             #[cfg(target_family = "unix")]
             {
                 if let Some(signal) = raw_output.exit_status.signal() {
                     if signal == TIMEOUT_CODE {
                         timed_out = true;
-                    } else {
+                    } else if exceeded_resource_limit.is_none() {
                         return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
                     }
                 }
             }
 
-            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
+            let mut exit_code = shell_exit_code(&raw_output.exit_status);
             if timed_out {
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
@@ -463,6 +481,13 @@ fn finalize_exec_result(
                 }));
             }
 
+            if let Some(limit) = exceeded_resource_limit {
+                return Err(CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+                    limit,
+                    output: Box::new(exec_output),
+                }));
+            }
+
             if is_likely_sandbox_denied(sandbox_type, &exec_output) {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
@@ -572,6 +597,7 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    pub exceeded_resource_limit: Option<ResourceLimitKind>,
 }
 
 impl StreamOutput<String> {
@@ -663,6 +689,7 @@ async fn exec(
     params: ExecParams,
     sandbox: SandboxType,
     sandbox_policy: &SandboxPolicy,
+    resource_limits: SandboxResourceLimits,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     #[cfg(target_os = "windows")]
@@ -691,6 +718,7 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
+    let resource_limits = ResourceLimitScope::new(resource_limits);
     let child = spawn_child_async(
         PathBuf::from(program),
         args.into(),
@@ -699,9 +727,25 @@ async fn exec(
         sandbox_policy,
         StdioPolicy::RedirectForShellTool,
         env,
+        &resource_limits,
     )
     .await?;
-    consume_truncated_output(child, expiration, stdout_stream).await
+    let mut raw_output = consume_truncated_output(child, expiration, stdout_stream).await?;
+    if !raw_output.timed_out {
+        raw_output.exceeded_resource_limit =
+            resource_limits.exceeded_limit(shell_exit_code(&raw_output.exit_status));
+    }
+    Ok(raw_output)
+}
+
+/// Exit code as a shell would report it: `128 + signal` for processes that
+/// were killed by a signal.
+fn shell_exit_code(exit_status: &ExitStatus) -> i32 {
+    #[cfg(unix)]
+    if let Some(signal) = exit_status.signal() {
+        return EXIT_CODE_SIGNAL_BASE + signal;
+    }
+    exit_status.code().unwrap_or(-1)
 }
 
 /// Consumes the output of a child process, truncating it so it is suitable for
@@ -808,6 +852,7 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        exceeded_resource_limit: None,
     })
 }
 
@@ -1067,7 +1112,14 @@ mod tests {
             arg0: None,
         };
 
-        let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::ReadOnly,
+            SandboxResourceLimits::default(),
+            None,
+        )
+        .await?;
         assert!(output.timed_out);
 
         let stdout = output.stdout.from_utf8_lossy().text;
@@ -1095,6 +1147,49 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn exec_reports_exceeded_file_size_limit() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let params = ExecParams {
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "head -c 2097152 /dev/zero > big".to_string(),
+            ],
+            cwd: dir.path().to_path_buf(),
+            expiration: 10_000.into(),
+            env: std::env::vars().collect(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+        };
+        let limits = SandboxResourceLimits {
+            max_file_size_mb: Some(1),
+            ..Default::default()
+        };
+
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::DangerFullAccess,
+            limits,
+            None,
+        )
+        .await?;
+
+        assert_eq!(
+            output.exceeded_resource_limit,
+            Some(ResourceLimitKind::FileSize)
+        );
+        assert_eq!(
+            std::fs::metadata(dir.path().join("big"))?.len(),
+            1024 * 1024
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         let command = long_running_command();
@@ -1120,6 +1215,7 @@ mod tests {
             params,
             &SandboxPolicy::DangerFullAccess,
             cwd.as_path(),
+            &[],
             &None,
             false,
             None,
@@ -103,8 +103,19 @@ pub enum Feature {
     RuntimeMetrics,
     /// Persist rollout metadata to a local SQLite database.
     Sqlite,
-    /// Enable the get_memory tool backed by SQLite thread memories.
+    /// Enable the memory tools (get_memory, remember, forget) and add saved
+    /// memories to the context at session start.
     MemoryTool,
+    /// Enable the code_intel tool backed by configured language servers.
+    CodeIntel,
+    /// Enable the fetch_url tool, which downloads a page and returns it as
+    /// Markdown.
+    FetchUrl,
+    /// Split large reviews across parallel reviewer sub-agents.
+    MultiPassReview,
+    /// Tell the model about files the user edited between turns and reject
+    /// patches that would overwrite those edits.
+    WorkspaceEdits,
     /// Append additional AGENTS.md guidance to user instructions.
     ChildAgentsMd,
     /// Enforce UTF8 output in Powershell.
@@ -455,6 +466,30 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::CodeIntel,
+        key: "code_intel",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::FetchUrl,
+        key: "fetch_url",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::MultiPassReview,
+        key: "multi_pass_review",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::WorkspaceEdits,
+        key: "workspace_edits",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::ChildAgentsMd,
         key: "child_agents_md",
@@ -1,5 +1,6 @@
-//! Watches skill roots for changes and broadcasts coarse-grained
-//! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Watches skill roots, config files and session workspaces for changes and
+//! broadcasts coarse-grained `FileWatcherEvent`s that higher-level components
+//! react to on the next turn.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -11,6 +12,7 @@ use std::sync::RwLock;
 use std::time::Duration;
 
 use notify::Event;
+use notify::EventKind;
 use notify::RecommendedWatcher;
 use notify::RecursiveMode;
 use notify::Watcher;
@@ -23,15 +25,33 @@ use tracing::info;
 use tracing::warn;
 
 use crate::config::Config;
+use crate::config::reload::config_watch_paths;
+use crate::features::Feature;
+use crate::git_info::get_git_repo_root;
 use crate::skills::loader::skill_roots_from_layer_stack_with_agents;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileWatcherEvent {
-    SkillsChanged { paths: Vec<PathBuf> },
+    SkillsChanged {
+        paths: Vec<PathBuf>,
+    },
+    /// Files changed inside a registered workspace. Sessions decide whether
+    /// the change came from the user.
+    WorkspaceChanged {
+        paths: Vec<PathBuf>,
+    },
+    /// A `config.toml` layer or an `AGENTS.md` file was created, changed or
+    /// removed.
+    ConfigChanged {
+        paths: Vec<PathBuf>,
+    },
 }
 
+#[derive(Default)]
 struct WatchState {
     skills_roots: HashSet<PathBuf>,
+    workspace_roots: HashSet<PathBuf>,
+    config_files: HashSet<PathBuf>,
 }
 
 struct FileWatcherInner {
@@ -41,6 +61,10 @@ struct FileWatcherInner {
 
 const WATCHER_THROTTLE_INTERVAL: Duration = Duration::from_secs(1);
 
+/// Upper bound on directories watched per workspace so a huge checkout cannot
+/// exhaust the OS watch limit.
+const MAX_WORKSPACE_WATCHED_DIRS: usize = 4096;
+
 /// Coalesces bursts of paths and emits at most once per interval.
 struct ThrottledPaths {
     pending: HashSet<PathBuf>,
@@ -86,7 +110,7 @@ impl ThrottledPaths {
 }
 
 pub(crate) struct FileWatcher {
-    inner: Option<Mutex<FileWatcherInner>>,
+    inner: Option<Arc<Mutex<FileWatcherInner>>>,
     state: Arc<RwLock<WatchState>>,
     tx: broadcast::Sender<FileWatcherEvent>,
 }
@@ -103,11 +127,9 @@ impl FileWatcher {
             watched_paths: HashMap::new(),
         };
         let (tx, _) = broadcast::channel(128);
-        let state = Arc::new(RwLock::new(WatchState {
-            skills_roots: HashSet::new(),
-        }));
+        let state = Arc::new(RwLock::new(WatchState::default()));
         let file_watcher = Self {
-            inner: Some(Mutex::new(inner)),
+            inner: Some(Arc::new(Mutex::new(inner))),
             state: Arc::clone(&state),
             tx: tx.clone(),
         };
@@ -119,9 +141,7 @@ impl FileWatcher {
         let (tx, _) = broadcast::channel(1);
         Self {
             inner: None,
-            state: Arc::new(RwLock::new(WatchState {
-                skills_roots: HashSet::new(),
-            })),
+            state: Arc::new(RwLock::new(WatchState::default())),
             tx,
         }
     }
@@ -136,6 +156,14 @@ impl FileWatcher {
         for root in roots {
             self.register_skills_root(root.path);
         }
+        for path in config_watch_paths(config) {
+            self.register_config_file(path);
+        }
+        if config.features.enabled(Feature::WorkspaceEdits)
+            && let Some(root) = get_git_repo_root(&config.cwd)
+        {
+            self.register_workspace_root(root);
+        }
     }
 
     // Bridge `notify`'s callback-based events into the Tokio runtime and
@@ -146,14 +174,24 @@ impl FileWatcher {
         state: Arc<RwLock<WatchState>>,
         tx: broadcast::Sender<FileWatcherEvent>,
     ) {
+        let inner = self.inner.clone();
         if let Ok(handle) = Handle::try_current() {
             handle.spawn(async move {
                 let now = Instant::now();
                 let mut skills = ThrottledPaths::new(now);
+                let mut workspace = ThrottledPaths::new(now);
+                let mut config = ThrottledPaths::new(now);
 
                 loop {
                     let now = Instant::now();
-                    let next_deadline = skills.next_deadline(now);
+                    let next_deadline = [
+                        skills.next_deadline(now),
+                        workspace.next_deadline(now),
+                        config.next_deadline(now),
+                    ]
+                        .into_iter()
+                        .flatten()
+                        .min();
                     let timer_deadline = next_deadline
                         .unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365));
                     let timer = sleep_until(timer_deadline);
@@ -169,13 +207,28 @@ impl FileWatcher {
                                         event_attrs = ?event.attrs,
                                         "file watcher received filesystem event"
                                     );
-                                    let skills_paths = classify_event(&event, &state);
+                                    let classified = classify_event(&event, &state);
+                                    // Directories created after registration need
+                                    // their own watches or edits inside them go unseen.
+                                    if let Some(inner) = &inner {
+                                        for directory in &classified.new_workspace_dirs {
+                                            watch_new_workspace_directory(inner, directory);
+                                        }
+                                    }
                                     let now = Instant::now();
-                                    skills.add(skills_paths);
+                                    skills.add(classified.skills);
+                                    workspace.add(classified.workspace);
+                                    config.add(classified.config);
 
                                     if let Some(paths) = skills.take_ready(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = workspace.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::WorkspaceChanged { paths });
+                                    }
+                                    if let Some(paths) = config.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                                    }
                                 }
                                 Some(Err(err)) => {
                                     warn!("file watcher error: {err}");
@@ -187,6 +240,12 @@ impl FileWatcher {
                                     if let Some(paths) = skills.take_pending(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = workspace.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::WorkspaceChanged { paths });
+                                    }
+                                    if let Some(paths) = config.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                                    }
                                     break;
                                 }
                             }
@@ -196,6 +255,12 @@ impl FileWatcher {
                             if let Some(paths) = skills.take_ready(now) {
                                 let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                             }
+                            if let Some(paths) = workspace.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::WorkspaceChanged { paths });
+                            }
+                            if let Some(paths) = config.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                            }
                         }
                     }
                 }
@@ -216,61 +281,173 @@ impl FileWatcher {
         self.watch_path(root, RecursiveMode::Recursive);
     }
 
-    fn watch_path(&self, path: PathBuf, mode: RecursiveMode) {
-        let Some(inner) = &self.inner else {
+    /// Watch a config or instructions file through its parent directory, so
+    /// files that editors replace on save, or that do not exist yet, are
+    /// still seen.
+    fn register_config_file(&self, path: PathBuf) {
+        let Some(parent) = path.parent().map(Path::to_path_buf) else {
             return;
         };
-        if !path.exists() {
-            return;
-        }
-        let watch_path = path;
-        let mut guard = match inner.lock() {
-            Ok(guard) => guard,
-            Err(err) => err.into_inner(),
-        };
-        if let Some(existing) = guard.watched_paths.get(&watch_path) {
-            if *existing == RecursiveMode::Recursive || *existing == mode {
+        {
+            let mut state = match self.state.write() {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+            if !state.config_files.insert(path) {
                 return;
             }
-            if let Err(err) = guard.watcher.unwatch(&watch_path) {
-                warn!("failed to unwatch {}: {err}", watch_path.display());
+        }
+        self.watch_path(parent, RecursiveMode::NonRecursive);
+    }
+
+    /// Watch every directory of the workspace that `.gitignore` does not
+    /// exclude. Directories are watched one by one rather than recursively so
+    /// ignored trees such as build outputs never consume watches.
+    fn register_workspace_root(&self, root: PathBuf) {
+        {
+            let mut state = match self.state.write() {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+            if !state.workspace_roots.insert(root.clone()) {
+                return;
             }
         }
-        if let Err(err) = guard.watcher.watch(&watch_path, mode) {
-            warn!("failed to watch {}: {err}", watch_path.display());
-            return;
+        if let Some(inner) = &self.inner {
+            watch_workspace_directories(inner, &root);
+        }
+    }
+
+    fn watch_path(&self, path: PathBuf, mode: RecursiveMode) {
+        if let Some(inner) = &self.inner {
+            watch_path(inner, path, mode);
         }
-        guard.watched_paths.insert(watch_path, mode);
     }
 }
 
-fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
-    let mut skills_paths = Vec::new();
-    let skills_roots = match state.read() {
-        Ok(state) => state.skills_roots.clone(),
-        Err(err) => {
-            let state = err.into_inner();
-            state.skills_roots.clone()
+/// Watch `root` and every directory below it that `.gitignore` does not
+/// exclude. Directories are watched one by one rather than recursively so
+/// ignored trees such as build outputs never consume watches.
+fn watch_workspace_directories(inner: &Mutex<FileWatcherInner>, root: &Path) {
+    let directories = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir())
+        })
+        .take(MAX_WORKSPACE_WATCHED_DIRS + 1)
+        .map(ignore::DirEntry::into_path)
+        .collect::<Vec<_>>();
+    if directories.len() > MAX_WORKSPACE_WATCHED_DIRS {
+        warn!(
+            "workspace {} has more than {MAX_WORKSPACE_WATCHED_DIRS} directories; only some are watched for user edits",
+            root.display()
+        );
+    }
+    for directory in directories.into_iter().take(MAX_WORKSPACE_WATCHED_DIRS) {
+        watch_path(inner, directory, RecursiveMode::NonRecursive);
+    }
+}
+
+/// Watch a directory created after its workspace was registered, unless
+/// `.gitignore` excludes it. A walk never filters its own root, so the check
+/// lists the parent instead.
+fn watch_new_workspace_directory(inner: &Mutex<FileWatcherInner>, directory: &Path) {
+    let Some(parent) = directory.parent() else {
+        return;
+    };
+    let ignored = !ignore::WalkBuilder::new(parent)
+        .hidden(false)
+        .max_depth(Some(1))
+        .build()
+        .filter_map(Result::ok)
+        .any(|entry| entry.path() == directory);
+    if !ignored {
+        watch_workspace_directories(inner, directory);
+    }
+}
+
+fn watch_path(inner: &Mutex<FileWatcherInner>, path: PathBuf, mode: RecursiveMode) {
+    if !path.exists() {
+        return;
+    }
+    let watch_path = path;
+    let mut guard = match inner.lock() {
+        Ok(guard) => guard,
+        Err(err) => err.into_inner(),
+    };
+    if let Some(existing) = guard.watched_paths.get(&watch_path) {
+        if *existing == RecursiveMode::Recursive || *existing == mode {
+            return;
+        }
+        if let Err(err) = guard.watcher.unwatch(&watch_path) {
+            warn!("failed to unwatch {}: {err}", watch_path.display());
         }
+    }
+    if let Err(err) = guard.watcher.watch(&watch_path, mode) {
+        warn!("failed to watch {}: {err}", watch_path.display());
+        return;
+    }
+    guard.watched_paths.insert(watch_path, mode);
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ClassifiedPaths {
+    skills: Vec<PathBuf>,
+    workspace: Vec<PathBuf>,
+    config: Vec<PathBuf>,
+    /// Directories created inside a workspace root that need watches of
+    /// their own.
+    new_workspace_dirs: Vec<PathBuf>,
+}
+
+fn classify_event(event: &Event, state: &RwLock<WatchState>) -> ClassifiedPaths {
+    let mut classified = ClassifiedPaths::default();
+    // Reading a workspace or config file is not an edit.
+    let is_change = !matches!(event.kind, EventKind::Access(_));
+    let is_create = matches!(event.kind, EventKind::Create(_));
+    let state = match state.read() {
+        Ok(state) => state,
+        Err(err) => err.into_inner(),
     };
 
     for path in &event.paths {
-        if is_skills_path(path, &skills_roots) {
-            skills_paths.push(path.clone());
+        if is_under_any_root(path, &state.skills_roots) {
+            classified.skills.push(path.clone());
+        }
+        if is_change
+            && is_under_any_root(path, &state.workspace_roots)
+            && !is_git_internal_path(path)
+        {
+            classified.workspace.push(path.clone());
+            if is_create && path.is_dir() {
+                classified.new_workspace_dirs.push(path.clone());
+            }
+        }
+        if is_change && state.config_files.contains(path) {
+            classified.config.push(path.clone());
         }
     }
 
-    skills_paths
+    classified
 }
 
-fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
+fn is_git_internal_path(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == ".git")
+}
+
+fn is_under_any_root(path: &Path, roots: &HashSet<PathBuf>) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use notify::EventKind;
     use pretty_assertions::assert_eq;
     use tokio::time::timeout;
 
@@ -326,6 +503,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root.clone()]),
+            ..Default::default()
         });
         let event = notify_event(vec![
             root.join("demo/SKILL.md"),
@@ -333,7 +511,7 @@ mod tests {
         ]);
 
         let classified = classify_event(&event, &state);
-        assert_eq!(classified, vec![root.join("demo/SKILL.md")]);
+        assert_eq!(classified.skills, vec![root.join("demo/SKILL.md")]);
     }
 
     #[test]
@@ -342,6 +520,7 @@ mod tests {
         let root_b = path("/tmp/workspace/.codex/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root_a.clone(), root_b.clone()]),
+            ..Default::default()
         });
         let event = notify_event(vec![
             root_a.join("alpha/SKILL.md"),
@@ -351,11 +530,94 @@ mod tests {
 
         let classified = classify_event(&event, &state);
         assert_eq!(
-            classified,
+            classified.skills,
             vec![root_a.join("alpha/SKILL.md"), root_b.join("beta/SKILL.md")]
         );
     }
 
+    #[test]
+    fn classify_event_reports_workspace_changes_outside_git_dir() {
+        let root = path("/tmp/workspace");
+        let state = RwLock::new(WatchState {
+            workspace_roots: HashSet::from([root.clone()]),
+            ..Default::default()
+        });
+        let event = notify_event(vec![
+            root.join("src/lib.rs"),
+            root.join(".git/index"),
+            path("/tmp/elsewhere/file.rs"),
+        ]);
+
+        assert_eq!(
+            classify_event(&event, &state),
+            ClassifiedPaths {
+                skills: Vec::new(),
+                workspace: vec![root.join("src/lib.rs")],
+                config: Vec::new(),
+                new_workspace_dirs: Vec::new(),
+            }
+        );
+
+        let read = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(root.join("src/lib.rs"));
+        assert_eq!(classify_event(&read, &state), ClassifiedPaths::default());
+    }
+
+    #[test]
+    fn new_workspace_directories_are_watched_unless_ignored() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let root = workspace.path().to_path_buf();
+        std::fs::create_dir(root.join(".git")).expect("create .git");
+        std::fs::write(root.join(".gitignore"), "target/\n").expect("write .gitignore");
+        let state = RwLock::new(WatchState {
+            workspace_roots: HashSet::from([root.clone()]),
+            ..Default::default()
+        });
+        let watcher = FileWatcher::new(root.clone()).expect("file watcher");
+        let inner = watcher.inner.as_ref().expect("watcher inner");
+
+        std::fs::create_dir_all(root.join("src/nested")).expect("create src");
+        std::fs::create_dir_all(root.join("target/debug")).expect("create target");
+        let created = Event::new(EventKind::Create(notify::event::CreateKind::Folder))
+            .add_path(root.join("src"))
+            .add_path(root.join("target"));
+        let classified = classify_event(&created, &state);
+        assert_eq!(
+            classified.new_workspace_dirs,
+            vec![root.join("src"), root.join("target")]
+        );
+        for directory in &classified.new_workspace_dirs {
+            watch_new_workspace_directory(inner, directory);
+        }
+
+        let guard = inner.lock().expect("watcher lock");
+        let mut watched: Vec<PathBuf> = guard.watched_paths.keys().cloned().collect();
+        watched.sort();
+        assert_eq!(watched, vec![root.join("src"), root.join("src/nested")]);
+    }
+
+    #[test]
+    fn classify_event_reports_registered_config_files_only() {
+        let codex_home = path("/tmp/codex-home");
+        let state = RwLock::new(WatchState {
+            config_files: HashSet::from([
+                codex_home.join("config.toml"),
+                codex_home.join("AGENTS.md"),
+            ]),
+            ..Default::default()
+        });
+        let event = notify_event(vec![
+            codex_home.join("config.toml"),
+            codex_home.join("config.toml.tmp"),
+            codex_home.join("history.jsonl"),
+        ]);
+
+        assert_eq!(
+            classify_event(&event, &state).config,
+            vec![codex_home.join("config.toml")]
+        );
+    }
+
     #[test]
     fn register_skills_root_dedupes_state_entries() {
         let watcher = FileWatcher::noop();
@@ -1,6 +1,9 @@
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::network_proxy::SandboxNetworkProxy;
+use crate::sandboxing::resource_limits::ResourceLimitScope;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -21,6 +24,7 @@ pub async fn spawn_command_under_linux_sandbox<P>(
     command_cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    denied_read_paths: &[AbsolutePathBuf],
     use_bwrap_sandbox: bool,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
@@ -32,6 +36,8 @@ where
         command,
         sandbox_policy,
         sandbox_policy_cwd,
+        denied_read_paths,
+        None,
         use_bwrap_sandbox,
     );
     let arg0 = Some("codex-linux-sandbox");
@@ -43,6 +49,7 @@ where
         sandbox_policy,
         stdio_policy,
         env,
+        &ResourceLimitScope::default(),
     )
     .await
 }
@@ -50,11 +57,17 @@ where
 /// Converts the sandbox policy into the CLI invocation for `codex-linux-sandbox`.
 ///
 /// The helper performs the actual sandboxing (bubblewrap + seccomp) after
-/// parsing these arguments. See `docs/linux_sandbox.md` for the Linux semantics.
+/// parsing these arguments. Each entry in `denied_read_paths` becomes a
+/// `--denied-read-path` flag so the helper can hide it from the command, and
+/// each `network_proxy` listener becomes a `--network-proxy` flag so the
+/// helper can run the command in a network namespace that only reaches those
+/// listeners. See `docs/linux_sandbox.md` for the Linux semantics.
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    denied_read_paths: &[AbsolutePathBuf],
+    network_proxy: Option<&SandboxNetworkProxy>,
     use_bwrap_sandbox: bool,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
@@ -73,6 +86,17 @@ pub(crate) fn create_linux_sandbox_command_args(
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
     ];
+    for path in denied_read_paths {
+        linux_cmd.push("--denied-read-path".to_string());
+        linux_cmd.push(path.to_string_lossy().to_string());
+    }
+    for endpoint in network_proxy
+        .map(SandboxNetworkProxy::endpoints)
+        .unwrap_or_default()
+    {
+        linux_cmd.push("--network-proxy".to_string());
+        linux_cmd.push(endpoint.to_string());
+    }
     if use_bwrap_sandbox {
         linux_cmd.push("--use-bwrap-sandbox".to_string());
     }
@@ -98,16 +122,66 @@ mod tests {
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::ReadOnly;
 
-        let with_bwrap = create_linux_sandbox_command_args(command.clone(), &policy, cwd, true);
+        let with_bwrap =
+            create_linux_sandbox_command_args(command.clone(), &policy, cwd, &[], None, true);
         assert_eq!(
             with_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             true
         );
 
-        let without_bwrap = create_linux_sandbox_command_args(command, &policy, cwd, false);
+        let without_bwrap =
+            create_linux_sandbox_command_args(command, &policy, cwd, &[], None, false);
         assert_eq!(
             without_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             false
         );
     }
+
+    #[test]
+    fn denied_read_paths_are_forwarded_before_separator() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::ReadOnly;
+        let denied = vec![
+            AbsolutePathBuf::from_absolute_path("/home/user/.ssh").expect("absolute path"),
+            AbsolutePathBuf::from_absolute_path("/home/user/.netrc").expect("absolute path"),
+        ];
+
+        let args = create_linux_sandbox_command_args(command, &policy, cwd, &denied, None, false);
+        let separator = args
+            .iter()
+            .position(|arg| arg == "--")
+            .expect("command separator");
+        let denied_flags: Vec<&str> = args[..separator]
+            .windows(2)
+            .filter(|pair| pair[0] == "--denied-read-path")
+            .map(|pair| pair[1].as_str())
+            .collect();
+        assert_eq!(denied_flags, vec!["/home/user/.ssh", "/home/user/.netrc"]);
+    }
+
+    #[test]
+    fn network_proxy_endpoints_are_forwarded_before_separator() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::ReadOnly;
+        let proxy = SandboxNetworkProxy {
+            http_addr: "127.0.0.1:3128".parse().expect("addr"),
+            socks_addr: Some("127.0.0.1:8081".parse().expect("addr")),
+            admin_addr: "127.0.0.1:8080".parse().expect("addr"),
+        };
+
+        let args =
+            create_linux_sandbox_command_args(command, &policy, cwd, &[], Some(&proxy), true);
+        let separator = args
+            .iter()
+            .position(|arg| arg == "--")
+            .expect("command separator");
+        let proxy_flags: Vec<&str> = args[..separator]
+            .windows(2)
+            .filter(|pair| pair[0] == "--network-proxy")
+            .map(|pair| pair[1].as_str())
+            .collect();
+        assert_eq!(proxy_flags, vec!["127.0.0.1:3128", "127.0.0.1:8081"]);
+    }
 }
@@ -10,6 +10,7 @@ pub mod api_bridge;
 mod apply_patch;
 pub mod auth;
 pub mod bash;
+mod budget;
 mod client;
 mod client_common;
 pub mod codex;
@@ -41,18 +42,23 @@ pub mod instructions;
 pub mod landlock;
 pub mod mcp;
 mod mcp_connection_manager;
+pub mod memories;
 pub mod models_manager;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_CAPABILITY;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_METHOD;
 pub use mcp_connection_manager::SandboxState;
+mod lsp;
 mod mcp_tool_call;
 mod mentions;
 mod message_history;
+mod model_fallback;
 mod model_provider_info;
+mod network_approval;
 pub mod parse_command;
 pub mod path_utils;
 pub mod personality_migration;
 pub mod powershell;
+mod pricing;
 mod proposed_plan_parser;
 pub mod sandboxing;
 mod session_prefix;
@@ -74,7 +80,9 @@ pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod event_mapping;
 pub mod review_format;
+mod review_passes;
 pub mod review_prompts;
+pub mod review_report;
 mod thread_manager;
 pub mod web_search;
 pub use codex_protocol::protocol::InitialHistory;
@@ -129,6 +137,8 @@ mod state;
 mod tasks;
 mod user_shell_command;
 pub mod util;
+mod web_fetch;
+mod workspace_edits;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use client::X_CODEX_TURN_METADATA_HEADER;
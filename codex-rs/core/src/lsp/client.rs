@@ -0,0 +1,734 @@
+//! Minimal LSP client speaking JSON-RPC over a server's stdio.
+//!
+//! Only the requests the `code_intel` tool needs are implemented. Documents
+//! are synced with full text read from disk, so the server always sees what
+//! the last patch left on disk.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::anyhow;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::debug;
+use tracing::warn;
+use url::Url;
+
+use crate::config::types::LspServerConfig;
+
+/// Upper bound on any single request, including `initialize`, which can be
+/// slow while a server indexes a large workspace.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+type PendingRequests = Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>;
+type SharedWriter = Arc<Mutex<Box<dyn AsyncWrite + Send + Unpin>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) struct Position {
+    /// Zero-based line.
+    pub(crate) line: u32,
+    /// Zero-based UTF-16 column.
+    pub(crate) character: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) struct Range {
+    pub(crate) start: Position,
+    pub(crate) end: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Location {
+    pub(crate) uri: String,
+    pub(crate) range: Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Diagnostic {
+    pub(crate) range: Range,
+    /// 1 = error, 2 = warning, 3 = information, 4 = hint.
+    #[serde(default)]
+    pub(crate) severity: Option<u8>,
+    #[serde(default)]
+    pub(crate) source: Option<String>,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SymbolMatch {
+    pub(crate) name: String,
+    pub(crate) kind: u32,
+    pub(crate) container_name: Option<String>,
+    pub(crate) location: Location,
+}
+
+#[derive(Default)]
+struct DiagnosticsState {
+    /// Latest published diagnostics keyed by document URI.
+    by_uri: HashMap<String, Vec<Diagnostic>>,
+    /// Incremented per URI on every publish so callers can wait for a fresh
+    /// result after changing a document.
+    generation: HashMap<String, u64>,
+}
+
+pub(crate) struct LspClient {
+    writer: SharedWriter,
+    pending: PendingRequests,
+    diagnostics: Arc<Mutex<DiagnosticsState>>,
+    diagnostics_updated: Arc<Notify>,
+    /// Open documents keyed by URI, mapped to the last version sent.
+    open_documents: Mutex<HashMap<String, i32>>,
+    next_id: AtomicI64,
+    reader_task: JoinHandle<()>,
+    _child: Option<Child>,
+}
+
+impl LspClient {
+    /// Spawn the configured server with `root` as its working directory and
+    /// workspace root.
+    pub(crate) async fn spawn(config: &LspServerConfig, root: &Path) -> anyhow::Result<Self> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .envs(&config.env)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("failed to start language server `{}`", config.command))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("language server stdin unavailable"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("language server stdout unavailable"))?;
+        Self::connect(stdout, stdin, Some(child), root).await
+    }
+
+    /// Run the `initialize` handshake over an already-connected transport.
+    pub(crate) async fn connect<R, W>(
+        reader: R,
+        writer: W,
+        child: Option<Child>,
+        root: &Path,
+    ) -> anyhow::Result<Self>
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let writer: SharedWriter = Arc::new(Mutex::new(Box::new(writer)));
+        let pending: PendingRequests = Arc::default();
+        let diagnostics: Arc<Mutex<DiagnosticsState>> = Arc::default();
+        let diagnostics_updated = Arc::new(Notify::new());
+        let reader_task = tokio::spawn(read_loop(
+            BufReader::new(reader),
+            Arc::clone(&writer),
+            Arc::clone(&pending),
+            Arc::clone(&diagnostics),
+            Arc::clone(&diagnostics_updated),
+        ));
+        let client = Self {
+            writer,
+            pending,
+            diagnostics,
+            diagnostics_updated,
+            open_documents: Mutex::new(HashMap::new()),
+            next_id: AtomicI64::new(1),
+            reader_task,
+            _child: child,
+        };
+
+        let root_uri = file_uri(root)?;
+        client
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "rootUri": root_uri,
+                    "workspaceFolders": [{ "uri": root_uri, "name": "workspace" }],
+                    "capabilities": {
+                        "textDocument": {
+                            "synchronization": { "didSave": false },
+                            "publishDiagnostics": { "relatedInformation": false },
+                            "definition": { "linkSupport": true },
+                            "references": {},
+                        },
+                        "workspace": { "symbol": {}, "workspaceFolders": true },
+                    },
+                }),
+            )
+            .await?;
+        client.notify("initialized", json!({})).await?;
+        Ok(client)
+    }
+
+    /// False once the server closed its output, e.g. because it exited.
+    pub(crate) fn is_alive(&self) -> bool {
+        !self.reader_task.is_finished()
+    }
+
+    /// Sync `path` from disk and wait up to `timeout` for the server to
+    /// publish diagnostics for it. Returns the latest known diagnostics when
+    /// the server stays silent.
+    pub(crate) async fn diagnostics(
+        &self,
+        path: &Path,
+        timeout: Duration,
+    ) -> anyhow::Result<Vec<Diagnostic>> {
+        let uri = file_uri(path)?;
+        let seen = self.diagnostics_generation(&uri).await;
+        self.sync_document(path).await?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let updated = self.diagnostics_updated.notified();
+            {
+                let state = self.diagnostics.lock().await;
+                if state.generation.get(&uri).copied().unwrap_or(0) > seen {
+                    return Ok(state.by_uri.get(&uri).cloned().unwrap_or_default());
+                }
+            }
+            if tokio::time::timeout_at(deadline, updated).await.is_err() {
+                let state = self.diagnostics.lock().await;
+                return Ok(state.by_uri.get(&uri).cloned().unwrap_or_default());
+            }
+        }
+    }
+
+    pub(crate) async fn definition(
+        &self,
+        path: &Path,
+        position: Position,
+    ) -> anyhow::Result<Vec<Location>> {
+        let uri = self.sync_document(path).await?;
+        let result = self
+            .request(
+                "textDocument/definition",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "position": { "line": position.line, "character": position.character },
+                }),
+            )
+            .await?;
+        Ok(parse_locations(result))
+    }
+
+    pub(crate) async fn references(
+        &self,
+        path: &Path,
+        position: Position,
+    ) -> anyhow::Result<Vec<Location>> {
+        let uri = self.sync_document(path).await?;
+        let result = self
+            .request(
+                "textDocument/references",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "position": { "line": position.line, "character": position.character },
+                    "context": { "includeDeclaration": true },
+                }),
+            )
+            .await?;
+        Ok(parse_locations(result))
+    }
+
+    pub(crate) async fn workspace_symbols(&self, query: &str) -> anyhow::Result<Vec<SymbolMatch>> {
+        let result = self
+            .request("workspace/symbol", json!({ "query": query }))
+            .await?;
+        let Value::Array(items) = result else {
+            return Ok(Vec::new());
+        };
+        Ok(items.into_iter().filter_map(parse_symbol).collect())
+    }
+
+    /// Send the current on-disk contents of `path` to the server, opening the
+    /// document on first use. Returns the document URI.
+    async fn sync_document(&self, path: &Path) -> anyhow::Result<String> {
+        let uri = file_uri(path)?;
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let mut open_documents = self.open_documents.lock().await;
+        match open_documents.get_mut(&uri) {
+            Some(version) => {
+                *version += 1;
+                self.notify(
+                    "textDocument/didChange",
+                    json!({
+                        "textDocument": { "uri": uri, "version": *version },
+                        "contentChanges": [{ "text": text }],
+                    }),
+                )
+                .await?;
+            }
+            None => {
+                self.notify(
+                    "textDocument/didOpen",
+                    json!({
+                        "textDocument": {
+                            "uri": uri,
+                            "languageId": language_id(path),
+                            "version": 1,
+                            "text": text,
+                        },
+                    }),
+                )
+                .await?;
+                open_documents.insert(uri.clone(), 1);
+            }
+        }
+        Ok(uri)
+    }
+
+    async fn diagnostics_generation(&self, uri: &str) -> u64 {
+        self.diagnostics
+            .lock()
+            .await
+            .generation
+            .get(uri)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    async fn request(&self, method: &str, params: Value) -> anyhow::Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(err) = write_message(&self.writer, &message).await {
+            self.pending.lock().await.remove(&id);
+            return Err(err);
+        }
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(Ok(result))) => Ok(result),
+            Ok(Ok(Err(message))) => Err(anyhow!("{method} failed: {message}")),
+            Ok(Err(_)) => Err(anyhow!("language server exited before answering {method}")),
+            Err(_) => {
+                self.pending.lock().await.remove(&id);
+                Err(anyhow!("{method} timed out"))
+            }
+        }
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> anyhow::Result<()> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&self.writer, &message).await
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        self.reader_task.abort();
+    }
+}
+
+async fn read_loop<R>(
+    mut reader: BufReader<R>,
+    writer: SharedWriter,
+    pending: PendingRequests,
+    diagnostics: Arc<Mutex<DiagnosticsState>>,
+    diagnostics_updated: Arc<Notify>,
+) where
+    R: AsyncRead + Unpin,
+{
+    loop {
+        let message = match read_message(&mut reader).await {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(err) => {
+                warn!("language server sent an unreadable message: {err:#}");
+                break;
+            }
+        };
+
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id").cloned();
+        match (method, id) {
+            // Response to one of our requests.
+            (None, Some(id)) => {
+                let Some(id) = id.as_i64() else {
+                    continue;
+                };
+                let Some(tx) = pending.lock().await.remove(&id) else {
+                    continue;
+                };
+                let result = match message.get("error") {
+                    Some(error) => Err(error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown error")
+                        .to_string()),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                let _ = tx.send(result);
+            }
+            // Server-to-client request. Answer with defaults so servers that
+            // wait on e.g. `workspace/configuration` keep going.
+            (Some(method), Some(id)) => {
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message
+                            .pointer("/params/items")
+                            .and_then(Value::as_array)
+                            .map_or(0, Vec::len);
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                if let Err(err) = write_message(&writer, &reply).await {
+                    debug!("failed to answer language server request {method}: {err:#}");
+                }
+            }
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let Some(params) = message.get("params") else {
+                    continue;
+                };
+                let Some(uri) = params.get("uri").and_then(Value::as_str) else {
+                    continue;
+                };
+                let published: Vec<Diagnostic> = params
+                    .get("diagnostics")
+                    .cloned()
+                    .and_then(|value| serde_json::from_value(value).ok())
+                    .unwrap_or_default();
+                let mut state = diagnostics.lock().await;
+                state.by_uri.insert(uri.to_string(), published);
+                *state.generation.entry(uri.to_string()).or_default() += 1;
+                drop(state);
+                diagnostics_updated.notify_waiters();
+            }
+            _ => {}
+        }
+    }
+
+    // Fail outstanding requests instead of leaving them to time out.
+    pending.lock().await.clear();
+}
+
+async fn read_message<R>(reader: &mut BufReader<R>) -> anyhow::Result<Option<Value>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+    let content_length = content_length.ok_or_else(|| anyhow!("missing Content-Length"))?;
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+async fn write_message(writer: &SharedWriter, message: &Value) -> anyhow::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    let mut writer = writer.lock().await;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(&body).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+pub(crate) fn file_uri(path: &Path) -> anyhow::Result<String> {
+    Url::from_file_path(path)
+        .map(String::from)
+        .map_err(|()| anyhow!("{} is not an absolute path", path.display()))
+}
+
+/// `textDocument/definition` may answer with a `Location`, a list of them,
+/// or a list of `LocationLink`s.
+fn parse_locations(value: Value) -> Vec<Location> {
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Null => Vec::new(),
+        other => vec![other],
+    };
+    items
+        .into_iter()
+        .filter_map(|item| {
+            if let Some(target_uri) = item.get("targetUri") {
+                let range = item
+                    .get("targetSelectionRange")
+                    .or_else(|| item.get("targetRange"))?;
+                return Some(Location {
+                    uri: target_uri.as_str()?.to_string(),
+                    range: serde_json::from_value(range.clone()).ok()?,
+                });
+            }
+            serde_json::from_value(item).ok()
+        })
+        .collect()
+}
+
+/// Accepts both `SymbolInformation` and `WorkspaceSymbol`. The latter may
+/// omit the range, in which case the symbol points at the top of the file.
+fn parse_symbol(item: Value) -> Option<SymbolMatch> {
+    let name = item.get("name")?.as_str()?.to_string();
+    let kind = u32::try_from(item.get("kind")?.as_u64()?).ok()?;
+    let container_name = item
+        .get("containerName")
+        .and_then(Value::as_str)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string);
+    let location = item.get("location")?;
+    let uri = location.get("uri")?.as_str()?.to_string();
+    let range = match location.get("range") {
+        Some(range) => serde_json::from_value(range.clone()).ok()?,
+        None => Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: Position {
+                line: 0,
+                character: 0,
+            },
+        },
+    };
+    Some(SymbolMatch {
+        name,
+        kind,
+        container_name,
+        location: Location { uri, range },
+    })
+}
+
+fn language_id(path: &Path) -> String {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    match extension {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "go" => "go",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" => "cpp",
+        other => other,
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+    use tokio::io::DuplexStream;
+
+    /// In-process language server: answers the requests the client sends
+    /// and publishes one diagnostic per `TODO` in every synced document.
+    async fn fake_server(stream: DuplexStream) {
+        let (reader, writer) = tokio::io::split(stream);
+        let mut reader = BufReader::new(reader);
+        let writer: SharedWriter = Arc::new(Mutex::new(Box::new(writer)));
+        while let Ok(Some(message)) = read_message(&mut reader).await {
+            let method = message
+                .get("method")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            let uri = params
+                .pointer("/textDocument/uri")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let result = match method {
+                "initialize" => json!({ "capabilities": {} }),
+                "textDocument/definition" => json!([{
+                    "targetUri": uri,
+                    "targetRange": { "start": { "line": 0, "character": 0 }, "end": { "line": 2, "character": 1 } },
+                    "targetSelectionRange": { "start": { "line": 0, "character": 3 }, "end": { "line": 0, "character": 7 } },
+                }]),
+                "textDocument/references" => json!([
+                    { "uri": uri, "range": { "start": { "line": 0, "character": 3 }, "end": { "line": 0, "character": 7 } } },
+                    { "uri": uri, "range": { "start": { "line": 4, "character": 4 }, "end": { "line": 4, "character": 8 } } },
+                ]),
+                "workspace/symbol" => json!([{
+                    "name": params["query"],
+                    "kind": 12,
+                    "containerName": "",
+                    "location": { "uri": "file:///repo/lib.rs" },
+                }]),
+                "textDocument/didOpen" | "textDocument/didChange" => {
+                    let text = params
+                        .pointer("/textDocument/text")
+                        .or_else(|| params.pointer("/contentChanges/0/text"))
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    let diagnostics: Vec<Value> = text
+                        .lines()
+                        .enumerate()
+                        .filter(|(_, line)| line.contains("TODO"))
+                        .map(|(line, _)| {
+                            json!({
+                                "range": { "start": { "line": line, "character": 0 }, "end": { "line": line, "character": 4 } },
+                                "severity": 2,
+                                "source": "fake",
+                                "message": "unfinished work",
+                            })
+                        })
+                        .collect();
+                    let publish = json!({
+                        "jsonrpc": "2.0",
+                        "method": "textDocument/publishDiagnostics",
+                        "params": { "uri": uri, "diagnostics": diagnostics },
+                    });
+                    write_message(&writer, &publish).await.expect("publish");
+                    continue;
+                }
+                _ => continue,
+            };
+            let Some(id) = message.get("id") else {
+                continue;
+            };
+            let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+            write_message(&writer, &reply).await.expect("reply");
+        }
+    }
+
+    async fn connect_fake(root: &Path) -> LspClient {
+        let (client_side, server_side) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(fake_server(server_side));
+        let (reader, writer) = tokio::io::split(client_side);
+        LspClient::connect(reader, writer, None, root)
+            .await
+            .expect("initialize")
+    }
+
+    #[tokio::test]
+    async fn diagnostics_follow_the_file_on_disk() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn main() {}\n// TODO\n").expect("write");
+        let client = connect_fake(dir.path()).await;
+
+        let diagnostics = client
+            .diagnostics(&path, Duration::from_secs(5))
+            .await
+            .expect("diagnostics");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 1);
+        assert_eq!(diagnostics[0].message, "unfinished work");
+
+        std::fs::write(&path, "fn main() {}\n").expect("write");
+        let diagnostics = client
+            .diagnostics(&path, Duration::from_secs(5))
+            .await
+            .expect("diagnostics");
+        assert_eq!(diagnostics, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn navigation_requests_parse_locations_and_symbols() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn main() {}\n").expect("write");
+        let client = connect_fake(dir.path()).await;
+        let uri = file_uri(&path).expect("uri");
+        let position = Position {
+            line: 0,
+            character: 4,
+        };
+
+        let definitions = client
+            .definition(&path, position)
+            .await
+            .expect("definition");
+        assert_eq!(
+            definitions,
+            vec![Location {
+                uri: uri.clone(),
+                range: Range {
+                    start: Position {
+                        line: 0,
+                        character: 3
+                    },
+                    end: Position {
+                        line: 0,
+                        character: 7
+                    },
+                },
+            }]
+        );
+
+        let references = client
+            .references(&path, position)
+            .await
+            .expect("references");
+        assert_eq!(
+            references
+                .iter()
+                .map(|l| l.range.start.line)
+                .collect::<Vec<_>>(),
+            vec![0, 4]
+        );
+
+        let symbols = client.workspace_symbols("main").await.expect("symbols");
+        assert_eq!(
+            symbols,
+            vec![SymbolMatch {
+                name: "main".to_string(),
+                kind: 12,
+                container_name: None,
+                location: Location {
+                    uri: "file:///repo/lib.rs".to_string(),
+                    range: Range {
+                        start: Position {
+                            line: 0,
+                            character: 0
+                        },
+                        end: Position {
+                            line: 0,
+                            character: 0
+                        },
+                    },
+                },
+            }]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Language servers backing the `code_intel` tool.
+//!
+//! Servers are declared under `[lsp_servers]` in `config.toml` and started
+//! lazily, one process per server and workspace root, the first time the
+//! tool needs them. They live until the session ends.
+
+mod client;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::config::types::LspServerConfig;
+
+pub(crate) use client::Diagnostic;
+pub(crate) use client::Location;
+pub(crate) use client::LspClient;
+pub(crate) use client::Position;
+#[cfg(test)]
+pub(crate) use client::Range;
+pub(crate) use client::SymbolMatch;
+
+pub(crate) struct LspManager {
+    servers: BTreeMap<String, LspServerConfig>,
+    clients: Mutex<HashMap<(String, PathBuf), Arc<LspClient>>>,
+}
+
+impl LspManager {
+    pub(crate) fn new(servers: BTreeMap<String, LspServerConfig>) -> Self {
+        Self {
+            servers,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn server_names(&self) -> impl Iterator<Item = &str> {
+        self.servers.keys().map(String::as_str)
+    }
+
+    /// Name of the first configured server (in name order) that handles the
+    /// extension of `path`.
+    pub(crate) fn server_for_path(&self, path: &Path) -> Option<&str> {
+        let extension = path.extension()?.to_str()?;
+        self.servers
+            .iter()
+            .find(|(_, config)| {
+                config
+                    .extensions
+                    .iter()
+                    .any(|candidate| candidate.trim_start_matches('.') == extension)
+            })
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Return the running client for `server` in `root`, starting it (or
+    /// restarting it after it exited) as needed.
+    pub(crate) async fn client(&self, server: &str, root: &Path) -> anyhow::Result<Arc<LspClient>> {
+        let config = self
+            .servers
+            .get(server)
+            .ok_or_else(|| anyhow::anyhow!("unknown language server `{server}`"))?;
+        let key = (server.to_string(), root.to_path_buf());
+
+        // Holding the lock across startup keeps concurrent calls from
+        // spawning the same server twice.
+        let mut clients = self.clients.lock().await;
+        if let Some(client) = clients.get(&key)
+            && client.is_alive()
+        {
+            return Ok(Arc::clone(client));
+        }
+        let client = Arc::new(LspClient::spawn(config, root).await?);
+        clients.insert(key, Arc::clone(&client));
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn server(extensions: &[&str]) -> LspServerConfig {
+        LspServerConfig {
+            command: "fake-ls".to_string(),
+            args: Vec::new(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            env: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn routes_paths_by_extension() {
+        let manager = LspManager::new(BTreeMap::from([
+            ("pyright".to_string(), server(&["py", "pyi"])),
+            ("rust-analyzer".to_string(), server(&[".rs"])),
+        ]));
+
+        assert_eq!(
+            manager.server_for_path(Path::new("/repo/src/lib.rs")),
+            Some("rust-analyzer")
+        );
+        assert_eq!(
+            manager.server_for_path(Path::new("/repo/stubs/mod.pyi")),
+            Some("pyright")
+        );
+        assert_eq!(manager.server_for_path(Path::new("/repo/main.go")), None);
+        assert_eq!(manager.server_for_path(Path::new("/repo/Makefile")), None);
+    }
+}
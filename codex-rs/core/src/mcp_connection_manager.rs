@@ -39,6 +39,7 @@ use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::SendElicitation;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
@@ -329,6 +330,9 @@ pub struct SandboxState {
     pub sandbox_cwd: PathBuf,
     #[serde(default)]
     pub use_linux_sandbox_bwrap: bool,
+    /// Paths that sandboxed commands must not be able to read.
+    #[serde(default)]
+    pub denied_read_paths: Vec<AbsolutePathBuf>,
 }
 
 /// A thin wrapper around a set of running [`RmcpClient`] instances.
@@ -0,0 +1,131 @@
+//! Durable facts carried across sessions.
+//!
+//! The agent saves memories with the `remember` tool and drops stale ones with
+//! `forget`; users review and edit them with `codex memory`. A memory applies
+//! either to every session of the user or to one repository, keyed by the
+//! repository root so worktrees of the same project share memories. At session
+//! start the relevant memories are added to the context, repository memories
+//! first, until `memory_max_tokens` is reached.
+
+use std::path::Path;
+
+use codex_state::Memory;
+use codex_state::StateRuntime;
+use tracing::warn;
+
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::truncate::approx_token_count;
+
+const MEMORIES_OPEN_TAG: &str = "<memories>";
+const MEMORIES_CLOSE_TAG: &str = "</memories>";
+const MEMORIES_PREAMBLE: &str = "Facts saved in earlier sessions. Rely on them instead of rediscovering the same details, and call `forget` with a memory's id when it turns out to be wrong or outdated.";
+
+/// Key identifying the repository a working directory belongs to: the root of
+/// the enclosing git project, or `cwd` itself outside of git.
+pub fn memory_repo_key(cwd: &Path) -> String {
+    resolve_root_git_project_for_trust(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Load the memories relevant to `cwd` and render them for the context.
+pub(crate) async fn load_memory_instructions(
+    state_db: Option<&StateRuntime>,
+    cwd: &Path,
+    max_tokens: usize,
+) -> Option<String> {
+    if max_tokens == 0 {
+        return None;
+    }
+    let state_db = state_db?;
+    let repo = memory_repo_key(cwd);
+    match state_db.list_memories(Some(repo.as_str())).await {
+        Ok(memories) => render_memory_instructions(&memories, max_tokens),
+        Err(err) => {
+            warn!("failed to load memories: {err}");
+            None
+        }
+    }
+}
+
+/// Render memories in priority order, dropping the rest once they no longer
+/// fit in `max_tokens`.
+fn render_memory_instructions(memories: &[Memory], max_tokens: usize) -> Option<String> {
+    let mut used = approx_token_count(MEMORIES_OPEN_TAG)
+        + approx_token_count(MEMORIES_PREAMBLE)
+        + approx_token_count(MEMORIES_CLOSE_TAG);
+    let mut lines = Vec::new();
+    for memory in memories {
+        let line = format!(
+            "- [{id}] ({scope}) {content}",
+            id = memory.id,
+            scope = memory.scope.as_str(),
+            content = memory.content.replace('\n', " ")
+        );
+        let tokens = approx_token_count(&line);
+        if used + tokens > max_tokens {
+            break;
+        }
+        used += tokens;
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{MEMORIES_OPEN_TAG}\n{MEMORIES_PREAMBLE}\n{}\n{MEMORIES_CLOSE_TAG}",
+        lines.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use codex_state::MemoryScope;
+    use codex_state::MemorySource;
+    use pretty_assertions::assert_eq;
+
+    fn memory(id: i64, scope: MemoryScope, content: &str) -> Memory {
+        Memory {
+            id,
+            scope,
+            repo: None,
+            content: content.to_string(),
+            source: MemorySource::Agent,
+            thread_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn renders_memories_in_order_within_budget() {
+        let memories = vec![
+            memory(
+                3,
+                MemoryScope::Repo,
+                "Integration tests need `docker compose up`.",
+            ),
+            memory(1, MemoryScope::User, "Prefers small commits."),
+            memory(2, MemoryScope::User, &"x".repeat(400)),
+        ];
+        assert_eq!(
+            render_memory_instructions(&memories, 120),
+            Some(format!(
+                "<memories>\n{MEMORIES_PREAMBLE}\n\
+                 - [3] (repo) Integration tests need `docker compose up`.\n\
+                 - [1] (user) Prefers small commits.\n\
+                 </memories>"
+            ))
+        );
+    }
+
+    #[test]
+    fn nothing_is_rendered_when_no_memory_fits() {
+        let memories = vec![memory(1, MemoryScope::User, "Prefers small commits.")];
+        assert_eq!(render_memory_instructions(&memories, 10), None);
+        assert_eq!(render_memory_instructions(&[], 1_000), None);
+    }
+}
@@ -0,0 +1,434 @@
+//! Fallback models for when the configured model keeps failing.
+//!
+//! When a request still fails with a rate-limit, capacity, server or
+//! connection error after the provider's retries are used up, the session
+//! moves to the next entry of `fallback_models`. It stays there until the
+//! cooldown has passed, or until the limit the error or latest rate-limit
+//! snapshot reported has reset if that is later, and returns to the configured
+//! model at the start of the next turn after that.
+//!
+//! Turns build their tools, instructions, truncation and compaction limits
+//! from the configured model, so only fallbacks that agree with it on all of
+//! those are used.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::protocol::RateLimitSnapshot;
+use reqwest::StatusCode;
+
+use crate::client::ModelClient;
+use crate::error::CodexErr;
+
+/// A fallback model and the client for its provider.
+#[derive(Debug)]
+pub(crate) struct ModelFallbackTarget {
+    pub(crate) model_info: ModelInfo,
+    pub(crate) provider_id: String,
+    pub(crate) client: ModelClient,
+}
+
+/// Describes a switch to a fallback model.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ModelFallbackSwitch {
+    pub(crate) from_model: String,
+    pub(crate) to_model: String,
+    pub(crate) to_provider_id: String,
+    /// When the next turn may return to the configured model.
+    pub(crate) resume_primary_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ActiveFallback {
+    index: usize,
+    resume_primary_at: DateTime<Utc>,
+}
+
+/// Session-scoped fallback state shared by all clones of a [`ModelClient`].
+#[derive(Debug, Default)]
+pub(crate) struct ModelFallbackChain {
+    primary_provider_id: String,
+    targets: Vec<ModelFallbackTarget>,
+    cooldown: Duration,
+    active: Mutex<Option<ActiveFallback>>,
+}
+
+impl ModelFallbackChain {
+    pub(crate) fn new(
+        primary_provider_id: String,
+        targets: Vec<ModelFallbackTarget>,
+        cooldown: Duration,
+    ) -> Self {
+        Self {
+            primary_provider_id,
+            targets,
+            cooldown,
+            active: Mutex::new(None),
+        }
+    }
+
+    /// The fallback currently serving the session, if any.
+    pub(crate) fn active(&self) -> Option<(usize, &ModelFallbackTarget)> {
+        let index = self.active_state()?.index;
+        Some((index, self.targets.get(index)?))
+    }
+
+    /// Moves to the next fallback after `err`, if the error calls for one and
+    /// a usable fallback is left. Usage limits apply to the whole account, so
+    /// fallbacks on the provider that reported one are skipped.
+    pub(crate) fn advance(
+        &self,
+        primary: &ModelInfo,
+        err: &CodexErr,
+        rate_limits: Option<&RateLimitSnapshot>,
+        now: DateTime<Utc>,
+    ) -> Option<ModelFallbackSwitch> {
+        if !triggers_fallback(err) {
+            return None;
+        }
+        let mut active = self.active.lock().ok()?;
+        let current = active.and_then(|active| self.targets.get(active.index));
+        let (from_model, from_provider) = match current {
+            Some(target) => (target.model_info.slug.as_str(), target.provider_id.as_str()),
+            None => (primary.slug.as_str(), self.primary_provider_id.as_str()),
+        };
+        let skip_provider = matches!(err, CodexErr::UsageLimitReached(_)).then_some(from_provider);
+        let start = active.map_or(0, |active| active.index + 1);
+        let (index, target) = self
+            .targets
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, target)| {
+                skip_provider != Some(target.provider_id.as_str())
+                    && incompatibility(primary, &target.model_info).is_none()
+            })?;
+
+        let cooldown = TimeDelta::from_std(self.cooldown).unwrap_or(TimeDelta::zero());
+        let mut resume_primary_at = (now + cooldown).max(limit_resets_at(err, rate_limits, now));
+        if let Some(previous) = *active {
+            resume_primary_at = resume_primary_at.max(previous.resume_primary_at);
+        }
+        *active = Some(ActiveFallback {
+            index,
+            resume_primary_at,
+        });
+        Some(ModelFallbackSwitch {
+            from_model: from_model.to_string(),
+            to_model: target.model_info.slug.clone(),
+            to_provider_id: target.provider_id.clone(),
+            resume_primary_at,
+        })
+    }
+
+    /// Returns to the configured model once the cooldown has passed, giving
+    /// back the fallback model that was serving the session.
+    pub(crate) fn restore_primary_if_due(&self, now: DateTime<Utc>) -> Option<String> {
+        let mut active = self.active.lock().ok()?;
+        let due = active.is_some_and(|active| now >= active.resume_primary_at);
+        if !due {
+            return None;
+        }
+        let index = active.take()?.index;
+        self.targets
+            .get(index)
+            .map(|target| target.model_info.slug.clone())
+    }
+
+    fn active_state(&self) -> Option<ActiveFallback> {
+        *self.active.lock().ok()?
+    }
+}
+
+/// What keeps `fallback` from standing in for `primary` within a turn built
+/// for `primary`, if anything.
+pub(crate) fn incompatibility(primary: &ModelInfo, fallback: &ModelInfo) -> Option<&'static str> {
+    if fallback.base_instructions != primary.base_instructions
+        || fallback.model_messages != primary.model_messages
+    {
+        return Some("instructions");
+    }
+    if fallback.shell_type != primary.shell_type
+        || fallback.apply_patch_tool_type != primary.apply_patch_tool_type
+        || fallback.supports_parallel_tool_calls != primary.supports_parallel_tool_calls
+        || fallback.experimental_supported_tools != primary.experimental_supported_tools
+    {
+        return Some("tools");
+    }
+    if fallback.truncation_policy != primary.truncation_policy {
+        return Some("output truncation");
+    }
+    // Compaction triggers at the configured model's limit, so the fallback
+    // must fit at least as much context.
+    let fits = |limit: Option<i64>, needed: Option<i64>| match (limit, needed) {
+        (_, None) => true,
+        (Some(limit), Some(needed)) => limit >= needed,
+        (None, Some(_)) => false,
+    };
+    if !fits(fallback.context_window, primary.context_window)
+        || !fits(
+            fallback.auto_compact_token_limit(),
+            primary.auto_compact_token_limit(),
+        )
+    {
+        return Some("context window");
+    }
+    if !primary
+        .input_modalities
+        .iter()
+        .all(|modality| fallback.input_modalities.contains(modality))
+    {
+        return Some("input modalities");
+    }
+    None
+}
+
+/// Errors that mean the model or provider is unavailable for now, as opposed
+/// to a problem with the request itself.
+fn triggers_fallback(err: &CodexErr) -> bool {
+    match err {
+        CodexErr::RetryLimit(_)
+        | CodexErr::ModelCap(_)
+        | CodexErr::UsageLimitReached(_)
+        | CodexErr::InternalServerError
+        | CodexErr::ConnectionFailed(_)
+        | CodexErr::ResponseStreamFailed(_)
+        | CodexErr::Stream(..)
+        | CodexErr::Timeout => true,
+        CodexErr::UnexpectedStatus(err) => {
+            err.status.is_server_error() || err.status == StatusCode::TOO_MANY_REQUESTS
+        }
+        _ => false,
+    }
+}
+
+/// The latest reset time the error or rate-limit snapshot reports for an
+/// exhausted limit, or `now` when neither does.
+fn limit_resets_at(
+    err: &CodexErr,
+    rate_limits: Option<&RateLimitSnapshot>,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let (from_error, rate_limits) = match err {
+        CodexErr::ModelCap(cap) => (
+            cap.reset_after_seconds
+                .and_then(|seconds| TimeDelta::try_seconds(i64::try_from(seconds).ok()?))
+                .and_then(|delay| now.checked_add_signed(delay)),
+            rate_limits,
+        ),
+        CodexErr::UsageLimitReached(limit) => {
+            (limit.resets_at, limit.rate_limits.as_ref().or(rate_limits))
+        }
+        _ => (None, rate_limits),
+    };
+    let from_snapshot = rate_limits
+        .into_iter()
+        .flat_map(|snapshot| [snapshot.primary.as_ref(), snapshot.secondary.as_ref()])
+        .flatten()
+        .filter(|window| window.used_percent >= 100.0)
+        .filter_map(|window| DateTime::from_timestamp(window.resets_at?, 0))
+        .max();
+    [Some(now), from_error, from_snapshot]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ModelCapError;
+    use crate::error::RetryLimitReachedError;
+    use crate::error::UsageLimitReachedError;
+    use crate::models_manager::manager::ModelsManager;
+    use codex_protocol::protocol::RateLimitWindow;
+    use pretty_assertions::assert_eq;
+
+    fn primary() -> ModelInfo {
+        let config = crate::config::test_config();
+        ModelsManager::construct_model_info_offline("gpt-5.1-codex", &config)
+    }
+
+    fn test_chain() -> ModelFallbackChain {
+        chain_with(|slug| ModelInfo {
+            slug: slug.to_string(),
+            ..primary()
+        })
+    }
+
+    fn chain_with(model_info: impl Fn(&str) -> ModelInfo) -> ModelFallbackChain {
+        let config = crate::config::test_config();
+        let target = |slug: &str, provider_id: &str| ModelFallbackTarget {
+            model_info: model_info(slug),
+            provider_id: provider_id.to_string(),
+            client: ModelClient::new(
+                None,
+                codex_protocol::ThreadId::new(),
+                config.model_provider.clone(),
+                codex_protocol::protocol::SessionSource::Exec,
+                None,
+                false,
+                false,
+                false,
+                false,
+                None,
+            ),
+        };
+        ModelFallbackChain::new(
+            "openai".to_string(),
+            vec![
+                target("gpt-5.1-codex-mini", "openai"),
+                target("gpt-oss:20b", "ollama"),
+            ],
+            Duration::from_secs(300),
+        )
+    }
+
+    fn retry_limit() -> CodexErr {
+        CodexErr::RetryLimit(RetryLimitReachedError {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            request_id: None,
+        })
+    }
+
+    #[test]
+    fn walks_the_chain_and_returns_after_the_cooldown() {
+        let chain = test_chain();
+        let now = Utc::now();
+
+        assert_eq!(
+            chain.advance(
+                &primary(),
+                &CodexErr::InvalidRequest("bad".into()),
+                None,
+                now
+            ),
+            None
+        );
+        assert_eq!(
+            chain.advance(&primary(), &retry_limit(), None, now),
+            Some(ModelFallbackSwitch {
+                from_model: "gpt-5.1-codex".to_string(),
+                to_model: "gpt-5.1-codex-mini".to_string(),
+                to_provider_id: "openai".to_string(),
+                resume_primary_at: now + TimeDelta::seconds(300),
+            })
+        );
+        let next = chain
+            .advance(&primary(), &CodexErr::InternalServerError, None, now)
+            .expect("second fallback");
+        assert_eq!(next.from_model, "gpt-5.1-codex-mini");
+        assert_eq!(next.to_model, "gpt-oss:20b");
+        assert_eq!(chain.advance(&primary(), &retry_limit(), None, now), None);
+        assert_eq!(chain.active().map(|(index, _)| index), Some(1));
+
+        assert_eq!(
+            chain.restore_primary_if_due(now + TimeDelta::seconds(10)),
+            None
+        );
+        assert_eq!(
+            chain.restore_primary_if_due(now + TimeDelta::seconds(300)),
+            Some("gpt-oss:20b".to_string())
+        );
+        assert!(chain.active().is_none());
+    }
+
+    #[test]
+    fn skips_fallbacks_that_do_not_match_the_configured_model() {
+        let chain = chain_with(|slug| {
+            let mut model_info = ModelInfo {
+                slug: slug.to_string(),
+                ..primary()
+            };
+            if slug == "gpt-5.1-codex-mini" {
+                model_info.supports_parallel_tool_calls = !model_info.supports_parallel_tool_calls;
+            }
+            model_info
+        });
+
+        let switch = chain
+            .advance(&primary(), &retry_limit(), None, Utc::now())
+            .expect("compatible fallback");
+        assert_eq!(switch.to_model, "gpt-oss:20b");
+    }
+
+    #[test]
+    fn incompatibility_names_what_differs() {
+        let primary = primary();
+        let config = crate::config::test_config();
+        let other = ModelsManager::construct_model_info_offline("gpt-oss:20b", &config);
+
+        assert_eq!(incompatibility(&primary, &primary), None);
+        assert_eq!(
+            incompatibility(
+                &primary,
+                &ModelInfo {
+                    base_instructions: "other".to_string(),
+                    ..primary.clone()
+                }
+            ),
+            Some("instructions")
+        );
+        assert_eq!(
+            incompatibility(
+                &primary,
+                &ModelInfo {
+                    supports_parallel_tool_calls: !primary.supports_parallel_tool_calls,
+                    ..primary.clone()
+                }
+            ),
+            Some("tools")
+        );
+        assert!(incompatibility(&primary, &other).is_some());
+    }
+
+    #[test]
+    fn waits_for_reported_limits_to_reset() {
+        let now = Utc::now();
+
+        let chain = test_chain();
+        let cap = CodexErr::ModelCap(ModelCapError {
+            model: "gpt-5.1-codex".to_string(),
+            reset_after_seconds: Some(3600),
+        });
+        let switch = chain
+            .advance(&primary(), &cap, None, now)
+            .expect("fallback for a model cap");
+        assert_eq!(switch.resume_primary_at, now + TimeDelta::seconds(3600));
+
+        // Usage limits cover the whole account, so the fallback on the same
+        // provider is skipped and the snapshot's reset time is honored.
+        let chain = test_chain();
+        let resets_at = now.timestamp() + 7200;
+        let snapshot = RateLimitSnapshot {
+            primary: Some(RateLimitWindow {
+                used_percent: 100.0,
+                window_minutes: Some(300),
+                resets_at: Some(resets_at),
+            }),
+            secondary: Some(RateLimitWindow {
+                used_percent: 40.0,
+                window_minutes: Some(10080),
+                resets_at: Some(now.timestamp() + 86_400),
+            }),
+            credits: None,
+            plan_type: None,
+        };
+        let usage_limit = CodexErr::UsageLimitReached(UsageLimitReachedError {
+            plan_type: None,
+            resets_at: None,
+            rate_limits: None,
+            promo_message: None,
+        });
+        let switch = chain
+            .advance(&primary(), &usage_limit, Some(&snapshot), now)
+            .expect("fallback on another provider");
+        assert_eq!(switch.to_model, "gpt-oss:20b");
+        assert_eq!(switch.resume_primary_at.timestamp(), resets_at);
+    }
+}
@@ -85,7 +85,7 @@ impl ModelsManager {
             error!("failed to refresh available models: {err}");
         }
         let remote_models = self.get_remote_models(config).await;
-        self.build_available_models(remote_models)
+        self.build_available_models(remote_models, &config.model_catalog)
     }
 
     /// List collaboration mode presets.
@@ -100,7 +100,7 @@ impl ModelsManager {
     /// Returns an error if the internal lock cannot be acquired.
     pub fn try_list_models(&self, config: &Config) -> Result<Vec<ModelPreset>, TryLockError> {
         let remote_models = self.try_get_remote_models(config)?;
-        Ok(self.build_available_models(remote_models))
+        Ok(self.build_available_models(remote_models, &config.model_catalog))
     }
 
     // todo(aibrahim): should be visible to core only and sent on session_configured event
@@ -124,7 +124,7 @@ impl ModelsManager {
             error!("failed to refresh available models: {err}");
         }
         let remote_models = self.get_remote_models(config).await;
-        let available = self.build_available_models(remote_models);
+        let available = self.build_available_models(remote_models, &config.model_catalog);
         available
             .iter()
             .find(|model| model.is_default)
@@ -135,7 +135,12 @@ impl ModelsManager {
 
     // todo(aibrahim): look if we can tighten it to pub(crate)
     /// Look up model metadata, applying remote overrides and config adjustments.
+    ///
+    /// Models in the local catalog win over remote and built-in metadata.
     pub async fn get_model_info(&self, model: &str, config: &Config) -> ModelInfo {
+        if let Some(local) = config.model_catalog.iter().find(|m| m.slug == model) {
+            return model_info::with_config_overrides(local.clone(), config);
+        }
         let remote = self
             .get_remote_models(config)
             .await
@@ -269,7 +274,13 @@ impl ModelsManager {
     }
 
     /// Merge remote model metadata into picker-ready presets, preserving existing entries.
-    fn build_available_models(&self, mut remote_models: Vec<ModelInfo>) -> Vec<ModelPreset> {
+    ///
+    /// Catalog models replace presets with the same slug and are otherwise appended.
+    fn build_available_models(
+        &self,
+        mut remote_models: Vec<ModelInfo>,
+        catalog: &[ModelInfo],
+    ) -> Vec<ModelPreset> {
         remote_models.sort_by(|a, b| a.priority.cmp(&b.priority));
 
         let remote_presets: Vec<ModelPreset> = remote_models.into_iter().map(Into::into).collect();
@@ -277,6 +288,16 @@ impl ModelsManager {
         let mut merged_presets = ModelPreset::merge(remote_presets, existing_presets);
         let chatgpt_mode = matches!(self.auth_manager.auth_mode(), Some(AuthMode::Chatgpt));
         merged_presets = ModelPreset::filter_by_auth(merged_presets, chatgpt_mode);
+        for model in catalog {
+            let preset = ModelPreset::from(model.clone());
+            match merged_presets
+                .iter_mut()
+                .find(|existing| existing.model == preset.model)
+            {
+                Some(existing) => *existing = preset,
+                None => merged_presets.push(preset),
+            }
+        }
 
         for preset in &mut merged_presets {
             preset.is_default = false;
@@ -346,7 +367,13 @@ impl ModelsManager {
     #[cfg(any(test, feature = "test-support"))]
     /// Build `ModelInfo` without consulting remote state or cache.
     pub fn construct_model_info_offline(model: &str, config: &Config) -> ModelInfo {
-        model_info::with_config_overrides(model_info::find_model_info_for_slug(model), config)
+        let model_info = config
+            .model_catalog
+            .iter()
+            .find(|m| m.slug == model)
+            .cloned()
+            .unwrap_or_else(|| model_info::find_model_info_for_slug(model));
+        model_info::with_config_overrides(model_info, config)
     }
 }
 
@@ -758,11 +785,46 @@ mod tests {
         let mut expected_visible = ModelPreset::from(visible_model.clone());
         expected_visible.is_default = true;
 
-        let available = manager.build_available_models(vec![hidden_model, visible_model]);
+        let available = manager.build_available_models(vec![hidden_model, visible_model], &[]);
 
         assert_eq!(available, vec![expected_hidden, expected_visible]);
     }
 
+    #[tokio::test]
+    async fn catalog_models_override_remote_metadata() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let provider = provider_for("http://example.test".to_string());
+        let mut manager =
+            ModelsManager::with_provider(codex_home.path().to_path_buf(), auth_manager, provider);
+        manager.local_models = Vec::new();
+
+        let remote = remote_model("shared", "Remote", 0);
+        let mut local_shared = remote_model("shared", "Local", 0);
+        local_shared.context_window = Some(64_000);
+        let mut local_only = remote_model("local-only", "Local Only", 1);
+        local_only.context_window = Some(32_000);
+        config.model_catalog = vec![local_shared.clone(), local_only.clone()];
+
+        let mut expected_shared = ModelPreset::from(local_shared);
+        expected_shared.is_default = true;
+        let available = manager.build_available_models(vec![remote], &config.model_catalog);
+        assert_eq!(
+            available,
+            vec![expected_shared, ModelPreset::from(local_only)]
+        );
+
+        let info = manager.get_model_info("local-only", &config).await;
+        assert_eq!(info.display_name, "Local Only");
+        assert_eq!(info.context_window, Some(32_000));
+    }
+
     #[test]
     fn bundled_models_json_roundtrips() {
         let file_contents = include_str!("../../models.json");
@@ -0,0 +1,255 @@
+//! Interactive approval of hosts blocked by `codex-network-proxy`.
+//!
+//! The proxy records every request it blocks in a queue exposed through its
+//! admin API (`GET /blocked`). While a session routes sandboxed commands
+//! through the proxy, [`NetworkApprovals`] drains that queue, turns each newly
+//! blocked host into a [`NetworkApprovalRequestEvent`], and applies the user's
+//! answer through `POST /allow` (and, for "always", `config.toml`).
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_protocol::protocol::NetworkApprovalDecision;
+use codex_protocol::protocol::NetworkApprovalRequestEvent;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::config::edit::ConfigEditsBuilder;
+
+/// How often the proxy's blocked-request queue is polled.
+pub(crate) const NETWORK_APPROVAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+const ADMIN_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Only allowlist misses can be approved; explicit denials and local-network
+/// blocks are policy, not prompts.
+const APPROVABLE_REASON: &str = "not_allowed";
+
+#[derive(Debug, Deserialize)]
+struct BlockedResponse {
+    blocked: Vec<BlockedRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockedRequest {
+    host: String,
+    reason: String,
+    #[serde(default)]
+    method: Option<String>,
+    protocol: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AllowRequest<'a> {
+    host: &'a str,
+    scope: &'static str,
+}
+
+#[derive(Default)]
+struct NetworkApprovalState {
+    /// Outstanding prompts keyed by request id, mapped to the blocked host.
+    pending: HashMap<String, String>,
+    /// Hosts the user has been (or is being) asked about. Denied hosts stay
+    /// here so they are not prompted for again this session.
+    prompted_hosts: HashSet<String>,
+    next_id: u64,
+}
+
+pub(crate) struct NetworkApprovals {
+    client: reqwest::Client,
+    admin_url: String,
+    codex_home: PathBuf,
+    state: Mutex<NetworkApprovalState>,
+}
+
+impl NetworkApprovals {
+    pub(crate) fn new(admin_addr: SocketAddr, codex_home: PathBuf) -> anyhow::Result<Self> {
+        // The admin API is on loopback; never send these requests through a
+        // proxy picked up from the environment.
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .timeout(ADMIN_REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            admin_url: format!("http://{admin_addr}"),
+            codex_home,
+            state: Mutex::new(NetworkApprovalState::default()),
+        })
+    }
+
+    /// Drain the proxy's blocked-request queue and return a prompt for each
+    /// approvable host the user has not been asked about yet.
+    pub(crate) async fn poll_new_requests(
+        &self,
+    ) -> anyhow::Result<Vec<NetworkApprovalRequestEvent>> {
+        let response: BlockedResponse = self
+            .client
+            .get(format!("{}/blocked", self.admin_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut state = self.state.lock().await;
+        let mut requests = Vec::new();
+        for blocked in response.blocked {
+            if blocked.reason != APPROVABLE_REASON {
+                continue;
+            }
+            let host = blocked.host.to_ascii_lowercase();
+            if !state.prompted_hosts.insert(host.clone()) {
+                continue;
+            }
+            state.next_id += 1;
+            let id = format!("network-{}", state.next_id);
+            state.pending.insert(id.clone(), host.clone());
+            requests.push(NetworkApprovalRequestEvent {
+                id,
+                host,
+                reason: blocked.reason,
+                protocol: blocked.protocol,
+                method: blocked.method,
+            });
+        }
+        Ok(requests)
+    }
+
+    /// Apply the user's answer to the prompt `id`. Returns the host the
+    /// decision applied to, or `None` when `id` is unknown.
+    pub(crate) async fn resolve(
+        &self,
+        id: &str,
+        decision: NetworkApprovalDecision,
+    ) -> anyhow::Result<Option<String>> {
+        let Some(host) = self.state.lock().await.pending.remove(id) else {
+            return Ok(None);
+        };
+
+        match decision {
+            NetworkApprovalDecision::AllowOnce => {
+                self.allow(&host, "once").await?;
+                // Ask again the next time the host is blocked.
+                self.state.lock().await.prompted_hosts.remove(&host);
+            }
+            NetworkApprovalDecision::AllowForSession => {
+                self.allow(&host, "session").await?;
+            }
+            NetworkApprovalDecision::AllowAlways => {
+                ConfigEditsBuilder::new(&self.codex_home)
+                    .add_network_allowed_domain(&host)
+                    .apply()
+                    .await?;
+                // The proxy reloads config.toml on its own; allowing the host
+                // for the session makes the change effective immediately.
+                self.allow(&host, "session").await?;
+            }
+            NetworkApprovalDecision::Deny => {}
+        }
+        Ok(Some(host))
+    }
+
+    async fn allow(&self, host: &str, scope: &'static str) -> anyhow::Result<()> {
+        self.client
+            .post(format!("{}/allow", self.admin_url))
+            .json(&AllowRequest { host, scope })
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::tempdir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_json;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    fn approvals_for(server: &MockServer, codex_home: PathBuf) -> NetworkApprovals {
+        NetworkApprovals::new(*server.address(), codex_home).expect("client")
+    }
+
+    #[tokio::test]
+    async fn polls_only_new_not_allowed_hosts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/blocked"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "blocked": [
+                    {"host": "registry.npmjs.org", "reason": "not_allowed", "protocol": "https_connect"},
+                    {"host": "Registry.npmjs.org", "reason": "not_allowed", "protocol": "http", "method": "GET"},
+                    {"host": "evil.example", "reason": "denied", "protocol": "http"},
+                    {"host": "127.0.0.1", "reason": "not_allowed_local", "protocol": "http"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        let tmp = tempdir().expect("tmpdir");
+        let approvals = approvals_for(&server, tmp.path().to_path_buf());
+
+        let requests = approvals.poll_new_requests().await.expect("poll");
+        let hosts: Vec<&str> = requests.iter().map(|r| r.host.as_str()).collect();
+        assert_eq!(hosts, vec!["registry.npmjs.org"]);
+
+        let again = approvals.poll_new_requests().await.expect("poll");
+        assert!(again.is_empty());
+    }
+
+    #[tokio::test]
+    async fn allow_always_persists_and_allows_for_session() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/blocked"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "blocked": [
+                    {"host": "registry.npmjs.org", "reason": "not_allowed", "protocol": "https_connect"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/allow"))
+            .and(body_json(
+                json!({"host": "registry.npmjs.org", "scope": "session"}),
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let tmp = tempdir().expect("tmpdir");
+        let approvals = approvals_for(&server, tmp.path().to_path_buf());
+
+        let requests = approvals.poll_new_requests().await.expect("poll");
+        let host = approvals
+            .resolve(&requests[0].id, NetworkApprovalDecision::AllowAlways)
+            .await
+            .expect("resolve");
+        assert_eq!(host.as_deref(), Some("registry.npmjs.org"));
+
+        let config = std::fs::read_to_string(tmp.path().join("config.toml")).expect("config");
+        assert_eq!(
+            config,
+            "[network]\nallowed_domains = [\"registry.npmjs.org\"]\n"
+        );
+        assert_eq!(
+            approvals
+                .resolve(&requests[0].id, NetworkApprovalDecision::AllowAlways)
+                .await
+                .expect("resolve twice"),
+            None
+        );
+    }
+}
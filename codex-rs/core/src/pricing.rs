@@ -0,0 +1,183 @@
+//! Cost estimates derived from token usage and per-model prices.
+//!
+//! Built-in prices cover the models Codex ships presets for; entries under
+//! `[model_prices]` in `config.toml` replace them or add new models. Estimates
+//! use list prices and ignore discounts, so they are an upper bound on what a
+//! thread cost rather than a bill. A thread that used a model without a price
+//! has an unknown cost rather than the sum of its priced turns.
+
+use std::collections::HashMap;
+
+use codex_protocol::protocol::TokenUsage;
+
+use crate::config::types::ModelPrice;
+
+const TOKENS_PER_PRICE_UNIT: f64 = 1_000_000.0;
+
+/// `(input, cached_input, output)` in US dollars per million tokens.
+///
+/// These are list prices as published when each model shipped; they are not
+/// refreshed at runtime, so `[model_prices]` is the place to correct them.
+const BUILTIN_PRICES: &[(&str, f64, f64, f64)] = &[
+    ("gpt-5.2-codex", 1.75, 0.175, 14.0),
+    ("gpt-5.2", 1.75, 0.175, 14.0),
+    ("gpt-5.1-codex-max", 1.25, 0.125, 10.0),
+    ("gpt-5.1-codex", 1.25, 0.125, 10.0),
+    ("gpt-5.1-codex-mini", 0.25, 0.025, 2.0),
+    ("gpt-5.1", 1.25, 0.125, 10.0),
+    ("gpt-5-codex", 1.25, 0.125, 10.0),
+    ("gpt-5-codex-mini", 0.25, 0.025, 2.0),
+    ("gpt-5", 1.25, 0.125, 10.0),
+];
+
+/// Merge the built-in price table with prices from config, which win.
+pub(crate) fn resolve_model_prices(
+    overrides: HashMap<String, ModelPrice>,
+) -> HashMap<String, ModelPrice> {
+    let mut prices: HashMap<String, ModelPrice> = BUILTIN_PRICES
+        .iter()
+        .map(|(model, input, cached_input, output)| {
+            (
+                (*model).to_string(),
+                ModelPrice {
+                    input: *input,
+                    cached_input: Some(*cached_input),
+                    output: *output,
+                    reasoning_output: None,
+                },
+            )
+        })
+        .collect();
+    prices.extend(overrides);
+    prices
+}
+
+/// Estimated cost in US dollars of `usage` at `price`.
+///
+/// Reasoning tokens are part of `output_tokens`; they are charged at the
+/// reasoning price and the rest of the output at the output price.
+pub(crate) fn estimate_cost_usd(usage: &TokenUsage, price: &ModelPrice) -> f64 {
+    let cached_input = usage.cached_input().max(0);
+    let output = usage.output_tokens.max(0);
+    let reasoning_output = usage.reasoning_output_tokens.clamp(0, output);
+    let cost = usage.non_cached_input() as f64 * price.input
+        + cached_input as f64 * price.cached_input.unwrap_or(price.input)
+        + (output - reasoning_output) as f64 * price.output
+        + reasoning_output as f64 * price.reasoning_output.unwrap_or(price.output);
+    cost / TOKENS_PER_PRICE_UNIT
+}
+
+/// Running cost estimate of a thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum ThreadCost {
+    /// No usage recorded yet.
+    #[default]
+    Empty,
+    Known(f64),
+    /// Some usage was on a model without a price; stays unknown for the rest
+    /// of the thread.
+    Unknown,
+}
+
+impl ThreadCost {
+    /// Restore the estimate recorded in a rollout. A missing estimate for a
+    /// thread that used tokens means its price was unknown.
+    pub(crate) fn restored(total_cost_usd: Option<f64>, total_tokens: i64) -> Self {
+        match total_cost_usd {
+            Some(cost_usd) => ThreadCost::Known(cost_usd),
+            None if total_tokens > 0 => ThreadCost::Unknown,
+            None => ThreadCost::Empty,
+        }
+    }
+
+    /// Add the cost of one response; `None` when its model has no price.
+    pub(crate) fn add(&mut self, cost_usd: Option<f64>) {
+        *self = match (*self, cost_usd) {
+            (ThreadCost::Unknown, _) | (_, None) => ThreadCost::Unknown,
+            (ThreadCost::Empty, Some(cost_usd)) => ThreadCost::Known(cost_usd),
+            (ThreadCost::Known(total), Some(cost_usd)) => ThreadCost::Known(total + cost_usd),
+        };
+    }
+
+    pub(crate) fn total_usd(self) -> Option<f64> {
+        match self {
+            ThreadCost::Known(total) => Some(total),
+            ThreadCost::Empty | ThreadCost::Unknown => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(input: i64, cached_input: i64, output: i64, reasoning_output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached_input,
+            output_tokens: output,
+            reasoning_output_tokens: reasoning_output,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn charges_cached_input_and_reasoning_separately() {
+        let price = ModelPrice {
+            input: 2.0,
+            cached_input: Some(0.5),
+            output: 8.0,
+            reasoning_output: Some(16.0),
+        };
+        let cost = estimate_cost_usd(&usage(1_000_000, 400_000, 500_000, 100_000), &price);
+        // 0.6M * $2 + 0.4M * $0.5 + 0.4M * $8 + 0.1M * $16
+        assert_eq!(cost, 6.2);
+    }
+
+    #[test]
+    fn missing_prices_fall_back_to_input_and_output() {
+        let price = ModelPrice {
+            input: 1.0,
+            cached_input: None,
+            output: 4.0,
+            reasoning_output: None,
+        };
+        let cost = estimate_cost_usd(&usage(2_000_000, 1_000_000, 1_000_000, 500_000), &price);
+        assert_eq!(cost, 6.0);
+    }
+
+    #[test]
+    fn config_prices_replace_builtins() {
+        let custom = ModelPrice {
+            input: 3.0,
+            cached_input: None,
+            output: 6.0,
+            reasoning_output: None,
+        };
+        let prices = resolve_model_prices(HashMap::from([
+            ("gpt-5".to_string(), custom),
+            ("local-model".to_string(), ModelPrice::default()),
+        ]));
+        assert_eq!(prices.get("gpt-5"), Some(&custom));
+        assert_eq!(prices.get("local-model"), Some(&ModelPrice::default()));
+        assert!(prices.contains_key("gpt-5.2-codex"));
+    }
+
+    #[test]
+    fn unpriced_usage_makes_thread_cost_unknown() {
+        let mut cost = ThreadCost::default();
+        cost.add(Some(0.5));
+        cost.add(Some(0.25));
+        assert_eq!(cost.total_usd(), Some(0.75));
+
+        cost.add(None);
+        cost.add(Some(1.0));
+        assert_eq!(cost, ThreadCost::Unknown);
+        assert_eq!(cost.total_usd(), None);
+
+        assert_eq!(ThreadCost::restored(None, 10), ThreadCost::Unknown);
+        assert_eq!(ThreadCost::restored(None, 0), ThreadCost::Empty);
+        assert_eq!(ThreadCost::restored(Some(2.0), 10), ThreadCost::Known(2.0));
+    }
+}
@@ -148,6 +148,54 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
 /// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
 /// is zero, returns an empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
+    let search_dirs = project_doc_search_dirs(config)?;
+    let mut found: Vec<PathBuf> = Vec::new();
+    let candidate_filenames = candidate_filenames(config);
+    for d in search_dirs {
+        for name in &candidate_filenames {
+            let candidate = d.join(name);
+            match std::fs::symlink_metadata(&candidate) {
+                Ok(md) => {
+                    let ft = md.file_type();
+                    // Allow regular files and symlinks; opening will later fail for dangling links.
+                    if ft.is_file() || ft.is_symlink() {
+                        found.push(candidate);
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+/// Every path whose creation, change or removal can change the instructions
+/// built by [`get_user_instructions`]: the global docs in `CODEX_HOME` and
+/// each candidate project doc, whether or not it exists yet.
+pub(crate) fn instruction_watch_paths(config: &Config) -> Vec<PathBuf> {
+    let mut paths = vec![
+        config.codex_home.join(LOCAL_PROJECT_DOC_FILENAME),
+        config.codex_home.join(DEFAULT_PROJECT_DOC_FILENAME),
+    ];
+    if config.project_doc_max_bytes == 0 {
+        return paths;
+    }
+    let search_dirs = project_doc_search_dirs(config).unwrap_or_default();
+    let candidate_filenames = candidate_filenames(config);
+    for dir in search_dirs {
+        for name in &candidate_filenames {
+            paths.push(dir.join(name));
+        }
+    }
+    paths
+}
+
+/// Directories searched for project docs, from the repository root (or the
+/// working directory outside a repository) down to the working directory.
+fn project_doc_search_dirs(config: &Config) -> std::io::Result<Vec<PathBuf>> {
     let mut dir = config.cwd.clone();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
@@ -192,27 +240,7 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         vec![config.cwd.clone()]
     };
 
-    let mut found: Vec<PathBuf> = Vec::new();
-    let candidate_filenames = candidate_filenames(config);
-    for d in search_dirs {
-        for name in &candidate_filenames {
-            let candidate = d.join(name);
-            match std::fs::symlink_metadata(&candidate) {
-                Ok(md) => {
-                    let ft = md.file_type();
-                    // Allow regular files and symlinks; opening will later fail for dangling links.
-                    if ft.is_file() || ft.is_symlink() {
-                        found.push(candidate);
-                        break;
-                    }
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-                Err(e) => return Err(e),
-            }
-        }
-    }
-
-    Ok(found)
+    Ok(search_dirs)
 }
 
 fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
@@ -0,0 +1,514 @@
+//! Multi-pass reviews.
+//!
+//! With the `multi_pass_review` feature, a review whose change touches more
+//! files than `review.files_per_pass` is split into groups of files that
+//! separate reviewer sub-agents cover in parallel. Configured lenses add
+//! passes over the whole change that look for a single kind of issue. The
+//! outputs of all passes are merged into one [`ReviewOutputEvent`], collapsing
+//! findings that several passes reported.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+
+use codex_git::merge_base_with_head;
+use codex_protocol::protocol::ReviewFinding;
+use codex_protocol::protocol::ReviewOutputEvent;
+use codex_protocol::protocol::ReviewTarget;
+
+use crate::config::types::ReviewLens;
+use crate::review_prompts::ResolvedReviewRequest;
+
+const FILE_GROUP_PROMPT: &str = "This review is split across several reviewers. Only review the changes to these files: {paths}. Other reviewers cover the remaining files, so do not report findings outside this list.";
+
+const SECURITY_LENS_PROMPT: &str = "Focus this pass only on security: injection, unsafe deserialization, path traversal, missing authentication or authorization checks, secrets committed to the code, and unsafe handling of untrusted input. Do not report other kinds of issues; other reviewers cover them.";
+const PERFORMANCE_LENS_PROMPT: &str = "Focus this pass only on performance: algorithmic complexity regressions, needless allocations or copies on hot paths, blocking calls in async code, repeated queries or I/O inside loops, and unbounded memory growth. Do not report other kinds of issues; other reviewers cover them.";
+const API_COMPATIBILITY_LENS_PROMPT: &str = "Focus this pass only on API compatibility: breaking changes to public functions and types, wire and file formats, CLI flags, configuration keys, and serialized data that existing callers or stored data depend on. Do not report other kinds of issues; other reviewers cover them.";
+
+const PATCH_IS_INCORRECT: &str = "patch is incorrect";
+
+/// Findings on lines this close together can describe the same issue.
+const DUPLICATE_LINE_SLACK: u32 = 3;
+
+/// Minimum word overlap (Jaccard index) between two finding titles for them
+/// to count as duplicates.
+const DUPLICATE_TITLE_SIMILARITY: f32 = 0.5;
+
+/// One reviewer sub-agent's share of a multi-pass review.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ReviewPass {
+    /// Short description shown while the pass runs, e.g. `files 2/3`.
+    pub(crate) label: String,
+    pub(crate) prompt: String,
+}
+
+/// Split `resolved` into passes. Returns an empty list when a single pass
+/// covers the review, including when the changed files cannot be listed.
+/// Listing them shells out to git, so that runs on a blocking thread.
+pub(crate) async fn plan_review_passes(
+    resolved: &ResolvedReviewRequest,
+    cwd: &Path,
+    files_per_pass: usize,
+    lenses: &[ReviewLens],
+) -> Vec<ReviewPass> {
+    let target = resolved.target.clone();
+    let cwd = cwd.to_path_buf();
+    let changed = tokio::task::spawn_blocking(move || changed_paths(&target, &cwd))
+        .await
+        .ok()
+        .flatten();
+    let groups = changed
+        .map(|paths| group_paths(filter_paths(paths, &resolved.paths), files_per_pass))
+        .unwrap_or_default();
+    build_passes(&resolved.prompt, groups, lenses)
+}
+
+fn build_passes(prompt: &str, groups: Vec<Vec<String>>, lenses: &[ReviewLens]) -> Vec<ReviewPass> {
+    if groups.len() <= 1 && lenses.is_empty() {
+        return Vec::new();
+    }
+
+    let mut passes = Vec::new();
+    if groups.len() > 1 {
+        let total = groups.len();
+        for (index, group) in groups.into_iter().enumerate() {
+            passes.push(ReviewPass {
+                label: format!("files {}/{total}", index + 1),
+                prompt: format!(
+                    "{prompt}\n\n{}",
+                    FILE_GROUP_PROMPT.replace("{paths}", &group.join(" "))
+                ),
+            });
+        }
+    } else {
+        passes.push(ReviewPass {
+            label: "general".to_string(),
+            prompt: prompt.to_string(),
+        });
+    }
+    let mut seen = BTreeSet::new();
+    for lens in lenses {
+        if !seen.insert(lens_label(*lens)) {
+            continue;
+        }
+        passes.push(ReviewPass {
+            label: lens_label(*lens).to_string(),
+            prompt: format!("{prompt}\n\n{}", lens_prompt(*lens)),
+        });
+    }
+    passes
+}
+
+fn lens_label(lens: ReviewLens) -> &'static str {
+    match lens {
+        ReviewLens::Security => "security",
+        ReviewLens::Performance => "performance",
+        ReviewLens::ApiCompatibility => "API compatibility",
+    }
+}
+
+fn lens_prompt(lens: ReviewLens) -> &'static str {
+    match lens {
+        ReviewLens::Security => SECURITY_LENS_PROMPT,
+        ReviewLens::Performance => PERFORMANCE_LENS_PROMPT,
+        ReviewLens::ApiCompatibility => API_COMPATIBILITY_LENS_PROMPT,
+    }
+}
+
+/// Repository-relative paths changed by `target`, or `None` when they cannot
+/// be determined (custom instructions, git failures).
+fn changed_paths(target: &ReviewTarget, cwd: &Path) -> Option<Vec<String>> {
+    match target {
+        ReviewTarget::UncommittedChanges => {
+            let mut paths = git_lines(cwd, &["diff", "--name-only", "HEAD"])?;
+            paths.extend(git_lines(
+                cwd,
+                &["ls-files", "--others", "--exclude-standard"],
+            )?);
+            Some(paths)
+        }
+        ReviewTarget::BaseBranch { branch } => {
+            let merge_base = merge_base_with_head(cwd, branch).ok().flatten()?;
+            git_lines(cwd, &["diff", "--name-only", merge_base.as_str()])
+        }
+        ReviewTarget::Commit { sha, .. } => git_lines(
+            cwd,
+            &[
+                "diff-tree",
+                "--no-commit-id",
+                "--name-only",
+                "-r",
+                "--root",
+                sha.as_str(),
+            ],
+        ),
+        ReviewTarget::Range { from, to } => {
+            git_lines(cwd, &["diff", "--name-only", from.as_str(), to.as_str()])
+        }
+        ReviewTarget::PatchFile { path } => {
+            let patch = std::fs::read_to_string(cwd.join(path)).ok()?;
+            Some(patch_paths(&patch))
+        }
+        ReviewTarget::Custom { .. } => None,
+    }
+}
+
+fn git_lines(cwd: &Path, args: &[&str]) -> Option<Vec<String>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Paths touched by a unified diff, taken from its `+++`/`---` headers.
+fn patch_paths(patch: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    for line in patch.lines() {
+        let Some(header) = line
+            .strip_prefix("+++ ")
+            .or_else(|| line.strip_prefix("--- "))
+        else {
+            continue;
+        };
+        // Drop a trailing timestamp (`--- a/file\t2024-01-01 ...`).
+        let header = header.split('\t').next().unwrap_or(header).trim();
+        if header == "/dev/null" {
+            continue;
+        }
+        let path = header
+            .strip_prefix("a/")
+            .or_else(|| header.strip_prefix("b/"))
+            .unwrap_or(header);
+        paths.push(path.to_string());
+    }
+    paths
+}
+
+/// Keep only `paths` under one of `filters`; an empty filter list keeps all.
+/// The result is sorted and deduplicated.
+fn filter_paths(paths: Vec<String>, filters: &[String]) -> Vec<String> {
+    let filters: Vec<&str> = filters
+        .iter()
+        .map(|filter| filter.trim_start_matches("./").trim_end_matches('/'))
+        .collect();
+    let kept: BTreeSet<String> = paths
+        .into_iter()
+        .filter(|path| {
+            filters.is_empty()
+                || filters.iter().any(|filter| {
+                    filter.is_empty()
+                        || *filter == "."
+                        || path == filter
+                        || path
+                            .strip_prefix(filter)
+                            .is_some_and(|rest| rest.starts_with('/'))
+                })
+        })
+        .collect();
+    kept.into_iter().collect()
+}
+
+/// Pack sorted `paths` into groups of at most `files_per_pass`, keeping files
+/// from the same directory together where they fit.
+fn group_paths(paths: Vec<String>, files_per_pass: usize) -> Vec<Vec<String>> {
+    let files_per_pass = files_per_pass.max(1);
+    let mut by_dir: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for path in &paths {
+        let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        by_dir.entry(dir).or_default().push(path.clone());
+    }
+
+    let mut groups = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for files in by_dir.into_values() {
+        if current.len() + files.len() > files_per_pass && !current.is_empty() {
+            groups.push(std::mem::take(&mut current));
+        }
+        for file in files {
+            if current.len() == files_per_pass {
+                groups.push(std::mem::take(&mut current));
+            }
+            current.push(file);
+        }
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
+
+/// Merge the outputs of the passes, labelled by pass, into one review.
+///
+/// Duplicate findings collapse into the one with the highest priority; the
+/// merged confidence is the chance that at least one pass was right
+/// (`1 - Π(1 - c)`). The patch is incorrect if any pass says so, and the
+/// overall confidence is that of the least confident pass.
+pub(crate) fn merge_review_outputs(outputs: Vec<(String, ReviewOutputEvent)>) -> ReviewOutputEvent {
+    let mut clusters: Vec<Vec<ReviewFinding>> = Vec::new();
+    for finding in outputs
+        .iter()
+        .flat_map(|(_, output)| output.findings.iter().cloned())
+    {
+        match clusters
+            .iter_mut()
+            .find(|cluster| cluster.iter().any(|other| is_duplicate(other, &finding)))
+        {
+            Some(cluster) => cluster.push(finding),
+            None => clusters.push(vec![finding]),
+        }
+    }
+    let mut findings: Vec<ReviewFinding> = clusters.into_iter().filter_map(merge_cluster).collect();
+    findings.sort_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then(b.confidence_score.total_cmp(&a.confidence_score))
+    });
+
+    let overall_correctness = if outputs
+        .iter()
+        .any(|(_, output)| output.overall_correctness == PATCH_IS_INCORRECT)
+    {
+        PATCH_IS_INCORRECT.to_string()
+    } else {
+        outputs
+            .iter()
+            .map(|(_, output)| output.overall_correctness.as_str())
+            .find(|correctness| !correctness.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let overall_explanation = outputs
+        .iter()
+        .filter(|(_, output)| !output.overall_explanation.trim().is_empty())
+        .map(|(label, output)| format!("[{label}] {}", output.overall_explanation.trim()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let overall_confidence_score = outputs
+        .iter()
+        .map(|(_, output)| output.overall_confidence_score)
+        .reduce(f32::min)
+        .unwrap_or_default();
+
+    ReviewOutputEvent {
+        findings,
+        overall_correctness,
+        overall_explanation,
+        overall_confidence_score,
+    }
+}
+
+fn merge_cluster(cluster: Vec<ReviewFinding>) -> Option<ReviewFinding> {
+    let missed = cluster.iter().fold(1.0_f32, |missed, finding| {
+        missed * (1.0 - finding.confidence_score.clamp(0.0, 1.0))
+    });
+    let mut merged = cluster.into_iter().min_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then(b.confidence_score.total_cmp(&a.confidence_score))
+    })?;
+    merged.confidence_score = 1.0 - missed;
+    Some(merged)
+}
+
+fn is_duplicate(a: &ReviewFinding, b: &ReviewFinding) -> bool {
+    let (a_loc, b_loc) = (&a.code_location, &b.code_location);
+    if a_loc.absolute_file_path != b_loc.absolute_file_path {
+        return false;
+    }
+    let near = a_loc.line_range.start <= b_loc.line_range.end.saturating_add(DUPLICATE_LINE_SLACK)
+        && b_loc.line_range.start <= a_loc.line_range.end.saturating_add(DUPLICATE_LINE_SLACK);
+    near && title_similarity(&a.title, &b.title) >= DUPLICATE_TITLE_SIMILARITY
+}
+
+/// Jaccard index of the lowercase words of two titles, ignoring the
+/// `[P0]`-style priority tag.
+fn title_similarity(a: &str, b: &str) -> f32 {
+    let words = |title: &str| -> BTreeSet<String> {
+        title
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .filter(|word| {
+                !(word.len() == 2
+                    && word.starts_with('p')
+                    && word.ends_with(|c: char| c.is_ascii_digit()))
+            })
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ReviewCodeLocation;
+    use codex_protocol::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(
+        title: &str,
+        path: &str,
+        start: u32,
+        confidence: f32,
+        priority: i32,
+    ) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: String::new(),
+            confidence_score: confidence,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from(path),
+                line_range: ReviewLineRange {
+                    start,
+                    end: start + 2,
+                },
+            },
+        }
+    }
+
+    fn output(
+        correctness: &str,
+        confidence: f32,
+        findings: Vec<ReviewFinding>,
+    ) -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings,
+            overall_correctness: correctness.to_string(),
+            overall_explanation: format!("looks {correctness}"),
+            overall_confidence_score: confidence,
+        }
+    }
+
+    #[test]
+    fn groups_paths_by_directory_within_limit() {
+        let paths = vec![
+            "README.md".to_string(),
+            "core/a.rs".to_string(),
+            "core/b.rs".to_string(),
+            "core/c.rs".to_string(),
+            "tui/x.rs".to_string(),
+        ];
+
+        assert_eq!(
+            group_paths(paths, 3),
+            vec![
+                vec!["README.md".to_string()],
+                vec![
+                    "core/a.rs".to_string(),
+                    "core/b.rs".to_string(),
+                    "core/c.rs".to_string(),
+                ],
+                vec!["tui/x.rs".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn single_group_without_lenses_needs_no_passes() {
+        let groups = vec![vec!["src/lib.rs".to_string()]];
+        assert_eq!(build_passes("Review.", groups.clone(), &[]), Vec::new());
+
+        let passes = build_passes("Review.", groups, &[ReviewLens::Security]);
+        let labels: Vec<&str> = passes.iter().map(|pass| pass.label.as_str()).collect();
+        assert_eq!(labels, vec!["general", "security"]);
+        assert_eq!(
+            passes[1].prompt,
+            format!("Review.\n\n{SECURITY_LENS_PROMPT}")
+        );
+    }
+
+    #[test]
+    fn filters_and_reads_patch_paths() {
+        let patch = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\n--- /dev/null\n+++ b/docs/new.md\n";
+        let paths = patch_paths(patch);
+
+        assert_eq!(
+            filter_paths(paths.clone(), &[]),
+            vec!["docs/new.md".to_string(), "src/a.rs".to_string()]
+        );
+        assert_eq!(
+            filter_paths(paths, &["./src/".to_string()]),
+            vec!["src/a.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn merges_duplicate_findings_and_aggregates_confidence() {
+        let merged = merge_review_outputs(vec![
+            (
+                "files 1/2".to_string(),
+                output(
+                    "patch is correct",
+                    0.8,
+                    vec![
+                        finding("[P2] Missing bounds check on index", "/r/a.rs", 10, 0.5, 2),
+                        finding("[P3] Typo in comment", "/r/a.rs", 40, 0.9, 3),
+                    ],
+                ),
+            ),
+            (
+                "security".to_string(),
+                output(
+                    PATCH_IS_INCORRECT,
+                    0.6,
+                    vec![
+                        finding(
+                            "[P1] Missing bounds check on index value",
+                            "/r/a.rs",
+                            12,
+                            0.5,
+                            1,
+                        ),
+                        finding("[P1] Missing bounds check on index", "/r/b.rs", 10, 0.7, 1),
+                    ],
+                ),
+            ),
+        ]);
+
+        let summary: Vec<(&str, i32, f32)> = merged
+            .findings
+            .iter()
+            .map(|finding| {
+                (
+                    finding.title.as_str(),
+                    finding.priority,
+                    finding.confidence_score,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("[P1] Missing bounds check on index value", 1, 0.75),
+                ("[P1] Missing bounds check on index", 1, 0.7),
+                ("[P3] Typo in comment", 3, 0.9),
+            ]
+        );
+        assert_eq!(merged.overall_correctness, PATCH_IS_INCORRECT);
+        assert_eq!(
+            merged.overall_explanation,
+            "[files 1/2] looks patch is correct\n[security] looks patch is incorrect"
+        );
+        assert_eq!(merged.overall_confidence_score, 0.6);
+    }
+}
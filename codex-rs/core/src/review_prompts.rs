@@ -8,6 +8,7 @@ pub struct ResolvedReviewRequest {
     pub target: ReviewTarget,
     pub prompt: String,
     pub user_facing_hint: String,
+    pub paths: Vec<String>,
 }
 
 const UNCOMMITTED_PROMPT: &str = "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.";
@@ -19,23 +20,55 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const RANGE_PROMPT: &str = "Review the code changes between {from} and {to}. Run `git log {from}..{to}` to see the commits involved and `git diff {from} {to}` to inspect the changes. Provide prioritized, actionable findings.";
+
+const PATCH_FILE_PROMPT: &str = "Review the code changes in the patch below, read from {path}. The patch has not been applied to the working tree; read the current sources where you need more context. Provide prioritized, actionable findings.\n\n```diff\n{patch}\n```";
+const PATCH_FILE_PROMPT_LARGE: &str = "Review the code changes in the patch file {path}. It is too large to include here, so read it in parts. The patch has not been applied to the working tree; read the current sources where you need more context. Provide prioritized, actionable findings.";
+
+const PATHS_PROMPT: &str = "Limit the review to changes under these paths: {paths}. Ignore changes to other files; when using git, append `-- {paths}` to narrow the diff.";
+
+/// Patches larger than this are referenced by path instead of inlined in the
+/// prompt.
+const MAX_INLINE_PATCH_BYTES: usize = 200 * 1024;
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
 ) -> anyhow::Result<ResolvedReviewRequest> {
     let target = request.target;
-    let prompt = review_prompt(&target, cwd)?;
+    let paths: Vec<String> = request
+        .paths
+        .iter()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect();
+    let mut prompt = review_prompt(&target, cwd)?;
+    if !paths.is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(&PATHS_PROMPT.replace("{paths}", &paths.join(" ")));
+    }
     let user_facing_hint = request
         .user_facing_hint
-        .unwrap_or_else(|| user_facing_hint(&target));
+        .unwrap_or_else(|| user_facing_hint_with_paths(&target, &paths));
 
     Ok(ResolvedReviewRequest {
         target,
         prompt,
         user_facing_hint,
+        paths,
     })
 }
 
+/// Parse a `FROM..TO` revision range as accepted by [`ReviewTarget::Range`].
+pub fn parse_revision_range(range: &str) -> Option<(String, String)> {
+    let (from, to) = range.trim().split_once("..")?;
+    let (from, to) = (from.trim(), to.trim());
+    if from.is_empty() || to.is_empty() || to.starts_with('.') {
+        return None;
+    }
+    Some((from.to_string(), to.to_string()))
+}
+
 pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String> {
     match target {
         ReviewTarget::UncommittedChanges => Ok(UNCOMMITTED_PROMPT.to_string()),
@@ -57,6 +90,26 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
                 Ok(COMMIT_PROMPT.replace("{sha}", sha))
             }
         }
+        ReviewTarget::Range { from, to } => {
+            Ok(RANGE_PROMPT.replace("{from}", from).replace("{to}", to))
+        }
+        ReviewTarget::PatchFile { path } => {
+            let resolved = cwd.join(path);
+            let patch = std::fs::read_to_string(&resolved).map_err(|err| {
+                anyhow::anyhow!("failed to read patch file {}: {err}", resolved.display())
+            })?;
+            if patch.trim().is_empty() {
+                anyhow::bail!("Patch file {} is empty", resolved.display());
+            }
+            let path = resolved.display().to_string();
+            if patch.len() > MAX_INLINE_PATCH_BYTES {
+                Ok(PATCH_FILE_PROMPT_LARGE.replace("{path}", &path))
+            } else {
+                Ok(PATCH_FILE_PROMPT
+                    .replace("{path}", &path)
+                    .replace("{patch}", patch.trim_end()))
+            }
+        }
         ReviewTarget::Custom { instructions } => {
             let prompt = instructions.trim();
             if prompt.is_empty() {
@@ -79,15 +132,116 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
                 format!("commit {short_sha}")
             }
         }
+        ReviewTarget::Range { from, to } => {
+            format!(
+                "changes in {}..{}",
+                short_revision(from),
+                short_revision(to)
+            )
+        }
+        ReviewTarget::PatchFile { path } => format!("patch {}", path.display()),
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
     }
 }
 
+/// [`user_facing_hint`] followed by the paths the review is restricted to.
+pub fn user_facing_hint_with_paths(target: &ReviewTarget, paths: &[String]) -> String {
+    let hint = user_facing_hint(target);
+    if paths.is_empty() {
+        hint
+    } else {
+        format!("{hint} in {}", paths.join(", "))
+    }
+}
+
+/// Abbreviate full commit hashes the way git does; leave refs alone.
+fn short_revision(revision: &str) -> &str {
+    if revision.len() >= 40 && revision.chars().all(|c| c.is_ascii_hexdigit()) {
+        revision.get(..7).unwrap_or(revision)
+    } else {
+        revision
+    }
+}
+
 impl From<ResolvedReviewRequest> for ReviewRequest {
     fn from(resolved: ResolvedReviewRequest) -> Self {
         ReviewRequest {
             target: resolved.target,
             user_facing_hint: Some(resolved.user_facing_hint),
+            paths: resolved.paths,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_two_dot_revision_ranges_only() {
+        assert_eq!(
+            parse_revision_range("origin/main..HEAD"),
+            Some(("origin/main".to_string(), "HEAD".to_string()))
+        );
+        assert_eq!(parse_revision_range("main...HEAD"), None);
+        assert_eq!(parse_revision_range("..HEAD"), None);
+        assert_eq!(parse_revision_range("HEAD"), None);
+    }
+
+    #[test]
+    fn paths_narrow_prompt_and_hint() {
+        let resolved = resolve_review_request(
+            ReviewRequest {
+                target: ReviewTarget::Range {
+                    from: "0123456789abcdef0123456789abcdef01234567".to_string(),
+                    to: "HEAD".to_string(),
+                },
+                user_facing_hint: None,
+                paths: vec!["core/src".to_string(), "  ".to_string(), "docs".to_string()],
+            },
+            Path::new("/repo"),
+        )
+        .expect("resolve");
+
+        assert_eq!(resolved.paths, vec!["core/src", "docs"]);
+        assert_eq!(
+            resolved.user_facing_hint,
+            "changes in 0123456..HEAD in core/src, docs"
+        );
+        assert!(
+            resolved
+                .prompt
+                .ends_with("append `-- core/src docs` to narrow the diff."),
+            "{}",
+            resolved.prompt
+        );
+    }
+
+    #[test]
+    fn patch_file_is_inlined_relative_to_cwd() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("fix.diff"), "--- a/x\n+++ b/x\n").expect("write");
+
+        let prompt = review_prompt(
+            &ReviewTarget::PatchFile {
+                path: PathBuf::from("fix.diff"),
+            },
+            dir.path(),
+        )
+        .expect("prompt");
+        assert!(
+            prompt.ends_with("```diff\n--- a/x\n+++ b/x\n```"),
+            "{prompt}"
+        );
+
+        let missing = review_prompt(
+            &ReviewTarget::PatchFile {
+                path: PathBuf::from("missing.diff"),
+            },
+            dir.path(),
+        );
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,446 @@
+//! Machine-readable renderings of a [`ReviewOutputEvent`] for CI.
+//!
+//! Paths are reported relative to the repository root passed in, using `/`
+//! separators, so annotations line up with the files in a pull request. Each
+//! finding carries a fingerprint derived from its path, title (without the
+//! `[P1]`-style tag) and body rather than its line numbers, so the same
+//! finding keeps its identity when unrelated edits shift it around between
+//! runs, while two different problems that share a generic title in one file
+//! stay distinct.
+
+use std::path::Path;
+
+use serde_json::Value;
+use serde_json::json;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::protocol::ReviewFinding;
+use crate::protocol::ReviewOutputEvent;
+
+const TOOL_NAME: &str = "codex";
+const TOOL_INFORMATION_URI: &str = "https://github.com/openai/codex";
+const FINGERPRINT_KEY: &str = "codexReviewFinding/v2";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewReportFormat {
+    /// SARIF 2.1.0, as consumed by code-scanning dashboards.
+    Sarif,
+    /// GitHub Actions workflow commands (`::error file=...::message`).
+    Github,
+    /// reviewdog diagnostic format (rdjson).
+    Rdjson,
+    /// JUnit XML with one failing test case per finding.
+    Junit,
+}
+
+/// Severity buckets shared by every format, derived from the finding's
+/// priority (0 = P0 ... 3 = P3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    fn for_finding(finding: &ReviewFinding) -> Self {
+        match finding.priority {
+            i32::MIN..=1 => Severity::Error,
+            2 => Severity::Warning,
+            _ => Severity::Note,
+        }
+    }
+}
+
+/// Render `output` in `format`. `root` is the repository root that finding
+/// paths are made relative to.
+pub fn render_review_report(
+    output: &ReviewOutputEvent,
+    format: ReviewReportFormat,
+    root: &Path,
+) -> String {
+    match format {
+        ReviewReportFormat::Sarif => render_sarif(output, root),
+        ReviewReportFormat::Github => render_github(output, root),
+        ReviewReportFormat::Rdjson => render_rdjson(output, root),
+        ReviewReportFormat::Junit => render_junit(output, root),
+    }
+}
+
+/// Stable identifier for a finding: a hash of its relative path, its
+/// normalized title and its normalized body.
+pub fn finding_fingerprint(finding: &ReviewFinding, root: &Path) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(relative_path(finding, root).as_bytes());
+    hasher.update([0]);
+    hasher.update(normalized_title(&finding.title).as_bytes());
+    hasher.update([0]);
+    hasher.update(normalize_text(&finding.body).as_bytes());
+    let hex = format!("{:x}", hasher.finalize());
+    hex.get(..32).unwrap_or(&hex).to_string()
+}
+
+fn relative_path(finding: &ReviewFinding, root: &Path) -> String {
+    let path = &finding.code_location.absolute_file_path;
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Title without the leading `[P0]`..`[P3]` tag, lowercased, with runs of
+/// whitespace collapsed.
+fn normalized_title(title: &str) -> String {
+    let trimmed = title.trim();
+    let untagged = match trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        Some((tag, rest)) if tag.len() == 2 && tag.starts_with(['P', 'p']) => rest,
+        _ => trimmed,
+    };
+    normalize_text(untagged)
+}
+
+/// Lowercased, with runs of whitespace collapsed to single spaces.
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// SARIF and reviewdog require lines >= 1 and start <= end.
+fn line_range(finding: &ReviewFinding) -> (u32, u32) {
+    let range = &finding.code_location.line_range;
+    let start = range.start.max(1);
+    (start, range.end.max(start))
+}
+
+fn render_sarif(output: &ReviewOutputEvent, root: &Path) -> String {
+    let results: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let (start, end) = line_range(finding);
+            let level = match Severity::for_finding(finding) {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note => "note",
+            };
+            json!({
+                "ruleId": format!("P{}", finding.priority.clamp(0, 3)),
+                "level": level,
+                "message": { "text": format!("{}\n\n{}", finding.title, finding.body) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": relative_path(finding, root) },
+                        "region": { "startLine": start, "endLine": end },
+                    },
+                }],
+                "partialFingerprints": { FINGERPRINT_KEY: finding_fingerprint(finding, root) },
+                "properties": {
+                    "priority": finding.priority,
+                    "confidence": finding.confidence_score,
+                },
+            })
+        })
+        .collect();
+    let rules: Vec<Value> = (0..=3)
+        .map(|priority| {
+            let (name, level) = match priority {
+                0 => ("Blocking issue", "error"),
+                1 => ("Urgent issue", "error"),
+                2 => ("Normal issue", "warning"),
+                _ => ("Low priority issue", "note"),
+            };
+            json!({
+                "id": format!("P{priority}"),
+                "shortDescription": { "text": name },
+                "defaultConfiguration": { "level": level },
+            })
+        })
+        .collect();
+    let sarif = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "informationUri": TOOL_INFORMATION_URI,
+                    "rules": rules,
+                },
+            },
+            "results": results,
+            "properties": {
+                "overallCorrectness": output.overall_correctness,
+                "overallExplanation": output.overall_explanation,
+                "overallConfidence": output.overall_confidence_score,
+            },
+        }],
+    });
+    serde_json::to_string_pretty(&sarif).unwrap_or_default()
+}
+
+fn render_github(output: &ReviewOutputEvent, root: &Path) -> String {
+    let mut lines: Vec<String> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let (start, end) = line_range(finding);
+            let command = match Severity::for_finding(finding) {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note => "notice",
+            };
+            format!(
+                "::{command} file={},line={start},endLine={end},title={}::{}",
+                escape_github_property(&relative_path(finding, root)),
+                escape_github_property(&finding.title),
+                escape_github_data(&finding.body),
+            )
+        })
+        .collect();
+    let explanation = output.overall_explanation.trim();
+    if !explanation.is_empty() {
+        lines.push(format!(
+            "::notice title=Codex review::{}",
+            escape_github_data(explanation)
+        ));
+    }
+    lines.join("\n")
+}
+
+fn escape_github_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_github_property(value: &str) -> String {
+    escape_github_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+fn render_rdjson(output: &ReviewOutputEvent, root: &Path) -> String {
+    let diagnostics: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let (start, end) = line_range(finding);
+            let severity = match Severity::for_finding(finding) {
+                Severity::Error => "ERROR",
+                Severity::Warning => "WARNING",
+                Severity::Note => "INFO",
+            };
+            json!({
+                "message": format!("{}\n\n{}", finding.title, finding.body),
+                "location": {
+                    "path": relative_path(finding, root),
+                    "range": {
+                        "start": { "line": start },
+                        "end": { "line": end },
+                    },
+                },
+                "severity": severity,
+                "code": { "value": finding_fingerprint(finding, root) },
+            })
+        })
+        .collect();
+    let rdjson = json!({
+        "source": { "name": TOOL_NAME, "url": TOOL_INFORMATION_URI },
+        "diagnostics": diagnostics,
+    });
+    serde_json::to_string_pretty(&rdjson).unwrap_or_default()
+}
+
+fn render_junit(output: &ReviewOutputEvent, root: &Path) -> String {
+    let mut cases = Vec::new();
+    for finding in &output.findings {
+        let path = relative_path(finding, root);
+        let (start, end) = line_range(finding);
+        cases.push(format!(
+            "    <testcase classname=\"{}\" name=\"{}\" file=\"{}\">\n      <failure message=\"{}\" type=\"P{}\">{}</failure>\n    </testcase>",
+            escape_xml(&path),
+            escape_xml(&finding.title),
+            escape_xml(&path),
+            escape_xml(&finding.title),
+            finding.priority.clamp(0, 3),
+            escape_xml(&format!("{path}:{start}-{end}\n\n{}", finding.body)),
+        ));
+    }
+    if cases.is_empty() {
+        // Keep the suite non-empty so CI shows the review ran and passed.
+        cases.push("    <testcase classname=\"codex\" name=\"review\"/>".to_string());
+    }
+    let failures = output.findings.len();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites tests=\"{tests}\" failures=\"{failures}\">\n  <testsuite name=\"codex review\" tests=\"{tests}\" failures=\"{failures}\">\n{cases}\n  </testsuite>\n</testsuites>",
+        tests = cases.len(),
+        cases = cases.join("\n"),
+    )
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 forbids most control characters outright.
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, priority: i32, start: u32, end: u32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: "Details, with: punctuation\nand a second line.".to_string(),
+            confidence_score: 0.8,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange { start, end },
+            },
+        }
+    }
+
+    fn output(findings: Vec<ReviewFinding>) -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings,
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One bug.".to_string(),
+            overall_confidence_score: 0.7,
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_lines_priority_tag_and_spacing() {
+        let root = Path::new("/repo");
+        let a = finding("[P1] Off-by-one in  loop", 1, 10, 12);
+        let b = finding("[P2] off-by-one in loop", 2, 40, 41);
+        let c = finding("[P1] Different issue", 1, 10, 12);
+        assert_eq!(finding_fingerprint(&a, root), finding_fingerprint(&b, root));
+        assert_ne!(finding_fingerprint(&a, root), finding_fingerprint(&c, root));
+    }
+
+    #[test]
+    fn fingerprint_separates_findings_sharing_path_and_title() {
+        let root = Path::new("/repo");
+        let unwrap_in_parser = ReviewFinding {
+            body: "`parse_header` unwraps the length field.".to_string(),
+            ..finding("[P1] Possible panic", 1, 10, 12)
+        };
+        let unwrap_in_writer = ReviewFinding {
+            body: "`write_frame` indexes past the buffer end.".to_string(),
+            ..finding("[P1] Possible panic", 1, 80, 82)
+        };
+        let reflowed = ReviewFinding {
+            body: "`parse_header`  unwraps the\nlength field.".to_string(),
+            ..finding("[P2] possible panic", 2, 14, 16)
+        };
+        assert_ne!(
+            finding_fingerprint(&unwrap_in_parser, root),
+            finding_fingerprint(&unwrap_in_writer, root)
+        );
+        assert_eq!(
+            finding_fingerprint(&unwrap_in_parser, root),
+            finding_fingerprint(&reflowed, root)
+        );
+    }
+
+    #[test]
+    fn github_annotations_escape_properties_and_data() {
+        let report = render_review_report(
+            &output(vec![finding("[P1] Bad: thing, here", 1, 3, 5)]),
+            ReviewReportFormat::Github,
+            Path::new("/repo"),
+        );
+        assert_eq!(
+            report,
+            "::error file=src/lib.rs,line=3,endLine=5,title=[P1] Bad%3A thing%2C here::Details, with: punctuation%0Aand a second line.\n::notice title=Codex review::One bug."
+        );
+    }
+
+    #[test]
+    fn sarif_reports_relative_uri_level_and_fingerprint() {
+        let root = Path::new("/repo");
+        let item = finding("[P3] Nit", 3, 0, 0);
+        let report =
+            render_review_report(&output(vec![item.clone()]), ReviewReportFormat::Sarif, root);
+        let sarif: Value = serde_json::from_str(&report).expect("valid json");
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["level"], "note");
+        assert_eq!(result["ruleId"], "P3");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/lib.rs" },
+                "region": { "startLine": 1, "endLine": 1 },
+            })
+        );
+        assert_eq!(
+            result["partialFingerprints"][FINGERPRINT_KEY],
+            finding_fingerprint(&item, root)
+        );
+    }
+
+    #[test]
+    fn rdjson_reports_severity_and_range() {
+        let report = render_review_report(
+            &output(vec![finding("[P2] Slow path", 2, 7, 9)]),
+            ReviewReportFormat::Rdjson,
+            Path::new("/repo"),
+        );
+        let rdjson: Value = serde_json::from_str(&report).expect("valid json");
+        let diagnostic = &rdjson["diagnostics"][0];
+        assert_eq!(diagnostic["severity"], "WARNING");
+        assert_eq!(
+            diagnostic["location"],
+            json!({
+                "path": "src/lib.rs",
+                "range": { "start": { "line": 7 }, "end": { "line": 9 } },
+            })
+        );
+    }
+
+    #[test]
+    fn junit_escapes_xml_and_passes_without_findings() {
+        let report = render_review_report(
+            &output(vec![finding("[P0] <unsafe> & \"quoted\"", 0, 1, 2)]),
+            ReviewReportFormat::Junit,
+            Path::new("/repo"),
+        );
+        assert!(report.contains("failures=\"1\""));
+        assert!(report.contains("name=\"[P0] &lt;unsafe&gt; &amp; &quot;quoted&quot;\""));
+        assert!(report.contains("type=\"P0\">src/lib.rs:1-2\n\nDetails"));
+
+        let empty = render_review_report(
+            &output(Vec::new()),
+            ReviewReportFormat::Junit,
+            Path::new("/repo"),
+        );
+        assert!(empty.contains("tests=\"1\" failures=\"0\""));
+        assert!(empty.contains("<testcase classname=\"codex\" name=\"review\"/>"));
+    }
+}
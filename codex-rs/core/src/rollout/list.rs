@@ -1044,7 +1044,7 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
             RolloutItem::TurnContext(_) => {
                 // Not included in `head`; skip.
             }
-            RolloutItem::Compacted(_) => {
+            RolloutItem::Compacted(_) | RolloutItem::ApprovalDecision(_) => {
                 // Not included in `head`; skip.
             }
             RolloutItem::EventMsg(ev) => {
@@ -1100,7 +1100,8 @@ pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Va
                 }
                 RolloutItem::Compacted(_)
                 | RolloutItem::TurnContext(_)
-                | RolloutItem::EventMsg(_) => {}
+                | RolloutItem::EventMsg(_)
+                | RolloutItem::ApprovalDecision(_) => {}
             }
         }
     }
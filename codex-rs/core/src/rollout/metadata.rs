@@ -64,7 +64,8 @@ pub(crate) fn builder_from_items(
         RolloutItem::ResponseItem(_)
         | RolloutItem::Compacted(_)
         | RolloutItem::TurnContext(_)
-        | RolloutItem::EventMsg(_) => None,
+        | RolloutItem::EventMsg(_)
+        | RolloutItem::ApprovalDecision(_) => None,
     }) && let Some(builder) = builder_from_session_meta(session_meta, rollout_path)
     {
         return Some(builder);
@@ -91,6 +92,17 @@ pub(crate) async fn extract_metadata_from_rollout(
     default_provider: &str,
     otel: Option<&OtelManager>,
 ) -> anyhow::Result<ExtractionOutcome> {
+    extract_metadata_and_items_from_rollout(rollout_path, default_provider, otel)
+        .await
+        .map(|(outcome, _items)| outcome)
+}
+
+/// Like [`extract_metadata_from_rollout`], also returning the parsed items.
+async fn extract_metadata_and_items_from_rollout(
+    rollout_path: &Path,
+    default_provider: &str,
+    otel: Option<&OtelManager>,
+) -> anyhow::Result<(ExtractionOutcome, Vec<RolloutItem>)> {
     let (items, _thread_id, parse_errors) =
         RolloutRecorder::load_rollout_items(rollout_path).await?;
     if items.is_empty() {
@@ -121,10 +133,13 @@ pub(crate) async fn extract_metadata_from_rollout(
             &[("stage", "extract_metadata_from_rollout")],
         );
     }
-    Ok(ExtractionOutcome {
-        metadata,
-        parse_errors,
-    })
+    Ok((
+        ExtractionOutcome {
+            metadata,
+            parse_errors,
+        },
+        items,
+    ))
 }
 
 pub(crate) async fn backfill_sessions(
@@ -202,14 +217,14 @@ pub(crate) async fn backfill_sessions(
     for batch in rollout_paths.chunks(BACKFILL_BATCH_SIZE) {
         for rollout in batch {
             stats.scanned = stats.scanned.saturating_add(1);
-            match extract_metadata_from_rollout(
+            match extract_metadata_and_items_from_rollout(
                 &rollout.path,
                 config.model_provider_id.as_str(),
                 otel,
             )
             .await
             {
-                Ok(outcome) => {
+                Ok((outcome, items)) => {
                     if outcome.parse_errors > 0
                         && let Some(otel) = otel
                     {
@@ -231,6 +246,15 @@ pub(crate) async fn backfill_sessions(
                         warn!("failed to upsert rollout {}: {err}", rollout.path.display());
                     } else {
                         stats.upserted = stats.upserted.saturating_add(1);
+                        if let Err(err) = runtime.replace_thread_search(metadata.id, &items).await {
+                            if let Some(otel) = otel {
+                                otel.counter(DB_ERROR_METRIC, 1, &[("stage", "backfill_search")]);
+                            }
+                            warn!(
+                                "failed to index rollout for search {}: {err}",
+                                rollout.path.display()
+                            );
+                        }
                         if let Ok(meta_line) =
                             rollout::list::read_session_meta_line(&rollout.path).await
                         {
@@ -477,6 +477,9 @@ impl RolloutRecorder {
                     RolloutItem::EventMsg(_ev) => {
                         items.push(RolloutItem::EventMsg(_ev));
                     }
+                    RolloutItem::ApprovalDecision(item) => {
+                        items.push(RolloutItem::ApprovalDecision(item));
+                    }
                 },
                 Err(e) => {
                     trace!("failed to parse rollout line: {e}");
@@ -6,6 +6,10 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+pub mod network_proxy;
+pub mod resource_limits;
+
+use crate::config::types::SandboxResourceLimits;
 use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
@@ -13,6 +17,7 @@ use crate::exec::StdoutStream;
 use crate::exec::execute_exec_env;
 use crate::landlock::create_linux_sandbox_command_args;
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::network_proxy::SandboxNetworkProxy;
 #[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 #[cfg(target_os = "macos")]
@@ -23,6 +28,7 @@ use crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use crate::tools::sandboxing::SandboxablePreference;
 use codex_protocol::config_types::WindowsSandboxLevel;
 pub use codex_protocol::models::SandboxPermissions;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -49,6 +55,7 @@ pub struct ExecEnv {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    pub resource_limits: SandboxResourceLimits,
 }
 
 /// Bundled arguments for sandbox transformation.
@@ -59,6 +66,13 @@ pub(crate) struct SandboxTransformRequest<'a> {
     pub policy: &'a SandboxPolicy,
     pub sandbox: SandboxType,
     pub sandbox_policy_cwd: &'a Path,
+    /// Paths hidden from the sandboxed command even though the rest of the
+    /// filesystem remains readable.
+    pub denied_read_paths: &'a [AbsolutePathBuf],
+    pub resource_limits: SandboxResourceLimits,
+    /// When set, commands without network access reach the network only
+    /// through these proxy listeners (Linux sandbox only).
+    pub network_proxy: Option<SandboxNetworkProxy>,
     pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: WindowsSandboxLevel,
@@ -124,12 +138,23 @@ impl SandboxManager {
             policy,
             sandbox,
             sandbox_policy_cwd,
+            denied_read_paths,
+            resource_limits,
+            network_proxy,
             codex_linux_sandbox_exe,
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
         } = request;
         let mut env = spec.env;
-        if !policy.has_full_network_access() {
+        // Only bubblewrap's network namespace can confine a command to the
+        // proxy, so the route is dropped (and the network stays disabled)
+        // everywhere else.
+        let network_proxy = network_proxy.filter(|_| {
+            sandbox == SandboxType::LinuxSeccomp
+                && use_linux_sandbox_bwrap
+                && !policy.has_full_network_access()
+        });
+        if !policy.has_full_network_access() && network_proxy.is_none() {
             env.insert(
                 CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR.to_string(),
                 "1".to_string(),
@@ -146,8 +171,12 @@ impl SandboxManager {
             SandboxType::MacosSeatbelt => {
                 let mut seatbelt_env = HashMap::new();
                 seatbelt_env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
-                let mut args =
-                    create_seatbelt_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_seatbelt_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    denied_read_paths,
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(MACOS_PATH_TO_SEATBELT_EXECUTABLE.to_string());
                 full_command.append(&mut args);
@@ -162,14 +191,20 @@ impl SandboxManager {
                     command.clone(),
                     policy,
                     sandbox_policy_cwd,
+                    denied_read_paths,
+                    network_proxy.as_ref(),
                     use_linux_sandbox_bwrap,
                 );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
                 full_command.append(&mut args);
+                let sandbox_env = network_proxy
+                    .as_ref()
+                    .map(SandboxNetworkProxy::env_vars)
+                    .unwrap_or_default();
                 (
                     full_command,
-                    HashMap::new(),
+                    sandbox_env,
                     Some("codex-linux-sandbox".to_string()),
                 )
             }
@@ -195,6 +230,7 @@ impl SandboxManager {
             sandbox_permissions: spec.sandbox_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            resource_limits,
         })
     }
 
@@ -0,0 +1,227 @@
+//! Routing of sandboxed network traffic through `codex-network-proxy`.
+//!
+//! When the `[network]` table enables the proxy, sandboxed commands that
+//! would otherwise have no network access instead run in a network namespace
+//! (Linux only) whose sole egress is the proxy's listeners. The Linux sandbox
+//! helper mirrors each listener on the namespace's loopback interface, and the
+//! command is pointed at those mirrors through the usual proxy environment
+//! variables.
+
+use crate::config::types::NetworkProxyToml;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use url::Url;
+
+const DEFAULT_HTTP_PROXY_PORT: u16 = 3128;
+const DEFAULT_SOCKS_PROXY_PORT: u16 = 8081;
+const DEFAULT_ADMIN_PORT: u16 = 8080;
+
+/// Proxy environment variables understood by most HTTP clients. Both
+/// spellings are set because tools disagree on which one they read.
+const HTTP_PROXY_ENV_VARS: [&str; 4] = ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"];
+const ALL_PROXY_ENV_VARS: [&str; 2] = ["ALL_PROXY", "all_proxy"];
+const NO_PROXY_ENV_VARS: [&str; 2] = ["NO_PROXY", "no_proxy"];
+
+/// Set (to the HTTP proxy URL) for commands whose network access is routed
+/// through the proxy. Its presence keeps
+/// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` from being set for them.
+pub const CODEX_SANDBOX_NETWORK_PROXY_ENV_VAR: &str = "CODEX_SANDBOX_NETWORK_PROXY";
+
+/// Resolved proxy listeners that sandboxed commands are routed through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxNetworkProxy {
+    /// The proxy's HTTP listener on the host.
+    pub http_addr: SocketAddr,
+    /// The proxy's SOCKS5 listener on the host, when enabled.
+    pub socks_addr: Option<SocketAddr>,
+    /// The proxy's admin API on the host. Only Codex talks to it; it is not
+    /// reachable from inside the sandbox.
+    pub admin_addr: SocketAddr,
+}
+
+impl SandboxNetworkProxy {
+    /// Resolves the `[network]` table. Returns `Ok(None)` unless the proxy is
+    /// enabled.
+    pub fn from_toml(toml: &NetworkProxyToml) -> std::io::Result<Option<Self>> {
+        if !toml.enabled.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let http_addr = parse_proxy_addr(
+            toml.proxy_url.as_deref(),
+            DEFAULT_HTTP_PROXY_PORT,
+            "network.proxy_url",
+        )?;
+        let socks_addr = if toml.enable_socks5.unwrap_or(false) {
+            Some(parse_proxy_addr(
+                toml.socks_url.as_deref(),
+                DEFAULT_SOCKS_PROXY_PORT,
+                "network.socks_url",
+            )?)
+        } else {
+            None
+        };
+
+        let admin_addr = parse_proxy_addr(
+            toml.admin_url.as_deref(),
+            DEFAULT_ADMIN_PORT,
+            "network.admin_url",
+        )?;
+
+        Ok(Some(Self {
+            http_addr,
+            socks_addr,
+            admin_addr,
+        }))
+    }
+
+    /// Host-side listeners the sandbox helper must relay to, HTTP first.
+    pub fn endpoints(&self) -> Vec<SocketAddr> {
+        std::iter::once(self.http_addr)
+            .chain(self.socks_addr)
+            .collect()
+    }
+
+    /// Environment variables pointing the sandboxed command at the loopback
+    /// mirrors of the proxy listeners inside its network namespace.
+    pub fn env_vars(&self) -> HashMap<String, String> {
+        let http_url = format!("http://{}", namespace_addr(self.http_addr));
+        let all_url = match self.socks_addr {
+            Some(addr) => format!("socks5h://{}", namespace_addr(addr)),
+            None => http_url.clone(),
+        };
+
+        let mut env = HashMap::new();
+        for key in HTTP_PROXY_ENV_VARS {
+            env.insert(key.to_string(), http_url.clone());
+        }
+        for key in ALL_PROXY_ENV_VARS {
+            env.insert(key.to_string(), all_url.clone());
+        }
+        for key in NO_PROXY_ENV_VARS {
+            env.insert(key.to_string(), "localhost,127.0.0.1,::1".to_string());
+        }
+        env.insert(CODEX_SANDBOX_NETWORK_PROXY_ENV_VAR.to_string(), http_url);
+        env
+    }
+}
+
+/// The address a proxy listener is mirrored on inside the sandbox's network
+/// namespace: the same port on IPv4 loopback.
+pub fn namespace_addr(endpoint: SocketAddr) -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), endpoint.port())
+}
+
+fn parse_proxy_addr(
+    value: Option<&str>,
+    default_port: u16,
+    key: &str,
+) -> std::io::Result<SocketAddr> {
+    let invalid = |reason: &str| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid {key}: {reason}"),
+        )
+    };
+
+    let value = value.map(str::trim).unwrap_or_default();
+    if value.is_empty() {
+        return Ok(SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::LOCALHOST),
+            default_port,
+        ));
+    }
+
+    let with_scheme = if value.contains("://") {
+        value.to_string()
+    } else {
+        format!("http://{value}")
+    };
+    let url = Url::parse(&with_scheme).map_err(|err| invalid(&format!("{value}: {err}")))?;
+    let port = url.port().unwrap_or(default_port);
+    let ip = match url.host() {
+        Some(url::Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(url::Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(url::Host::Domain(domain)) if domain.eq_ignore_ascii_case("localhost") => {
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        }
+        Some(url::Host::Domain(_)) | None => {
+            return Err(invalid(&format!(
+                "{value}: expected an IP address or localhost"
+            )));
+        }
+    };
+    Ok(SocketAddr::new(ip, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn disabled_proxy_resolves_to_none() {
+        let toml = NetworkProxyToml {
+            proxy_url: Some("http://127.0.0.1:9999".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            SandboxNetworkProxy::from_toml(&toml).expect("resolve"),
+            None
+        );
+    }
+
+    #[test]
+    fn enabled_proxy_uses_defaults_and_optional_socks() {
+        let toml = NetworkProxyToml {
+            enabled: Some(true),
+            enable_socks5: Some(true),
+            socks_url: Some("localhost:1080".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            SandboxNetworkProxy::from_toml(&toml).expect("resolve"),
+            Some(SandboxNetworkProxy {
+                http_addr: "127.0.0.1:3128".parse().expect("addr"),
+                socks_addr: Some("127.0.0.1:1080".parse().expect("addr")),
+                admin_addr: "127.0.0.1:8080".parse().expect("addr"),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_hostnames_other_than_localhost() {
+        let toml = NetworkProxyToml {
+            enabled: Some(true),
+            proxy_url: Some("http://proxy.example.com:3128".to_string()),
+            ..Default::default()
+        };
+        let err = SandboxNetworkProxy::from_toml(&toml).expect_err("hostname is rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn env_vars_point_at_namespace_loopback() {
+        let proxy = SandboxNetworkProxy {
+            http_addr: "[::1]:3128".parse().expect("addr"),
+            socks_addr: Some("127.0.0.1:8081".parse().expect("addr")),
+            admin_addr: "127.0.0.1:8080".parse().expect("addr"),
+        };
+        let env = proxy.env_vars();
+        assert_eq!(
+            env.get("HTTPS_PROXY").map(String::as_str),
+            Some("http://127.0.0.1:3128")
+        );
+        assert_eq!(
+            env.get("all_proxy").map(String::as_str),
+            Some("socks5h://127.0.0.1:8081")
+        );
+        assert_eq!(
+            env.get(CODEX_SANDBOX_NETWORK_PROXY_ENV_VAR)
+                .map(String::as_str),
+            Some("http://127.0.0.1:3128")
+        );
+    }
+}
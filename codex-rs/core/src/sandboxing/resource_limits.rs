@@ -0,0 +1,645 @@
+//! CPU time, memory, process count and file size limits for commands run on
+//! the model's behalf.
+//!
+//! On Linux, memory and process limits are enforced with a dedicated cgroup v2
+//! leaf when Codex runs in a cgroup subtree it is allowed to manage. The
+//! cgroup also lets us tell afterwards whether a limit was actually hit.
+//! Without one those two limits are not enforced: `RLIMIT_NPROC` counts every
+//! process the user owns and `RLIMIT_AS` breaks runtimes that reserve far more
+//! address space than they use, so neither is a usable substitute. CPU time and
+//! file size are always applied with `setrlimit(2)`, which the sandbox helper,
+//! `bwrap` and the command inherit.
+
+use std::fmt;
+#[cfg(unix)]
+use std::io;
+use std::sync::Once;
+
+use crate::config::types::SandboxResourceLimits;
+#[cfg(unix)]
+use crate::exec::EXIT_CODE_SIGNAL_BASE;
+
+#[cfg(unix)]
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+/// Extra CPU seconds between `SIGXCPU` and the `SIGKILL` sent by the kernel
+/// when a command ignores the soft limit.
+#[cfg(unix)]
+const CPU_TIME_HARD_LIMIT_GRACE_SECS: u64 = 1;
+
+/// Units of the shell's `ulimit -f`.
+#[cfg(unix)]
+const ULIMIT_FILE_SIZE_BLOCK_BYTES: u64 = 512;
+
+/// `argv[0]` of the shell that applies limits before exec'ing a command.
+#[cfg(unix)]
+const LAUNCHER_ARG0: &str = "codex-resource-limits";
+
+static UNENFORCED_LIMITS_WARNING: Once = Once::new();
+
+/// The resource whose limit a command ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimitKind {
+    CpuTime,
+    Memory,
+    Processes,
+    FileSize,
+}
+
+impl fmt::Display for ResourceLimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ResourceLimitKind::CpuTime => "CPU time",
+            ResourceLimitKind::Memory => "memory",
+            ResourceLimitKind::Processes => "process count",
+            ResourceLimitKind::FileSize => "file size",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Limits prepared for a single command spawn.
+///
+/// The scope must outlive the child process: on Linux it owns the cgroup the
+/// command runs in, which is removed again when the scope is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct ResourceLimitScope {
+    limits: SandboxResourceLimits,
+    #[cfg(target_os = "linux")]
+    cgroup: Option<cgroup::CommandCgroup>,
+}
+
+impl ResourceLimitScope {
+    pub(crate) fn new(limits: SandboxResourceLimits) -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            let memory_bytes = limits.memory_mb.map(mib_to_bytes);
+            let cgroup = if memory_bytes.is_some() || limits.max_processes.is_some() {
+                cgroup::CommandCgroup::create(memory_bytes, limits.max_processes)
+                    .inspect_err(|err| {
+                        tracing::debug!("cgroup resource limits unavailable: {err}");
+                    })
+                    .ok()
+            } else {
+                None
+            };
+            let scope = Self { limits, cgroup };
+            scope.warn_about_unenforced_limits();
+            scope
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let scope = Self { limits };
+            scope.warn_about_unenforced_limits();
+            scope
+        }
+    }
+
+    /// Warns (once per process) when `memory_mb` or `max_processes` is set but
+    /// no cgroup is available to enforce it.
+    fn warn_about_unenforced_limits(&self) {
+        let mut unenforced = Vec::new();
+        if self.limits.memory_mb.is_some() && !self.cgroup_enforces(ResourceLimitKind::Memory) {
+            unenforced.push("memory_mb");
+        }
+        if self.limits.max_processes.is_some()
+            && !self.cgroup_enforces(ResourceLimitKind::Processes)
+        {
+            unenforced.push("max_processes");
+        }
+        if unenforced.is_empty() {
+            return;
+        }
+        UNENFORCED_LIMITS_WARNING.call_once(|| {
+            tracing::warn!(
+                "sandbox_resource_limits: {} not enforced because no delegated cgroup v2 subtree is available",
+                unenforced.join(" and ")
+            );
+        });
+    }
+
+    /// Returns a hook for `Command::pre_exec` that applies the limits to the
+    /// child before it execs.
+    ///
+    /// Everything the hook needs is computed up front so that it only makes
+    /// async-signal-safe system calls after `fork(2)`.
+    #[cfg(unix)]
+    pub(crate) fn pre_exec_hook(&self) -> impl FnMut() -> io::Result<()> + Send + Sync + 'static {
+        let rlimits = self.rlimits();
+        #[cfg(target_os = "linux")]
+        let cgroup_procs_fd = self.cgroup.as_ref().map(cgroup::CommandCgroup::procs_fd);
+        move || {
+            #[cfg(target_os = "linux")]
+            if let Some(fd) = cgroup_procs_fd {
+                cgroup::join_from_pre_exec(fd)?;
+            }
+            for &(resource, soft, hard) in &rlimits {
+                set_rlimit(resource, soft, hard)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Wraps `command` in a `/bin/sh` launcher that applies the limits and
+    /// then execs it, for spawners (such as PTYs) that do not expose a
+    /// `pre_exec` hook. The command is exec'd by name, so `command[0]` may be
+    /// an `arg0` alias found on `PATH`. Returns `None` when there is nothing to
+    /// apply.
+    #[cfg(unix)]
+    pub(crate) fn launcher_command(&self, command: &[String]) -> Option<Vec<String>> {
+        let mut steps = Vec::new();
+        #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
+        let mut launcher_args = Vec::new();
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &self.cgroup {
+            steps.push("echo $$ > \"$1\" && shift".to_string());
+            launcher_args.push(cgroup.procs_path().to_string_lossy().into_owned());
+        }
+        for (resource, soft, hard) in self.rlimits() {
+            let (soft, hard) = clamp_to_hard_limit(resource, soft, hard);
+            let (flag, soft, hard) = if resource == libc::RLIMIT_FSIZE {
+                let block = ULIMIT_FILE_SIZE_BLOCK_BYTES as libc::rlim_t;
+                ('f', soft / block, hard / block)
+            } else {
+                ('t', soft, hard)
+            };
+            steps.push(format!(
+                "ulimit -H -{flag} {hard} && ulimit -S -{flag} {soft}"
+            ));
+        }
+        if steps.is_empty() {
+            return None;
+        }
+        steps.push("exec \"$@\"".to_string());
+
+        let mut launcher = vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            steps.join(" && "),
+            LAUNCHER_ARG0.to_string(),
+        ];
+        launcher.extend(launcher_args);
+        launcher.extend(command.iter().cloned());
+        Some(launcher)
+    }
+
+    /// Reports which limit, if any, made a command fail. `exit_code` follows
+    /// the shell convention of `128 + signal` for commands killed by a signal.
+    pub(crate) fn exceeded_limit(&self, exit_code: i32) -> Option<ResourceLimitKind> {
+        if exit_code == 0 || self.limits.is_empty() {
+            return None;
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(kind) = self
+            .cgroup
+            .as_ref()
+            .and_then(cgroup::CommandCgroup::exceeded_limit)
+        {
+            return Some(kind);
+        }
+
+        #[cfg(unix)]
+        {
+            let signal = exit_code - EXIT_CODE_SIGNAL_BASE;
+            if signal == libc::SIGXCPU && self.limits.cpu_time_sec.is_some() {
+                return Some(ResourceLimitKind::CpuTime);
+            }
+            if signal == libc::SIGXFSZ && self.limits.max_file_size_mb.is_some() {
+                return Some(ResourceLimitKind::FileSize);
+            }
+        }
+
+        None
+    }
+
+    /// `(resource, soft, hard)` triples to install with `setrlimit(2)`.
+    #[cfg(unix)]
+    fn rlimits(&self) -> Vec<(RlimitResource, libc::rlim_t, libc::rlim_t)> {
+        let mut rlimits = Vec::new();
+        if let Some(secs) = self.limits.cpu_time_sec {
+            rlimits.push((
+                libc::RLIMIT_CPU,
+                secs as libc::rlim_t,
+                secs.saturating_add(CPU_TIME_HARD_LIMIT_GRACE_SECS) as libc::rlim_t,
+            ));
+        }
+        if let Some(mib) = self.limits.max_file_size_mb {
+            let bytes = mib_to_bytes(mib) as libc::rlim_t;
+            rlimits.push((libc::RLIMIT_FSIZE, bytes, bytes));
+        }
+        rlimits
+    }
+
+    fn cgroup_enforces(&self, kind: ResourceLimitKind) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.cgroup
+                .as_ref()
+                .is_some_and(|cgroup| cgroup.enforces(kind))
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = kind;
+            false
+        }
+    }
+}
+
+#[cfg(unix)]
+fn mib_to_bytes(mib: u64) -> u64 {
+    mib.saturating_mul(BYTES_PER_MIB)
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+/// Lowers the limit for `resource`, never raising it above the hard limit the
+/// process already has (which would fail with `EPERM`).
+#[cfg(unix)]
+fn set_rlimit(resource: RlimitResource, soft: libc::rlim_t, hard: libc::rlim_t) -> io::Result<()> {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let hard = hard.min(current.rlim_max);
+    let rlim = libc::rlimit {
+        rlim_cur: soft.min(hard),
+        rlim_max: hard,
+    };
+    if unsafe { libc::setrlimit(resource, &rlim) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// `(soft, hard)` lowered to the hard limit Codex itself runs with, which the
+/// launched command inherits and cannot raise.
+#[cfg(unix)]
+fn clamp_to_hard_limit(
+    resource: RlimitResource,
+    soft: libc::rlim_t,
+    hard: libc::rlim_t,
+) -> (libc::rlim_t, libc::rlim_t) {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    let hard = if unsafe { libc::getrlimit(resource, &mut current) } == 0 {
+        hard.min(current.rlim_max)
+    } else {
+        hard
+    };
+    (soft.min(hard), hard)
+}
+
+#[cfg(target_os = "linux")]
+mod cgroup {
+    use std::fs;
+    use std::fs::File;
+    use std::fs::OpenOptions;
+    use std::io;
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    use std::os::fd::RawFd;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+
+    use super::ResourceLimitKind;
+
+    const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+    static NEXT_CGROUP_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// A cgroup v2 leaf created for one command, removed on drop.
+    #[derive(Debug)]
+    pub(super) struct CommandCgroup {
+        path: PathBuf,
+        procs: Option<File>,
+        memory: bool,
+        pids: bool,
+    }
+
+    impl CommandCgroup {
+        /// Creates a leaf below the cgroup Codex itself runs in. Fails when
+        /// cgroup v2 is not mounted, the subtree is not delegated to us, or
+        /// neither the memory nor the pids controller can be enabled.
+        pub(super) fn create(
+            memory_bytes: Option<u64>,
+            max_processes: Option<u64>,
+        ) -> io::Result<Self> {
+            let parent = current_cgroup_dir()?;
+            let mut controllers = Vec::new();
+            if memory_bytes.is_some() {
+                controllers.push("memory");
+            }
+            if max_processes.is_some() {
+                controllers.push("pids");
+            }
+            enable_controllers(&parent, &controllers);
+
+            let path = parent.join(format!(
+                "codex-exec-{}-{}",
+                std::process::id(),
+                NEXT_CGROUP_ID.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir(&path)?;
+            let mut cgroup = Self {
+                path,
+                procs: None,
+                memory: false,
+                pids: false,
+            };
+            if let Some(bytes) = memory_bytes {
+                cgroup.memory = write_control_file(&cgroup.path.join("memory.max"), bytes).is_ok();
+                if cgroup.memory {
+                    // Without this the command could keep going by swapping
+                    // instead of hitting the limit.
+                    let _ = write_control_file(&cgroup.path.join("memory.swap.max"), 0);
+                }
+            }
+            if let Some(max_processes) = max_processes {
+                cgroup.pids =
+                    write_control_file(&cgroup.path.join("pids.max"), max_processes).is_ok();
+            }
+            if !cgroup.memory && !cgroup.pids {
+                return Err(io::Error::other(format!(
+                    "memory and pids controllers are not available in {}",
+                    cgroup.path.display()
+                )));
+            }
+            cgroup.procs = Some(
+                OpenOptions::new()
+                    .write(true)
+                    .open(cgroup.path.join("cgroup.procs"))?,
+            );
+            Ok(cgroup)
+        }
+
+        pub(super) fn enforces(&self, kind: ResourceLimitKind) -> bool {
+            match kind {
+                ResourceLimitKind::Memory => self.memory,
+                ResourceLimitKind::Processes => self.pids,
+                ResourceLimitKind::CpuTime | ResourceLimitKind::FileSize => false,
+            }
+        }
+
+        pub(super) fn procs_fd(&self) -> RawFd {
+            self.procs.as_ref().map_or(-1, AsRawFd::as_raw_fd)
+        }
+
+        pub(super) fn procs_path(&self) -> PathBuf {
+            self.path.join("cgroup.procs")
+        }
+
+        pub(super) fn exceeded_limit(&self) -> Option<ResourceLimitKind> {
+            if self.memory && event_count(&self.path.join("memory.events"), "oom_kill") > 0 {
+                return Some(ResourceLimitKind::Memory);
+            }
+            if self.pids && event_count(&self.path.join("pids.events"), "max") > 0 {
+                return Some(ResourceLimitKind::Processes);
+            }
+            None
+        }
+    }
+
+    impl Drop for CommandCgroup {
+        fn drop(&mut self) {
+            self.procs = None;
+            // Fails while detached descendants are still running; the kernel
+            // keeps the cgroup until they exit.
+            if let Err(err) = fs::remove_dir(&self.path) {
+                tracing::debug!("failed to remove cgroup {}: {err}", self.path.display());
+            }
+        }
+    }
+
+    /// Moves the calling process into the cgroup whose `cgroup.procs` is open
+    /// as `fd`. Runs between `fork(2)` and `exec(2)`, so it formats the pid
+    /// without allocating.
+    pub(super) fn join_from_pre_exec(fd: RawFd) -> io::Result<()> {
+        let mut pid = unsafe { libc::getpid() } as u32;
+        let mut buf = [0u8; 10];
+        let mut start = buf.len();
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (pid % 10) as u8;
+            pid /= 10;
+            if pid == 0 {
+                break;
+            }
+        }
+        let digits = &buf[start..];
+        let written = unsafe { libc::write(fd, digits.as_ptr().cast(), digits.len()) };
+        if written < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn current_cgroup_dir() -> io::Result<PathBuf> {
+        let contents = fs::read_to_string("/proc/self/cgroup")?;
+        // The unified (v2) hierarchy is the entry with hierarchy id 0.
+        let relative = contents
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .ok_or_else(|| io::Error::other("cgroup v2 hierarchy not found"))?;
+        Ok(Path::new(CGROUP_ROOT).join(relative.trim_start_matches('/')))
+    }
+
+    fn enable_controllers(parent: &Path, controllers: &[&str]) {
+        let subtree_control = parent.join("cgroup.subtree_control");
+        let enabled = fs::read_to_string(&subtree_control).unwrap_or_default();
+        for controller in controllers {
+            if enabled.split_whitespace().any(|name| name == *controller) {
+                continue;
+            }
+            // Commonly fails with EBUSY when the parent also holds processes
+            // (including Codex itself); the leaf then lacks the control file.
+            let _ = OpenOptions::new()
+                .write(true)
+                .open(&subtree_control)
+                .and_then(|mut file| file.write_all(format!("+{controller}").as_bytes()));
+        }
+    }
+
+    fn write_control_file(path: &Path, value: u64) -> io::Result<()> {
+        OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .write_all(value.to_string().as_bytes())
+    }
+
+    fn event_count(path: &Path, key: &str) -> u64 {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| parse_event_count(&contents, key))
+            .unwrap_or(0)
+    }
+
+    pub(super) fn parse_event_count(contents: &str, key: &str) -> Option<u64> {
+        contents.lines().find_map(|line| {
+            let (name, count) = line.split_once(' ')?;
+            if name == key {
+                count.trim().parse().ok()
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn scope(limits: SandboxResourceLimits) -> ResourceLimitScope {
+        ResourceLimitScope {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unlimited_scope_never_reports_a_limit() {
+        let scope = scope(SandboxResourceLimits::default());
+        assert_eq!(scope.exceeded_limit(128 + 24), None);
+        assert_eq!(scope.exceeded_limit(1), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_exit_codes_map_to_configured_limits() {
+        let scope = scope(SandboxResourceLimits {
+            cpu_time_sec: Some(10),
+            max_file_size_mb: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(
+            scope.exceeded_limit(128 + libc::SIGXCPU),
+            Some(ResourceLimitKind::CpuTime)
+        );
+        assert_eq!(
+            scope.exceeded_limit(128 + libc::SIGXFSZ),
+            Some(ResourceLimitKind::FileSize)
+        );
+        assert_eq!(scope.exceeded_limit(128 + libc::SIGKILL), None);
+        assert_eq!(scope.exceeded_limit(0), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_exit_codes_ignored_without_matching_limit() {
+        let scope = scope(SandboxResourceLimits {
+            memory_mb: Some(512),
+            ..Default::default()
+        });
+        assert_eq!(scope.exceeded_limit(128 + libc::SIGXCPU), None);
+        assert_eq!(scope.exceeded_limit(128 + libc::SIGXFSZ), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn memory_and_process_limits_never_become_rlimits() {
+        let scope = scope(SandboxResourceLimits {
+            cpu_time_sec: Some(30),
+            memory_mb: Some(2),
+            max_processes: Some(64),
+            max_file_size_mb: Some(1),
+        });
+        let mib = BYTES_PER_MIB as libc::rlim_t;
+        assert_eq!(
+            scope.rlimits(),
+            vec![(libc::RLIMIT_CPU, 30, 31), (libc::RLIMIT_FSIZE, mib, mib)]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launcher_applies_limits_before_exec() {
+        let command = vec!["bash".to_string(), "-lc".to_string(), "make".to_string()];
+        assert_eq!(
+            scope(SandboxResourceLimits {
+                memory_mb: Some(512),
+                ..Default::default()
+            })
+            .launcher_command(&command),
+            None
+        );
+
+        let launcher = scope(SandboxResourceLimits {
+            cpu_time_sec: Some(30),
+            max_file_size_mb: Some(1),
+            ..Default::default()
+        })
+        .launcher_command(&command)
+        .expect("limits to apply");
+        let (soft_cpu, hard_cpu) = clamp_to_hard_limit(libc::RLIMIT_CPU, 30, 31);
+        let (soft_fsize, hard_fsize) = clamp_to_hard_limit(
+            libc::RLIMIT_FSIZE,
+            BYTES_PER_MIB as libc::rlim_t,
+            BYTES_PER_MIB as libc::rlim_t,
+        );
+        let block = ULIMIT_FILE_SIZE_BLOCK_BYTES as libc::rlim_t;
+        assert_eq!(
+            launcher,
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                format!(
+                    "ulimit -H -t {hard_cpu} && ulimit -S -t {soft_cpu} && ulimit -H -f {} && ulimit -S -f {} && exec \"$@\"",
+                    hard_fsize / block,
+                    soft_fsize / block
+                ),
+                LAUNCHER_ARG0.to_string(),
+                "bash".to_string(),
+                "-lc".to_string(),
+                "make".to_string(),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launched_command_runs_with_limits() {
+        let launcher = scope(SandboxResourceLimits {
+            cpu_time_sec: Some(30),
+            ..Default::default()
+        })
+        .launcher_command(&[
+            "sh".to_string(),
+            "-c".to_string(),
+            "ulimit -S -t".to_string(),
+        ])
+        .expect("limits to apply");
+        let output = std::process::Command::new(&launcher[0])
+            .args(&launcher[1..])
+            .output()
+            .expect("launcher to run");
+        let (soft, _) = clamp_to_hard_limit(libc::RLIMIT_CPU, 30, 31);
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            soft.to_string()
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_cgroup_event_counts() {
+        let memory_events = "low 0\nhigh 0\nmax 3\noom 1\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(
+            cgroup::parse_event_count(memory_events, "oom_kill"),
+            Some(1)
+        );
+        assert_eq!(cgroup::parse_event_count(memory_events, "max"), Some(3));
+        assert_eq!(cgroup::parse_event_count("max 0\n", "oom_kill"), None);
+    }
+}
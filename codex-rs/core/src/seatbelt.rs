@@ -7,9 +7,11 @@ use std::path::PathBuf;
 use tokio::process::Child;
 
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::resource_limits::ResourceLimitScope;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
+use codex_utils_absolute_path::AbsolutePathBuf;
 
 const MACOS_SEATBELT_BASE_POLICY: &str = include_str!("seatbelt_base_policy.sbpl");
 const MACOS_SEATBELT_NETWORK_POLICY: &str = include_str!("seatbelt_network_policy.sbpl");
@@ -25,10 +27,16 @@ pub async fn spawn_command_under_seatbelt(
     command_cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    denied_read_paths: &[AbsolutePathBuf],
     stdio_policy: StdioPolicy,
     mut env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
-    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_seatbelt_command_args(
+        command,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        denied_read_paths,
+    );
     let arg0 = None;
     env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
     spawn_child_async(
@@ -39,6 +47,7 @@ pub async fn spawn_command_under_seatbelt(
         sandbox_policy,
         stdio_policy,
         env,
+        &ResourceLimitScope::default(),
     )
     .await
 }
@@ -47,6 +56,7 @@ pub(crate) fn create_seatbelt_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    denied_read_paths: &[AbsolutePathBuf],
 ) -> Vec<String> {
     let (file_write_policy, file_write_dir_params) = {
         if sandbox_policy.has_full_disk_write_access() {
@@ -111,6 +121,10 @@ pub(crate) fn create_seatbelt_command_args(
         ""
     };
 
+    // Later rules take precedence in Seatbelt, so these denials carve the
+    // credential paths back out of the blanket read (and write) grants above.
+    let (file_deny_policy, file_deny_params) = create_denied_read_policy(denied_read_paths);
+
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
         MACOS_SEATBELT_NETWORK_POLICY
@@ -118,11 +132,15 @@ pub(crate) fn create_seatbelt_command_args(
         ""
     };
 
-    let full_policy = format!(
+    let mut full_policy = format!(
         "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
     );
+    if !file_deny_policy.is_empty() {
+        full_policy.push_str(&file_deny_policy);
+        full_policy.push('\n');
+    }
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [file_write_dir_params, file_deny_params, macos_dir_params()].concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -134,6 +152,33 @@ pub(crate) fn create_seatbelt_command_args(
     seatbelt_args
 }
 
+/// Builds `(deny file-read* file-write* ...)` rules for the paths that must stay
+/// hidden from the sandboxed command. Paths that do not exist are skipped so
+/// that the policy only references real locations.
+fn create_denied_read_policy(
+    denied_read_paths: &[AbsolutePathBuf],
+) -> (String, Vec<(String, PathBuf)>) {
+    let mut denied_policies: Vec<String> = Vec::new();
+    let mut denied_params: Vec<(String, PathBuf)> = Vec::new();
+    for path in denied_read_paths {
+        let Ok(canonical_path) = path.as_path().canonicalize() else {
+            continue;
+        };
+        let param = format!("DENIED_READ_PATH_{}", denied_params.len());
+        denied_policies.push(format!("(subpath (param \"{param}\"))"));
+        denied_params.push((param, canonical_path));
+    }
+
+    if denied_policies.is_empty() {
+        return (String::new(), Vec::new());
+    }
+    let policy = format!(
+        "; hide credential paths from the sandboxed command\n(deny file-read* file-write*\n{}\n)",
+        denied_policies.join(" ")
+    );
+    (policy, denied_params)
+}
+
 /// Wraps libc::confstr to return a String.
 fn confstr(name: libc::c_int) -> Option<String> {
     let mut buf = vec![0_i8; (libc::PATH_MAX as usize) + 1];
@@ -227,7 +272,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd);
+        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd, &[]);
 
         // Build the expected policy text using a raw string for readability.
         // Note that the policy includes:
@@ -315,7 +360,8 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let write_hooks_file_args = create_seatbelt_command_args(shell_command_git, &policy, &cwd);
+        let write_hooks_file_args =
+            create_seatbelt_command_args(shell_command_git, &policy, &cwd, &[]);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_hooks_file_args)
             .current_dir(&cwd)
@@ -346,7 +392,7 @@ mod tests {
         .map(std::string::ToString::to_string)
         .collect();
         let write_allowed_file_args =
-            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd);
+            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd, &[]);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_allowed_file_args)
             .current_dir(&cwd)
@@ -406,7 +452,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args = create_seatbelt_command_args(shell_command, &policy, &cwd);
+        let args = create_seatbelt_command_args(shell_command, &policy, &cwd, &[]);
 
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&args)
@@ -436,7 +482,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let gitdir_args = create_seatbelt_command_args(shell_command_gitdir, &policy, &cwd);
+        let gitdir_args = create_seatbelt_command_args(shell_command_gitdir, &policy, &cwd, &[]);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&gitdir_args)
             .current_dir(&cwd)
@@ -492,8 +538,12 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args =
-            create_seatbelt_command_args(shell_command.clone(), &policy, vulnerable_root.as_path());
+        let args = create_seatbelt_command_args(
+            shell_command.clone(),
+            &policy,
+            vulnerable_root.as_path(),
+            &[],
+        );
 
         let tmpdir_env_var = std::env::var("TMPDIR")
             .ok()
@@ -562,6 +612,51 @@ mod tests {
         assert_eq!(expected_args, args);
     }
 
+    #[test]
+    fn denied_read_paths_cannot_be_read_under_seatbelt() {
+        let tmp = TempDir::new().expect("tempdir");
+        let dot_ssh = tmp.path().join(".ssh");
+        fs::create_dir_all(&dot_ssh).expect("create .ssh");
+        let id_rsa = dot_ssh.join("id_rsa");
+        fs::write(&id_rsa, "secret").expect("write id_rsa");
+        let readable = tmp.path().join("readable.txt");
+        fs::write(&readable, "public").expect("write readable file");
+
+        let cwd = tmp.path().join("cwd");
+        fs::create_dir_all(&cwd).expect("create cwd");
+        let policy = SandboxPolicy::new_read_only_policy();
+        let denied = vec![dot_ssh.try_into().expect("dot_ssh is absolute")];
+
+        let cat_secret: Vec<String> = ["cat", id_rsa.to_string_lossy().as_ref()]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let args = create_seatbelt_command_args(cat_secret, &policy, &cwd, &denied);
+        let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
+            .args(&args)
+            .current_dir(&cwd)
+            .output()
+            .expect("execute seatbelt command");
+        assert!(
+            !output.status.success(),
+            "reading {} should fail under seatbelt",
+            id_rsa.display()
+        );
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+
+        let cat_readable: Vec<String> = ["cat", readable.to_string_lossy().as_ref()]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let args = create_seatbelt_command_args(cat_readable, &policy, &cwd, &denied);
+        let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
+            .args(&args)
+            .current_dir(&cwd)
+            .output()
+            .expect("execute seatbelt command");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "public");
+    }
+
     struct PopulatedTmp {
         /// Path containing a .git and .codex subfolder.
         /// For the purposes of this test, we consider this a "vulnerable" root
@@ -6,12 +6,15 @@ use tokio::process::Command;
 use tracing::trace;
 
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::network_proxy::CODEX_SANDBOX_NETWORK_PROXY_ENV_VAR;
+use crate::sandboxing::resource_limits::ResourceLimitScope;
 
 /// Experimental environment variable that will be set to some non-empty value
 /// if both of the following are true:
 ///
 /// 1. The process was spawned by Codex as part of a shell tool call.
 /// 2. SandboxPolicy.has_full_network_access() was false for the tool call.
+/// 3. The command's network access is not routed through the network proxy.
 ///
 /// We may try to have just one environment variable for all sandboxing
 /// attributes, so this may change in the future.
@@ -35,6 +38,10 @@ pub enum StdioPolicy {
 /// For now, we take `SandboxPolicy` as a parameter to spawn_child() because
 /// we need to determine whether to set the
 /// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` environment variable.
+///
+/// `resource_limits` is applied in the child before it execs, so the limits
+/// also cover any sandbox wrapper (and everything it spawns).
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn spawn_child_async(
     program: PathBuf,
     args: Vec<String>,
@@ -43,6 +50,7 @@ pub(crate) async fn spawn_child_async(
     sandbox_policy: &SandboxPolicy,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
+    #[cfg_attr(not(unix), allow(unused_variables))] resource_limits: &ResourceLimitScope,
 ) -> std::io::Result<Child> {
     trace!(
         "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
@@ -54,9 +62,12 @@ pub(crate) async fn spawn_child_async(
     cmd.args(args);
     cmd.current_dir(cwd);
     cmd.env_clear();
+    // Commands routed through the network proxy do have (filtered) network
+    // access, so tools must not skip it.
+    let routed_through_proxy = env.contains_key(CODEX_SANDBOX_NETWORK_PROXY_ENV_VAR);
     cmd.envs(env);
 
-    if !sandbox_policy.has_full_network_access() {
+    if !sandbox_policy.has_full_network_access() && !routed_through_proxy {
         cmd.env(CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR, "1");
     }
 
@@ -83,6 +94,7 @@ pub(crate) async fn spawn_child_async(
             }
             Ok(())
         });
+        cmd.pre_exec(resource_limits.pre_exec_hook());
     }
 
     match stdio_policy {
@@ -8,12 +8,16 @@ use crate::client::ModelClient;
 use crate::exec_policy::ExecPolicyManager;
 use crate::file_watcher::FileWatcher;
 use crate::hooks::Hooks;
+use crate::lsp::LspManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::network_approval::NetworkApprovals;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::web_fetch::WebFetcher;
+use crate::workspace_edits::WorkspaceEdits;
 use codex_otel::OtelManager;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
@@ -37,6 +41,15 @@ pub(crate) struct SessionServices {
     pub(crate) file_watcher: Arc<FileWatcher>,
     pub(crate) agent_control: AgentControl,
     pub(crate) state_db: Option<StateDbHandle>,
+    /// Prompts for hosts blocked by the network proxy, when sandboxed
+    /// commands are routed through it.
+    pub(crate) network_approvals: Option<Arc<NetworkApprovals>>,
+    /// Language servers started on demand by the `code_intel` tool.
+    pub(crate) lsp_manager: Arc<LspManager>,
+    /// Files the user edited between turns.
+    pub(crate) workspace_edits: Arc<WorkspaceEdits>,
+    /// Pages downloaded by the `fetch_url` tool.
+    pub(crate) web_fetcher: WebFetcher,
     /// Session-scoped model client shared across turns.
     pub(crate) model_client: ModelClient,
 }
@@ -4,8 +4,10 @@ use codex_protocol::models::ResponseItem;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use crate::budget::SessionBudget;
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::pricing::ThreadCost;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -26,6 +28,9 @@ pub(crate) struct SessionState {
     pub(crate) initial_context_seeded: bool,
     /// Previous rollout model for one-shot model-switch handling on first turn after resume.
     pub(crate) pending_resume_previous_model: Option<String>,
+    pub(crate) budget: SessionBudget,
+    /// Estimated cost of the thread so far.
+    pub(crate) cost: ThreadCost,
 }
 
 impl SessionState {
@@ -41,6 +46,8 @@ impl SessionState {
             mcp_dependency_prompted: HashSet::new(),
             initial_context_seeded: false,
             pending_resume_previous_model: None,
+            budget: SessionBudget::default(),
+            cost: ThreadCost::default(),
         }
     }
 
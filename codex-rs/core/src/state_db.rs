@@ -14,8 +14,25 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 use codex_state::DB_METRIC_COMPARE_ERROR;
 pub use codex_state::LogEntry;
+pub use codex_state::Memory;
+pub use codex_state::MemoryScope;
+pub use codex_state::MemorySource;
+pub use codex_state::NamedCount;
+pub use codex_state::NewMemory;
+pub use codex_state::NewScheduledJob;
 use codex_state::STATE_DB_VERSION;
+pub use codex_state::ScheduledJob;
+pub use codex_state::ScheduledJobRun;
+pub use codex_state::ScheduledRunOutcome;
+pub use codex_state::ScheduledRunStatus;
 use codex_state::ThreadMetadataBuilder;
+pub use codex_state::ThreadSearchMatch;
+pub use codex_state::ThreadSearchMatchKind;
+pub use codex_state::ThreadSearchResult;
+pub use codex_state::UsageStats;
+pub use codex_state::UsageStatsGroup;
+pub use codex_state::UsageStatsGroupBy;
+pub use codex_state::UsageStatsQuery;
 use serde_json::Value;
 use std::path::Path;
 use std::path::PathBuf;
@@ -364,6 +381,38 @@ pub async fn get_last_n_thread_memories_for_cwd(
     }
 }
 
+/// Full-text search over thread messages, commands and touched files using SQLite.
+pub async fn search_threads_db(
+    context: Option<&codex_state::StateRuntime>,
+    query: &str,
+    limit: usize,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    cwd: Option<&Path>,
+    archived: bool,
+) -> anyhow::Result<Vec<ThreadSearchResult>> {
+    let Some(ctx) = context else {
+        anyhow::bail!("session search requires the state database (`[features] sqlite = true`)");
+    };
+    let allowed_sources: Vec<String> = allowed_sources
+        .iter()
+        .map(|value| match serde_json::to_value(value) {
+            Ok(Value::String(s)) => s,
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        })
+        .collect();
+    ctx.search_threads(&codex_state::ThreadSearchQuery {
+        query: query.to_string(),
+        limit,
+        allowed_sources,
+        model_providers: model_providers.map(<[String]>::to_vec),
+        cwd: cwd.map(Path::to_path_buf),
+        archived,
+    })
+    .await
+}
+
 /// Reconcile rollout items into SQLite, falling back to scanning the rollout file.
 pub async fn reconcile_rollout(
     context: Option<&codex_state::StateRuntime>,
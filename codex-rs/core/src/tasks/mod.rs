@@ -122,6 +122,7 @@ impl Session {
         self.abort_all_tasks(TurnAbortReason::Replaced).await;
         self.seed_initial_context_if_needed(turn_context.as_ref())
             .await;
+        self.services.workspace_edits.mark_turn_started();
 
         let task: Arc<dyn SessionTask> = Arc::new(task);
         let task_kind = task.kind();
@@ -181,6 +182,7 @@ impl Session {
         for task in self.take_all_running_tasks().await {
             self.handle_task_abort(task, reason.clone()).await;
         }
+        self.services.workspace_edits.mark_turn_ended();
         self.close_unified_exec_processes().await;
     }
 
@@ -201,6 +203,7 @@ impl Session {
         }
         if should_close_processes {
             *active = None;
+            self.services.workspace_edits.mark_turn_ended();
         }
         drop(active);
         if !pending_input.is_empty() {
@@ -214,7 +217,14 @@ impl Session {
         if should_close_processes {
             self.close_unified_exec_processes().await;
         }
-        let event = EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message });
+        // A turn that ran out of budget ends like an aborted one.
+        let event = match self.take_budget_exceeded().await {
+            Some(budget) => EventMsg::TurnAborted(TurnAbortedEvent {
+                reason: TurnAbortReason::BudgetExceeded,
+                budget: Some(budget),
+            }),
+            None => EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }),
+        };
         self.send_event(turn_context.as_ref(), event).await;
     }
 
@@ -290,7 +300,10 @@ impl Session {
             self.flush_rollout().await;
         }
 
-        let event = EventMsg::TurnAborted(TurnAbortedEvent { reason });
+        let event = EventMsg::TurnAborted(TurnAbortedEvent {
+            reason,
+            budget: None,
+        });
         self.send_event(task.turn_context.as_ref(), event).await;
     }
 }
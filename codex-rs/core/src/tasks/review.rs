@@ -7,31 +7,51 @@ use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::AgentMessageContentDeltaEvent;
 use codex_protocol::protocol::AgentMessageDeltaEvent;
+use codex_protocol::protocol::BackgroundEventEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExitedReviewModeEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::ReviewOutputEvent;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SubAgentSource;
+use codex_protocol::protocol::WarningEvent;
+use futures::StreamExt;
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 
+use crate::agent::AgentStatus;
+use crate::agent::status::is_final;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex_delegate::run_codex_thread_one_shot;
+use crate::config::Config;
 use crate::config::Constrained;
+use crate::features::Feature;
 use crate::review_format::format_review_findings_block;
 use crate::review_format::render_review_output_text;
+use crate::review_passes::ReviewPass;
+use crate::review_passes::merge_review_outputs;
 use crate::state::TaskKind;
 use codex_protocol::user_input::UserInput;
 
 use super::SessionTask;
 use super::SessionTaskContext;
 
-#[derive(Clone, Copy)]
-pub(crate) struct ReviewTask;
+#[derive(Clone)]
+pub(crate) struct ReviewTask {
+    /// When non-empty, each pass runs in its own reviewer sub-agent and the
+    /// task input is ignored.
+    passes: Vec<ReviewPass>,
+}
 
 impl ReviewTask {
     pub(crate) fn new() -> Self {
-        Self
+        Self { passes: Vec::new() }
+    }
+
+    pub(crate) fn with_passes(passes: Vec<ReviewPass>) -> Self {
+        Self { passes }
     }
 }
 
@@ -54,17 +74,22 @@ impl SessionTask for ReviewTask {
             .otel_manager
             .counter("codex.task.review", 1, &[]);
 
-        // Start sub-codex conversation and get the receiver for events.
-        let output = match start_review_conversation(
-            session.clone(),
-            ctx.clone(),
-            input,
-            cancellation_token.clone(),
-        )
-        .await
-        {
-            Some(receiver) => process_review_events(session.clone(), ctx.clone(), receiver).await,
-            None => None,
+        let output = if self.passes.is_empty() {
+            run_review_pass(
+                session.clone(),
+                ctx.clone(),
+                input,
+                cancellation_token.clone(),
+            )
+            .await
+        } else {
+            run_review_passes(
+                session.clone(),
+                ctx.clone(),
+                &self.passes,
+                cancellation_token.clone(),
+            )
+            .await
         };
         if !cancellation_token.is_cancelled() {
             exit_review_mode(session.clone_session(), output.clone(), ctx.clone()).await;
@@ -77,12 +102,180 @@ impl SessionTask for ReviewTask {
     }
 }
 
-async fn start_review_conversation(
+async fn run_review_pass(
     session: Arc<SessionTaskContext>,
     ctx: Arc<TurnContext>,
     input: Vec<UserInput>,
     cancellation_token: CancellationToken,
-) -> Option<async_channel::Receiver<Event>> {
+) -> Option<ReviewOutputEvent> {
+    // Start sub-codex conversation and get the receiver for events.
+    let receiver =
+        start_review_conversation(session.clone(), ctx.clone(), input, cancellation_token).await?;
+    process_review_events(session, ctx, receiver).await
+}
+
+/// Run `passes` as reviewer agent threads through [`AgentControl`], at most
+/// `agents.max_threads` at a time, and merge the outputs of those that
+/// completed. Each pass streams its events on its own thread, so this review
+/// only reports when a pass starts and how it ended.
+///
+/// [`AgentControl`]: crate::agent::AgentControl
+async fn run_review_passes(
+    session: Arc<SessionTaskContext>,
+    ctx: Arc<TurnContext>,
+    passes: &[ReviewPass],
+    cancellation_token: CancellationToken,
+) -> Option<ReviewOutputEvent> {
+    let total = passes.len();
+    let concurrency = ctx.config.agent_max_threads.unwrap_or(total).max(1);
+    let config = review_agent_config(ctx.as_ref());
+    let outputs: Vec<(String, Option<ReviewOutputEvent>)> =
+        futures::stream::iter(passes.iter().enumerate())
+            .map(|(index, pass)| {
+                let session = session.clone_session();
+                let ctx = ctx.clone();
+                let config = config.clone();
+                let cancellation_token = cancellation_token.clone();
+                async move {
+                    let tag = format!("Review pass {}/{total} ({})", index + 1, pass.label);
+                    session
+                        .send_event(
+                            ctx.as_ref(),
+                            EventMsg::BackgroundEvent(BackgroundEventEvent {
+                                message: format!("{tag} started"),
+                            }),
+                        )
+                        .await;
+                    let output = run_review_agent(
+                        session.as_ref(),
+                        config,
+                        pass.prompt.clone(),
+                        &cancellation_token,
+                    )
+                    .await;
+                    if output.is_some() {
+                        session
+                            .send_event(
+                                ctx.as_ref(),
+                                EventMsg::BackgroundEvent(BackgroundEventEvent {
+                                    message: format!("{tag} finished"),
+                                }),
+                            )
+                            .await;
+                    }
+                    (pass.label.clone(), output)
+                }
+            })
+            .buffered(concurrency)
+            .collect()
+            .await;
+    if cancellation_token.is_cancelled() {
+        return None;
+    }
+
+    let mut completed = Vec::new();
+    for (label, output) in outputs {
+        match output {
+            Some(output) => completed.push((label, output)),
+            None => {
+                session
+                    .clone_session()
+                    .send_event(
+                        ctx.as_ref(),
+                        EventMsg::Warning(WarningEvent {
+                            message: format!(
+                                "Review pass `{label}` did not finish; its findings are missing."
+                            ),
+                        }),
+                    )
+                    .await;
+            }
+        }
+    }
+    if completed.is_empty() {
+        return None;
+    }
+    Some(merge_review_outputs(completed))
+}
+
+/// Run one pass in a reviewer agent thread, wait for its turn to end and
+/// parse its final message. The thread is shut down afterwards either way.
+async fn run_review_agent(
+    session: &Session,
+    config: Config,
+    prompt: String,
+    cancellation_token: &CancellationToken,
+) -> Option<ReviewOutputEvent> {
+    let agent_control = &session.services.agent_control;
+    let agent_id = match agent_control
+        .spawn_agent(
+            config,
+            prompt,
+            Some(SessionSource::SubAgent(SubAgentSource::Review)),
+        )
+        .await
+    {
+        Ok(agent_id) => agent_id,
+        Err(err) => {
+            tracing::warn!("failed to start review pass agent: {err}");
+            return None;
+        }
+    };
+    let status = match agent_control.subscribe_status(agent_id).await {
+        Ok(mut status_rx) => {
+            tokio::select! {
+                status = wait_for_final_status(&mut status_rx) => status,
+                _ = cancellation_token.cancelled() => {
+                    let _ = agent_control.interrupt_agent(agent_id).await;
+                    None
+                }
+            }
+        }
+        Err(err) => {
+            tracing::warn!("failed to follow review pass agent {agent_id}: {err}");
+            None
+        }
+    };
+    let _ = agent_control.shutdown_agent(agent_id).await;
+    match status {
+        Some(AgentStatus::Completed(Some(message))) => Some(parse_review_output_event(&message)),
+        _ => None,
+    }
+}
+
+async fn wait_for_final_status(
+    status_rx: &mut watch::Receiver<AgentStatus>,
+) -> Option<AgentStatus> {
+    loop {
+        let status = status_rx.borrow_and_update().clone();
+        if is_final(&status) {
+            return Some(status);
+        }
+        if status_rx.changed().await.is_err() {
+            return None;
+        }
+    }
+}
+
+/// Config for a reviewer agent thread: the review sub-agent config pinned to
+/// the review turn's cwd and policies, without the ability to spawn agents of
+/// its own.
+fn review_agent_config(ctx: &TurnContext) -> Config {
+    let mut config = review_sub_agent_config(ctx);
+    config.cwd = ctx.cwd.clone();
+    if let Err(err) = config.approval_policy.set(ctx.approval_policy) {
+        tracing::warn!("failed to pin review agent approval_policy: {err}");
+    }
+    if let Err(err) = config.sandbox_policy.set(ctx.sandbox_policy.clone()) {
+        tracing::warn!("failed to pin review agent sandbox_policy: {err}");
+    }
+    config.features.disable(Feature::Collab);
+    config
+}
+
+/// The session config adjusted for a reviewer: review rubric, review model
+/// and no web search.
+fn review_sub_agent_config(ctx: &TurnContext) -> Config {
     let config = ctx.config.clone();
     let mut sub_agent_config = config.as_ref().clone();
     // Carry over review-only feature restrictions so the delegate cannot
@@ -110,6 +303,16 @@ async fn start_review_conversation(
         .clone()
         .unwrap_or_else(|| ctx.model_info.slug.clone());
     sub_agent_config.model = Some(model);
+    sub_agent_config
+}
+
+async fn start_review_conversation(
+    session: Arc<SessionTaskContext>,
+    ctx: Arc<TurnContext>,
+    input: Vec<UserInput>,
+    cancellation_token: CancellationToken,
+) -> Option<async_channel::Receiver<Event>> {
+    let sub_agent_config = review_sub_agent_config(ctx.as_ref());
     (run_codex_thread_one_shot(
         sub_agent_config,
         session.auth_manager(),
@@ -10,6 +10,7 @@ use tracing::error;
 use uuid::Uuid;
 
 use crate::codex::TurnContext;
+use crate::config::types::SandboxResourceLimits;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
@@ -151,6 +152,7 @@ pub(crate) async fn execute_user_shell_command(
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        resource_limits: SandboxResourceLimits::default(),
     };
 
     let stdout_stream = Some(StdoutStream {
@@ -292,6 +292,18 @@ impl ToolEmitter {
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
             }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+                limit,
+                output,
+            }))) => {
+                let response = format!(
+                    "Command stopped after exceeding its {limit} limit (configured via `sandbox_resource_limits`).\n{}",
+                    self.format_exec_output_for_model(&output, ctx)
+                );
+                let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
             Err(ToolError::Codex(err)) => {
                 let message = format!("execution error: {err:?}");
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
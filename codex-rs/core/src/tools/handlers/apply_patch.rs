@@ -60,6 +60,40 @@ fn to_abs_path(cwd: &Path, path: &Path) -> Option<AbsolutePathBuf> {
     AbsolutePathBuf::resolve_path_against_base(path, cwd).ok()
 }
 
+/// Refuse a patch touching files the user edited after the model last read
+/// them, so hand edits made between turns are not silently overwritten.
+fn reject_user_edit_conflicts(
+    session: &Session,
+    action: &ApplyPatchAction,
+) -> Result<(), FunctionCallError> {
+    let paths = file_paths_for_action(action)
+        .into_iter()
+        .map(AbsolutePathBuf::into_path_buf)
+        .collect::<Vec<_>>();
+    let conflicts = session.services.workspace_edits.conflicts(&paths);
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    let cwd = action.cwd.as_path();
+    let conflicts = conflicts
+        .iter()
+        .map(|path| path.strip_prefix(cwd).unwrap_or(path).display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(FunctionCallError::RespondToModel(format!(
+        "apply_patch rejected: {conflicts} changed by the user since you last read it. Re-read the current contents and write the patch against them."
+    )))
+}
+
+fn record_patched_files(session: &Session, file_paths: &[AbsolutePathBuf]) {
+    for path in file_paths {
+        session
+            .services
+            .workspace_edits
+            .record_model_version(path.as_path());
+    }
+}
+
 #[async_trait]
 impl ToolHandler for ApplyPatchHandler {
     fn kind(&self) -> ToolKind {
@@ -106,6 +140,7 @@ impl ToolHandler for ApplyPatchHandler {
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
         match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
+                reject_user_edit_conflicts(session.as_ref(), &changes)?;
                 match apply_patch::apply_patch(turn.as_ref(), changes).await {
                     InternalApplyPatchInvocation::Output(item) => {
                         let content = item?;
@@ -129,7 +164,7 @@ impl ToolHandler for ApplyPatchHandler {
 
                         let req = ApplyPatchRequest {
                             action: apply.action,
-                            file_paths,
+                            file_paths: file_paths.clone(),
                             changes,
                             exec_approval_requirement: apply.exec_approval_requirement,
                             timeout_ms: None,
@@ -154,6 +189,7 @@ impl ToolHandler for ApplyPatchHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        record_patched_files(session.as_ref(), &file_paths);
                         Ok(ToolOutput::Function {
                             body: FunctionCallOutputBody::Text(content),
                             success: Some(true),
@@ -200,6 +236,7 @@ pub(crate) async fn intercept_apply_patch(
                     turn,
                 )
                 .await;
+            reject_user_edit_conflicts(session, &changes)?;
             match apply_patch::apply_patch(turn, changes).await {
                 InternalApplyPatchInvocation::Output(item) => {
                     let content = item?;
@@ -218,7 +255,7 @@ pub(crate) async fn intercept_apply_patch(
 
                     let req = ApplyPatchRequest {
                         action: apply.action,
-                        file_paths: approval_keys,
+                        file_paths: approval_keys.clone(),
                         changes,
                         exec_approval_requirement: apply.exec_approval_requirement,
                         timeout_ms,
@@ -239,6 +276,7 @@ pub(crate) async fn intercept_apply_patch(
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
                     let content = emitter.finish(event_ctx, out).await?;
+                    record_patched_files(session, &approval_keys);
                     Ok(Some(ToolOutput::Function {
                         body: FunctionCallOutputBody::Text(content),
                         success: Some(true),
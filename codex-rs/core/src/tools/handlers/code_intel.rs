@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+use url::Url;
+
+use crate::function_tool::FunctionCallError;
+use crate::lsp::Diagnostic;
+use crate::lsp::Location;
+use crate::lsp::LspManager;
+use crate::lsp::Position;
+use crate::lsp::SymbolMatch;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct CodeIntelHandler;
+
+/// How long to wait for a server to publish diagnostics after a file is
+/// synced. Servers that are still indexing may need most of it.
+const DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_RESULTS: usize = 200;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum CodeIntelAction {
+    Diagnostics,
+    Definition,
+    References,
+    WorkspaceSymbols,
+}
+
+#[derive(Deserialize)]
+struct CodeIntelArgs {
+    action: CodeIntelAction,
+    #[serde(default)]
+    paths: Vec<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    line: Option<u32>,
+    #[serde(default)]
+    column: Option<u32>,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    server: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for CodeIntelHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "code_intel handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: CodeIntelArgs = parse_arguments(&arguments)?;
+        let manager = session.services.lsp_manager.as_ref();
+        if manager.server_names().next().is_none() {
+            return Err(FunctionCallError::RespondToModel(
+                "no language servers are configured; add one under [lsp_servers] in config.toml"
+                    .to_string(),
+            ));
+        }
+        let root = turn.cwd.as_path();
+
+        let lines = match args.action {
+            CodeIntelAction::Diagnostics => {
+                let paths = if args.paths.is_empty() {
+                    // Default to everything apply_patch touched this turn.
+                    tracker.lock().await.touched_paths()
+                } else {
+                    args.paths
+                        .into_iter()
+                        .map(|path| turn.resolve_path(Some(path)))
+                        .collect()
+                };
+                if paths.is_empty() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "paths must not be empty when no files were patched this turn".to_string(),
+                    ));
+                }
+                diagnostics(manager, root, &paths).await?
+            }
+            CodeIntelAction::Definition | CodeIntelAction::References => {
+                let path = turn.resolve_path(Some(args.path.ok_or_else(|| {
+                    FunctionCallError::RespondToModel("path is required".to_string())
+                })?));
+                let position = position_from_args(args.line, args.column)?;
+                let server = manager.server_for_path(&path).ok_or_else(|| {
+                    FunctionCallError::RespondToModel(format!(
+                        "no language server is configured for `{}`",
+                        path.display()
+                    ))
+                })?;
+                let client = manager
+                    .client(server, root)
+                    .await
+                    .map_err(|err| respond(server, err))?;
+                let locations = if args.action == CodeIntelAction::Definition {
+                    client.definition(&path, position).await
+                } else {
+                    client.references(&path, position).await
+                }
+                .map_err(|err| respond(server, err))?;
+                format_locations(root, &locations).await
+            }
+            CodeIntelAction::WorkspaceSymbols => {
+                let query = args.query.unwrap_or_default();
+                let server = symbol_server(
+                    manager,
+                    args.server.as_deref(),
+                    args.path.map(|path| turn.resolve_path(Some(path))),
+                )?;
+                let client = manager
+                    .client(&server, root)
+                    .await
+                    .map_err(|err| respond(&server, err))?;
+                let symbols = client
+                    .workspace_symbols(&query)
+                    .await
+                    .map_err(|err| respond(&server, err))?;
+                symbols
+                    .iter()
+                    .map(|symbol| format_symbol(root, symbol))
+                    .collect()
+            }
+        };
+
+        let body = if lines.is_empty() {
+            "No results found.".to_string()
+        } else {
+            truncate_lines(lines).join("\n")
+        };
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(body),
+            success: Some(true),
+        })
+    }
+}
+
+async fn diagnostics(
+    manager: &LspManager,
+    root: &Path,
+    paths: &[PathBuf],
+) -> Result<Vec<String>, FunctionCallError> {
+    let mut lines = Vec::new();
+    for path in paths {
+        let display = display_path(root, path);
+        let Some(server) = manager.server_for_path(path) else {
+            lines.push(format!("{display}: no language server configured"));
+            continue;
+        };
+        if !path.is_file() {
+            lines.push(format!("{display}: file does not exist"));
+            continue;
+        }
+        let client = manager
+            .client(server, root)
+            .await
+            .map_err(|err| respond(server, err))?;
+        let found = client
+            .diagnostics(path, DIAGNOSTICS_TIMEOUT)
+            .await
+            .map_err(|err| respond(server, err))?;
+        if found.is_empty() {
+            lines.push(format!("{display}: no diagnostics"));
+        }
+        lines.extend(
+            found
+                .iter()
+                .map(|diagnostic| format_diagnostic(&display, diagnostic)),
+        );
+    }
+    Ok(lines)
+}
+
+/// Pick the server for `workspace_symbols`: the one named explicitly, the one
+/// handling `path`, or the only one configured.
+fn symbol_server(
+    manager: &LspManager,
+    server: Option<&str>,
+    path: Option<PathBuf>,
+) -> Result<String, FunctionCallError> {
+    if let Some(server) = server {
+        if manager.server_names().any(|name| name == server) {
+            return Ok(server.to_string());
+        }
+        return Err(FunctionCallError::RespondToModel(format!(
+            "unknown language server `{server}`; configured: {}",
+            manager.server_names().collect::<Vec<_>>().join(", ")
+        )));
+    }
+    if let Some(path) = path {
+        return manager
+            .server_for_path(&path)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                FunctionCallError::RespondToModel(format!(
+                    "no language server is configured for `{}`",
+                    path.display()
+                ))
+            });
+    }
+    let names: Vec<&str> = manager.server_names().collect();
+    match names.as_slice() {
+        [only] => Ok((*only).to_string()),
+        _ => Err(FunctionCallError::RespondToModel(format!(
+            "several language servers are configured ({}); pass `server` or `path`",
+            names.join(", ")
+        ))),
+    }
+}
+
+fn position_from_args(
+    line: Option<u32>,
+    column: Option<u32>,
+) -> Result<Position, FunctionCallError> {
+    match (line, column) {
+        (Some(line), Some(column)) if line > 0 && column > 0 => Ok(Position {
+            line: line - 1,
+            character: column - 1,
+        }),
+        _ => Err(FunctionCallError::RespondToModel(
+            "line and column are required and 1-based".to_string(),
+        )),
+    }
+}
+
+fn respond(server: &str, err: anyhow::Error) -> FunctionCallError {
+    FunctionCallError::RespondToModel(format!("language server `{server}`: {err:#}"))
+}
+
+fn format_diagnostic(display: &str, diagnostic: &Diagnostic) -> String {
+    let severity = match diagnostic.severity {
+        Some(1) => "error",
+        Some(2) => "warning",
+        Some(3) => "info",
+        Some(4) => "hint",
+        _ => "diagnostic",
+    };
+    let source = diagnostic
+        .source
+        .as_deref()
+        .map(|source| format!(" [{source}]"))
+        .unwrap_or_default();
+    format!(
+        "{display}:{}:{}: {severity}{source}: {}",
+        diagnostic.range.start.line + 1,
+        diagnostic.range.start.character + 1,
+        diagnostic.message
+    )
+}
+
+/// Render each location as `path:line:column: <source line>`, reading every
+/// file at most once.
+async fn format_locations(root: &Path, locations: &[Location]) -> Vec<String> {
+    let mut files: HashMap<String, Option<Vec<String>>> = HashMap::new();
+    let mut lines = Vec::with_capacity(locations.len());
+    for location in locations {
+        let path = uri_to_path(&location.uri);
+        if !files.contains_key(&location.uri) {
+            let text = match &path {
+                Some(path) => tokio::fs::read_to_string(path)
+                    .await
+                    .ok()
+                    .map(|text| text.lines().map(str::to_string).collect()),
+                None => None,
+            };
+            files.insert(location.uri.clone(), text);
+        }
+        let display = path
+            .as_deref()
+            .map_or_else(|| location.uri.clone(), |path| display_path(root, path));
+        let start = location.range.start;
+        let mut line = format!("{display}:{}:{}", start.line + 1, start.character + 1);
+        if let Some(Some(text)) = files.get(&location.uri)
+            && let Some(source) = text.get(start.line as usize)
+        {
+            line.push_str(": ");
+            line.push_str(source.trim());
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn format_symbol(root: &Path, symbol: &SymbolMatch) -> String {
+    let display = uri_to_path(&symbol.location.uri).map_or_else(
+        || symbol.location.uri.clone(),
+        |path| display_path(root, &path),
+    );
+    let container = symbol
+        .container_name
+        .as_deref()
+        .map(|container| format!(" in {container}"))
+        .unwrap_or_default();
+    let start = symbol.location.range.start;
+    format!(
+        "{} ({}{container}) {display}:{}:{}",
+        symbol.name,
+        symbol_kind(symbol.kind),
+        start.line + 1,
+        start.character + 1
+    )
+}
+
+/// Names for LSP `SymbolKind` values.
+fn symbol_kind(kind: u32) -> &'static str {
+    match kind {
+        1 => "file",
+        2 => "module",
+        3 => "namespace",
+        4 => "package",
+        5 => "class",
+        6 => "method",
+        7 => "property",
+        8 => "field",
+        9 => "constructor",
+        10 => "enum",
+        11 => "interface",
+        12 => "function",
+        13 => "variable",
+        14 => "constant",
+        22 => "enum member",
+        23 => "struct",
+        24 => "event",
+        25 => "operator",
+        26 => "type parameter",
+        _ => "symbol",
+    }
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+fn display_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn truncate_lines(mut lines: Vec<String>) -> Vec<String> {
+    if lines.len() > MAX_RESULTS {
+        let omitted = lines.len() - MAX_RESULTS;
+        lines.truncate(MAX_RESULTS);
+        lines.push(format!("... {omitted} more results omitted"));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsp::Range;
+    use pretty_assertions::assert_eq;
+
+    fn range(line: u32, character: u32) -> Range {
+        Range {
+            start: Position { line, character },
+            end: Position { line, character },
+        }
+    }
+
+    #[test]
+    fn positions_are_one_based() {
+        assert_eq!(
+            position_from_args(Some(3), Some(5)).expect("position"),
+            Position {
+                line: 2,
+                character: 4
+            }
+        );
+        assert!(position_from_args(Some(0), Some(1)).is_err());
+        assert!(position_from_args(Some(1), None).is_err());
+    }
+
+    #[test]
+    fn diagnostics_render_as_compiler_style_lines() {
+        let diagnostic = Diagnostic {
+            range: range(9, 4),
+            severity: Some(1),
+            source: Some("rustc".to_string()),
+            message: "mismatched types".to_string(),
+        };
+        assert_eq!(
+            format_diagnostic("src/lib.rs", &diagnostic),
+            "src/lib.rs:10:5: error [rustc]: mismatched types"
+        );
+    }
+
+    #[tokio::test]
+    async fn locations_include_the_source_line_relative_to_root() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn helper() {}\n\nfn main() {\n    helper();\n}\n").expect("write");
+        let uri = Url::from_file_path(&path).expect("uri").to_string();
+
+        let lines = format_locations(
+            dir.path(),
+            &[
+                Location {
+                    uri: uri.clone(),
+                    range: range(0, 3),
+                },
+                Location {
+                    uri,
+                    range: range(3, 4),
+                },
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            lines,
+            vec![
+                "lib.rs:1:4: fn helper() {}".to_string(),
+                "lib.rs:4:5: helper();".to_string(),
+            ]
+        );
+    }
+}
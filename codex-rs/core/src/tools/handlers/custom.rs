@@ -0,0 +1,248 @@
+use std::io::Write;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde_json::Value;
+
+use crate::exec_env::create_env;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::ExecCommandSource;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::runtimes::shell::ShellRequest;
+use crate::tools::runtimes::shell::ShellRuntime;
+use crate::tools::sandboxing::ToolCtx;
+use crate::truncate::formatted_truncate_text;
+
+/// Environment variable holding the path of the file with the call's
+/// arguments.
+const TOOL_INPUT_ENV_VAR: &str = "CODEX_TOOL_INPUT";
+
+/// Redirects the arguments file to the tool's stdin. The tool's command is
+/// passed as positional parameters so the shell never re-parses it.
+const STDIN_WRAPPER_SCRIPT: &str = r#"exec "$@" < "$CODEX_TOOL_INPUT""#;
+
+/// Runs the commands declared under `[tools.custom.<name>]` and in
+/// `.codex/tools/`, under the session's sandbox and approval policy.
+pub struct CustomToolHandler;
+
+#[async_trait]
+impl ToolHandler for CustomToolHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "custom tool handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let Some(tool) = turn
+            .tools_config
+            .custom_tools
+            .iter()
+            .find(|tool| tool.name == tool_name)
+            .cloned()
+        else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "unknown custom tool: {tool_name}"
+            )));
+        };
+        if cfg!(windows) {
+            return Err(FunctionCallError::RespondToModel(
+                "custom tools are not supported on Windows".to_string(),
+            ));
+        }
+
+        let args: Value = parse_arguments(&arguments)?;
+        let input = write_input_file(&args).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to pass arguments to {tool_name}: {err}"
+            ))
+        })?;
+
+        let mut env = create_env(
+            &turn.shell_environment_policy,
+            Some(session.conversation_id),
+        );
+        env.extend(session.dependency_env().await);
+        env.insert(
+            TOOL_INPUT_ENV_VAR.to_string(),
+            input.path().to_string_lossy().into_owned(),
+        );
+        let mut command = vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            STDIN_WRAPPER_SCRIPT.to_string(),
+            "sh".to_string(),
+        ];
+        command.extend(tool.command.iter().cloned());
+
+        // Rules and known-safe checks apply to the tool's own command, not to
+        // the stdin wrapper.
+        let exec_approval_requirement = session
+            .services
+            .exec_policy
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command: &tool.command,
+                approval_policy: turn.approval_policy,
+                sandbox_policy: &turn.sandbox_policy,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+            })
+            .await;
+
+        let emitter = ToolEmitter::shell(
+            tool.command.clone(),
+            tool.cwd.clone(),
+            ExecCommandSource::Agent,
+            false,
+        );
+        let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+        emitter.begin(event_ctx).await;
+
+        let req = ShellRequest {
+            command,
+            cwd: tool.cwd.clone(),
+            timeout_ms: tool.timeout_ms,
+            env,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: Some(format!("Run the `{tool_name}` tool")),
+            exec_approval_requirement,
+        };
+        let mut orchestrator = ToolOrchestrator::new();
+        let mut runtime = ShellRuntime::new();
+        let tool_ctx = ToolCtx {
+            session: session.as_ref(),
+            turn: turn.as_ref(),
+            call_id: call_id.clone(),
+            tool_name,
+        };
+        let out = orchestrator
+            .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
+            .await;
+        drop(input);
+
+        // A successful call returns just its stdout; failures keep the exit
+        // code and stderr so the model can react.
+        let exited = out.is_ok();
+        let success = out.as_ref().is_ok_and(|output| output.exit_code == 0);
+        let stdout = out
+            .as_ref()
+            .ok()
+            .filter(|output| output.exit_code == 0)
+            .map(|output| formatted_truncate_text(&output.stdout.text, turn.truncation_policy));
+        let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+        let content = match emitter.finish(event_ctx, out).await {
+            Ok(content) => content,
+            Err(FunctionCallError::RespondToModel(content)) if exited => content,
+            Err(err) => return Err(err),
+        };
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(stdout.unwrap_or(content)),
+            success: Some(success),
+        })
+    }
+}
+
+fn write_input_file(args: &Value) -> std::io::Result<tempfile::NamedTempFile> {
+    let mut file = tempfile::NamedTempFile::new()?;
+    serde_json::to_writer(&mut file, args)?;
+    file.flush()?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::config::types::CustomToolConfig;
+    use crate::protocol::AskForApproval;
+    use crate::protocol::SandboxPolicy;
+    use crate::turn_diff_tracker::TurnDiffTracker;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    async fn run_tool(command: &[&str], arguments: &str) -> (String, Option<bool>) {
+        let (session, mut turn) = make_session_and_context().await;
+        turn.approval_policy = AskForApproval::Never;
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        turn.tools_config.custom_tools = vec![CustomToolConfig {
+            name: "probe".to_string(),
+            description: "Test tool".to_string(),
+            command: command.iter().map(ToString::to_string).collect(),
+            input_schema: json!({ "type": "object", "properties": {} }),
+            timeout_ms: Some(10_000),
+            cwd: turn.cwd.clone(),
+        }];
+        let output = CustomToolHandler
+            .handle(ToolInvocation {
+                session: Arc::new(session),
+                turn: Arc::new(turn),
+                tracker: Arc::new(Mutex::new(TurnDiffTracker::default())),
+                call_id: "call-1".to_string(),
+                tool_name: "probe".to_string(),
+                payload: ToolPayload::Function {
+                    arguments: arguments.to_string(),
+                },
+            })
+            .await
+            .expect("custom tool runs");
+        let ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(text),
+            success,
+        } = output
+        else {
+            panic!("expected text function output");
+        };
+        (text, success)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn arguments_go_to_stdin_and_stdout_comes_back() {
+        let arguments = json!({ "message": "hello" }).to_string();
+
+        let (text, success) = run_tool(&["cat"], &arguments).await;
+
+        assert_eq!((text, success), (arguments, Some(true)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn non_zero_exit_is_reported_as_failure() {
+        let (text, success) =
+            run_tool(&["/bin/sh", "-c", "echo tool broke >&2; exit 3"], "{}").await;
+
+        assert_eq!(success, Some(false));
+        assert!(text.contains("tool broke"), "unexpected output: {text}");
+    }
+}
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+use url::Url;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::web_fetch::FetchedPage;
+use crate::web_fetch::MAX_RESPONSE_BYTES;
+use crate::web_fetch::NetworkRoute;
+
+pub struct FetchUrlHandler;
+
+/// Characters of page content returned per call.
+const PAGE_CHARS: usize = 20_000;
+
+#[derive(Deserialize)]
+struct FetchUrlArgs {
+    url: String,
+    #[serde(default)]
+    offset: usize,
+}
+
+#[async_trait]
+impl ToolHandler for FetchUrlHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "fetch_url handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let FetchUrlArgs { url, offset } = parse_arguments(&arguments)?;
+
+        let url = Url::parse(&url).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("invalid url `{url}`: {err}"))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "fetch_url only supports http and https URLs, got `{}`",
+                url.scheme()
+            )));
+        }
+
+        // Requests follow the same network rules as sandboxed commands: through
+        // the network proxy when one is configured, directly only when the
+        // sandbox allows full network access.
+        let route = match &turn.config.sandbox_network_proxy {
+            Some(proxy) => NetworkRoute::Proxy(proxy.http_addr),
+            None if turn.sandbox_policy.has_full_network_access() => NetworkRoute::Direct,
+            None => {
+                return Err(FunctionCallError::RespondToModel(
+                    "fetch_url needs network access, which the current sandbox policy does not allow"
+                        .to_string(),
+                ));
+            }
+        };
+
+        let page = session
+            .services
+            .web_fetcher
+            .fetch(&url, route)
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("failed to fetch {url}: {err:#}"))
+            })?;
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(render_page(&page, offset)?),
+            success: Some(true),
+        })
+    }
+}
+
+/// Formats up to [`PAGE_CHARS`] characters of the page starting at `offset`,
+/// after a header that tells the model where the next chunk starts.
+fn render_page(page: &FetchedPage, offset: usize) -> Result<String, FunctionCallError> {
+    let total = page.content.chars().count();
+    if offset > 0 && offset >= total {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "offset {offset} is past the end of the page ({total} characters)"
+        )));
+    }
+    let chunk: String = page.content.chars().skip(offset).take(PAGE_CHARS).collect();
+    let end = offset + chunk.chars().count();
+
+    let mut output = format!("URL: {}\n", page.url);
+    if let Some(title) = &page.title {
+        output.push_str(&format!("Title: {title}\n"));
+    }
+    output.push_str(&format!("Characters {offset}-{end} of {total}"));
+    if end < total {
+        output.push_str(&format!(
+            "; call fetch_url again with offset {end} for more"
+        ));
+    }
+    output.push('\n');
+    if page.truncated {
+        output.push_str(&format!(
+            "The response was larger than {} MB; content past that point is missing.\n",
+            MAX_RESPONSE_BYTES / (1024 * 1024)
+        ));
+    }
+    output.push('\n');
+    output.push_str(&chunk);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn pages_through_long_content() {
+        let page = FetchedPage {
+            url: "https://example.com/".to_string(),
+            title: Some("Example".to_string()),
+            content: "é".repeat(PAGE_CHARS + 5),
+            truncated: false,
+        };
+
+        let first = render_page(&page, 0).expect("first chunk");
+        assert!(first.starts_with(&format!(
+            "URL: https://example.com/\nTitle: Example\nCharacters 0-{PAGE_CHARS} of {}; call fetch_url again with offset {PAGE_CHARS} for more\n\n",
+            PAGE_CHARS + 5
+        )));
+
+        let last = render_page(&page, PAGE_CHARS).expect("last chunk");
+        assert_eq!(
+            last,
+            format!(
+                "URL: https://example.com/\nTitle: Example\nCharacters {PAGE_CHARS}-{} of {}\n\nééééé",
+                PAGE_CHARS + 5,
+                PAGE_CHARS + 5
+            )
+        );
+
+        assert!(render_page(&page, PAGE_CHARS + 5).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::function_tool::FunctionCallError;
+use crate::memories::memory_repo_key;
+use crate::state_db::MemoryScope;
+use crate::state_db::MemorySource;
+use crate::state_db::NewMemory;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Handles the `remember` and `forget` tools.
+pub struct MemoryHandler;
+
+#[derive(Deserialize)]
+struct RememberArgs {
+    content: String,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ForgetArgs {
+    id: i64,
+}
+
+#[async_trait]
+impl ToolHandler for MemoryHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let Some(state_db) = session.state_db() else {
+            return Err(FunctionCallError::RespondToModel(
+                "memories are unavailable because the state database is disabled".to_string(),
+            ));
+        };
+
+        let result = match tool_name.as_str() {
+            "remember" => {
+                let args: RememberArgs = parse_arguments(&arguments)?;
+                // User-wide memories apply to every repository, so only the
+                // user adds them, with `codex memory add --user`.
+                if let Some(scope) = args.scope.as_deref()
+                    && MemoryScope::parse(scope) != Some(MemoryScope::Repo)
+                {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "memories can only be saved for the current repository, got scope {scope:?}"
+                    )));
+                }
+                let memory = state_db
+                    .add_memory(&NewMemory {
+                        scope: MemoryScope::Repo,
+                        repo: Some(memory_repo_key(&turn.cwd)),
+                        content: args.content,
+                        source: MemorySource::Agent,
+                        thread_id: Some(session.conversation_id),
+                    })
+                    .await
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!("failed to save memory: {err}"))
+                    })?;
+                json!({
+                    "id": memory.id,
+                    "scope": memory.scope.as_str(),
+                    "content": memory.content,
+                })
+            }
+            "forget" => {
+                let args: ForgetArgs = parse_arguments(&arguments)?;
+                let forget_error = |err: anyhow::Error| {
+                    FunctionCallError::RespondToModel(format!("failed to forget memory: {err}"))
+                };
+                // Only memories shown in this session can be forgotten, so a
+                // guessed id cannot delete another repository's memories.
+                let repo = memory_repo_key(&turn.cwd);
+                let visible = state_db
+                    .get_memory(args.id)
+                    .await
+                    .map_err(forget_error)?
+                    .is_some_and(|memory| match memory.scope {
+                        MemoryScope::User => true,
+                        MemoryScope::Repo => memory.repo.as_deref() == Some(repo.as_str()),
+                    });
+                if !visible
+                    || !state_db
+                        .delete_memory(args.id)
+                        .await
+                        .map_err(forget_error)?
+                {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "no memory with id {}",
+                        args.id
+                    )));
+                }
+                json!({ "id": args.id, "forgotten": true })
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported memory tool: {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(result.to_string()),
+            success: Some(true),
+        })
+    }
+}
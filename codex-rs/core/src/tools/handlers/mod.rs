@@ -1,11 +1,15 @@
 pub mod apply_patch;
+mod code_intel;
 pub(crate) mod collab;
+mod custom;
 mod dynamic;
+mod fetch_url;
 mod get_memory;
 mod grep_files;
 mod list_dir;
 mod mcp;
 mod mcp_resource;
+mod memories;
 mod plan;
 mod read_file;
 mod request_user_input;
@@ -19,13 +23,17 @@ use serde::Deserialize;
 
 use crate::function_tool::FunctionCallError;
 pub use apply_patch::ApplyPatchHandler;
+pub use code_intel::CodeIntelHandler;
 pub use collab::CollabHandler;
+pub use custom::CustomToolHandler;
 pub use dynamic::DynamicToolHandler;
+pub use fetch_url::FetchUrlHandler;
 pub use get_memory::GetMemoryHandler;
 pub use grep_files::GrepFilesHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
+pub use memories::MemoryHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
 pub use request_user_input::RequestUserInputHandler;
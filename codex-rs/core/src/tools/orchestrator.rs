@@ -19,8 +19,10 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
 use codex_otel::ToolDecisionSource;
+use codex_protocol::protocol::ApprovalDecisionItem;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::RolloutItem;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -73,6 +75,7 @@ impl ToolOrchestrator {
                 let decision = tool.start_approval_async(req, approval_ctx).await;
 
                 otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
+                record_approval_decision(tool_ctx, &decision).await;
 
                 match decision {
                     ReviewDecision::Denied | ReviewDecision::Abort => {
@@ -104,6 +107,9 @@ impl ToolOrchestrator {
             policy: &turn_ctx.sandbox_policy,
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
+            denied_read_paths: &turn_ctx.config.sandbox_denied_read_paths,
+            resource_limits: turn_ctx.config.sandbox_resource_limits,
+            network_proxy: turn_ctx.config.sandbox_network_proxy,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
@@ -140,6 +146,7 @@ impl ToolOrchestrator {
 
                     let decision = tool.start_approval_async(req, approval_ctx).await;
                     otel.tool_decision(otel_tn, otel_ci, &decision, otel_user);
+                    record_approval_decision(tool_ctx, &decision).await;
 
                     match decision {
                         ReviewDecision::Denied | ReviewDecision::Abort => {
@@ -156,6 +163,9 @@ impl ToolOrchestrator {
                     policy: &turn_ctx.sandbox_policy,
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
+                    denied_read_paths: &[],
+                    resource_limits: turn_ctx.config.sandbox_resource_limits,
+                    network_proxy: None,
                     codex_linux_sandbox_exe: None,
                     use_linux_sandbox_bwrap,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
@@ -174,3 +184,13 @@ fn build_denial_reason_from_output(_output: &ExecToolCallOutput) -> String {
     // output so we can evolve heuristics later without touching call sites.
     "command failed; retry without sandbox?".to_string()
 }
+
+/// Persist the user's answer so usage statistics can report approval rates.
+async fn record_approval_decision(tool_ctx: &ToolCtx<'_>, decision: &ReviewDecision) {
+    let item = RolloutItem::ApprovalDecision(ApprovalDecisionItem {
+        call_id: tool_ctx.call_id.clone(),
+        tool_name: tool_ctx.tool_name.clone(),
+        decision: decision.clone(),
+    });
+    tool_ctx.session.persist_rollout_items(&[item]).await;
+}
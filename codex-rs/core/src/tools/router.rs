@@ -146,6 +146,7 @@ impl ToolRouter {
         } = call;
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
         let failure_call_id = call_id.clone();
+        session.record_tool_call_for_budget().await;
 
         let invocation = ToolInvocation {
             session,
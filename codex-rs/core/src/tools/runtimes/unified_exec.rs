@@ -202,6 +202,12 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
                         output: Box::new(output),
                     }))
                 }
+                UnifiedExecError::ResourceLimitExceeded { limit, output, .. } => {
+                    ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimitExceeded {
+                        limit,
+                        output: Box::new(output),
+                    }))
+                }
                 other => ToolError::Rejected(other.to_string()),
             })
     }
@@ -6,15 +6,18 @@
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::SandboxResourceLimits;
 use crate::error::CodexErr;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxTransformError;
+use crate::sandboxing::network_proxy::SandboxNetworkProxy;
 use crate::state::SessionServices;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -273,6 +276,9 @@ pub(crate) struct SandboxAttempt<'a> {
     pub policy: &'a crate::protocol::SandboxPolicy,
     pub(crate) manager: &'a SandboxManager,
     pub(crate) sandbox_cwd: &'a Path,
+    pub(crate) denied_read_paths: &'a [AbsolutePathBuf],
+    pub(crate) resource_limits: SandboxResourceLimits,
+    pub(crate) network_proxy: Option<SandboxNetworkProxy>,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
@@ -289,6 +295,9 @@ impl<'a> SandboxAttempt<'a> {
                 policy: self.policy,
                 sandbox: self.sandbox,
                 sandbox_policy_cwd: self.sandbox_cwd,
+                denied_read_paths: self.denied_read_paths,
+                resource_limits: self.resource_limits,
+                network_proxy: self.network_proxy,
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
                 windows_sandbox_level: self.windows_sandbox_level,
@@ -1,6 +1,7 @@
 use crate::agent::AgentRole;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::CustomToolConfig;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::tools::handlers::PLAN_TOOL;
@@ -32,8 +33,11 @@ pub(crate) struct ToolsConfig {
     pub collab_tools: bool,
     pub collaboration_modes_tools: bool,
     pub memory_tools: bool,
+    pub code_intel_tools: bool,
+    pub fetch_url_tool: bool,
     pub request_rule_enabled: bool,
     pub experimental_supported_tools: Vec<String>,
+    pub custom_tools: Vec<CustomToolConfig>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -53,6 +57,8 @@ impl ToolsConfig {
         let include_collab_tools = features.enabled(Feature::Collab);
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let include_memory_tools = features.enabled(Feature::MemoryTool);
+        let include_code_intel_tools = features.enabled(Feature::CodeIntel);
+        let include_fetch_url_tool = features.enabled(Feature::FetchUrl);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
@@ -87,10 +93,19 @@ impl ToolsConfig {
             collab_tools: include_collab_tools,
             collaboration_modes_tools: include_collaboration_modes_tools,
             memory_tools: include_memory_tools,
+            code_intel_tools: include_code_intel_tools,
+            fetch_url_tool: include_fetch_url_tool,
             request_rule_enabled,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
+            custom_tools: Vec::new(),
         }
     }
+
+    /// Offer the config-defined command tools to the model as well.
+    pub fn with_custom_tools(mut self, custom_tools: Vec<CustomToolConfig>) -> Self {
+        self.custom_tools = custom_tools;
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -682,6 +697,165 @@ fn create_get_memory_tool() -> ToolSpec {
     })
 }
 
+fn create_remember_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "content".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "The fact to remember, written so it makes sense without this conversation."
+                    .to_string(),
+            ),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "remember".to_string(),
+        description: "Saves a durable fact about the current repository for future sessions, \
+                      such as a build quirk or a test command. Saved memories are shown at the \
+                      start of later sessions in this repository. Only save facts that will stay \
+                      true and are not obvious from the code."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["content".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_forget_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "id".to_string(),
+        JsonSchema::Number {
+            description: Some("Id of the memory to delete, as shown in <memories>.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "forget".to_string(),
+        description: "Deletes a saved memory that is wrong or no longer applies.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_fetch_url_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "url".to_string(),
+            JsonSchema::String {
+                description: Some("Absolute http or https URL to fetch.".to_string()),
+            },
+        ),
+        (
+            "offset".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Character offset to start from when paging through a long page. \
+                     Defaults to 0."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "fetch_url".to_string(),
+        description: "Downloads a web page and returns its main content as Markdown. Long \
+                      pages are returned in chunks; the header says which offset to pass for \
+                      the next one. Pages are cached for the session. Prefer this over \
+                      running curl in the shell."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_code_intel_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "action".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "One of \"diagnostics\", \"definition\", \"references\" or \
+                     \"workspace_symbols\"."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "paths".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(
+                    "Files to check for diagnostics. Defaults to the files changed by \
+                     apply_patch in this turn."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "File containing the symbol for definition and references. For \
+                     workspace_symbols, selects the language server by file type."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "line".to_string(),
+            JsonSchema::Number {
+                description: Some("1-based line of the symbol.".to_string()),
+            },
+        ),
+        (
+            "column".to_string(),
+            JsonSchema::Number {
+                description: Some("1-based column of the symbol.".to_string()),
+            },
+        ),
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some("Symbol name (or prefix) for workspace_symbols.".to_string()),
+            },
+        ),
+        (
+            "server".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Configured language server to query for workspace_symbols.".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "code_intel".to_string(),
+        description: "Queries the workspace's language servers: compiler diagnostics for files \
+                      (check after apply_patch), go-to-definition, find-references and workspace \
+                      symbol search. Results are `path:line:column` lines."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["action".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_close_agent_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -1136,6 +1310,19 @@ pub(crate) fn mcp_tool_to_openai_tool(
     })
 }
 
+fn custom_tool_to_openai_tool(
+    tool: &CustomToolConfig,
+) -> Result<ResponsesApiTool, serde_json::Error> {
+    let input_schema = parse_tool_input_schema(&tool.input_schema)?;
+
+    Ok(ResponsesApiTool {
+        name: tool.name.clone(),
+        description: tool.description.clone(),
+        strict: false,
+        parameters: input_schema,
+    })
+}
+
 fn dynamic_tool_to_openai_tool(
     tool: &DynamicToolSpec,
 ) -> Result<ResponsesApiTool, serde_json::Error> {
@@ -1274,13 +1461,17 @@ pub(crate) fn build_specs(
     dynamic_tools: &[DynamicToolSpec],
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::CodeIntelHandler;
     use crate::tools::handlers::CollabHandler;
+    use crate::tools::handlers::CustomToolHandler;
     use crate::tools::handlers::DynamicToolHandler;
+    use crate::tools::handlers::FetchUrlHandler;
     use crate::tools::handlers::GetMemoryHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
+    use crate::tools::handlers::MemoryHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::RequestUserInputHandler;
@@ -1361,6 +1552,21 @@ pub(crate) fn build_specs(
     if config.memory_tools {
         builder.push_spec(create_get_memory_tool());
         builder.register_handler("get_memory", get_memory_handler);
+        let memory_handler = Arc::new(MemoryHandler);
+        builder.push_spec(create_remember_tool());
+        builder.push_spec(create_forget_tool());
+        builder.register_handler("remember", memory_handler.clone());
+        builder.register_handler("forget", memory_handler);
+    }
+
+    if config.code_intel_tools {
+        builder.push_spec_with_parallel_support(create_code_intel_tool(), true);
+        builder.register_handler("code_intel", Arc::new(CodeIntelHandler));
+    }
+
+    if config.fetch_url_tool {
+        builder.push_spec_with_parallel_support(create_fetch_url_tool(), true);
+        builder.register_handler("fetch_url", Arc::new(FetchUrlHandler));
     }
 
     if let Some(apply_patch_tool_type) = &config.apply_patch_tool_type {
@@ -1477,6 +1683,31 @@ pub(crate) fn build_specs(
         }
     }
 
+    if !config.custom_tools.is_empty() {
+        let custom_tool_handler = Arc::new(CustomToolHandler);
+        for tool in &config.custom_tools {
+            if builder.has_handler(&tool.name) {
+                tracing::warn!(
+                    "Skipping custom tool {:?}: another tool already uses that name",
+                    tool.name
+                );
+                continue;
+            }
+            match custom_tool_to_openai_tool(tool) {
+                Ok(converted_tool) => {
+                    builder.push_spec(ToolSpec::Function(converted_tool));
+                    builder.register_handler(tool.name.clone(), custom_tool_handler.clone());
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to convert custom tool {:?} to OpenAI tool: {e:?}",
+                        tool.name
+                    );
+                }
+            }
+        }
+    }
+
     builder
 }
 
@@ -1733,7 +1964,7 @@ mod tests {
     }
 
     #[test]
-    fn get_memory_requires_memory_tool_feature() {
+    fn memory_tools_require_memory_tool_feature() {
         let config = test_config();
         let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
         let mut features = Features::with_defaults();
@@ -1756,7 +1987,92 @@ mod tests {
             web_search_mode: Some(WebSearchMode::Cached),
         });
         let (tools, _) = build_specs(&tools_config, None, &[]).build();
-        assert_contains_tool_names(&tools, &["get_memory"]);
+        assert_contains_tool_names(&tools, &["get_memory", "remember", "forget"]);
+    }
+
+    #[test]
+    fn code_intel_requires_code_intel_feature() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(
+            !tools.iter().any(|t| t.spec.name() == "code_intel"),
+            "code_intel should be disabled by default"
+        );
+
+        features.enable(Feature::CodeIntel);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(find_tool(&tools, "code_intel").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn fetch_url_requires_fetch_url_feature() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(
+            !tools.iter().any(|t| t.spec.name() == "fetch_url"),
+            "fetch_url should be disabled by default"
+        );
+
+        features.enable(Feature::FetchUrl);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(find_tool(&tools, "fetch_url").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn custom_tools_are_registered_unless_the_name_is_taken() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let custom_tool = |name: &str| CustomToolConfig {
+            name: name.to_string(),
+            description: format!("custom {name}"),
+            command: vec!["true".to_string()],
+            input_schema: json!({ "type": "object", "properties": {} }),
+            timeout_ms: None,
+            cwd: std::path::PathBuf::from("/tmp"),
+        };
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        })
+        .with_custom_tools(vec![custom_tool("codegen"), custom_tool("update_plan")]);
+
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+
+        let ToolSpec::Function(codegen) = &find_tool(&tools, "codegen").spec else {
+            panic!("codegen should be a function tool");
+        };
+        assert_eq!(codegen.description, "custom codegen");
+        let plan_tools = tools
+            .iter()
+            .filter(|tool| tool.spec.name() == "update_plan")
+            .count();
+        assert_eq!(plan_tools, 1);
     }
 
     fn assert_model_tools(
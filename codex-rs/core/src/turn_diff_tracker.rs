@@ -47,6 +47,14 @@ impl TurnDiffTracker {
         Self::default()
     }
 
+    /// Current paths of every file touched by a patch this turn, following
+    /// renames, in sorted order.
+    pub fn touched_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.temp_name_to_current_path.values().cloned().collect();
+        paths.sort();
+        paths
+    }
+
     /// Front-run apply patch calls to track the starting contents of any modified files.
     /// - Creates an in-memory baseline snapshot for files that already exist on disk when first seen.
     /// - For additions, we intentionally do not create a baseline snapshot so that diffs are proper additions.
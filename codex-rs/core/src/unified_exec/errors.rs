@@ -1,4 +1,5 @@
 use crate::exec::ExecToolCallOutput;
+use crate::sandboxing::resource_limits::ResourceLimitKind;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -21,6 +22,12 @@ pub(crate) enum UnifiedExecError {
         message: String,
         output: ExecToolCallOutput,
     },
+    #[error("Command exceeded its {limit} limit: {message}")]
+    ResourceLimitExceeded {
+        limit: ResourceLimitKind,
+        message: String,
+        output: ExecToolCallOutput,
+    },
 }
 
 impl UnifiedExecError {
@@ -31,4 +38,16 @@ impl UnifiedExecError {
     pub(crate) fn sandbox_denied(message: String, output: ExecToolCallOutput) -> Self {
         Self::SandboxDenied { message, output }
     }
+
+    pub(crate) fn resource_limit_exceeded(
+        limit: ResourceLimitKind,
+        message: String,
+        output: ExecToolCallOutput,
+    ) -> Self {
+        Self::ResourceLimitExceeded {
+            limit,
+            message,
+            output,
+        }
+    }
 }
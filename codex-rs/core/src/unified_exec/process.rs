@@ -13,6 +13,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::sandboxing::resource_limits::ResourceLimitScope;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
@@ -38,6 +39,7 @@ pub(crate) struct UnifiedExecProcess {
     output_drained: Arc<Notify>,
     output_task: JoinHandle<()>,
     sandbox_type: SandboxType,
+    resource_limits: ResourceLimitScope,
 }
 
 impl UnifiedExecProcess {
@@ -45,6 +47,7 @@ impl UnifiedExecProcess {
         process_handle: ExecCommandSession,
         initial_output_rx: tokio::sync::broadcast::Receiver<Vec<u8>>,
         sandbox_type: SandboxType,
+        resource_limits: ResourceLimitScope,
     ) -> Self {
         let output_buffer = Arc::new(Mutex::new(HeadTailBuffer::default()));
         let output_notify = Arc::new(Notify::new());
@@ -76,6 +79,7 @@ impl UnifiedExecProcess {
             output_drained,
             output_task,
             sandbox_type,
+            resource_limits,
         }
     }
 
@@ -142,12 +146,13 @@ impl UnifiedExecProcess {
         Ok(())
     }
 
+    /// Also reports processes that were stopped by one of the configured
+    /// resource limits, which applies whether or not a sandbox is in use.
     pub(super) async fn check_for_sandbox_denial_with_text(
         &self,
         text: &str,
     ) -> Result<(), UnifiedExecError> {
-        let sandbox_type = self.sandbox_type();
-        if sandbox_type == SandboxType::None || !self.has_exited() {
+        if !self.has_exited() {
             return Ok(());
         }
 
@@ -158,17 +163,29 @@ impl UnifiedExecProcess {
             aggregated_output: StreamOutput::new(text.to_string()),
             ..Default::default()
         };
-        if is_likely_sandbox_denied(sandbox_type, &exec_output) {
+        let message = || {
             let snippet = formatted_truncate_text(
                 text,
                 TruncationPolicy::Tokens(UNIFIED_EXEC_OUTPUT_MAX_TOKENS),
             );
-            let message = if snippet.is_empty() {
+            if snippet.is_empty() {
                 format!("Process exited with code {exit_code}")
             } else {
                 snippet
-            };
-            return Err(UnifiedExecError::sandbox_denied(message, exec_output));
+            }
+        };
+        if let Some(limit) = self.resource_limits.exceeded_limit(exit_code) {
+            return Err(UnifiedExecError::resource_limit_exceeded(
+                limit,
+                message(),
+                exec_output,
+            ));
+        }
+
+        let sandbox_type = self.sandbox_type();
+        if sandbox_type != SandboxType::None && is_likely_sandbox_denied(sandbox_type, &exec_output)
+        {
+            return Err(UnifiedExecError::sandbox_denied(message(), exec_output));
         }
         Ok(())
     }
@@ -176,13 +193,14 @@ impl UnifiedExecProcess {
     pub(super) async fn from_spawned(
         spawned: SpawnedPty,
         sandbox_type: SandboxType,
+        resource_limits: ResourceLimitScope,
     ) -> Result<Self, UnifiedExecError> {
         let SpawnedPty {
             session: process_handle,
             output_rx,
             mut exit_rx,
         } = spawned;
-        let managed = Self::new(process_handle, output_rx, sandbox_type);
+        let managed = Self::new(process_handle, output_rx, sandbox_type, resource_limits);
 
         let exit_ready = matches!(exit_rx.try_recv(), Ok(_) | Err(TryRecvError::Closed));
 
@@ -14,6 +14,7 @@ use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::protocol::ExecCommandSource;
 use crate::sandboxing::ExecEnv;
+use crate::sandboxing::resource_limits::ResourceLimitScope;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::events::ToolEventStage;
@@ -448,33 +449,43 @@ impl UnifiedExecProcessManager {
         env: &ExecEnv,
         tty: bool,
     ) -> Result<UnifiedExecProcess, UnifiedExecError> {
-        let (program, args) = env
-            .command
+        let resource_limits = ResourceLimitScope::new(env.resource_limits);
+        // Neither spawner takes a `pre_exec` hook, so limits are applied by a
+        // launcher that execs the command (by its `arg0` alias, if any).
+        #[cfg(unix)]
+        let (command, arg0) = match env.command.split_first() {
+            Some((program, args)) => {
+                let mut command = vec![env.arg0.clone().unwrap_or_else(|| program.clone())];
+                command.extend(args.iter().cloned());
+                match resource_limits.launcher_command(&command) {
+                    Some(launcher) => (launcher, None),
+                    None => (env.command.clone(), env.arg0.clone()),
+                }
+            }
+            None => (Vec::new(), None),
+        };
+        #[cfg(not(unix))]
+        let (command, arg0) = (env.command.clone(), env.arg0.clone());
+        let (program, args) = command
             .split_first()
             .ok_or(UnifiedExecError::MissingCommandLine)?;
 
         let spawn_result = if tty {
-            codex_utils_pty::pty::spawn_process(
-                program,
-                args,
-                env.cwd.as_path(),
-                &env.env,
-                &env.arg0,
-            )
-            .await
+            codex_utils_pty::pty::spawn_process(program, args, env.cwd.as_path(), &env.env, &arg0)
+                .await
         } else {
             codex_utils_pty::pipe::spawn_process_no_stdin(
                 program,
                 args,
                 env.cwd.as_path(),
                 &env.env,
-                &env.arg0,
+                &arg0,
             )
             .await
         };
         let spawned =
             spawn_result.map_err(|err| UnifiedExecError::create_process(err.to_string()))?;
-        UnifiedExecProcess::from_spawned(spawned, env.sandbox).await
+        UnifiedExecProcess::from_spawned(spawned, env.sandbox, resource_limits).await
     }
 
     pub(super) async fn open_session_with_sandbox(
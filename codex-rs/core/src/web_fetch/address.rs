@@ -0,0 +1,148 @@
+//! Keeps direct `fetch_url` requests on public addresses, so a page cannot be
+//! used to reach services on the local machine or network (including cloud
+//! metadata endpoints) that sandboxed commands cannot reach either.
+//!
+//! The address classes match `is_non_public_ip` in `codex-network-proxy`,
+//! which depends on this crate and so cannot be used from here.
+
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::SocketAddr;
+
+use anyhow::bail;
+use reqwest::dns::Addrs;
+use reqwest::dns::Name;
+use reqwest::dns::Resolve;
+use reqwest::dns::Resolving;
+use url::Host;
+use url::Url;
+
+/// Rejects URLs whose host is a non-public IP literal. Host names are checked
+/// when [`PublicAddressResolver`] resolves them.
+pub(super) fn ensure_public_host(url: &Url) -> anyhow::Result<()> {
+    let ip = match url.host() {
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(Host::Domain(_)) | None => return Ok(()),
+    };
+    if !is_public_ip(ip) {
+        bail!("{ip} is not a public address; fetch_url only connects directly to public hosts");
+    }
+    Ok(())
+}
+
+/// Resolves host names with the system resolver and fails when any address
+/// is not public.
+pub(super) struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+                return Err(format!(
+                    "{host} resolves to {}, which is not a public address; fetch_url only connects directly to public hosts",
+                    addr.ip()
+                )
+                .into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || ip.is_broadcast()
+        || ipv4_in_cidr(ip, [0, 0, 0, 0], 8) // "this network" (RFC 1122)
+        || ipv4_in_cidr(ip, [100, 64, 0, 0], 10) // CGNAT (RFC 6598)
+        || ipv4_in_cidr(ip, [192, 0, 0, 0], 24) // IETF Protocol Assignments (RFC 6890)
+        || ipv4_in_cidr(ip, [192, 0, 2, 0], 24) // TEST-NET-1 (RFC 5737)
+        || ipv4_in_cidr(ip, [198, 18, 0, 0], 15) // Benchmarking (RFC 2544)
+        || ipv4_in_cidr(ip, [198, 51, 100, 0], 24) // TEST-NET-2 (RFC 5737)
+        || ipv4_in_cidr(ip, [203, 0, 113, 0], 24) // TEST-NET-3 (RFC 5737)
+        || ipv4_in_cidr(ip, [240, 0, 0, 0], 4)) // Reserved (RFC 6890)
+}
+
+fn ipv4_in_cidr(ip: Ipv4Addr, base: [u8; 4], prefix: u8) -> bool {
+    let mask = u32::MAX << (32 - prefix);
+    (u32::from(ip) & mask) == (u32::from(Ipv4Addr::from(base)) & mask)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(v4) = ip.to_ipv4() {
+        return !ip.is_loopback() && is_public_ipv4(v4);
+    }
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn classifies_public_and_non_public_addresses() {
+        let cases = [
+            ("8.8.8.8", true),
+            ("2606:4700:4700::1111", true),
+            ("127.0.0.1", false),
+            ("10.1.2.3", false),
+            ("172.16.0.1", false),
+            ("192.168.1.1", false),
+            ("169.254.169.254", false),
+            ("100.64.0.1", false),
+            ("0.0.0.0", false),
+            ("::1", false),
+            ("fe80::1", false),
+            ("fd00::1", false),
+            ("::ffff:127.0.0.1", false),
+        ];
+        for (ip, public) in cases {
+            let parsed: IpAddr = ip.parse().expect("ip literal");
+            assert_eq!(is_public_ip(parsed), public, "{ip}");
+        }
+    }
+
+    #[test]
+    fn rejects_non_public_ip_literals_only() -> anyhow::Result<()> {
+        for url in [
+            "http://127.0.0.1:8080/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]/",
+        ] {
+            assert!(ensure_public_host(&Url::parse(url)?).is_err(), "{url}");
+        }
+        ensure_public_host(&Url::parse("https://8.8.8.8/")?)?;
+        ensure_public_host(&Url::parse("https://example.com/")?)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resolver_refuses_names_for_non_public_addresses() {
+        let name: Name = "localhost".parse().expect("name");
+        let Err(err) = PublicAddressResolver.resolve(name).await else {
+            panic!("localhost should be refused");
+        };
+        assert!(err.to_string().contains("is not a public address"), "{err}");
+    }
+}
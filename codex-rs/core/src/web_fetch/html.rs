@@ -0,0 +1,742 @@
+//! HTML to Markdown conversion for `fetch_url`.
+//!
+//! This is a forgiving tokenizer rather than a full HTML parser. It keeps
+//! headings, paragraphs, lists, links, code, quotes and tables, drops scripts,
+//! styles, forms and navigation, and converts only the page's `<main>` element
+//! (or its single `<article>`) when it has one.
+
+use url::Url;
+
+pub(crate) struct Document {
+    pub(crate) title: Option<String>,
+    pub(crate) markdown: String,
+}
+
+/// Elements whose contents never make it into the output.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "aside", "button", "canvas", "dialog", "form", "iframe", "nav", "noscript", "object", "script",
+    "select", "style", "svg", "template", "title",
+];
+
+/// Page chrome, dropped only when the page has no main content element.
+/// Inside `<main>` or `<article>`, a header usually holds the title.
+const CHROME_ELEMENTS: &[&str] = &["footer", "header"];
+
+/// Elements whose contents are not markup; they are skipped without being
+/// tokenized.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "body",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "header",
+    "main",
+    "p",
+    "section",
+    "summary",
+];
+
+pub(crate) fn html_to_markdown(html: &str, base: &Url) -> Document {
+    let tokens = tokenize(html);
+    let title = page_title(&tokens);
+    let (tokens, has_main) = match main_content(&tokens) {
+        Some(main) => (main, true),
+        None => (tokens.as_slice(), false),
+    };
+    let mut writer = MarkdownWriter::new(base, !has_main);
+    for token in tokens {
+        writer.token(token);
+    }
+    Document {
+        title,
+        markdown: writer.finish(),
+    }
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    Text(&'a str),
+    Start {
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    End {
+        name: String,
+    },
+}
+
+impl Token<'_> {
+    fn is_start(&self, tag: &str) -> bool {
+        matches!(self, Token::Start { name, self_closing: false, .. } if name == tag)
+    }
+
+    fn is_end(&self, tag: &str) -> bool {
+        matches!(self, Token::End { name } if name == tag)
+    }
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if lt > 0 {
+            tokens.push(Token::Text(&rest[..lt]));
+        }
+        rest = &rest[lt..];
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').unwrap_or(after.len());
+            tokens.push(Token::End {
+                name: tag_name(&after[..end]).to_ascii_lowercase(),
+            });
+            rest = after.get(end + 1..).unwrap_or_default();
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let (token, after) = start_tag(&rest[1..]);
+            rest = after;
+            if let Token::Start {
+                name,
+                self_closing: false,
+                ..
+            } = &token
+                && RAW_TEXT_ELEMENTS.contains(&name.as_str())
+            {
+                let close = format!("</{name}");
+                let end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+                rest = &rest[end..];
+            }
+            tokens.push(token);
+        } else {
+            tokens.push(Token::Text("<"));
+            rest = &rest[1..];
+        }
+    }
+    tokens
+}
+
+fn tag_name(tag: &str) -> &str {
+    let end = tag
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':'))
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+/// Parses a start tag from just after its `<`, returning the token and the
+/// input after the closing `>`.
+fn start_tag(tag: &str) -> (Token<'static>, &str) {
+    let name = tag_name(tag);
+    let mut rest = &tag[name.len()..];
+    let mut attrs = Vec::new();
+    let mut self_closing = false;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            rest = after;
+            break;
+        }
+        if let Some(after) = rest.strip_prefix("/>") {
+            self_closing = true;
+            rest = after;
+            break;
+        }
+        let key_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        if key_len == 0 {
+            // A stray `=` or `/`.
+            rest = &rest[1..];
+            continue;
+        }
+        let key = rest[..key_len].to_ascii_lowercase();
+        rest = rest[key_len..].trim_start();
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            if let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') {
+                let quoted = &after[1..];
+                let end = quoted.find(quote).unwrap_or(quoted.len());
+                value = decode_entities(&quoted[..end]);
+                rest = quoted.get(end + 1..).unwrap_or_default();
+            } else {
+                let end = after
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(after.len());
+                value = decode_entities(&after[..end]);
+                rest = &after[end..];
+            }
+        }
+        attrs.push((key, value));
+    }
+    let token = Token::Start {
+        name: name.to_ascii_lowercase(),
+        attrs,
+        self_closing,
+    };
+    (token, rest)
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+fn page_title(tokens: &[Token<'_>]) -> Option<String> {
+    let start = tokens.iter().position(|token| token.is_start("title"))?;
+    let text: String = tokens[start + 1..]
+        .iter()
+        .take_while(|token| !token.is_end("title"))
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(decode_entities(text)),
+            _ => None,
+        })
+        .collect();
+    let title = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+/// The contents of `<main>` (or `role="main"`), or of the page's only
+/// `<article>`.
+fn main_content<'t, 'a>(tokens: &'t [Token<'a>]) -> Option<&'t [Token<'a>]> {
+    let main = tokens.iter().position(|token| match token {
+        Token::Start {
+            name,
+            attrs,
+            self_closing: false,
+        } => name == "main" || attr(attrs, "role") == Some("main"),
+        _ => false,
+    });
+    let start = match main {
+        Some(start) => start,
+        None => {
+            let mut articles = tokens
+                .iter()
+                .enumerate()
+                .filter(|(_, token)| token.is_start("article"))
+                .map(|(index, _)| index);
+            let first = articles.next()?;
+            if articles.next().is_some() {
+                return None;
+            }
+            first
+        }
+    };
+    let Token::Start { name, .. } = &tokens[start] else {
+        return None;
+    };
+    let mut depth = 0usize;
+    let mut end = tokens.len();
+    for (index, token) in tokens.iter().enumerate().skip(start + 1) {
+        if token.is_start(name) {
+            depth += 1;
+        } else if token.is_end(name) {
+            if depth == 0 {
+                end = index;
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    Some(&tokens[start + 1..end])
+}
+
+struct MarkdownWriter<'a> {
+    base: &'a Url,
+    skip_chrome: bool,
+    out: String,
+    /// The skipped element being passed over, and how deeply it is nested in
+    /// itself.
+    skipping: Option<(String, usize)>,
+    /// Open lists, with the next item number for ordered ones.
+    lists: Vec<Option<usize>>,
+    /// Set right after a list marker so block elements inside the item do not
+    /// break the line.
+    item_start: bool,
+    pre_depth: usize,
+    /// Open links: where their text starts in `out`, and the resolved target.
+    links: Vec<Option<(usize, String)>>,
+    /// Where each open blockquote starts in `out`.
+    quotes: Vec<usize>,
+    /// Open tables, and whether their header separator has been written.
+    tables: Vec<bool>,
+    /// Cells in the current row, and whether they were all header cells.
+    row: Option<(usize, bool)>,
+    in_cell: bool,
+}
+
+impl<'a> MarkdownWriter<'a> {
+    fn new(base: &'a Url, skip_chrome: bool) -> Self {
+        Self {
+            base,
+            skip_chrome,
+            out: String::new(),
+            skipping: None,
+            lists: Vec::new(),
+            item_start: false,
+            pre_depth: 0,
+            links: Vec::new(),
+            quotes: Vec::new(),
+            tables: Vec::new(),
+            row: None,
+            in_cell: false,
+        }
+    }
+
+    fn token(&mut self, token: &Token<'_>) {
+        if let Some((skipped, depth)) = &mut self.skipping {
+            if token.is_start(skipped) {
+                *depth += 1;
+            } else if token.is_end(skipped) {
+                if *depth == 0 {
+                    self.skipping = None;
+                } else {
+                    *depth -= 1;
+                }
+            }
+            return;
+        }
+        match token {
+            Token::Text(text) => self.text(text),
+            Token::Start {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                let skipped = SKIPPED_ELEMENTS.contains(&name.as_str())
+                    || (self.skip_chrome && CHROME_ELEMENTS.contains(&name.as_str()));
+                if skipped {
+                    if !self_closing {
+                        self.skipping = Some((name.clone(), 0));
+                    }
+                    return;
+                }
+                self.start(name, attrs);
+            }
+            Token::End { name } => self.end(name),
+        }
+    }
+
+    fn start(&mut self, name: &str, attrs: &[(String, String)]) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.break_line(true);
+                let level = usize::from(name.as_bytes()[1] - b'0');
+                self.push(&format!("{} ", "#".repeat(level)));
+            }
+            "br" => {
+                if self.pre_depth > 0 {
+                    self.out.push('\n');
+                } else {
+                    self.break_line(false);
+                }
+            }
+            "hr" => {
+                self.break_line(true);
+                self.push("---");
+                self.break_line(true);
+            }
+            "pre" => {
+                self.break_line(true);
+                self.push("```\n");
+                self.pre_depth += 1;
+            }
+            "code" if self.pre_depth == 0 => self.push("`"),
+            "strong" | "b" => self.push("**"),
+            "em" | "i" => self.push("*"),
+            "a" => {
+                let target = attr(attrs, "href")
+                    .filter(|href| !href.starts_with('#') && !href.starts_with("javascript:"))
+                    .and_then(|href| self.base.join(href).ok());
+                match target {
+                    Some(target) => {
+                        self.links.push(Some((self.out.len(), target.to_string())));
+                        self.push("[");
+                    }
+                    None => self.links.push(None),
+                }
+            }
+            "img" => {
+                let alt = attr(attrs, "alt").map(str::trim).unwrap_or_default();
+                if !alt.is_empty()
+                    && let Some(src) = attr(attrs, "src").and_then(|src| self.base.join(src).ok())
+                {
+                    self.push(&format!("![{alt}]({src})"));
+                }
+            }
+            "ul" | "ol" => {
+                self.break_line(self.lists.is_empty());
+                let start = attr(attrs, "start").and_then(|start| start.parse().ok());
+                self.lists
+                    .push((name == "ol").then_some(start.unwrap_or(1)));
+            }
+            "li" => {
+                self.item_start = false;
+                self.break_line(false);
+                trim_trailing_spaces(&mut self.out);
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(next)) => {
+                        *next += 1;
+                        format!("{}. ", *next - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.out.push_str(&indent);
+                self.out.push_str(&marker);
+                self.item_start = true;
+            }
+            "blockquote" => {
+                self.break_line(true);
+                self.quotes.push(self.out.len());
+            }
+            "table" => {
+                self.break_line(true);
+                self.tables.push(false);
+            }
+            "tr" => {
+                self.break_line(false);
+                self.out.push('|');
+                self.row = Some((0, true));
+            }
+            "th" | "td" => {
+                if let Some((_, all_headers)) = &mut self.row {
+                    *all_headers &= name == "th";
+                }
+                self.in_cell = true;
+                self.out.push(' ');
+            }
+            _ if BLOCK_ELEMENTS.contains(&name) => self.break_line(true),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => self.break_line(true),
+            "pre" if self.pre_depth > 0 => {
+                self.pre_depth -= 1;
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```");
+                self.break_line(true);
+            }
+            "code" if self.pre_depth == 0 => self.push("`"),
+            "strong" | "b" => self.push("**"),
+            "em" | "i" => self.push("*"),
+            "a" => {
+                if let Some(Some((start, target))) = self.links.pop() {
+                    let text = self.out[start + 1..].trim().to_string();
+                    self.out.truncate(start);
+                    if !text.is_empty() {
+                        self.push(&format!("[{text}]({target})"));
+                    }
+                }
+            }
+            "ul" | "ol" => {
+                self.lists.pop();
+                self.item_start = false;
+                self.break_line(self.lists.is_empty());
+            }
+            "blockquote" => {
+                if let Some(start) = self.quotes.pop() {
+                    let quoted: Vec<String> = self.out[start..]
+                        .trim()
+                        .lines()
+                        .map(|line| format!("> {line}").trim_end().to_string())
+                        .collect();
+                    self.out.truncate(start);
+                    self.out.push_str(&quoted.join("\n"));
+                    self.break_line(true);
+                }
+            }
+            "table" => {
+                self.tables.pop();
+                self.break_line(true);
+            }
+            "th" | "td" => {
+                trim_trailing_spaces(&mut self.out);
+                self.out.push_str(" |");
+                self.in_cell = false;
+                if let Some((cells, _)) = &mut self.row {
+                    *cells += 1;
+                }
+            }
+            "tr" => {
+                if let Some((cells, all_headers)) = self.row.take()
+                    && all_headers
+                    && cells > 0
+                    && let Some(separated) = self.tables.last_mut()
+                    && !*separated
+                {
+                    *separated = true;
+                    self.out.push_str("\n|");
+                    self.out.push_str(&" --- |".repeat(cells));
+                }
+                self.break_line(false);
+            }
+            _ if BLOCK_ELEMENTS.contains(&name) => self.break_line(true),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        let text = decode_entities(raw);
+        if self.pre_depth > 0 {
+            // Like browsers, drop the newline right after `<pre>`.
+            let text = match self.out.ends_with("```\n") {
+                true => text.strip_prefix('\n').unwrap_or(&text),
+                false => &text,
+            };
+            self.push(text);
+            return;
+        }
+        let mut pending_space = false;
+        for c in text.chars() {
+            if c.is_whitespace() {
+                pending_space = true;
+                continue;
+            }
+            if pending_space && !self.out.is_empty() && !self.out.ends_with(char::is_whitespace) {
+                self.out.push(' ');
+            }
+            pending_space = false;
+            self.item_start = false;
+            self.out.push(c);
+        }
+        if pending_space && !self.out.is_empty() && !self.out.ends_with(char::is_whitespace) {
+            self.out.push(' ');
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        self.item_start = false;
+        self.out.push_str(text);
+    }
+
+    /// Ends the current line, leaving a blank line after it when `blank` is
+    /// set. Inside lists, continuation lines are indented under the item
+    /// instead, and inside table cells the break becomes a space.
+    fn break_line(&mut self, blank: bool) {
+        if self.item_start || self.pre_depth > 0 {
+            return;
+        }
+        if self.in_cell {
+            if !self.out.ends_with(' ') {
+                self.out.push(' ');
+            }
+            return;
+        }
+        trim_trailing_spaces(&mut self.out);
+        if self.out.is_empty() {
+            return;
+        }
+        if !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        if blank && self.lists.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+        if !self.lists.is_empty() {
+            self.out.push_str(&"  ".repeat(self.lists.len()));
+        }
+    }
+
+    /// Trims trailing whitespace and collapses runs of blank lines outside
+    /// code blocks.
+    fn finish(mut self) -> String {
+        // Links left open by truncated input keep their text only.
+        for (start, _) in self.links.iter().rev().flatten() {
+            self.out.remove(*start);
+        }
+        if self.pre_depth > 0 {
+            self.out.push_str("\n```");
+        }
+        let mut markdown = String::new();
+        let mut in_fence = false;
+        let mut blank_lines = 0;
+        for line in self.out.lines() {
+            let line = line.trim_end();
+            if line.is_empty() && !in_fence {
+                blank_lines += 1;
+                continue;
+            }
+            if !markdown.is_empty() {
+                markdown.push('\n');
+                if blank_lines > 0 {
+                    markdown.push('\n');
+                }
+            }
+            blank_lines = 0;
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+            }
+            markdown.push_str(line);
+        }
+        markdown
+    }
+}
+
+fn trim_trailing_spaces(out: &mut String) {
+    let len = out.trim_end_matches([' ', '\t']).len();
+    out.truncate(len);
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end + 1])?, end + 2)));
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "middot" => '·',
+        "bull" => '•',
+        "times" => '×',
+        "deg" => '°',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        _ => return None,
+    };
+    Some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn convert(html: &str) -> Document {
+        let base = Url::parse("https://docs.example.com/guide/intro.html").expect("base url");
+        html_to_markdown(html, &base)
+    }
+
+    #[test]
+    fn converts_common_elements() {
+        let document = convert(
+            r#"<!DOCTYPE html>
+<html><head><title>Intro &amp; Setup</title>
+<style>body { color: red; }</style>
+<script>if (a < b) { document.write("<p>no</p>"); }</script></head>
+<body>
+<nav><a href="/">Home</a></nav>
+<h1>Getting   started</h1>
+<p>Install the <code>cli</code> with <a href="../install.html">the installer</a>,
+then read <em>the&nbsp;docs</em>.</p>
+<ul>
+  <li>First</li>
+  <li>Second
+    <ol><li>Nested</li></ol>
+  </li>
+</ul>
+<pre><code>cargo build
+
+cargo test
+</code></pre>
+<table><tr><th>Flag</th><th>Meaning</th></tr><tr><td>-q</td><td>Quiet</td></tr></table>
+<blockquote><p>Quoted</p></blockquote>
+<footer>Copyright</footer>
+</body></html>"#,
+        );
+
+        assert_eq!(document.title.as_deref(), Some("Intro & Setup"));
+        assert_eq!(
+            document.markdown,
+            "# Getting started
+
+Install the `cli` with [the installer](https://docs.example.com/install.html), then read *the docs*.
+
+- First
+- Second
+  1. Nested
+
+```
+cargo build
+
+cargo test
+```
+
+| Flag | Meaning |
+| --- | --- |
+| -q | Quiet |
+
+> Quoted"
+        );
+    }
+
+    #[test]
+    fn prefers_main_content() {
+        let document = convert(
+            r#"<header>Site banner</header>
+<div class="sidebar">Related links</div>
+<main><article><header><h2>Release notes</h2></header>
+<p>Version 2 is out.</p></article></main>
+<div>Comments</div>"#,
+        );
+
+        assert_eq!(document.title, None);
+        assert_eq!(document.markdown, "## Release notes\n\nVersion 2 is out.");
+    }
+}
@@ -0,0 +1,515 @@
+//! Downloads for the `fetch_url` tool.
+//!
+//! Pages are requested over the session's [`NetworkRoute`], checked against
+//! the `robots.txt` of every origin they redirect through, capped at
+//! [`MAX_RESPONSE_BYTES`] and converted to Markdown. Direct requests only
+//! connect to public addresses. Results are cached for the rest of the
+//! session, so paging through a long document downloads it once.
+
+mod address;
+mod html;
+mod robots;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::bail;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::header::LOCATION;
+use reqwest::redirect;
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::default_client::get_codex_user_agent;
+use address::PublicAddressResolver;
+use address::ensure_public_host;
+use robots::RobotsRules;
+
+/// Response bytes kept per page; anything past this is dropped.
+pub(crate) const MAX_RESPONSE_BYTES: usize = 5 * 1024 * 1024;
+
+const MAX_ROBOTS_TXT_BYTES: usize = 512 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_REDIRECTS: usize = 5;
+
+/// Product token matched against `User-agent` lines in `robots.txt`.
+const ROBOTS_USER_AGENT: &str = "codex";
+
+/// Header the network proxy sets on responses it generates for blocked
+/// requests.
+const PROXY_ERROR_HEADER: &str = "x-proxy-error";
+
+/// How `fetch_url` requests reach the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NetworkRoute {
+    /// Connect directly; the sandbox policy allows full network access.
+    /// Loopback, link-local and private addresses are refused.
+    Direct,
+    /// Go through the session's network proxy so its allow and deny lists
+    /// apply.
+    Proxy(SocketAddr),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct FetchedPage {
+    /// Final URL after redirects.
+    pub(crate) url: String,
+    pub(crate) title: Option<String>,
+    /// Markdown for HTML pages; other text formats are kept as-is.
+    pub(crate) content: String,
+    /// Whether the response was cut off at [`MAX_RESPONSE_BYTES`].
+    pub(crate) truncated: bool,
+}
+
+/// Session-scoped page and `robots.txt` cache.
+#[derive(Default)]
+pub(crate) struct WebFetcher {
+    pages: Mutex<HashMap<String, Arc<FetchedPage>>>,
+    robots: Mutex<HashMap<String, Arc<RobotsRules>>>,
+    /// Lets direct requests reach non-public addresses; only tests, which
+    /// serve pages from loopback, set this.
+    allow_non_public: bool,
+}
+
+impl WebFetcher {
+    pub(crate) async fn fetch(
+        &self,
+        url: &Url,
+        route: NetworkRoute,
+    ) -> anyhow::Result<Arc<FetchedPage>> {
+        if let Some(page) = self.pages.lock().await.get(url.as_str()) {
+            return Ok(Arc::clone(page));
+        }
+
+        let public_only = route == NetworkRoute::Direct && !self.allow_non_public;
+        // Redirects are followed here rather than by reqwest so every hop is
+        // checked against its own origin's `robots.txt`.
+        let client = build_client(route, public_only, redirect::Policy::none())?;
+        let robots_client = build_client(route, public_only, robots_redirect_policy(public_only))?;
+        let mut current = url.clone();
+        let mut redirects = 0;
+        let response = loop {
+            if public_only {
+                ensure_public_host(&current)?;
+            }
+            self.ensure_robots_allow(&robots_client, &current).await?;
+            let response = send(&client, &current, route).await?;
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|value| value.to_str().ok());
+            let Some(location) = location.filter(|_| response.status().is_redirection()) else {
+                break response;
+            };
+            if redirects == MAX_REDIRECTS {
+                bail!("{url} redirected more than {MAX_REDIRECTS} times");
+            }
+            redirects += 1;
+            let next = current.join(location).with_context(|| {
+                format!("{current} redirected to invalid location `{location}`")
+            })?;
+            if !matches!(next.scheme(), "http" | "https") {
+                bail!("{current} redirected to unsupported URL {next}");
+            }
+            current = next;
+        };
+
+        let page = Arc::new(into_page(response, &current).await?);
+        self.pages
+            .lock()
+            .await
+            .insert(url.to_string(), Arc::clone(&page));
+        Ok(page)
+    }
+
+    async fn ensure_robots_allow(&self, client: &reqwest::Client, url: &Url) -> anyhow::Result<()> {
+        let robots = self.robots_rules(client, url).await;
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        if !robots.allows(&path) {
+            bail!(
+                "robots.txt for {} disallows fetching {path}",
+                url.origin().ascii_serialization()
+            );
+        }
+        Ok(())
+    }
+
+    async fn robots_rules(&self, client: &reqwest::Client, url: &Url) -> Arc<RobotsRules> {
+        let origin = url.origin().ascii_serialization();
+        if let Some(rules) = self.robots.lock().await.get(&origin) {
+            return Arc::clone(rules);
+        }
+        let rules = Arc::new(fetch_robots_txt(client, url).await.unwrap_or_default());
+        self.robots.lock().await.insert(origin, Arc::clone(&rules));
+        rules
+    }
+}
+
+fn build_client(
+    route: NetworkRoute,
+    public_only: bool,
+    redirect_policy: redirect::Policy,
+) -> anyhow::Result<reqwest::Client> {
+    // Unlike `build_reqwest_client`, this sends no Codex-specific headers:
+    // these requests go to arbitrary sites.
+    let builder = reqwest::Client::builder()
+        .user_agent(get_codex_user_agent())
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(redirect_policy);
+    let builder = match route {
+        NetworkRoute::Direct => builder.no_proxy(),
+        NetworkRoute::Proxy(addr) => builder.proxy(reqwest::Proxy::all(format!("http://{addr}"))?),
+    };
+    // Resolving through `PublicAddressResolver` checks the addresses actually
+    // connected to, so a name cannot be re-pointed at a private address after
+    // it was checked.
+    let builder = if public_only {
+        builder.dns_resolver(Arc::new(PublicAddressResolver))
+    } else {
+        builder
+    };
+    Ok(builder.build()?)
+}
+
+/// `robots.txt` redirects are followed by reqwest, refusing hops to
+/// non-public IP literals like every other direct request.
+fn robots_redirect_policy(public_only: bool) -> redirect::Policy {
+    redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() > MAX_REDIRECTS {
+            attempt.error(format!("more than {MAX_REDIRECTS} redirects"))
+        } else if public_only && let Err(err) = ensure_public_host(attempt.url()) {
+            attempt.error(err.to_string())
+        } else {
+            attempt.follow()
+        }
+    })
+}
+
+/// A missing or unreachable `robots.txt` places no restrictions.
+async fn fetch_robots_txt(client: &reqwest::Client, url: &Url) -> Option<RobotsRules> {
+    let response = client
+        .get(url.join("/robots.txt").ok()?)
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let (body, _) = read_body(response, MAX_ROBOTS_TXT_BYTES).await.ok()?;
+    Some(RobotsRules::parse(
+        &String::from_utf8_lossy(&body),
+        ROBOTS_USER_AGENT,
+    ))
+}
+
+async fn send(
+    client: &reqwest::Client,
+    url: &Url,
+    route: NetworkRoute,
+) -> anyhow::Result<reqwest::Response> {
+    client
+        .get(url.clone())
+        .send()
+        .await
+        .with_context(|| match route {
+            NetworkRoute::Direct => format!("request to {url} failed"),
+            NetworkRoute::Proxy(_) => format!(
+                "request to {url} through the network proxy failed; the host may not be allowed by the network policy"
+            ),
+        })
+}
+
+/// Converts the response from `url`, the last hop of any redirects.
+async fn into_page(response: reqwest::Response, url: &Url) -> anyhow::Result<FetchedPage> {
+    let status = response.status();
+    if let Some(reason) = response
+        .headers()
+        .get(PROXY_ERROR_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        bail!("the network proxy blocked {url} ({reason})");
+    }
+    if !status.is_success() {
+        bail!("{url} returned HTTP {status}");
+    }
+
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let (body, truncated) = read_body(response, MAX_RESPONSE_BYTES).await?;
+    let body = String::from_utf8_lossy(&body);
+
+    let is_html = content_type.contains("html")
+        || (content_type.is_empty() && body.trim_start().starts_with('<'));
+    let is_text = content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+        || content_type.contains("markdown")
+        || content_type.is_empty();
+    let (title, content) = if is_html {
+        let document = html::html_to_markdown(&body, url);
+        (document.title, document.markdown)
+    } else if is_text {
+        (None, body.into_owned())
+    } else {
+        bail!("{url} returned unsupported content type `{content_type}`");
+    };
+
+    Ok(FetchedPage {
+        url: url.to_string(),
+        title,
+        content,
+        truncated,
+    })
+}
+
+/// Reads at most `limit` bytes of the body, reporting whether more remained.
+async fn read_body(
+    mut response: reqwest::Response,
+    limit: usize,
+) -> reqwest::Result<(Vec<u8>, bool)> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let remaining = limit - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    fn html_response(body: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_raw(body, "text/html; charset=utf-8")
+    }
+
+    /// The mock servers listen on loopback, which direct requests refuse.
+    fn local_fetcher() -> WebFetcher {
+        WebFetcher {
+            allow_non_public: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn converts_and_caches_pages() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/docs"))
+            .respond_with(html_response(
+                "<html><head><title>Docs</title></head>\
+                 <body><main><h1>Hello</h1><p>See <a href=\"/api\">the API</a>.</p></main></body></html>",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let fetcher = local_fetcher();
+        let url = Url::parse(&format!("{}/docs", server.uri()))?;
+
+        let page = fetcher.fetch(&url, NetworkRoute::Direct).await?;
+        let again = fetcher.fetch(&url, NetworkRoute::Direct).await?;
+
+        assert_eq!(
+            *page,
+            FetchedPage {
+                url: url.to_string(),
+                title: Some("Docs".to_string()),
+                content: format!("# Hello\n\nSee [the API]({}/api).", server.uri()),
+                truncated: false,
+            }
+        );
+        assert!(Arc::ptr_eq(&page, &again));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn respects_robots_txt() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /private\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/private/page"))
+            .respond_with(html_response("<p>secret</p>"))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/public"))
+            .respond_with(html_response("<p>open</p>"))
+            .mount(&server)
+            .await;
+        let fetcher = local_fetcher();
+
+        let err = fetcher
+            .fetch(
+                &Url::parse(&format!("{}/private/page", server.uri()))?,
+                NetworkRoute::Direct,
+            )
+            .await
+            .expect_err("robots.txt disallows /private");
+        assert!(err.to_string().contains("disallows fetching /private/page"));
+
+        let page = fetcher
+            .fetch(
+                &Url::parse(&format!("{}/public", server.uri()))?,
+                NetworkRoute::Direct,
+            )
+            .await?;
+        assert_eq!(page.content, "open");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn truncates_large_responses_and_rejects_binary_ones() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/large.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw("a".repeat(MAX_RESPONSE_BYTES + 10), "text/plain"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/image.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(vec![0x89, b'P'], "image/png"))
+            .mount(&server)
+            .await;
+        let fetcher = local_fetcher();
+
+        let page = fetcher
+            .fetch(
+                &Url::parse(&format!("{}/large.txt", server.uri()))?,
+                NetworkRoute::Direct,
+            )
+            .await?;
+        assert_eq!(page.content.len(), MAX_RESPONSE_BYTES);
+        assert!(page.truncated);
+
+        let err = fetcher
+            .fetch(
+                &Url::parse(&format!("{}/image.png", server.uri()))?,
+                NetworkRoute::Direct,
+            )
+            .await
+            .expect_err("images are not supported");
+        assert!(
+            err.to_string()
+                .contains("unsupported content type `image/png`")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn direct_requests_refuse_non_public_addresses() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(html_response("<p>internal</p>"))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let fetcher = WebFetcher::default();
+
+        for url in [
+            format!("{}/admin", server.uri()),
+            format!("http://localhost:{}/admin", server.address().port()),
+            "http://169.254.169.254/latest/meta-data/".to_string(),
+        ] {
+            let err = fetcher
+                .fetch(&Url::parse(&url)?, NetworkRoute::Direct)
+                .await
+                .expect_err("non-public addresses are refused");
+            assert!(
+                format!("{err:#}").contains("is not a public address"),
+                "{url}: {err:#}"
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn redirects_are_checked_against_each_origins_robots_txt() -> anyhow::Result<()> {
+        let start = MockServer::start().await;
+        let target = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/moved"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", format!("{}/private/page", target.uri())),
+            )
+            .mount(&start)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /private\n"),
+            )
+            .expect(1)
+            .mount(&target)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/private/page"))
+            .respond_with(html_response("<p>secret</p>"))
+            .expect(0)
+            .mount(&target)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/moved-public"))
+            .respond_with(ResponseTemplate::new(301).insert_header("location", "/public"))
+            .mount(&start)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/public"))
+            .respond_with(html_response("<p>open</p>"))
+            .mount(&start)
+            .await;
+        let fetcher = local_fetcher();
+
+        let err = fetcher
+            .fetch(
+                &Url::parse(&format!("{}/moved", start.uri()))?,
+                NetworkRoute::Direct,
+            )
+            .await
+            .expect_err("the redirect target is disallowed");
+        assert!(
+            err.to_string().contains(&format!(
+                "robots.txt for {} disallows fetching /private/page",
+                target.uri()
+            )),
+            "{err:#}"
+        );
+
+        let url = Url::parse(&format!("{}/moved-public", start.uri()))?;
+        let page = fetcher.fetch(&url, NetworkRoute::Direct).await?;
+        assert_eq!(page.url, format!("{}/public", start.uri()));
+        assert_eq!(page.content, "open");
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! `robots.txt` rules for `fetch_url`.
+
+/// Allow and disallow rules from the `robots.txt` group that applies to us.
+#[derive(Debug, Default)]
+pub(crate) struct RobotsRules {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+impl RobotsRules {
+    /// Rules from the groups naming `user_agent`, or from the `*` groups when
+    /// none do.
+    pub(crate) fn parse(robots_txt: &str, user_agent: &str) -> Self {
+        let mut own = Vec::new();
+        let mut wildcard = Vec::new();
+        let mut named = false;
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        for line in robots_txt.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+            match key.as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group.
+                    if in_rules {
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_ascii_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    let ours = group_agents
+                        .iter()
+                        .any(|agent| agent.eq_ignore_ascii_case(user_agent));
+                    named |= ours;
+                    // An empty `Disallow:` allows everything.
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = Rule {
+                        allow: key == "allow",
+                        pattern: value.to_string(),
+                    };
+                    if ours {
+                        own.push(rule);
+                    } else if group_agents.iter().any(|agent| agent == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Self {
+            rules: if named { own } else { wildcard },
+        }
+    }
+
+    /// Whether `path` (including any query string) may be fetched. The
+    /// longest matching rule wins, and `Allow` wins ties.
+    pub(crate) fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| pattern_matches(&rule.pattern, path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+}
+
+/// Prefix match supporting the `*` wildcard and a trailing `$` anchor.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (index, part) in parts.iter().enumerate() {
+        if anchored && index + 1 == parts.len() {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn applies_the_most_specific_group_and_rule() {
+        let robots_txt = "\
+User-agent: *
+Disallow: /
+
+User-agent: GoogleBot
+User-agent: Codex
+Disallow: /private
+Allow: /private/docs
+Disallow: /*.pdf$
+";
+        let rules = RobotsRules::parse(robots_txt, "codex");
+        let checks: Vec<(&str, bool)> = [
+            "/",
+            "/private/keys",
+            "/private/docs/intro",
+            "/papers/report.pdf",
+            "/papers/report.pdf?download=1",
+        ]
+        .into_iter()
+        .map(|path| (path, rules.allows(path)))
+        .collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("/", true),
+                ("/private/keys", false),
+                ("/private/docs/intro", true),
+                ("/papers/report.pdf", false),
+                ("/papers/report.pdf?download=1", true),
+            ]
+        );
+
+        let rules = RobotsRules::parse(robots_txt, "otherbot");
+        assert!(!rules.allows("/anything"));
+    }
+}
@@ -0,0 +1,325 @@
+//! Tracks files the user edits in the workspace between turns.
+//!
+//! The file watcher reports every change under the session's git repository.
+//! Changes seen while no turn is running are attributed to the user: they are
+//! summarized for the model at the start of the next turn, and `apply_patch`
+//! refuses to overwrite a user-edited file until the model has seen it again.
+//! Only `read_file` and `apply_patch` record what the model has seen; reads
+//! through shell commands are not tracked.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::models::DeveloperInstructions;
+use codex_protocol::models::ResponseItem;
+use ignore::Match;
+use ignore::gitignore::Gitignore;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::git_info::get_git_repo_root;
+
+/// Watcher events are throttled, so changes made by the model's own commands
+/// can arrive shortly after its turn ended.
+const TURN_END_GRACE: Duration = Duration::from_secs(2);
+
+/// Upper bound on files listed in a single report.
+const MAX_REPORTED_PATHS: usize = 50;
+
+type ContentHash = [u8; 32];
+
+#[derive(Default)]
+struct EditState {
+    /// Content the model last read or wrote, `None` when the file was absent.
+    known: HashMap<PathBuf, Option<ContentHash>>,
+    /// Files the user changed that the model has not seen since.
+    edited: BTreeSet<PathBuf>,
+    /// Files changed since the last report.
+    unreported: BTreeSet<PathBuf>,
+    turn_active: bool,
+    last_turn_end: Option<Instant>,
+}
+
+pub(crate) struct WorkspaceEdits {
+    root: Option<PathBuf>,
+    state: Mutex<EditState>,
+    gitignores: Mutex<HashMap<PathBuf, Gitignore>>,
+}
+
+impl WorkspaceEdits {
+    /// Tracks the git repository containing `cwd`. Outside a repository
+    /// nothing is tracked.
+    pub(crate) fn new(cwd: &Path) -> Self {
+        Self::with_root(get_git_repo_root(cwd))
+    }
+
+    /// A tracker that never reports anything, used when the feature is off.
+    pub(crate) fn disabled() -> Self {
+        Self::with_root(None)
+    }
+
+    fn with_root(root: Option<PathBuf>) -> Self {
+        Self {
+            root,
+            state: Mutex::new(EditState::default()),
+            gitignores: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn mark_turn_started(&self) {
+        self.lock_state().turn_active = true;
+    }
+
+    pub(crate) fn mark_turn_ended(&self) {
+        let mut state = self.lock_state();
+        state.turn_active = false;
+        state.last_turn_end = Some(Instant::now());
+    }
+
+    /// Remember the content the model just read or wrote at `path`.
+    pub(crate) fn record_model_version(&self, path: &Path) {
+        if !self.is_tracked(path) {
+            return;
+        }
+        let hash = content_hash(path);
+        let mut state = self.lock_state();
+        state.edited.remove(path);
+        state.known.insert(path.to_path_buf(), hash);
+    }
+
+    /// Record paths reported by the file watcher. Changes made while a turn
+    /// is running belong to the model and are ignored.
+    pub(crate) fn record_user_changes(&self, paths: &[PathBuf]) {
+        {
+            let state = self.lock_state();
+            if state.turn_active
+                || state
+                    .last_turn_end
+                    .is_some_and(|ended| ended.elapsed() < TURN_END_GRACE)
+            {
+                return;
+            }
+        }
+        for path in paths {
+            if !self.is_tracked(path) || path.is_dir() || self.is_ignored(path) {
+                continue;
+            }
+            let hash = content_hash(path);
+            let mut state = self.lock_state();
+            match state.known.get(path) {
+                Some(known) if *known == hash => continue,
+                // Editor scratch files that came and went.
+                None if hash.is_none() => continue,
+                _ => {}
+            }
+            state.edited.insert(path.clone());
+            state.unreported.insert(path.clone());
+        }
+    }
+
+    /// Developer message listing files changed since the last report, with
+    /// paths relative to `cwd` where possible.
+    pub(crate) fn take_report(&self, cwd: &Path) -> Option<ResponseItem> {
+        let paths = std::mem::take(&mut self.lock_state().unreported);
+        if paths.is_empty() {
+            return None;
+        }
+        let mut lines = vec![
+            "The user changed these files since your last turn. Re-read them before relying on earlier contents or editing them:".to_string(),
+        ];
+        for path in paths.iter().take(MAX_REPORTED_PATHS) {
+            let display = path.strip_prefix(cwd).unwrap_or(path).display();
+            if path.exists() {
+                lines.push(format!("- {display}"));
+            } else {
+                lines.push(format!("- {display} (deleted)"));
+            }
+        }
+        if paths.len() > MAX_REPORTED_PATHS {
+            lines.push(format!("... and {} more", paths.len() - MAX_REPORTED_PATHS));
+        }
+        Some(DeveloperInstructions::new(lines.join("\n")).into())
+    }
+
+    /// Paths among `paths` that the user changed after the model last read
+    /// them. A conflict is reported once: the current content becomes the
+    /// version the model is assumed to have seen, so a patch written after
+    /// re-reading goes through.
+    pub(crate) fn conflicts(&self, paths: &[PathBuf]) -> Vec<PathBuf> {
+        let mut conflicts = Vec::new();
+        let mut state = self.lock_state();
+        for path in paths {
+            if !state.edited.contains(path) {
+                continue;
+            }
+            let Some(known) = state.known.get(path).copied() else {
+                continue;
+            };
+            let current = content_hash(path);
+            if current != known {
+                conflicts.push(path.clone());
+            }
+            state.edited.remove(path);
+            state.known.insert(path.clone(), current);
+        }
+        conflicts
+    }
+
+    fn is_tracked(&self, path: &Path) -> bool {
+        self.root
+            .as_ref()
+            .is_some_and(|root| path.starts_with(root))
+    }
+
+    /// Whether a `.gitignore` between the repository root and `path`, or the
+    /// repository's `info/exclude`, ignores `path`. The nearest match wins.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let Some(root) = self.root.as_deref() else {
+            return false;
+        };
+        let mut gitignores = match self.gitignores.lock() {
+            Ok(gitignores) => gitignores,
+            Err(err) => err.into_inner(),
+        };
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(root) {
+                break;
+            }
+            let gitignore = gitignores
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| load_gitignore(dir, dir == root));
+            match gitignore.matched_path_or_any_parents(path, false) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, EditState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+fn load_gitignore(dir: &Path, is_root: bool) -> Gitignore {
+    let mut builder = ignore::gitignore::GitignoreBuilder::new(dir);
+    builder.add(dir.join(".gitignore"));
+    if is_root {
+        builder.add(dir.join(".git").join("info").join("exclude"));
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+fn content_hash(path: &Path) -> Option<ContentHash> {
+    let contents = std::fs::read(path).ok()?;
+    Some(Sha256::digest(contents).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn tracker(root: &TempDir) -> WorkspaceEdits {
+        WorkspaceEdits::with_root(Some(root.path().to_path_buf()))
+    }
+
+    fn report_text(item: ResponseItem) -> String {
+        let ResponseItem::Message { content, .. } = item else {
+            panic!("expected a message, got {item:?}");
+        };
+        content
+            .into_iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } => text,
+                other => panic!("unexpected content {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_user_edits_once_and_skips_ignored_files() {
+        let root = TempDir::new().expect("tempdir");
+        std::fs::write(root.path().join(".gitignore"), "*.log\n").expect("write");
+        std::fs::create_dir(root.path().join("src")).expect("mkdir");
+        let edited = root.path().join("src/lib.rs");
+        let ignored = root.path().join("build.log");
+        std::fs::write(&edited, "fn main() {}\n").expect("write");
+        std::fs::write(&ignored, "noise").expect("write");
+        let edits = tracker(&root);
+
+        edits.record_user_changes(&[edited, ignored, root.path().join("gone.swp")]);
+
+        let report = edits.take_report(root.path()).expect("report");
+        assert_eq!(
+            report_text(report),
+            "The user changed these files since your last turn. Re-read them before relying on earlier contents or editing them:\n- src/lib.rs"
+        );
+        assert!(edits.take_report(root.path()).is_none());
+    }
+
+    #[test]
+    fn changes_during_a_turn_are_not_attributed_to_the_user() {
+        let root = TempDir::new().expect("tempdir");
+        let file = root.path().join("a.txt");
+        std::fs::write(&file, "model").expect("write");
+        let edits = tracker(&root);
+
+        edits.mark_turn_started();
+        edits.record_user_changes(std::slice::from_ref(&file));
+        edits.mark_turn_ended();
+        edits.record_user_changes(std::slice::from_ref(&file));
+
+        assert!(edits.take_report(root.path()).is_none());
+    }
+
+    #[test]
+    fn patching_a_file_edited_after_the_last_read_conflicts_once() {
+        let root = TempDir::new().expect("tempdir");
+        let file = root.path().join("a.txt");
+        std::fs::write(&file, "original").expect("write");
+        let edits = tracker(&root);
+        edits.record_model_version(&file);
+
+        std::fs::write(&file, "user edit").expect("write");
+        edits.record_user_changes(std::slice::from_ref(&file));
+
+        assert_eq!(
+            edits.conflicts(std::slice::from_ref(&file)),
+            vec![file.clone()]
+        );
+        assert_eq!(
+            edits.conflicts(std::slice::from_ref(&file)),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn reading_again_clears_the_conflict() {
+        let root = TempDir::new().expect("tempdir");
+        let file = root.path().join("a.txt");
+        std::fs::write(&file, "original").expect("write");
+        let edits = tracker(&root);
+        edits.record_model_version(&file);
+
+        std::fs::write(&file, "user edit").expect("write");
+        edits.record_user_changes(std::slice::from_ref(&file));
+        edits.record_model_version(&file);
+
+        assert_eq!(
+            edits.conflicts(std::slice::from_ref(&file)),
+            Vec::<PathBuf>::new()
+        );
+    }
+}
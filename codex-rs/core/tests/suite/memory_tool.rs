@@ -2,6 +2,9 @@
 
 use anyhow::Result;
 use codex_core::features::Feature;
+use codex_core::state_db::MemoryScope;
+use codex_core::state_db::MemorySource;
+use codex_core::state_db::NewMemory;
 use core_test_support::responses::ev_assistant_message;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_response_created;
@@ -99,3 +102,41 @@ async fn get_memory_tool_returns_persisted_thread_memory() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn forget_refuses_memories_from_other_repositories() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mut builder = test_codex().with_config(|config| {
+        config.features.enable(Feature::Sqlite);
+        config.features.enable(Feature::MemoryTool);
+    });
+    let test = builder.build(&server).await?;
+
+    let db = test.codex.state_db().expect("state db enabled");
+    let foreign = db
+        .add_memory(&NewMemory {
+            scope: MemoryScope::Repo,
+            repo: Some("/elsewhere/other-repo".to_string()),
+            content: "Deploys go through the staging cluster.".to_string(),
+            source: MemorySource::User,
+            thread_id: None,
+        })
+        .await?;
+
+    let call_id = "forget-call-1";
+    let arguments = json!({ "id": foreign.id }).to_string();
+    let mocks = mount_function_call_agent_response(&server, call_id, &arguments, "forget").await;
+
+    test.submit_turn("forget that memory").await?;
+
+    let completion_request = mocks.completion.single_request();
+    let (content, _success) = completion_request
+        .function_call_output_content_and_success(call_id)
+        .expect("function_call_output should be present");
+    assert_eq!(content, Some(format!("no memory with id {}", foreign.id)));
+    assert_eq!(db.get_memory(foreign.id).await?, Some(foreign));
+
+    Ok(())
+}
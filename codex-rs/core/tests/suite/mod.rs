@@ -106,6 +106,7 @@ mod review;
 mod rmcp_client;
 mod rollout_list_find;
 mod seatbelt;
+mod session_budget;
 mod shell_command;
 mod shell_serialization;
 mod shell_snapshot;
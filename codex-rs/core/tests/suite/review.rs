@@ -80,6 +80,7 @@ async fn review_op_emits_lifecycle_and_review_output() {
                     instructions: "Please review my changes".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -201,6 +202,7 @@ async fn review_op_with_plain_text_emits_review_fallback() {
                     instructions: "Plain text review".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -262,6 +264,7 @@ async fn review_filters_agent_message_related_events() {
                     instructions: "Filter streaming events".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -344,6 +347,7 @@ async fn review_does_not_emit_agent_message_on_structured_output() {
                     instructions: "check structured".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -404,6 +408,7 @@ async fn review_uses_custom_review_model_from_config() {
                     instructions: "use custom model".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -457,6 +462,7 @@ async fn review_uses_session_model_when_review_model_unset() {
                     instructions: "use session model".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -575,6 +581,7 @@ async fn review_input_isolated_from_parent_history() {
                     instructions: review_prompt.clone(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -687,6 +694,7 @@ async fn review_history_surfaces_in_parent_session() {
                     instructions: "Start a review".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -838,6 +846,7 @@ async fn review_uses_overridden_cwd_for_base_branch_merge_base() {
                     branch: "main".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
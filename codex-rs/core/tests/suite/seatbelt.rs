@@ -189,6 +189,7 @@ assert os.read(master, 4) == b"ping""#
         command_cwd,
         &policy,
         sandbox_cwd.as_path(),
+        &[],
         StdioPolicy::RedirectForShellTool,
         HashMap::new(),
     )
@@ -241,6 +242,7 @@ async fn java_home_finds_runtime_under_seatbelt() {
         command_cwd,
         &policy,
         sandbox_cwd.as_path(),
+        &[],
         StdioPolicy::RedirectForShellTool,
         env,
     )
@@ -297,6 +299,7 @@ async fn touch(path: &Path, policy: &SandboxPolicy) -> bool {
         command_cwd,
         policy,
         sandbox_cwd.as_path(),
+        &[],
         StdioPolicy::RedirectForShellTool,
         HashMap::new(),
     )
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use codex_core::config::types::SessionBudgetConfig;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SessionBudgetKind;
+use codex_core::protocol::SessionBudgetUsage;
+use codex_core::protocol::TurnAbortReason;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_aborts_when_tool_call_budget_runs_out() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let plan_args = json!({
+        "plan": [{"step": "Inspect workspace", "status": "in_progress"}],
+    })
+    .to_string();
+    // Only one response is mounted: the follow-up request that would carry the
+    // tool output must never be sent.
+    let first = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call("plan-call", "update_plan", &plan_args),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.session_budget = SessionBudgetConfig {
+            max_tool_calls: Some(1),
+            ..Default::default()
+        };
+    });
+    let test = builder.build(&server).await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "update the plan".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let aborted = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnAborted(_) | EventMsg::TurnComplete(_))
+    })
+    .await;
+    let EventMsg::TurnAborted(aborted) = aborted else {
+        panic!("expected TurnAborted, got {aborted:?}");
+    };
+    assert_eq!(aborted.reason, TurnAbortReason::BudgetExceeded);
+    assert_eq!(
+        aborted.budget,
+        Some(SessionBudgetUsage {
+            kind: SessionBudgetKind::ToolCalls,
+            limit: 1,
+            used: 1,
+        })
+    );
+    first.single_request();
+    let model_requests = server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|request| request.url.path().ends_with("/responses"))
+        .count();
+    assert_eq!(model_requests, 1);
+
+    Ok(())
+}
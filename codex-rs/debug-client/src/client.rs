@@ -11,6 +11,7 @@ use std::sync::Mutex;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 use std::sync::mpsc::Sender;
+use std::thread;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -33,6 +34,7 @@ use codex_app_server_protocol::ThreadStartResponse;
 use codex_app_server_protocol::TurnStartParams;
 use codex_app_server_protocol::UserInput;
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::output::Output;
 use crate::reader::start_reader;
@@ -222,6 +224,39 @@ impl AppServerClient {
         Ok(())
     }
 
+    /// Forwards raw server lines to `lines` without interpreting them; used
+    /// by scenario runs, which answer server requests themselves.
+    pub fn start_line_reader(&mut self, lines: Sender<String>) -> Result<()> {
+        let mut stdout = self.stdout.take().context("reader already started")?;
+        thread::spawn(move || {
+            let mut buffer = String::new();
+            loop {
+                buffer.clear();
+                match stdout.read_line(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                let line = buffer.trim_end_matches(['\n', '\r']);
+                if !line.is_empty() && lines.send(line.to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    pub fn new_request(&self, method: &str, params: Option<Value>) -> JSONRPCRequest {
+        JSONRPCRequest {
+            id: self.next_request_id(),
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    pub fn send_message(&self, message: &JSONRPCMessage) -> Result<()> {
+        self.send(message)
+    }
+
     pub fn thread_id(&self) -> Option<String> {
         let state = self.state.lock().expect("state lock poisoned");
         state.thread_id.clone()
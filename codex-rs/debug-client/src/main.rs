@@ -1,11 +1,16 @@
 mod client;
 mod commands;
+mod mock_model;
 mod output;
 mod reader;
+mod scenario;
 mod state;
 
+use std::fs::File;
 use std::io;
 use std::io::BufRead;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::mpsc;
 
 use anyhow::Context;
@@ -20,7 +25,10 @@ use crate::client::build_thread_start_params;
 use crate::commands::InputAction;
 use crate::commands::UserCommand;
 use crate::commands::parse_input;
+use crate::mock_model::MockModelServer;
 use crate::output::Output;
+use crate::scenario::Scenario;
+use crate::scenario::ScenarioRunner;
 use crate::state::ReaderEvent;
 
 #[derive(Parser)]
@@ -61,11 +69,23 @@ struct Cli {
     /// Optional working directory override when starting/resuming a thread.
     #[arg(long)]
     cwd: Option<String>,
+
+    /// Run a scenario file instead of the interactive prompt; exits non-zero
+    /// on the first failing step.
+    #[arg(long, value_name = "PATH")]
+    scenario: Option<PathBuf>,
+
+    /// Write a JSONL transcript of the scenario run.
+    #[arg(long, value_name = "PATH", requires = "scenario")]
+    transcript: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
     let output = Output::new();
+    if let Some(path) = cli.scenario.as_deref() {
+        return run_scenario(&cli, path, output);
+    }
     let approval_policy = parse_approval_policy(&cli.approval_policy)?;
 
     let mut client = AppServerClient::spawn(
@@ -148,6 +168,32 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+fn run_scenario(cli: &Cli, path: &Path, output: Output) -> Result<()> {
+    let scenario = Scenario::load(path)?;
+    let mut config_overrides = cli.config_overrides.clone();
+    if let Some(mock_model) = scenario.mock_model.clone() {
+        config_overrides.extend(MockModelServer::start(mock_model)?.config_overrides());
+    }
+    let transcript = cli
+        .transcript
+        .as_ref()
+        .map(|transcript_path| {
+            File::create(transcript_path)
+                .with_context(|| format!("create transcript {}", transcript_path.display()))
+        })
+        .transpose()?;
+
+    let mut client =
+        AppServerClient::spawn(&cli.codex_bin, &config_overrides, output.clone(), false)?;
+    client.initialize()?;
+    let (line_tx, line_rx) = mpsc::channel();
+    client.start_line_reader(line_tx)?;
+
+    let result = ScenarioRunner::new(&client, &scenario, line_rx, output, transcript).run();
+    client.shutdown();
+    result
+}
+
 fn handle_command(
     command: UserCommand,
     client: &AppServerClient,
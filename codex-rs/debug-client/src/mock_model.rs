@@ -0,0 +1,292 @@
+//! Scripted stand-in for the Responses API used by scenario runs.
+//!
+//! Every `POST .../responses` is answered with the next scripted response as a
+//! complete SSE body; anything else gets a 404. Once the script is exhausted
+//! the server answers with a 500 so a runaway turn fails instead of hanging.
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+
+const PROVIDER_ID: &str = "scenario_mock";
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MockModel {
+    /// One entry per model request, served in order.
+    pub responses: Vec<MockResponse>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum MockResponse {
+    /// A single assistant message.
+    AssistantMessage(String),
+    /// A `shell_command` tool call running the given command line.
+    ShellCommand(String),
+    /// Raw Responses API events, sent as-is between `response.created` and
+    /// `response.completed`.
+    Events(Vec<Value>),
+}
+
+pub struct MockModelServer {
+    addr: SocketAddr,
+}
+
+impl MockModelServer {
+    pub fn start(model: MockModel) -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).context("bind mock model server")?;
+        let addr = listener.local_addr().context("mock model server address")?;
+        let responses = Arc::new(Mutex::new(model.responses.into_iter().enumerate()));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let responses = Arc::clone(&responses);
+                thread::spawn(move || {
+                    let _ = handle_connection(stream, &responses);
+                });
+            }
+        });
+        Ok(Self { addr })
+    }
+
+    /// `--config` overrides that point `codex` at this server.
+    pub fn config_overrides(&self) -> Vec<String> {
+        let prefix = format!("model_providers.{PROVIDER_ID}");
+        vec![
+            format!("model_provider=\"{PROVIDER_ID}\""),
+            format!("{prefix}.name=\"Scenario mock\""),
+            format!("{prefix}.base_url=\"http://{}/v1\"", self.addr),
+            format!("{prefix}.wire_api=\"responses\""),
+            format!("{prefix}.request_max_retries=0"),
+            format!("{prefix}.stream_max_retries=0"),
+        ]
+    }
+}
+
+type ScriptedResponses = Mutex<std::iter::Enumerate<std::vec::IntoIter<MockResponse>>>;
+
+fn handle_connection(stream: TcpStream, responses: &ScriptedResponses) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut chunked = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            }
+        }
+    }
+    // Drain the request body so closing the socket does not reset the
+    // connection before the client has read our response.
+    if chunked {
+        drain_chunked_body(&mut reader)?;
+    } else {
+        std::io::copy(
+            &mut reader.by_ref().take(content_length),
+            &mut std::io::sink(),
+        )?;
+    }
+
+    let (status, content_type, body) = if method == "POST" && path.ends_with("/responses") {
+        let next = responses
+            .lock()
+            .ok()
+            .and_then(|mut responses| responses.next());
+        match next {
+            Some((index, response)) => (
+                "200 OK",
+                "text/event-stream",
+                sse_body(&response_events(&response, index + 1)),
+            ),
+            None => (
+                "500 Internal Server Error",
+                "text/plain",
+                "scenario mock model has no responses left".to_string(),
+            ),
+        }
+    } else {
+        ("404 Not Found", "text/plain", String::new())
+    };
+
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn drain_chunked_body(reader: &mut BufReader<TcpStream>) -> std::io::Result<()> {
+    loop {
+        let mut size_line = String::new();
+        if reader.read_line(&mut size_line)? == 0 {
+            return Ok(());
+        }
+        let size = size_line.trim().split(';').next().unwrap_or_default();
+        let size = u64::from_str_radix(size, 16).unwrap_or(0);
+        // Chunk data is followed by CRLF; the last chunk by optional trailers.
+        std::io::copy(&mut reader.by_ref().take(size), &mut std::io::sink())?;
+        if size == 0 {
+            loop {
+                let mut trailer = String::new();
+                if reader.read_line(&mut trailer)? == 0 || trailer.trim().is_empty() {
+                    return Ok(());
+                }
+            }
+        }
+        let mut crlf = String::new();
+        reader.read_line(&mut crlf)?;
+    }
+}
+
+/// Events for the `index`-th (1-based) model request.
+fn response_events(response: &MockResponse, index: usize) -> Vec<Value> {
+    let response_id = format!("resp-{index}");
+    let mut events = vec![json!({
+        "type": "response.created",
+        "response": { "id": response_id },
+    })];
+    match response {
+        MockResponse::AssistantMessage(text) => events.push(json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "message",
+                "role": "assistant",
+                "id": format!("msg-{index}"),
+                "content": [{ "type": "output_text", "text": text }],
+            },
+        })),
+        MockResponse::ShellCommand(command) => events.push(json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "function_call",
+                "call_id": format!("call-{index}"),
+                "name": "shell_command",
+                "arguments": json!({ "command": command }).to_string(),
+            },
+        })),
+        MockResponse::Events(raw) => events.extend(raw.iter().cloned()),
+    }
+    events.push(json!({
+        "type": "response.completed",
+        "response": {
+            "id": response_id,
+            "usage": {
+                "input_tokens": 0,
+                "input_tokens_details": null,
+                "output_tokens": 0,
+                "output_tokens_details": null,
+                "total_tokens": 0,
+            },
+        },
+    }));
+    events
+}
+
+fn sse_body(events: &[Value]) -> String {
+    let mut body = String::new();
+    for event in events {
+        let kind = event
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    use pretty_assertions::assert_eq;
+
+    use super::MockModel;
+    use super::MockModelServer;
+    use super::MockResponse;
+
+    fn post(server: &MockModelServer, path: &str) -> String {
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        let body = "{}";
+        write!(
+            stream,
+            "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_scripted_responses_in_order() {
+        let server = MockModelServer::start(MockModel {
+            responses: vec![
+                MockResponse::ShellCommand("echo hi".to_string()),
+                MockResponse::AssistantMessage("done".to_string()),
+            ],
+        })
+        .unwrap();
+
+        let first = post(&server, "/v1/responses");
+        assert!(first.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(first.contains(r#""name":"shell_command""#));
+        assert!(first.contains(r#""arguments":"{\"command\":\"echo hi\"}""#));
+
+        assert!(post(&server, "/v1/models").starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let second = post(&server, "/v1/responses");
+        let events: Vec<&str> = second
+            .lines()
+            .filter_map(|line| line.strip_prefix("event: "))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                "response.created",
+                "response.output_item.done",
+                "response.completed"
+            ]
+        );
+        assert!(second.contains(r#""text":"done""#));
+
+        assert!(post(&server, "/v1/responses").starts_with("HTTP/1.1 500 "));
+    }
+}
@@ -0,0 +1,634 @@
+//! Scripted, non-interactive sessions for integration tests.
+//!
+//! A scenario is a JSON file with a list of steps that are run in order
+//! against the spawned app-server. Server requests are answered from the
+//! scenario's approval rules, every message is optionally logged to a JSONL
+//! transcript, and the first failing step aborts the run.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::mpsc::TryRecvError;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCRequest;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use wildmatch::WildMatch;
+
+use crate::client::AppServerClient;
+use crate::mock_model::MockModel;
+use crate::output::Output;
+
+/// Replaced with the id of the most recently started or resumed thread.
+const THREAD_ID_PLACEHOLDER: &str = "$thread_id";
+const METHOD_NOT_FOUND: i64 = -32601;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Serve model requests from a local mock instead of the configured provider.
+    #[serde(default)]
+    pub mock_model: Option<MockModel>,
+    /// Answers for server requests; the first matching rule wins.
+    #[serde(default)]
+    pub approvals: Vec<ApprovalRule>,
+    /// Default timeout for responses and `wait_for` steps.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    pub steps: Vec<Step>,
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+impl Scenario {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("read scenario {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("parse scenario {}", path.display()))
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ApprovalRule {
+    /// Glob matched against the server request method.
+    pub method: String,
+    /// Glob matched against `params.command`; requests without one never match.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Sent back as `{"decision": <decision>}`.
+    pub decision: Value,
+}
+
+impl ApprovalRule {
+    fn matches(&self, method: &str, params: Option<&Value>) -> bool {
+        if !WildMatch::new(&self.method).matches(method) {
+            return false;
+        }
+        match &self.command {
+            Some(pattern) => params
+                .and_then(|params| params.get("command"))
+                .and_then(Value::as_str)
+                .is_some_and(|command| WildMatch::new(pattern).matches(command)),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Step {
+    /// Send a request and wait for its response. A `thread` in the result
+    /// becomes the active thread.
+    Request {
+        method: String,
+        #[serde(default)]
+        params: Option<Value>,
+        /// Fail unless the result contains this value.
+        #[serde(default)]
+        result: Option<Value>,
+    },
+    /// Start a turn on the active thread with a text message.
+    Turn(String),
+    /// Wait for a notification that was not matched by an earlier `wait_for`.
+    WaitFor {
+        method: String,
+        #[serde(default)]
+        params: Option<Value>,
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+    },
+    /// Fail unless a matching notification arrived since the last `turn`.
+    AssertNotification {
+        method: String,
+        #[serde(default)]
+        params: Option<Value>,
+    },
+    /// Fail if a matching notification arrived since the last `turn`.
+    AssertNoNotification {
+        method: String,
+        #[serde(default)]
+        params: Option<Value>,
+    },
+}
+
+impl Step {
+    fn label(&self) -> String {
+        match self {
+            Step::Request { method, .. } => format!("request {method}"),
+            Step::Turn(_) => "turn".to_string(),
+            Step::WaitFor { method, .. } => format!("wait_for {method}"),
+            Step::AssertNotification { method, .. } => format!("assert_notification {method}"),
+            Step::AssertNoNotification { method, .. } => {
+                format!("assert_no_notification {method}")
+            }
+        }
+    }
+}
+
+pub struct ScenarioRunner<'a> {
+    client: &'a AppServerClient,
+    scenario: &'a Scenario,
+    lines: Receiver<String>,
+    output: Output,
+    transcript: Option<File>,
+    thread_id: Option<String>,
+    notifications: Vec<JSONRPCNotification>,
+    /// Index of the first notification received after the latest `turn`.
+    turn_start: usize,
+    /// Index after the last notification matched by `wait_for`.
+    cursor: usize,
+    responses: HashMap<RequestId, Result<Value, JSONRPCErrorError>>,
+}
+
+impl<'a> ScenarioRunner<'a> {
+    pub fn new(
+        client: &'a AppServerClient,
+        scenario: &'a Scenario,
+        lines: Receiver<String>,
+        output: Output,
+        transcript: Option<File>,
+    ) -> Self {
+        Self {
+            client,
+            scenario,
+            lines,
+            output,
+            transcript,
+            thread_id: None,
+            notifications: Vec::new(),
+            turn_start: 0,
+            cursor: 0,
+            responses: HashMap::new(),
+        }
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        let scenario = self.scenario;
+        for (index, step) in scenario.steps.iter().enumerate() {
+            let number = index + 1;
+            let label = step.label();
+            let _ = self
+                .output
+                .client_line(&format!("scenario step {number}: {label}"));
+            if let Err(err) = self.run_step(step) {
+                self.record(json!({
+                    "dir": "scenario",
+                    "step": number,
+                    "label": label,
+                    "status": "failed",
+                    "error": format!("{err:#}"),
+                }));
+                anyhow::bail!("scenario step {number} ({label}) failed: {err:#}");
+            }
+            self.record(json!({
+                "dir": "scenario",
+                "step": number,
+                "label": label,
+                "status": "passed",
+            }));
+        }
+        let _ = self
+            .output
+            .client_line(&format!("scenario passed ({} steps)", scenario.steps.len()));
+        Ok(())
+    }
+
+    fn run_step(&mut self, step: &Step) -> Result<()> {
+        match step {
+            Step::Request {
+                method,
+                params,
+                result,
+            } => {
+                let params = params.as_ref().map(|params| self.substitute(params));
+                let value = self.request(method, params)?;
+                if let Some(thread_id) = value
+                    .get("thread")
+                    .and_then(|thread| thread.get("id"))
+                    .and_then(Value::as_str)
+                {
+                    self.thread_id = Some(thread_id.to_string());
+                }
+                if let Some(expected) = result {
+                    let expected = self.substitute(expected);
+                    anyhow::ensure!(
+                        json_contains(&value, &expected),
+                        "result {value} does not contain {expected}"
+                    );
+                }
+                Ok(())
+            }
+            Step::Turn(text) => {
+                let thread_id = self.thread_id.clone().context(
+                    "no active thread; start one with a `thread/start` request step first",
+                )?;
+                let params = serde_json::to_value(TurnStartParams {
+                    thread_id,
+                    input: vec![UserInput::Text {
+                        text: text.clone(),
+                        text_elements: Vec::new(),
+                    }],
+                    ..Default::default()
+                })
+                .context("serialize turn/start params")?;
+                self.drain()?;
+                self.turn_start = self.notifications.len();
+                self.cursor = self.turn_start;
+                self.request("turn/start", Some(params))?;
+                Ok(())
+            }
+            Step::WaitFor {
+                method,
+                params,
+                timeout_secs,
+            } => {
+                let params = params.as_ref().map(|params| self.substitute(params));
+                let timeout =
+                    Duration::from_secs(timeout_secs.unwrap_or(self.scenario.timeout_secs));
+                let deadline = Instant::now() + timeout;
+                loop {
+                    if let Some(offset) =
+                        self.notifications[self.cursor..]
+                            .iter()
+                            .position(|notification| {
+                                notification_matches(notification, method, params.as_ref())
+                            })
+                    {
+                        self.cursor += offset + 1;
+                        return Ok(());
+                    }
+                    let now = Instant::now();
+                    if now >= deadline {
+                        anyhow::bail!("timed out after {}s", timeout.as_secs());
+                    }
+                    self.pump(deadline - now)?;
+                }
+            }
+            Step::AssertNotification { method, params } => {
+                let params = params.as_ref().map(|params| self.substitute(params));
+                self.drain()?;
+                anyhow::ensure!(
+                    self.notifications[self.turn_start..]
+                        .iter()
+                        .any(|notification| notification_matches(
+                            notification,
+                            method,
+                            params.as_ref()
+                        )),
+                    "no matching notification since the last turn"
+                );
+                Ok(())
+            }
+            Step::AssertNoNotification { method, params } => {
+                let params = params.as_ref().map(|params| self.substitute(params));
+                self.drain()?;
+                if let Some(notification) =
+                    self.notifications[self.turn_start..]
+                        .iter()
+                        .find(|notification| {
+                            notification_matches(notification, method, params.as_ref())
+                        })
+                {
+                    let notification = serde_json::to_string(notification)?;
+                    anyhow::bail!("unexpected notification {notification}");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn request(&mut self, method: &str, params: Option<Value>) -> Result<Value> {
+        let request = self.client.new_request(method, params);
+        let request_id = request.id.clone();
+        let message = JSONRPCMessage::Request(request);
+        self.record_message("client", &message);
+        self.client.send_message(&message)?;
+
+        let timeout = Duration::from_secs(self.scenario.timeout_secs);
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(response) = self.responses.remove(&request_id) {
+                return response.map_err(|error| {
+                    anyhow::anyhow!("{method} failed: {} (code {})", error.message, error.code)
+                });
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                anyhow::bail!(
+                    "timed out after {}s waiting for the {method} response",
+                    timeout.as_secs()
+                );
+            }
+            self.pump(deadline - now)?;
+        }
+    }
+
+    /// Handles the next server line, waiting at most `timeout` for it.
+    fn pump(&mut self, timeout: Duration) -> Result<()> {
+        match self.lines.recv_timeout(timeout) {
+            Ok(line) => self.handle_line(&line),
+            Err(RecvTimeoutError::Timeout) => Ok(()),
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("app-server closed its output"),
+        }
+    }
+
+    /// Handles every server line that has already arrived.
+    fn drain(&mut self) -> Result<()> {
+        loop {
+            match self.lines.try_recv() {
+                Ok(line) => self.handle_line(&line)?,
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => return Ok(()),
+            }
+        }
+    }
+
+    fn handle_line(&mut self, line: &str) -> Result<()> {
+        let _ = self.output.server_line(line);
+        let Ok(message) = serde_json::from_str::<JSONRPCMessage>(line) else {
+            self.record(json!({ "dir": "server", "line": line }));
+            return Ok(());
+        };
+        self.record_message("server", &message);
+
+        match message {
+            JSONRPCMessage::Notification(notification) => self.notifications.push(notification),
+            JSONRPCMessage::Request(request) => self.answer_server_request(request)?,
+            JSONRPCMessage::Response(response) => {
+                self.responses.insert(response.id, Ok(response.result));
+            }
+            JSONRPCMessage::Error(error) => {
+                self.responses.insert(error.id, Err(error.error));
+            }
+        }
+        Ok(())
+    }
+
+    fn answer_server_request(&mut self, request: JSONRPCRequest) -> Result<()> {
+        let reply = match approval_decision(
+            &self.scenario.approvals,
+            &request.method,
+            request.params.as_ref(),
+        ) {
+            Some(decision) => JSONRPCMessage::Response(JSONRPCResponse {
+                id: request.id,
+                result: json!({ "decision": decision }),
+            }),
+            None => JSONRPCMessage::Error(JSONRPCError {
+                error: JSONRPCErrorError {
+                    code: METHOD_NOT_FOUND,
+                    data: None,
+                    message: format!("scenario has no answer for {}", request.method),
+                },
+                id: request.id,
+            }),
+        };
+        self.record_message("client", &reply);
+        self.client.send_message(&reply)
+    }
+
+    fn substitute(&self, value: &Value) -> Value {
+        substitute_thread_id(value, self.thread_id.as_deref())
+    }
+
+    fn record_message(&mut self, dir: &str, message: &JSONRPCMessage) {
+        self.record(json!({ "dir": dir, "message": message }));
+    }
+
+    fn record(&mut self, entry: Value) {
+        let Some(transcript) = self.transcript.as_mut() else {
+            return;
+        };
+        if let Err(err) = writeln!(transcript, "{entry}") {
+            let _ = self
+                .output
+                .client_line(&format!("failed to write transcript: {err}"));
+            self.transcript = None;
+        }
+    }
+}
+
+/// Decision for a server request: the first matching rule, otherwise decline
+/// for approvals and no answer (an error response) for anything else.
+fn approval_decision(
+    rules: &[ApprovalRule],
+    method: &str,
+    params: Option<&Value>,
+) -> Option<Value> {
+    if let Some(rule) = rules.iter().find(|rule| rule.matches(method, params)) {
+        return Some(rule.decision.clone());
+    }
+    method
+        .ends_with("/requestApproval")
+        .then(|| Value::String("decline".to_string()))
+}
+
+fn notification_matches(
+    notification: &JSONRPCNotification,
+    method: &str,
+    params: Option<&Value>,
+) -> bool {
+    notification.method == method
+        && params.is_none_or(|expected| {
+            notification
+                .params
+                .as_ref()
+                .is_some_and(|actual| json_contains(actual, expected))
+        })
+}
+
+/// True when every field in `expected` is present in `actual` with a matching
+/// value. Objects match recursively; arrays and scalars must be equal.
+fn json_contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            expected.iter().all(|(key, expected)| {
+                actual
+                    .get(key)
+                    .is_some_and(|actual| json_contains(actual, expected))
+            })
+        }
+        (Value::Array(actual), Value::Array(expected)) => {
+            actual.len() == expected.len()
+                && actual
+                    .iter()
+                    .zip(expected)
+                    .all(|(actual, expected)| json_contains(actual, expected))
+        }
+        _ => actual == expected,
+    }
+}
+
+fn substitute_thread_id(value: &Value, thread_id: Option<&str>) -> Value {
+    match value {
+        Value::String(text) if text == THREAD_ID_PLACEHOLDER => match thread_id {
+            Some(thread_id) => Value::String(thread_id.to_string()),
+            None => value.clone(),
+        },
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute_thread_id(item, thread_id))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), substitute_thread_id(value, thread_id)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::ApprovalRule;
+    use super::Scenario;
+    use super::Step;
+    use super::approval_decision;
+    use super::json_contains;
+    use super::substitute_thread_id;
+    use crate::mock_model::MockModel;
+    use crate::mock_model::MockResponse;
+
+    #[test]
+    fn parses_scenario_file() {
+        let scenario: Scenario = serde_json::from_value(json!({
+            "mock_model": { "responses": [{ "assistant_message": "hi" }] },
+            "approvals": [
+                { "method": "item/commandExecution/requestApproval", "command": "ls*", "decision": "accept" }
+            ],
+            "steps": [
+                { "request": { "method": "thread/start", "params": {} } },
+                { "turn": "hello" },
+                { "wait_for": { "method": "turn/completed", "timeout_secs": 5 } },
+                { "assert_no_notification": { "method": "error" } }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            scenario,
+            Scenario {
+                mock_model: Some(MockModel {
+                    responses: vec![MockResponse::AssistantMessage("hi".to_string())],
+                }),
+                approvals: vec![ApprovalRule {
+                    method: "item/commandExecution/requestApproval".to_string(),
+                    command: Some("ls*".to_string()),
+                    decision: json!("accept"),
+                }],
+                timeout_secs: 60,
+                steps: vec![
+                    Step::Request {
+                        method: "thread/start".to_string(),
+                        params: Some(json!({})),
+                        result: None,
+                    },
+                    Step::Turn("hello".to_string()),
+                    Step::WaitFor {
+                        method: "turn/completed".to_string(),
+                        params: None,
+                        timeout_secs: Some(5),
+                    },
+                    Step::AssertNoNotification {
+                        method: "error".to_string(),
+                        params: None,
+                    },
+                ],
+            }
+        );
+
+        let typo = serde_json::from_value::<Scenario>(json!({
+            "steps": [{ "wait_for": { "method": "turn/completed", "timeout": 5 } }]
+        }));
+        assert!(typo.is_err());
+    }
+
+    #[test]
+    fn approval_rules_match_method_and_command() {
+        let rules = vec![
+            ApprovalRule {
+                method: "item/commandExecution/requestApproval".to_string(),
+                command: Some("git status*".to_string()),
+                decision: json!("acceptForSession"),
+            },
+            ApprovalRule {
+                method: "item/fileChange/*".to_string(),
+                command: None,
+                decision: json!("accept"),
+            },
+        ];
+        let command_approval = "item/commandExecution/requestApproval";
+
+        assert_eq!(
+            approval_decision(
+                &rules,
+                command_approval,
+                Some(&json!({ "command": "git status --short" }))
+            ),
+            Some(json!("acceptForSession"))
+        );
+        assert_eq!(
+            approval_decision(
+                &rules,
+                command_approval,
+                Some(&json!({ "command": "rm -rf /" }))
+            ),
+            Some(json!("decline"))
+        );
+        assert_eq!(
+            approval_decision(&rules, "item/fileChange/requestApproval", None),
+            Some(json!("accept"))
+        );
+        assert_eq!(approval_decision(&rules, "item/tool/call", None), None);
+    }
+
+    #[test]
+    fn matches_partial_json_and_substitutes_thread_id() {
+        let actual = json!({
+            "threadId": "thr_1",
+            "turn": { "id": "turn_1", "status": "completed", "items": [] }
+        });
+        assert!(json_contains(
+            &actual,
+            &json!({ "turn": { "status": "completed" } })
+        ));
+        assert!(!json_contains(
+            &actual,
+            &json!({ "turn": { "status": "failed" } })
+        ));
+        assert!(!json_contains(&actual, &json!({ "error": null })));
+
+        assert_eq!(
+            substitute_thread_id(
+                &json!({ "threadId": "$thread_id", "input": ["$thread_id"] }),
+                Some("thr_1")
+            ),
+            json!({ "threadId": "thr_1", "input": ["thr_1"] })
+        );
+    }
+}
@@ -94,6 +94,7 @@ impl EscalateServer {
             },
             &sandbox_state.sandbox_policy,
             &sandbox_state.sandbox_cwd,
+            &sandbox_state.denied_read_paths,
             &sandbox_state.codex_linux_sandbox_exe,
             sandbox_state.use_linux_sandbox_bwrap,
             None,
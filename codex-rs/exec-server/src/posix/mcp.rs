@@ -127,6 +127,7 @@ impl ExecTool {
                     codex_linux_sandbox_exe: None,
                     sandbox_cwd: PathBuf::from(&params.workdir),
                     use_linux_sandbox_bwrap: false,
+                    denied_read_paths: Vec::new(),
                 });
         let escalate_server = EscalateServer::new(
             self.bash_path.clone(),
@@ -92,6 +92,7 @@ where
         codex_linux_sandbox_exe,
         sandbox_cwd: sandbox_cwd.as_ref().to_path_buf(),
         use_linux_sandbox_bwrap: false,
+        denied_read_paths: Vec::new(),
     };
     send_sandbox_state_update(sandbox_state, service).await
 }
@@ -120,6 +121,7 @@ where
         codex_linux_sandbox_exe,
         sandbox_cwd: writable_folder.as_ref().to_path_buf(),
         use_linux_sandbox_bwrap: false,
+        denied_read_paths: Vec::new(),
     };
     send_sandbox_state_update(sandbox_state, service).await
 }
@@ -0,0 +1,606 @@
+//! `codex exec batch`: run many headless tasks from a JSONL file.
+//!
+//! Every task runs in its own thread with the task's working directory and
+//! overrides, at most `--concurrency` at a time. A summary line is appended to
+//! the summary file as soon as a task finishes, so an interrupted batch can be
+//! resumed by running the same command again: tasks that already succeeded
+//! are skipped and failed ones are retried.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+use anyhow::Context;
+use codex_common::CliConfigOverrides;
+use codex_core::AuthManager;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_core::auth::AuthCredentialsStoreMode;
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
+use codex_core::config_loader::CloudRequirementsLoader;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::models_manager::manager::RefreshStrategy;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SessionSource;
+use codex_core::protocol::TokenUsage;
+use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::approvals::NetworkApprovalDecision;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::user_input::UserInput;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::cli::BatchArgs;
+
+/// Settings from the `codex exec` command line that apply to every task
+/// unless the task overrides them.
+pub(crate) struct BatchDefaults {
+    pub codex_home: PathBuf,
+    pub auth_credentials_store_mode: AuthCredentialsStoreMode,
+    pub model: Option<String>,
+    pub config_profile: Option<String>,
+    pub sandbox_mode: Option<SandboxMode>,
+    pub skip_git_repo_check: bool,
+    pub ephemeral: bool,
+    /// Raw `-c key=value` overrides.
+    pub config_overrides: Vec<String>,
+    pub cloud_requirements: CloudRequirementsLoader,
+    pub codex_linux_sandbox_exe: Option<PathBuf>,
+}
+
+/// One line of the input file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchTaskSpec {
+    #[serde(default)]
+    id: Option<String>,
+    cwd: PathBuf,
+    prompt: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    sandbox: Option<SandboxMode>,
+    /// Extra `key=value` overrides, as accepted by `-c`.
+    #[serde(default)]
+    config: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BatchTask {
+    /// The task's `id`, or a hash of its contents.
+    id: String,
+    cwd: PathBuf,
+    prompt: String,
+    model: Option<String>,
+    profile: Option<String>,
+    sandbox: Option<SandboxMode>,
+    config: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchTaskStatus {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DiffStats {
+    files_changed: usize,
+    insertions: usize,
+    deletions: usize,
+}
+
+/// One line of the summary file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BatchTaskSummary {
+    id: String,
+    status: BatchTaskStatus,
+    cwd: PathBuf,
+    thread_id: Option<String>,
+    final_message: Option<String>,
+    diff: DiffStats,
+    usage: Option<TokenUsage>,
+    cost_usd: Option<f64>,
+    duration_ms: u64,
+    error: Option<String>,
+}
+
+struct BatchContext {
+    defaults: BatchDefaults,
+    thread_manager: Arc<ThreadManager>,
+}
+
+pub(crate) async fn run_batch(args: BatchArgs, defaults: BatchDefaults) -> anyhow::Result<()> {
+    let contents = tokio::fs::read_to_string(&args.input)
+        .await
+        .with_context(|| format!("failed to read {}", args.input.display()))?;
+    let base_dir = args
+        .input
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let tasks = parse_tasks(&contents, &base_dir)?;
+
+    let summary_path = args
+        .summary
+        .unwrap_or_else(|| args.input.with_extension("summary.jsonl"));
+    let previous_summary = match tokio::fs::read_to_string(&summary_path).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", summary_path.display()));
+        }
+    };
+    let succeeded = succeeded_task_ids(&previous_summary);
+    let total = tasks.len();
+    let pending: Vec<BatchTask> = tasks
+        .into_iter()
+        .filter(|task| !succeeded.contains(&task.id))
+        .collect();
+    eprintln!(
+        "Running {} of {total} tasks ({} already succeeded); writing summaries to {}",
+        pending.len(),
+        total - pending.len(),
+        summary_path.display()
+    );
+
+    let mut summary_file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&summary_path)
+        .await
+        .with_context(|| format!("failed to open {}", summary_path.display()))?;
+    // A previous run may have been killed halfway through a line.
+    if !previous_summary.is_empty() && !previous_summary.ends_with('\n') {
+        summary_file.write_all(b"\n").await?;
+    }
+    let summary_file = Arc::new(Mutex::new(summary_file));
+
+    let auth_manager = AuthManager::shared(
+        defaults.codex_home.clone(),
+        true,
+        defaults.auth_credentials_store_mode,
+    );
+    let thread_manager = Arc::new(ThreadManager::new(
+        defaults.codex_home.clone(),
+        auth_manager,
+        SessionSource::Exec,
+    ));
+    let context = Arc::new(BatchContext {
+        defaults,
+        thread_manager,
+    });
+
+    let pending_count = pending.len();
+    let finished = Arc::new(AtomicUsize::new(0));
+    let semaphore = Arc::new(Semaphore::new(args.concurrency.max(1)));
+    let mut join_set = JoinSet::new();
+    for task in pending {
+        let permit = Arc::clone(&semaphore).acquire_owned().await?;
+        let context = Arc::clone(&context);
+        let summary_file = Arc::clone(&summary_file);
+        let finished = Arc::clone(&finished);
+        join_set.spawn(async move {
+            let _permit = permit;
+            let summary = run_task(task, &context).await;
+            let mut line = serde_json::to_string(&summary)?;
+            line.push('\n');
+            {
+                let mut summary_file = summary_file.lock().await;
+                summary_file.write_all(line.as_bytes()).await?;
+                summary_file.flush().await?;
+            }
+            let finished = finished.fetch_add(1, Ordering::SeqCst) + 1;
+            match summary.status {
+                BatchTaskStatus::Succeeded => eprintln!(
+                    "[{finished}/{pending_count}] {} succeeded ({} files, +{} -{})",
+                    summary.id,
+                    summary.diff.files_changed,
+                    summary.diff.insertions,
+                    summary.diff.deletions
+                ),
+                BatchTaskStatus::Failed => eprintln!(
+                    "[{finished}/{pending_count}] {} failed: {}",
+                    summary.id,
+                    summary.error.as_deref().unwrap_or("unknown error")
+                ),
+            }
+            anyhow::Ok(summary.status)
+        });
+    }
+
+    let mut failed = 0;
+    while let Some(result) = join_set.join_next().await {
+        if result?? == BatchTaskStatus::Failed {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} of {pending_count} tasks failed; rerun the same command to retry them"
+        );
+    }
+    Ok(())
+}
+
+async fn run_task(task: BatchTask, context: &BatchContext) -> BatchTaskSummary {
+    let started = Instant::now();
+    let mut summary = BatchTaskSummary {
+        id: task.id.clone(),
+        status: BatchTaskStatus::Failed,
+        cwd: task.cwd.clone(),
+        thread_id: None,
+        final_message: None,
+        diff: DiffStats::default(),
+        usage: None,
+        cost_usd: None,
+        duration_ms: 0,
+        error: None,
+    };
+    if let Err(err) = drive_task(&task, context, &mut summary).await {
+        summary.status = BatchTaskStatus::Failed;
+        summary.error = Some(format!("{err:#}"));
+    }
+    summary.duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    summary
+}
+
+async fn drive_task(
+    task: &BatchTask,
+    context: &BatchContext,
+    summary: &mut BatchTaskSummary,
+) -> anyhow::Result<()> {
+    let defaults = &context.defaults;
+    let mut raw_overrides = defaults.config_overrides.clone();
+    raw_overrides.extend(task.config.iter().cloned());
+    let cli_kv_overrides = CliConfigOverrides { raw_overrides }
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let cwd = task
+        .cwd
+        .canonicalize()
+        .with_context(|| format!("cannot access {}", task.cwd.display()))?;
+    let overrides = ConfigOverrides {
+        model: task.model.clone().or_else(|| defaults.model.clone()),
+        config_profile: task
+            .profile
+            .clone()
+            .or_else(|| defaults.config_profile.clone()),
+        // Tasks run unattended, like `codex exec`.
+        approval_policy: Some(AskForApproval::Never),
+        sandbox_mode: task.sandbox.or(defaults.sandbox_mode),
+        cwd: Some(cwd),
+        codex_linux_sandbox_exe: defaults.codex_linux_sandbox_exe.clone(),
+        ephemeral: defaults.ephemeral.then_some(true),
+        ..Default::default()
+    };
+    let config = ConfigBuilder::default()
+        .codex_home(defaults.codex_home.clone())
+        .cli_overrides(cli_kv_overrides)
+        .harness_overrides(overrides)
+        .cloud_requirements(defaults.cloud_requirements.clone())
+        .build()
+        .await?;
+    if !defaults.skip_git_repo_check && get_git_repo_root(&config.cwd).is_none() {
+        anyhow::bail!(
+            "{} is not inside a git repository; pass --skip-git-repo-check to run it anyway",
+            config.cwd.display()
+        );
+    }
+
+    let thread_manager = &context.thread_manager;
+    let model = thread_manager
+        .get_models_manager()
+        .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
+        .await;
+    let NewThread {
+        thread_id, thread, ..
+    } = thread_manager.start_thread(config.clone()).await?;
+    summary.thread_id = Some(thread_id.to_string());
+    thread
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: task.prompt.clone(),
+                text_elements: Vec::new(),
+            }],
+            cwd: config.cwd.to_path_buf(),
+            approval_policy: config.approval_policy.value(),
+            sandbox_policy: config.sandbox_policy.get().clone(),
+            model,
+            effort: config.model_reasoning_effort,
+            summary: config.model_reasoning_summary,
+            final_output_json_schema: None,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    let mut error = None;
+    let mut shutdown_requested = false;
+    loop {
+        let event = thread.next_event().await?;
+        let turn_ended = match event.msg {
+            EventMsg::AgentMessage(event) => {
+                summary.final_message = Some(event.message);
+                false
+            }
+            EventMsg::TokenCount(event) => {
+                if let Some(info) = event.info {
+                    summary.usage = Some(info.total_token_usage);
+                    summary.cost_usd = event.total_cost_usd;
+                }
+                false
+            }
+            EventMsg::TurnDiff(event) => {
+                summary.diff = diff_stats(&event.unified_diff);
+                false
+            }
+            EventMsg::Error(event) => {
+                error = Some(event.message);
+                false
+            }
+            EventMsg::ElicitationRequest(event) => {
+                // Nobody can answer elicitations in a batch.
+                thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: event.server_name,
+                        request_id: event.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await?;
+                false
+            }
+            EventMsg::NetworkApprovalRequest(event) => {
+                thread
+                    .submit(Op::NetworkApproval {
+                        id: event.id,
+                        decision: NetworkApprovalDecision::Deny,
+                    })
+                    .await?;
+                false
+            }
+            EventMsg::TurnComplete(event) => {
+                if let Some(message) = event.last_agent_message {
+                    summary.final_message = Some(message);
+                }
+                true
+            }
+            EventMsg::TurnAborted(_) => {
+                error.get_or_insert_with(|| "the turn was aborted".to_string());
+                true
+            }
+            EventMsg::ShutdownComplete => break,
+            _ => false,
+        };
+        if turn_ended && !shutdown_requested {
+            thread.submit(Op::Shutdown).await?;
+            shutdown_requested = true;
+        }
+    }
+    thread_manager.remove_thread(&thread_id).await;
+
+    match error {
+        Some(error) => {
+            summary.status = BatchTaskStatus::Failed;
+            summary.error = Some(error);
+        }
+        None => summary.status = BatchTaskStatus::Succeeded,
+    }
+    Ok(())
+}
+
+fn parse_tasks(contents: &str, base_dir: &Path) -> anyhow::Result<Vec<BatchTask>> {
+    let mut tasks = Vec::new();
+    let mut ids = HashSet::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let spec: BatchTaskSpec = serde_json::from_str(line)
+            .with_context(|| format!("invalid task on line {line_number}"))?;
+        if spec.prompt.trim().is_empty() {
+            anyhow::bail!("task on line {line_number} has an empty prompt");
+        }
+        let id = match spec.id.clone() {
+            Some(id) => id,
+            None => content_id(&spec)?,
+        };
+        if !ids.insert(id.clone()) {
+            anyhow::bail!("duplicate task id {id:?} on line {line_number}");
+        }
+        tasks.push(BatchTask {
+            id,
+            cwd: base_dir.join(spec.cwd),
+            prompt: spec.prompt,
+            model: spec.model,
+            profile: spec.profile,
+            sandbox: spec.sandbox,
+            config: spec.config,
+        });
+    }
+    Ok(tasks)
+}
+
+/// Id for a task without one: a hash of its contents, so editing other lines
+/// of the input file does not change which tasks a resumed batch skips.
+fn content_id(spec: &BatchTaskSpec) -> anyhow::Result<String> {
+    let hex = format!("{:x}", Sha256::digest(serde_json::to_vec(spec)?));
+    Ok(hex.get(..16).unwrap_or(&hex).to_string())
+}
+
+/// Ids of tasks whose most recent summary line says they succeeded.
+/// Unreadable lines, e.g. one cut short by a crash, are ignored.
+fn succeeded_task_ids(summary: &str) -> HashSet<String> {
+    let mut succeeded = HashSet::new();
+    for line in summary.lines() {
+        let Ok(entry) = serde_json::from_str::<BatchTaskSummary>(line) else {
+            continue;
+        };
+        match entry.status {
+            BatchTaskStatus::Succeeded => succeeded.insert(entry.id),
+            BatchTaskStatus::Failed => succeeded.remove(&entry.id),
+        };
+    }
+    succeeded
+}
+
+fn diff_stats(unified_diff: &str) -> DiffStats {
+    let mut stats = DiffStats::default();
+    // `---`/`+++` are file headers only between `diff --git` and the first
+    // hunk; inside a hunk they are removed or added lines.
+    let mut in_file_header = false;
+    for line in unified_diff.lines() {
+        if line.starts_with("diff --git ") {
+            stats.files_changed += 1;
+            in_file_header = true;
+        } else if line.starts_with("@@") {
+            in_file_header = false;
+        } else if in_file_header {
+            continue;
+        } else if line.starts_with('+') {
+            stats.insertions += 1;
+        } else if line.starts_with('-') {
+            stats.deletions += 1;
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_tasks_with_defaults_and_relative_directories() {
+        let contents = r#"{"id": "billing", "cwd": "services/billing", "prompt": "Bump the SDK", "sandbox": "workspace-write", "config": ["model_reasoning_effort=\"high\""]}
+
+{"cwd": "/srv/search", "prompt": "Bump the SDK", "model": "gpt-5.1-codex"}
+"#;
+        let tasks = parse_tasks(contents, Path::new("/work")).expect("parse tasks");
+        assert_eq!(
+            tasks,
+            vec![
+                BatchTask {
+                    id: "billing".to_string(),
+                    cwd: PathBuf::from("/work/services/billing"),
+                    prompt: "Bump the SDK".to_string(),
+                    model: None,
+                    profile: None,
+                    sandbox: Some(SandboxMode::WorkspaceWrite),
+                    config: vec!["model_reasoning_effort=\"high\"".to_string()],
+                },
+                BatchTask {
+                    id: "f4074cd03f82c974".to_string(),
+                    cwd: PathBuf::from("/srv/search"),
+                    prompt: "Bump the SDK".to_string(),
+                    model: Some("gpt-5.1-codex".to_string()),
+                    profile: None,
+                    sandbox: None,
+                    config: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tasks_without_an_id_keep_their_id_when_lines_move() {
+        let task = r#"{"cwd": "/srv/search", "prompt": "Bump the SDK"}"#;
+        let other = r#"{"id": "billing", "cwd": ".", "prompt": "Bump the SDK"}"#;
+        let first =
+            parse_tasks(&format!("{task}\n{other}\n"), Path::new("/work")).expect("parse tasks");
+        let moved = parse_tasks(&format!("{other}\n\n{task}\n"), Path::new("/work"))
+            .expect("parse moved tasks");
+        assert_eq!(first[0].id, moved[1].id);
+
+        let edited = r#"{"cwd": "/srv/search", "prompt": "Bump the SDK to v4"}"#;
+        let edited = parse_tasks(edited, Path::new("/work")).expect("parse edited task");
+        assert_ne!(first[0].id, edited[0].id);
+    }
+
+    #[test]
+    fn rejects_duplicate_ids_and_unknown_fields() {
+        let duplicate = "{\"id\": \"a\", \"cwd\": \".\", \"prompt\": \"x\"}\n\
+                         {\"id\": \"a\", \"cwd\": \".\", \"prompt\": \"y\"}\n";
+        assert!(parse_tasks(duplicate, Path::new("/work")).is_err());
+        let unknown = "{\"cwd\": \".\", \"prompt\": \"x\", \"modle\": \"typo\"}\n";
+        assert!(parse_tasks(unknown, Path::new("/work")).is_err());
+    }
+
+    #[test]
+    fn resume_skips_only_tasks_whose_latest_run_succeeded() {
+        let entry = |id: &str, status: BatchTaskStatus| {
+            serde_json::to_string(&BatchTaskSummary {
+                id: id.to_string(),
+                status,
+                cwd: PathBuf::from("/work"),
+                thread_id: None,
+                final_message: None,
+                diff: DiffStats::default(),
+                usage: None,
+                cost_usd: None,
+                duration_ms: 1,
+                error: None,
+            })
+            .expect("serialize summary")
+        };
+        let summary = [
+            entry("a", BatchTaskStatus::Succeeded),
+            entry("b", BatchTaskStatus::Failed),
+            entry("c", BatchTaskStatus::Failed),
+            entry("c", BatchTaskStatus::Succeeded),
+            "{\"id\": \"d\", \"sta".to_string(),
+        ]
+        .join("\n");
+        let mut succeeded: Vec<_> = succeeded_task_ids(&summary).into_iter().collect();
+        succeeded.sort();
+        assert_eq!(succeeded, vec!["a".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn counts_files_and_lines_in_unified_diff() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,3 @@
+-use old;
++use new;
++use more;
+ fn main() {}
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1,2 +1,2 @@
+-Old
+---- removed rule
++New
+++++ added rule
+";
+        assert_eq!(
+            diff_stats(diff),
+            DiffStats {
+                files_changed: 2,
+                insertions: 4,
+                deletions: 3,
+            }
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use clap::Args;
+use clap::CommandFactory;
 use clap::FromArgMatches;
 use clap::Parser;
 use clap::ValueEnum;
+use clap::error::ErrorKind;
 use codex_common::CliConfigOverrides;
+use codex_core::review_report::ReviewReportFormat;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -105,6 +108,31 @@ pub struct Cli {
     pub prompt: Option<String>,
 }
 
+impl Cli {
+    /// Reject options that only apply to a single session when running
+    /// `batch`, the way clap reports conflicting arguments.
+    pub fn check_batch_conflicts(&self) -> Result<(), clap::Error> {
+        if !matches!(self.command, Some(Command::Batch(_))) {
+            return Ok(());
+        }
+        let conflict = [
+            (self.oss, "--oss"),
+            (!self.images.is_empty(), "--image <FILE>"),
+            (self.output_schema.is_some(), "--output-schema <FILE>"),
+            (self.json, "--json"),
+        ]
+        .into_iter()
+        .find_map(|(set, flag)| set.then_some(flag));
+        match conflict {
+            Some(flag) => Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                format!("the argument '{flag}' cannot be used with 'batch'"),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum Command {
     /// Resume a previous session by id or pick the most recent with --last.
@@ -112,6 +140,9 @@ pub enum Command {
 
     /// Run a code review against the current repository.
     Review(ReviewArgs),
+
+    /// Run many tasks from a JSONL file, each in its own thread.
+    Batch(BatchArgs),
 }
 
 #[derive(Args, Debug)]
@@ -211,7 +242,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["base", "commit", "range", "patch", "prompt"]
     )]
     pub uncommitted: bool,
 
@@ -219,7 +250,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "base",
         value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "commit", "range", "patch", "prompt"]
     )]
     pub base: Option<String>,
 
@@ -227,10 +258,26 @@ pub struct ReviewArgs {
     #[arg(
         long = "commit",
         value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "base", "range", "patch", "prompt"]
     )]
     pub commit: Option<String>,
 
+    /// Review the changes between two revisions, e.g. `origin/main..HEAD`.
+    #[arg(
+        long = "range",
+        value_name = "FROM..TO",
+        conflicts_with_all = ["uncommitted", "base", "commit", "patch", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// Review a patch file without applying it.
+    #[arg(
+        long = "patch",
+        value_name = "FILE",
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "prompt"]
+    )]
+    pub patch: Option<PathBuf>,
+
     /// Optional commit title to display in the review summary.
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
@@ -238,6 +285,61 @@ pub struct ReviewArgs {
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    /// Only review changes under this file or directory. Repeatable.
+    #[arg(long = "path", value_name = "PATH")]
+    pub paths: Vec<String>,
+
+    /// Print the findings to stdout in a machine-readable format for CI
+    /// instead of the final review message.
+    #[arg(long = "format", value_enum, value_name = "FORMAT")]
+    pub format: Option<ReviewFormat>,
+}
+
+#[derive(Parser, Debug)]
+pub struct BatchArgs {
+    /// JSONL file with one task per line, e.g.
+    /// `{"id": "billing", "cwd": "services/billing", "prompt": "...", "model": "...",
+    /// "profile": "...", "sandbox": "workspace-write", "config": ["key=value"]}`.
+    /// Only `cwd` and `prompt` are required; relative paths are resolved against
+    /// the file's directory.
+    #[arg(long = "input", value_name = "FILE")]
+    pub input: PathBuf,
+
+    /// JSONL file a summary of each finished task is appended to. Tasks that
+    /// already succeeded according to this file are skipped, so rerunning the
+    /// same command resumes an interrupted batch. Defaults to
+    /// `<input>.summary.jsonl`.
+    #[arg(long = "summary", value_name = "FILE")]
+    pub summary: Option<PathBuf>,
+
+    /// Maximum number of tasks to run at the same time.
+    #[arg(long = "concurrency", short = 'j', default_value_t = 4)]
+    pub concurrency: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    /// SARIF 2.1.0 for code-scanning dashboards.
+    Sarif,
+    /// GitHub Actions annotations.
+    Github,
+    /// reviewdog diagnostic format.
+    Rdjson,
+    /// JUnit XML.
+    Junit,
+}
+
+impl From<ReviewFormat> for ReviewReportFormat {
+    fn from(format: ReviewFormat) -> Self {
+        match format {
+            ReviewFormat::Sarif => ReviewReportFormat::Sarif,
+            ReviewFormat::Github => ReviewReportFormat::Github,
+            ReviewFormat::Rdjson => ReviewReportFormat::Rdjson,
+            ReviewFormat::Junit => ReviewReportFormat::Junit,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
@@ -283,4 +385,54 @@ mod tests {
         });
         assert_eq!(effective_prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn batch_parses_input_after_global_flags() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--sandbox",
+            "workspace-write",
+            "batch",
+            "--input",
+            "tasks.jsonl",
+            "-j",
+            "8",
+            "--skip-git-repo-check",
+        ]);
+
+        assert!(cli.skip_git_repo_check);
+        let Some(Command::Batch(args)) = cli.command else {
+            panic!("expected batch command");
+        };
+        assert_eq!(args.input, PathBuf::from("tasks.jsonl"));
+        assert_eq!(args.summary, None);
+        assert_eq!(args.concurrency, 8);
+    }
+
+    #[test]
+    fn batch_rejects_single_session_options() {
+        for flags in [
+            vec!["--oss"],
+            vec!["--image", "shot.png"],
+            vec!["--output-schema", "schema.json"],
+            vec!["--json"],
+        ] {
+            let mut args = vec!["codex-exec"];
+            args.extend(&flags);
+            args.extend(["--skip-git-repo-check", "batch", "--input", "tasks.jsonl"]);
+            let err = Cli::parse_from(args)
+                .check_batch_conflicts()
+                .expect_err("conflict");
+            assert_eq!(err.kind(), ErrorKind::ArgumentConflict, "{flags:?}");
+        }
+
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--json",
+            "--output-schema",
+            "schema.json",
+            "hi",
+        ]);
+        assert!(cli.check_batch_conflicts().is_ok());
+    }
 }
@@ -13,6 +13,7 @@ use codex_core::protocol::CollabCloseBeginEvent;
 use codex_core::protocol::CollabCloseEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::ConfigReloadedEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
@@ -24,6 +25,7 @@ use codex_core::protocol::ItemCompletedEvent;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelFallbackEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
@@ -198,6 +200,43 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     ts_msg!(self, "  {}", details.style(self.dimmed));
                 }
             }
+            EventMsg::ConfigReloaded(ConfigReloadedEvent {
+                changed,
+                restart_required,
+                error,
+                ..
+            }) => {
+                let label = "config:".style(self.magenta).style(self.bold);
+                if let Some(error) = error {
+                    ts_msg!(self, "{label} rejected changes: {error}");
+                } else if !changed.is_empty() {
+                    ts_msg!(self, "{label} reloaded {}", changed.join(", "));
+                }
+                if !restart_required.is_empty() {
+                    ts_msg!(
+                        self,
+                        "  {}",
+                        format!("needs a new session: {}", restart_required.join(", "))
+                            .style(self.dimmed)
+                    );
+                }
+            }
+            EventMsg::ModelFallback(ModelFallbackEvent {
+                from_model,
+                to_model,
+                model_provider_id,
+                reason,
+                ..
+            }) => {
+                let label = "model:".style(self.magenta).style(self.bold);
+                match reason {
+                    Some(reason) => ts_msg!(
+                        self,
+                        "{label} {from_model} failed, switching to {to_model} ({model_provider_id}): {reason}"
+                    ),
+                    None => ts_msg!(self, "{label} returning from {from_model} to {to_model}"),
+                }
+            }
             EventMsg::McpStartupUpdate(update) => {
                 let status_text = match update.status {
                     codex_core::protocol::McpStartupStatus::Starting => "starting".to_string(),
@@ -264,6 +303,19 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "auto-cancelling (not supported in exec mode)".style(self.dimmed)
                 );
             }
+            EventMsg::NetworkApprovalRequest(ev) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "network request blocked".style(self.magenta),
+                    ev.host.style(self.dimmed)
+                );
+                ts_msg!(
+                    self,
+                    "{}",
+                    "auto-denying (not supported in exec mode)".style(self.dimmed)
+                );
+            }
             EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
                 let last_message = last_agent_message
                     .as_deref()
@@ -604,6 +656,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     TurnAbortReason::ReviewEnded => {
                         ts_msg!(self, "task aborted: review ended");
                     }
+                    TurnAbortReason::BudgetExceeded => match &abort_reason.budget {
+                        Some(budget) => ts_msg!(self, "task aborted: session {budget}"),
+                        None => ts_msg!(self, "task aborted: session budget exceeded"),
+                    },
                 }
                 return CodexStatus::InitiateShutdown;
             }
@@ -6,6 +6,8 @@ use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
 use crate::exec_events::AgentMessageItem;
+use crate::exec_events::BudgetExceeded;
+use crate::exec_events::BudgetKind;
 use crate::exec_events::CollabAgentState;
 use crate::exec_events::CollabAgentStatus;
 use crate::exec_events::CollabTool;
@@ -66,10 +68,12 @@ pub struct EventProcessorWithJsonOutput {
     // Tracks the todo list for the current turn (at most one per turn).
     running_todo_list: Option<RunningTodoList>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsage>,
+    last_total_cost_usd: Option<f64>,
     running_mcp_tool_calls: HashMap<String, RunningMcpToolCall>,
     running_collab_tool_calls: HashMap<String, RunningCollabToolCall>,
     running_web_search_calls: HashMap<String, String>,
     last_critical_error: Option<ThreadErrorEvent>,
+    last_budget_exceeded: Option<BudgetExceeded>,
 }
 
 #[derive(Debug, Clone)]
@@ -109,10 +113,12 @@ impl EventProcessorWithJsonOutput {
             running_patch_applies: HashMap::new(),
             running_todo_list: None,
             last_total_token_usage: None,
+            last_total_cost_usd: None,
             running_mcp_tool_calls: HashMap::new(),
             running_collab_tool_calls: HashMap::new(),
             running_web_search_calls: HashMap::new(),
             last_critical_error: None,
+            last_budget_exceeded: None,
         }
     }
 
@@ -156,11 +162,13 @@ impl EventProcessorWithJsonOutput {
             protocol::EventMsg::TokenCount(ev) => {
                 if let Some(info) = &ev.info {
                     self.last_total_token_usage = Some(info.total_token_usage.clone());
+                    self.last_total_cost_usd = ev.total_cost_usd;
                 }
                 Vec::new()
             }
             protocol::EventMsg::TurnStarted(ev) => self.handle_task_started(ev),
             protocol::EventMsg::TurnComplete(_) => self.handle_task_complete(),
+            protocol::EventMsg::TurnAborted(ev) => self.handle_turn_aborted(ev),
             protocol::EventMsg::Error(ev) => {
                 let error = ThreadErrorEvent {
                     message: ev.message.clone(),
@@ -746,15 +754,39 @@ impl EventProcessorWithJsonOutput {
 
     fn handle_task_started(&mut self, _: &protocol::TurnStartedEvent) -> Vec<ThreadEvent> {
         self.last_critical_error = None;
+        self.last_budget_exceeded = None;
         vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]
     }
 
+    /// Only budget aborts are reported: they end the turn the same way a
+    /// failure does. Other aborts come from the user and end `exec` anyway.
+    fn handle_turn_aborted(&mut self, ev: &protocol::TurnAbortedEvent) -> Vec<ThreadEvent> {
+        let Some(budget) = ev.budget.as_ref() else {
+            return Vec::new();
+        };
+        self.last_critical_error = Some(ThreadErrorEvent {
+            message: format!("session {budget}"),
+        });
+        self.last_budget_exceeded = Some(BudgetExceeded {
+            kind: match budget.kind {
+                protocol::SessionBudgetKind::Turns => BudgetKind::Turns,
+                protocol::SessionBudgetKind::TotalTokens => BudgetKind::TotalTokens,
+                protocol::SessionBudgetKind::ToolCalls => BudgetKind::ToolCalls,
+                protocol::SessionBudgetKind::Duration => BudgetKind::Duration,
+            },
+            limit: budget.limit,
+            used: budget.used,
+        });
+        self.handle_task_complete()
+    }
+
     fn handle_task_complete(&mut self) -> Vec<ThreadEvent> {
         let usage = if let Some(u) = &self.last_total_token_usage {
             Usage {
                 input_tokens: u.input_tokens,
                 cached_input_tokens: u.cached_input_tokens,
                 output_tokens: u.output_tokens,
+                cost_usd: self.last_total_cost_usd,
             }
         } else {
             Usage::default()
@@ -788,7 +820,10 @@ impl EventProcessorWithJsonOutput {
         }
 
         if let Some(error) = self.last_critical_error.take() {
-            items.push(ThreadEvent::TurnFailed(TurnFailedEvent { error }));
+            items.push(ThreadEvent::TurnFailed(TurnFailedEvent {
+                error,
+                budget: self.last_budget_exceeded.take(),
+            }));
         } else {
             items.push(ThreadEvent::TurnCompleted(TurnCompletedEvent { usage }));
         }
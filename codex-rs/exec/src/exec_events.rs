@@ -54,6 +54,29 @@ pub struct TurnCompletedEvent {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct TurnFailedEvent {
     pub error: ThreadErrorEvent,
+    /// The session budget that stopped the turn, when that is why it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub budget: Option<BudgetExceeded>,
+}
+
+/// A `[budget]` limit from `config.toml` that ran out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct BudgetExceeded {
+    pub kind: BudgetKind,
+    /// The configured limit, in turns, tokens, tool calls or seconds.
+    pub limit: i64,
+    /// The amount used when the turn stopped, in the same unit.
+    pub used: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    Turns,
+    TotalTokens,
+    ToolCalls,
+    Duration,
 }
 
 /// Describes the usage of tokens during a turn.
@@ -65,6 +88,11 @@ pub struct Usage {
     pub cached_input_tokens: i64,
     /// The number of output tokens used during the turn.
     pub output_tokens: i64,
+    /// Estimated cost of the thread so far in US dollars. Absent when any
+    /// model used in the thread has no known price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cost_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
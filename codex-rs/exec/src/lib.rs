@@ -4,12 +4,14 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod batch;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
 
+pub use cli::BatchArgs;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
@@ -39,7 +41,9 @@ use codex_core::protocol::Op;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::review_report::render_review_report;
 use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::approvals::NetworkApprovalDecision;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -91,6 +95,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         tracing::warn!(?err, "Failed to set codex exec originator override {err:?}");
     }
 
+    if let Err(err) = cli.check_batch_conflicts() {
+        err.exit();
+    }
+
     let Cli {
         command,
         images,
@@ -113,6 +121,14 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         config_overrides,
     } = cli;
 
+    let review_format = match &command {
+        Some(ExecCommand::Review(args)) => args.format,
+        _ => None,
+    };
+    if review_format.is_some() && json_mode {
+        anyhow::bail!("--format cannot be combined with --json");
+    }
+
     let (stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
         cli::Color::Never => (false, false),
@@ -207,6 +223,32 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // TODO(gt): Make cloud requirements failures blocking once we can fail-closed.
     let cloud_requirements = cloud_requirements_loader(cloud_auth_manager, chatgpt_base_url);
 
+    let command = match command {
+        Some(ExecCommand::Batch(args)) => {
+            let _ = tracing_subscriber::registry().with(fmt_layer).try_init();
+            return batch::run_batch(
+                args,
+                batch::BatchDefaults {
+                    codex_home,
+                    auth_credentials_store_mode: config_toml
+                        .cli_auth_credentials_store
+                        .unwrap_or_default(),
+                    model: model_cli_arg,
+                    config_profile,
+                    sandbox_mode,
+                    skip_git_repo_check: skip_git_repo_check
+                        || dangerously_bypass_approvals_and_sandbox,
+                    ephemeral,
+                    config_overrides: config_overrides.raw_overrides,
+                    cloud_requirements,
+                    codex_linux_sandbox_exe,
+                },
+            )
+            .await;
+        }
+        command => command,
+    };
+
     let model_provider = if oss {
         let resolved = resolve_oss_provider(
             oss_provider.as_deref(),
@@ -379,6 +421,9 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         thread_manager.start_thread(config.clone()).await?
     };
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
+        (Some(ExecCommand::Batch(_)), _, _) => {
+            anyhow::bail!("batch runs are started before any thread is created");
+        }
         (Some(ExecCommand::Review(review_cli)), _, _) => {
             let review_request = build_review_request(review_cli)?;
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
@@ -524,6 +569,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     let mut shutdown_requested = false;
+    let mut review_output = None;
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
@@ -540,6 +586,15 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 })
                 .await?;
         }
+        if let EventMsg::NetworkApprovalRequest(ev) = &event.msg {
+            // Blocked hosts stay blocked in exec mode; allow them in config instead.
+            thread
+                .submit(Op::NetworkApproval {
+                    id: ev.id.clone(),
+                    decision: NetworkApprovalDecision::Deny,
+                })
+                .await?;
+        }
         if let EventMsg::McpStartupUpdate(update) = &event.msg
             && required_mcp_servers.contains(&update.server)
             && let codex_core::protocol::McpStartupStatus::Failed { error } = &update.status
@@ -557,6 +612,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         if matches!(event.msg, EventMsg::Error(_)) {
             error_seen = true;
         }
+        if thread_id == primary_thread_id
+            && let EventMsg::ExitedReviewMode(ev) = &event.msg
+        {
+            review_output = ev.review_output.clone();
+        }
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
         }
@@ -576,7 +636,21 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             CodexStatus::Shutdown => continue,
         }
     }
-    event_processor.print_final_output();
+    if let Some(format) = review_format {
+        // The report replaces the final message on stdout.
+        let Some(output) = review_output else {
+            eprintln!("Review finished without producing findings.");
+            std::process::exit(1);
+        };
+        let root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.clone());
+        let report = render_review_report(&output, format.into(), &root);
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{report}");
+        }
+    } else {
+        event_processor.print_final_output();
+    }
     if error_seen {
         std::process::exit(1);
     }
@@ -811,6 +885,13 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
             sha,
             title: args.commit_title,
         }
+    } else if let Some(range) = args.range {
+        let Some((from, to)) = codex_core::review_prompts::parse_revision_range(&range) else {
+            anyhow::bail!("--range must look like FROM..TO");
+        };
+        ReviewTarget::Range { from, to }
+    } else if let Some(path) = args.patch {
+        ReviewTarget::PatchFile { path }
     } else if let Some(prompt_arg) = args.prompt {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -821,13 +902,14 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --range, --patch, or provide custom review instructions"
         );
     };
 
     Ok(ReviewRequest {
         target,
         user_facing_hint: None,
+        paths: args.paths,
     })
 }
 
@@ -842,14 +924,19 @@ mod tests {
             uncommitted: true,
             base: None,
             commit: None,
+            range: None,
+            patch: None,
             commit_title: None,
             prompt: None,
+            paths: Vec::new(),
+            format: None,
         })
         .expect("builds uncommitted review request");
 
         let expected = ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -861,8 +948,12 @@ mod tests {
             uncommitted: false,
             base: None,
             commit: Some("123456789".to_string()),
+            range: None,
+            patch: None,
             commit_title: Some("Add review command".to_string()),
             prompt: None,
+            paths: Vec::new(),
+            format: None,
         })
         .expect("builds commit review request");
 
@@ -872,6 +963,7 @@ mod tests {
                 title: Some("Add review command".to_string()),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -883,8 +975,12 @@ mod tests {
             uncommitted: false,
             base: None,
             commit: None,
+            range: None,
+            patch: None,
             commit_title: None,
             prompt: Some("  custom review instructions  ".to_string()),
+            paths: Vec::new(),
+            format: None,
         })
         .expect("builds custom review request");
 
@@ -893,6 +989,34 @@ mod tests {
                 instructions: "custom review instructions".to_string(),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
+        };
+
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn builds_range_review_request_with_paths() {
+        let request = build_review_request(ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            range: Some("origin/main..HEAD".to_string()),
+            patch: None,
+            commit_title: None,
+            prompt: None,
+            paths: vec!["core/src".to_string()],
+            format: None,
+        })
+        .expect("builds range review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::Range {
+                from: "origin/main".to_string(),
+                to: "HEAD".to_string(),
+            },
+            user_facing_hint: None,
+            paths: vec!["core/src".to_string()],
         };
 
         assert_eq!(request, expected);
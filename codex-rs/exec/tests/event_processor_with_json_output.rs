@@ -18,12 +18,18 @@ use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SessionBudgetKind;
+use codex_core::protocol::SessionBudgetUsage;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnAbortedEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_exec::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
 use codex_exec::exec_events::AgentMessageItem;
+use codex_exec::exec_events::BudgetExceeded;
+use codex_exec::exec_events::BudgetKind;
 use codex_exec::exec_events::CollabAgentState;
 use codex_exec::exec_events::CollabAgentStatus;
 use codex_exec::exec_events::CollabTool;
@@ -837,6 +843,37 @@ fn error_followed_by_task_complete_produces_turn_failed() {
             error: ThreadErrorEvent {
                 message: "boom".to_string(),
             },
+            budget: None,
+        })]
+    );
+}
+
+#[test]
+fn budget_abort_produces_turn_failed_with_budget() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+
+    let aborted = event(
+        "e1",
+        EventMsg::TurnAborted(TurnAbortedEvent {
+            reason: TurnAbortReason::BudgetExceeded,
+            budget: Some(SessionBudgetUsage {
+                kind: SessionBudgetKind::ToolCalls,
+                limit: 50,
+                used: 50,
+            }),
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&aborted),
+        vec![ThreadEvent::TurnFailed(TurnFailedEvent {
+            error: ThreadErrorEvent {
+                message: "session tool call budget: 50 of 50 tool calls used".to_string(),
+            },
+            budget: Some(BudgetExceeded {
+                kind: BudgetKind::ToolCalls,
+                limit: 50,
+                used: 50,
+            }),
         })]
     );
 }
@@ -1267,6 +1304,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
             info: Some(info),
             rate_limits: None,
+            total_cost_usd: Some(0.0042),
         }),
     );
     assert!(ep.collect_thread_events(&token_count_event).is_empty());
@@ -1286,6 +1324,7 @@ fn task_complete_produces_turn_completed_with_usage() {
                 input_tokens: 1200,
                 cached_input_tokens: 200,
                 output_tokens: 345,
+                cost_usd: Some(0.0042),
             },
         })]
     );
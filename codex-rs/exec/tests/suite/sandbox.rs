@@ -26,6 +26,7 @@ async fn spawn_command_under_sandbox(
         command_cwd,
         sandbox_policy,
         sandbox_cwd,
+        &[],
         stdio_policy,
         env,
     )
@@ -50,6 +51,7 @@ async fn spawn_command_under_sandbox(
         command_cwd,
         sandbox_policy,
         sandbox_cwd,
+        &[],
         false,
         stdio_policy,
         env,
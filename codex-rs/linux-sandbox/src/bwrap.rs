@@ -4,7 +4,9 @@
 //! - the filesystem is read-only by default,
 //! - explicit writable roots are layered on top, and
 //! - sensitive subpaths such as `.git` and `.codex` remain read-only even when
-//!   their parent root is writable.
+//!   their parent root is writable, and
+//! - denied read paths (credential stores such as `~/.ssh`) are hidden
+//!   entirely.
 //!
 //! The overall Linux sandbox is composed of:
 //! - seccomp + `PR_SET_NO_NEW_PRIVS` applied in-process, and
@@ -26,11 +28,17 @@ pub(crate) struct BwrapOptions {
     /// This is the secure default, but some restrictive container environments
     /// deny `--proc /proc` even when PID namespaces are available.
     pub mount_proc: bool,
+    /// Whether to isolate the network namespace. bubblewrap brings up
+    /// loopback in the new namespace; nothing else is reachable.
+    pub unshare_net: bool,
 }
 
 impl Default for BwrapOptions {
     fn default() -> Self {
-        Self { mount_proc: true }
+        Self {
+            mount_proc: true,
+            unshare_net: false,
+        }
     }
 }
 
@@ -43,13 +51,14 @@ pub(crate) fn create_bwrap_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    denied_read_paths: &[PathBuf],
     options: BwrapOptions,
 ) -> Result<Vec<String>> {
     if sandbox_policy.has_full_disk_write_access() {
         return Ok(command);
     }
 
-    create_bwrap_flags(command, sandbox_policy, cwd, options)
+    create_bwrap_flags(command, sandbox_policy, cwd, denied_read_paths, options)
 }
 
 /// Build the bubblewrap flags (everything after `argv[0]`).
@@ -57,14 +66,22 @@ fn create_bwrap_flags(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    denied_read_paths: &[PathBuf],
     options: BwrapOptions,
 ) -> Result<Vec<String>> {
     let mut args = Vec::new();
     args.push("--new-session".to_string());
     args.push("--die-with-parent".to_string());
-    args.extend(create_filesystem_args(sandbox_policy, cwd)?);
+    args.extend(create_filesystem_args(
+        sandbox_policy,
+        cwd,
+        denied_read_paths,
+    )?);
     // Isolate the PID namespace.
     args.push("--unshare-pid".to_string());
+    if options.unshare_net {
+        args.push("--unshare-net".to_string());
+    }
     // Mount a fresh /proc unless the caller explicitly disables it.
     if options.mount_proc {
         args.push("--proc".to_string());
@@ -82,9 +99,15 @@ fn create_bwrap_flags(
 /// 2. `--bind <root> <root>` re-enables writes for allowed roots.
 /// 3. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
-/// 4. `--dev-bind /dev/null /dev/null` preserves the common sink even under a
+/// 4. `--tmpfs <dir>` (remounted read-only) or `--ro-bind /dev/null <file>`
+///    hides denied read paths, even when they live under a writable root.
+/// 5. `--dev-bind /dev/null /dev/null` preserves the common sink even under a
 ///    read-only root.
-fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<Vec<String>> {
+fn create_filesystem_args(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    denied_read_paths: &[PathBuf],
+) -> Result<Vec<String>> {
     let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
     ensure_mount_targets_exist(&writable_roots)?;
 
@@ -134,6 +157,21 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
         }
     }
 
+    // Hide denied paths after every other mount so they cannot be re-exposed
+    // by a writable bind that contains them.
+    for denied_path in collect_existing_denied_paths(denied_read_paths) {
+        if denied_path.is_dir() {
+            args.push("--tmpfs".to_string());
+            args.push(path_to_string(&denied_path));
+            args.push("--remount-ro".to_string());
+            args.push(path_to_string(&denied_path));
+        } else {
+            args.push("--ro-bind".to_string());
+            args.push("/dev/null".to_string());
+            args.push(path_to_string(&denied_path));
+        }
+    }
+
     // Ensure `/dev/null` remains usable regardless of the root bind.
     args.push("--dev-bind".to_string());
     args.push("/dev/null".to_string());
@@ -153,6 +191,20 @@ fn collect_read_only_subpaths(writable_roots: &[WritableRoot]) -> Vec<PathBuf> {
     subpaths.into_iter().collect()
 }
 
+/// Collect unique denied read paths that exist on disk.
+///
+/// Bubblewrap cannot mount over a path that does not exist, and there is
+/// nothing to hide in that case anyway.
+fn collect_existing_denied_paths(denied_read_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut paths: BTreeSet<PathBuf> = BTreeSet::new();
+    for path in denied_read_paths {
+        if path.exists() {
+            paths.insert(path.clone());
+        }
+    }
+    paths.into_iter().collect()
+}
+
 /// Validate that writable roots exist before constructing mounts.
 ///
 /// Bubblewrap requires bind mount targets to exist. We fail fast with a clear
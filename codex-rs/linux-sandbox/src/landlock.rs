@@ -4,6 +4,7 @@
 //! Landlock helpers remain available here as legacy/backup utilities.
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
@@ -35,13 +36,19 @@ use seccompiler::apply_filter;
 ///
 /// This function is responsible for:
 /// - enabling `PR_SET_NO_NEW_PRIVS` when restrictions apply, and
-/// - installing the network seccomp filter when network access is disabled.
+/// - installing the network seccomp filter when network access is disabled,
+///   unless `apply_network_seccomp` is false because a network namespace
+///   already confines the command to a proxy.
 ///
-/// Filesystem restrictions are intentionally handled by bubblewrap.
+/// Filesystem restrictions (including `denied_read_paths`) are intentionally
+/// handled by bubblewrap unless `apply_landlock_fs` selects the legacy
+/// Landlock pipeline.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     apply_landlock_fs: bool,
+    denied_read_paths: &[PathBuf],
+    apply_network_seccomp: bool,
 ) -> Result<()> {
     // `PR_SET_NO_NEW_PRIVS` is required for seccomp, but it also prevents
     // setuid privilege elevation. Many `bwrap` deployments rely on setuid, so
@@ -53,7 +60,7 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
         set_no_new_privs()?;
     }
 
-    if !sandbox_policy.has_full_network_access() {
+    if apply_network_seccomp && !sandbox_policy.has_full_network_access() {
         install_network_seccomp_filter_on_current_thread()?;
     }
 
@@ -63,12 +70,9 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        install_filesystem_landlock_rules_on_current_thread(writable_roots, denied_read_paths)?;
     }
 
-    // TODO(ragona): Add appropriate restrictions if
-    // `sandbox_policy.has_full_disk_read_access()` is `false`.
-
     Ok(())
 }
 
@@ -83,7 +87,8 @@ fn set_no_new_privs() -> Result<()> {
 
 /// Installs Landlock file-system rules on the current thread allowing read
 /// access to the entire file-system while restricting write access to
-/// `/dev/null` and the provided list of `writable_roots`.
+/// `/dev/null` and the provided list of `writable_roots`. Anything under
+/// `denied_read_paths` is left without any access at all.
 ///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
@@ -92,21 +97,34 @@ fn set_no_new_privs() -> Result<()> {
 /// via bubblewrap. It is kept for reference and potential fallback use.
 fn install_filesystem_landlock_rules_on_current_thread(
     writable_roots: Vec<AbsolutePathBuf>,
+    denied_read_paths: &[PathBuf],
 ) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
     let access_ro = AccessFs::from_read(abi);
 
+    let denied_read_paths = canonicalize_existing_paths(denied_read_paths);
+    let readable = paths_beneath_excluding(&[PathBuf::from("/")], &denied_read_paths);
+    let writable_roots: Vec<PathBuf> = writable_roots
+        .into_iter()
+        .map(AbsolutePathBuf::into_path_buf)
+        .collect();
+    let writable = paths_beneath_excluding(&writable_roots, &denied_read_paths);
+
     let mut ruleset = Ruleset::default()
         .set_compatibility(CompatLevel::BestEffort)
         .handle_access(access_rw)?
         .create()?
-        .add_rules(landlock::path_beneath_rules(&["/"], access_ro))?
+        .add_rules(landlock::path_beneath_rules(&readable.allowed, access_ro))?
+        .add_rules(landlock::path_beneath_rules(
+            &readable.traversed,
+            AccessFs::ReadDir,
+        ))?
         .add_rules(landlock::path_beneath_rules(&["/dev/null"], access_rw))?
         .set_no_new_privs(true);
 
-    if !writable_roots.is_empty() {
-        ruleset = ruleset.add_rules(landlock::path_beneath_rules(&writable_roots, access_rw))?;
+    if !writable.allowed.is_empty() {
+        ruleset = ruleset.add_rules(landlock::path_beneath_rules(&writable.allowed, access_rw))?;
     }
 
     let status = ruleset.restrict_self()?;
@@ -118,6 +136,88 @@ fn install_filesystem_landlock_rules_on_current_thread(
     Ok(())
 }
 
+/// Paths that can be granted via `path_beneath` rules without exposing any
+/// denied path.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PathsBeneath {
+    /// Paths whose entire hierarchy can be granted.
+    allowed: Vec<PathBuf>,
+    /// Ancestors of denied paths. Only directory listing is granted here:
+    /// any broader `path_beneath` right would also reach the denied path.
+    traversed: Vec<PathBuf>,
+}
+
+/// Expand `roots` into rules that cover everything beneath them except
+/// `denied_paths`.
+///
+/// Landlock rules can only add access, so a denied path nested under a root is
+/// excluded by granting each sibling along the way to it instead of the root
+/// itself. Consequently, entries created later directly inside a traversed
+/// directory are not accessible for the lifetime of the sandbox.
+fn paths_beneath_excluding(roots: &[PathBuf], denied_paths: &[PathBuf]) -> PathsBeneath {
+    let mut paths = PathsBeneath::default();
+    for root in roots {
+        collect_paths_beneath_excluding(root, denied_paths, &mut paths);
+    }
+    paths
+}
+
+fn collect_paths_beneath_excluding(
+    path: &Path,
+    denied_paths: &[PathBuf],
+    paths: &mut PathsBeneath,
+) {
+    // Landlock resolves symlinks when opening rule paths, so evaluate the
+    // target rather than the link itself.
+    let is_symlink = std::fs::symlink_metadata(path)
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false);
+    let resolved = if is_symlink {
+        match path.canonicalize() {
+            Ok(resolved) => resolved,
+            Err(_) => return,
+        }
+    } else {
+        path.to_path_buf()
+    };
+
+    if denied_paths
+        .iter()
+        .any(|denied| resolved.starts_with(denied))
+    {
+        return;
+    }
+    if !denied_paths
+        .iter()
+        .any(|denied| denied.starts_with(&resolved))
+    {
+        paths.allowed.push(path.to_path_buf());
+        return;
+    }
+    // A symlink to an ancestor of a denied path is covered by walking the
+    // real directory instead.
+    if is_symlink {
+        return;
+    }
+
+    paths.traversed.push(path.to_path_buf());
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        collect_paths_beneath_excluding(&entry.path(), denied_paths, paths);
+    }
+}
+
+/// Canonicalize the denied paths that exist so symlinked credential
+/// directories are matched by their real location.
+fn canonicalize_existing_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect()
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
 /// AF_UNIX domain sockets.
 ///
@@ -183,3 +283,59 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn paths_beneath_excluding_skips_denied_subtree() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path().canonicalize().expect("canonical tempdir");
+        let home = root.join("home");
+        let dot_ssh = home.join(".ssh");
+        let projects = home.join("projects");
+        let notes = home.join("notes.txt");
+        std::fs::create_dir_all(&dot_ssh).expect("create .ssh");
+        std::fs::create_dir_all(&projects).expect("create projects");
+        std::fs::write(&notes, "notes").expect("write notes");
+        std::fs::write(dot_ssh.join("id_rsa"), "secret").expect("write id_rsa");
+        let other = root.join("other");
+        std::fs::create_dir_all(&other).expect("create other");
+
+        let mut paths = paths_beneath_excluding(&[root.clone()], &[dot_ssh]);
+        paths.allowed.sort();
+        paths.traversed.sort();
+
+        assert_eq!(
+            paths,
+            PathsBeneath {
+                allowed: vec![notes, projects, other],
+                traversed: vec![root, home],
+            }
+        );
+    }
+
+    #[test]
+    fn paths_beneath_excluding_does_not_follow_symlink_into_denied_path() {
+        use std::os::unix::fs::symlink;
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path().canonicalize().expect("canonical tempdir");
+        let secrets = root.join("secrets");
+        std::fs::create_dir_all(&secrets).expect("create secrets");
+        let link = root.join("link-to-secrets");
+        symlink(&secrets, &link).expect("create symlink");
+
+        let paths = paths_beneath_excluding(&[root.clone()], &[secrets]);
+
+        assert_eq!(
+            paths,
+            PathsBeneath {
+                allowed: Vec::new(),
+                traversed: vec![root],
+            }
+        );
+    }
+}
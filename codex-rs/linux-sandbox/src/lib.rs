@@ -2,7 +2,8 @@
 //!
 //! On Linux, `codex-linux-sandbox` applies:
 //! - in-process restrictions (`no_new_privs` + seccomp), and
-//! - bubblewrap for filesystem isolation.
+//! - bubblewrap for filesystem isolation, and
+//! - optionally, a network namespace whose only egress is a network proxy.
 #[cfg(target_os = "linux")]
 mod bwrap;
 #[cfg(target_os = "linux")]
@@ -10,6 +11,8 @@ mod landlock;
 #[cfg(target_os = "linux")]
 mod linux_run_main;
 #[cfg(target_os = "linux")]
+mod proxy_relay;
+#[cfg(target_os = "linux")]
 mod vendored_bwrap;
 
 #[cfg(target_os = "linux")]
@@ -2,13 +2,17 @@ use clap::Parser;
 use std::ffi::CString;
 use std::fs::File;
 use std::io::Read;
+use std::net::SocketAddr;
 use std::os::fd::FromRawFd;
+use std::os::fd::RawFd;
 use std::path::Path;
 use std::path::PathBuf;
 
 use crate::bwrap::BwrapOptions;
 use crate::bwrap::create_bwrap_command_args;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::proxy_relay::spawn_host_relay;
+use crate::proxy_relay::spawn_namespace_relay;
 use crate::vendored_bwrap::exec_vendored_bwrap;
 use crate::vendored_bwrap::run_vendored_bwrap_main;
 
@@ -26,6 +30,23 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_core::protocol::SandboxPolicy,
 
+    /// Files or directories that must not be readable from within the
+    /// sandbox, such as credential stores. May be repeated.
+    #[arg(long = "denied-read-path")]
+    pub denied_read_paths: Vec<PathBuf>,
+
+    /// Host address of a `codex-network-proxy` listener. When set and the
+    /// policy disables network access, the command runs in its own network
+    /// namespace where the same port on loopback relays to this listener and
+    /// nothing else is reachable. May be repeated.
+    #[arg(long = "network-proxy")]
+    pub network_proxy: Vec<SocketAddr>,
+
+    /// Internal: socket pair fd connecting the inner stage to the host-side
+    /// network proxy relay.
+    #[arg(long = "network-proxy-fd", hide = true)]
+    pub network_proxy_fd: Option<RawFd>,
+
     /// Opt-in: use the bubblewrap-based Linux sandbox pipeline.
     ///
     /// When not set, we fall back to the legacy Landlock + mount pipeline.
@@ -62,6 +83,9 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        denied_read_paths,
+        network_proxy,
+        network_proxy_fd,
         use_bwrap_sandbox,
         apply_seccomp_then_exec,
         no_proc,
@@ -72,21 +96,40 @@ pub fn run_main() -> ! {
         panic!("No command specified to execute.");
     }
 
+    let network_proxy = if sandbox_policy.has_full_network_access() {
+        Vec::new()
+    } else {
+        network_proxy
+    };
+
     // Inner stage: apply seccomp/no_new_privs after bubblewrap has already
-    // established the filesystem view.
+    // established the filesystem view. When routing through the network
+    // proxy, start the loopback relay first; the network namespace replaces
+    // the seccomp network filter.
     if apply_seccomp_then_exec {
-        if let Err(e) =
-            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false)
-        {
+        if let Some(channel) = network_proxy_fd {
+            spawn_namespace_relay(channel, &network_proxy);
+        }
+        if let Err(e) = apply_sandbox_policy_to_current_thread(
+            &sandbox_policy,
+            &sandbox_policy_cwd,
+            false,
+            &[],
+            network_proxy_fd.is_none(),
+        ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
         exec_or_panic(command);
     }
 
     if sandbox_policy.has_full_disk_write_access() {
-        if let Err(e) =
-            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false)
-        {
+        if let Err(e) = apply_sandbox_policy_to_current_thread(
+            &sandbox_policy,
+            &sandbox_policy_cwd,
+            false,
+            &[],
+            true,
+        ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
         exec_or_panic(command);
@@ -96,19 +139,40 @@ pub fn run_main() -> ! {
         // Outer stage: bubblewrap first, then re-enter this binary in the
         // sandboxed environment to apply seccomp. This path never falls back
         // to legacy Landlock on failure.
+        let network_proxy_fd =
+            (!network_proxy.is_empty()).then(|| spawn_host_relay(&network_proxy));
         let inner = build_inner_seccomp_command(
             &sandbox_policy_cwd,
             &sandbox_policy,
             use_bwrap_sandbox,
+            &network_proxy,
+            network_proxy_fd,
             command,
         );
-        run_bwrap_with_proc_fallback(&sandbox_policy_cwd, &sandbox_policy, inner, !no_proc);
+        run_bwrap_with_proc_fallback(
+            &sandbox_policy_cwd,
+            &sandbox_policy,
+            &denied_read_paths,
+            inner,
+            !no_proc,
+            network_proxy_fd.is_some(),
+        );
+    }
+
+    // The relay only exists inside bubblewrap's network namespace. Fail
+    // closed rather than run a command that expects to reach the proxy.
+    if !network_proxy.is_empty() {
+        panic!("routing network access through a proxy requires --use-bwrap-sandbox");
     }
 
     // Legacy path: Landlock enforcement only, when bwrap sandboxing is not enabled.
-    if let Err(e) =
-        apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, true)
-    {
+    if let Err(e) = apply_sandbox_policy_to_current_thread(
+        &sandbox_policy,
+        &sandbox_policy_cwd,
+        true,
+        &denied_read_paths,
+        true,
+    ) {
         panic!("error applying legacy Linux sandbox restrictions: {e:?}");
     }
     exec_or_panic(command);
@@ -117,18 +181,31 @@ pub fn run_main() -> ! {
 fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
+    denied_read_paths: &[PathBuf],
     inner: Vec<String>,
     mount_proc: bool,
+    unshare_net: bool,
 ) -> ! {
     let mut mount_proc = mount_proc;
 
-    if mount_proc && !preflight_proc_mount_support(sandbox_policy_cwd, sandbox_policy) {
+    if mount_proc
+        && !preflight_proc_mount_support(sandbox_policy_cwd, sandbox_policy, denied_read_paths)
+    {
         eprintln!("codex-linux-sandbox: bwrap could not mount /proc; retrying with --no-proc");
         mount_proc = false;
     }
 
-    let options = BwrapOptions { mount_proc };
-    let argv = build_bwrap_argv(inner, sandbox_policy, sandbox_policy_cwd, options);
+    let options = BwrapOptions {
+        mount_proc,
+        unshare_net,
+    };
+    let argv = build_bwrap_argv(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        denied_read_paths,
+        options,
+    );
     exec_vendored_bwrap(argv);
 }
 
@@ -136,10 +213,17 @@ fn build_bwrap_argv(
     inner: Vec<String>,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    denied_read_paths: &[PathBuf],
     options: BwrapOptions,
 ) -> Vec<String> {
-    let mut args = create_bwrap_command_args(inner, sandbox_policy, sandbox_policy_cwd, options)
-        .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
+    let mut args = create_bwrap_command_args(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        denied_read_paths,
+        options,
+    )
+    .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
 
     let command_separator_index = args
         .iter()
@@ -158,13 +242,18 @@ fn build_bwrap_argv(
 fn preflight_proc_mount_support(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
+    denied_read_paths: &[PathBuf],
 ) -> bool {
     let preflight_command = vec![resolve_true_command()];
     let preflight_argv = build_bwrap_argv(
         preflight_command,
         sandbox_policy,
         sandbox_policy_cwd,
-        BwrapOptions { mount_proc: true },
+        denied_read_paths,
+        BwrapOptions {
+            mount_proc: true,
+            unshare_net: false,
+        },
     );
     let stderr = run_bwrap_in_child_capture_stderr(preflight_argv);
     !is_proc_mount_failure(stderr.as_str())
@@ -249,7 +338,7 @@ fn run_bwrap_in_child_capture_stderr(argv: Vec<String>) -> String {
 /// We use explicit close() checks here (instead of ignoring return codes)
 /// because this code runs in low-level sandbox setup paths where fd leaks or
 /// close errors can mask the root cause of later failures.
-fn close_fd_or_panic(fd: libc::c_int, context: &str) {
+pub(crate) fn close_fd_or_panic(fd: libc::c_int, context: &str) {
     let close_res = unsafe { libc::close(fd) };
     if close_res < 0 {
         let err = std::io::Error::last_os_error();
@@ -268,6 +357,8 @@ fn build_inner_seccomp_command(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
     use_bwrap_sandbox: bool,
+    network_proxy: &[SocketAddr],
+    network_proxy_fd: Option<RawFd>,
     command: Vec<String>,
 ) -> Vec<String> {
     let current_exe = match std::env::current_exe() {
@@ -290,6 +381,14 @@ fn build_inner_seccomp_command(
         inner.push("--use-bwrap-sandbox".to_string());
         inner.push("--apply-seccomp-then-exec".to_string());
     }
+    if let Some(fd) = network_proxy_fd {
+        for endpoint in network_proxy {
+            inner.push("--network-proxy".to_string());
+            inner.push(endpoint.to_string());
+        }
+        inner.push("--network-proxy-fd".to_string());
+        inner.push(fd.to_string());
+    }
     inner.push("--".to_string());
     inner.extend(command);
     inner
@@ -342,7 +441,11 @@ mod tests {
             vec!["/bin/true".to_string()],
             &SandboxPolicy::ReadOnly,
             Path::new("/"),
-            BwrapOptions { mount_proc: true },
+            &[],
+            BwrapOptions {
+                mount_proc: true,
+                unshare_net: false,
+            },
         );
         assert_eq!(
             argv,
@@ -366,4 +469,98 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn hides_denied_read_paths_after_other_mounts() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dot_ssh = tmp.path().join(".ssh");
+        std::fs::create_dir_all(&dot_ssh).expect("create .ssh");
+        let netrc = tmp.path().join(".netrc");
+        std::fs::write(&netrc, "machine example.com").expect("write .netrc");
+        let missing = tmp.path().join(".aws");
+        let dot_ssh_str = dot_ssh.to_string_lossy().to_string();
+        let netrc_str = netrc.to_string_lossy().to_string();
+
+        let argv = build_bwrap_argv(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::ReadOnly,
+            Path::new("/"),
+            &[dot_ssh, netrc, missing],
+            BwrapOptions {
+                mount_proc: false,
+                unshare_net: false,
+            },
+        );
+        assert_eq!(
+            argv,
+            vec![
+                "bwrap".to_string(),
+                "--new-session".to_string(),
+                "--die-with-parent".to_string(),
+                "--ro-bind".to_string(),
+                "/".to_string(),
+                "/".to_string(),
+                "--ro-bind".to_string(),
+                "/dev/null".to_string(),
+                netrc_str,
+                "--tmpfs".to_string(),
+                dot_ssh_str.clone(),
+                "--remount-ro".to_string(),
+                dot_ssh_str,
+                "--dev-bind".to_string(),
+                "/dev/null".to_string(),
+                "/dev/null".to_string(),
+                "--unshare-pid".to_string(),
+                "--argv0".to_string(),
+                "codex-linux-sandbox".to_string(),
+                "--".to_string(),
+                "/bin/true".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn network_proxy_isolates_network_namespace() {
+        let argv = build_bwrap_argv(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::ReadOnly,
+            Path::new("/"),
+            &[],
+            BwrapOptions {
+                mount_proc: false,
+                unshare_net: true,
+            },
+        );
+        let pid_index = argv
+            .iter()
+            .position(|arg| arg == "--unshare-pid")
+            .expect("--unshare-pid");
+        assert_eq!(argv[pid_index + 1], "--unshare-net");
+    }
+
+    #[test]
+    fn inner_command_forwards_network_proxy_relay() {
+        let endpoint: SocketAddr = "127.0.0.1:3128".parse().expect("addr");
+        let inner = build_inner_seccomp_command(
+            Path::new("/"),
+            &SandboxPolicy::ReadOnly,
+            true,
+            &[endpoint],
+            Some(7),
+            vec!["/bin/true".to_string()],
+        );
+        let separator = inner
+            .iter()
+            .position(|arg| arg == "--")
+            .expect("command separator");
+        assert_eq!(
+            inner[separator - 4..separator].to_vec(),
+            vec![
+                "--network-proxy".to_string(),
+                "127.0.0.1:3128".to_string(),
+                "--network-proxy-fd".to_string(),
+                "7".to_string(),
+            ]
+        );
+    }
 }
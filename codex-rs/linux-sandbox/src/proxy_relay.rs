@@ -0,0 +1,323 @@
+//! Loopback relay that connects a network-isolated sandbox to
+//! `codex-network-proxy`.
+//!
+//! When the helper runs with `--network-proxy`, bubblewrap places the command
+//! in a fresh network namespace (`--unshare-net`) that only has a loopback
+//! interface. Two small relay processes bridge that namespace to the proxy:
+//!
+//! 1. Before bubblewrap starts, [`spawn_host_relay`] forks a process that stays
+//!    in the host network namespace and holds one end of a Unix socket pair.
+//! 2. Inside the sandbox, [`spawn_namespace_relay`] binds each proxy port on
+//!    loopback and forks a process that passes every accepted connection over
+//!    the socket pair (`SCM_RIGHTS`).
+//! 3. The host relay connects to the real proxy listener and copies bytes in
+//!    both directions.
+//!
+//! Nothing else in the namespace can reach the host network, so every
+//! connection the command makes is subject to the proxy's policy.
+use std::io;
+use std::net::Shutdown;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::RawFd;
+
+use codex_core::sandboxing::network_proxy::namespace_addr;
+
+use crate::linux_run_main::close_fd_or_panic;
+
+/// Control buffer large enough for one `SCM_RIGHTS` message carrying a single
+/// fd, with `cmsghdr` alignment.
+type ControlBuffer = [u64; 4];
+
+/// Fork the host-side relay and return the sandbox end of its socket pair.
+///
+/// The returned fd is inheritable so it survives the exec into bubblewrap;
+/// pass its number to the inner stage with `--network-proxy-fd`.
+pub(crate) fn spawn_host_relay(endpoints: &[SocketAddr]) -> RawFd {
+    let mut fds = [0; 2];
+    let res = unsafe {
+        libc::socketpair(
+            libc::AF_UNIX,
+            libc::SOCK_SEQPACKET | libc::SOCK_CLOEXEC,
+            0,
+            fds.as_mut_ptr(),
+        )
+    };
+    if res < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to create network proxy relay socket pair: {err}");
+    }
+    let [host_fd, sandbox_fd] = fds;
+
+    let parent = unsafe { libc::getpid() };
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to fork network proxy relay: {err}");
+    }
+
+    if pid == 0 {
+        close_fd_or_panic(sandbox_fd, "close sandbox end in network proxy relay");
+        detach_relay_process(parent);
+        run_host_relay(host_fd, endpoints);
+        std::process::exit(0);
+    }
+
+    close_fd_or_panic(host_fd, "close host end in network proxy relay parent");
+    let flags_res = unsafe { libc::fcntl(sandbox_fd, libc::F_SETFD, 0) };
+    if flags_res < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to make network proxy relay fd inheritable: {err}");
+    }
+    sandbox_fd
+}
+
+/// Bind the proxy ports on the namespace's loopback interface and fork the
+/// process that hands accepted connections to the host relay over `channel`.
+///
+/// Must run before seccomp is applied; `channel` is closed in the caller so
+/// the sandboxed command never sees it.
+pub(crate) fn spawn_namespace_relay(channel: RawFd, endpoints: &[SocketAddr]) {
+    let listeners: Vec<TcpListener> = endpoints
+        .iter()
+        .map(|endpoint| {
+            let addr = namespace_addr(*endpoint);
+            TcpListener::bind(addr)
+                .unwrap_or_else(|err| panic!("failed to bind network proxy relay on {addr}: {err}"))
+        })
+        .collect();
+
+    let parent = unsafe { libc::getpid() };
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to fork network proxy relay: {err}");
+    }
+
+    if pid == 0 {
+        detach_relay_process(parent);
+        let workers: Vec<_> = listeners
+            .into_iter()
+            .enumerate()
+            .map(|(index, listener)| {
+                std::thread::spawn(move || forward_accepted_connections(channel, index, listener))
+            })
+            .collect();
+        for worker in workers {
+            let _ = worker.join();
+        }
+        std::process::exit(0);
+    }
+
+    drop(listeners);
+    close_fd_or_panic(channel, "close network proxy relay fd before exec");
+}
+
+/// Tie a relay process's lifetime to the process it was forked from and
+/// release the inherited stdio so the caller's output pipes can close.
+fn detach_relay_process(parent: libc::pid_t) {
+    unsafe {
+        libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+        if libc::getppid() != parent {
+            libc::_exit(0);
+        }
+        let devnull = libc::open(c"/dev/null".as_ptr(), libc::O_RDWR);
+        if devnull >= 0 {
+            for fd in [libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+                libc::dup2(devnull, fd);
+            }
+            if devnull > libc::STDERR_FILENO {
+                libc::close(devnull);
+            }
+        }
+    }
+}
+
+fn forward_accepted_connections(channel: RawFd, index: usize, listener: TcpListener) {
+    let Ok(index) = u8::try_from(index) else {
+        return;
+    };
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        // The stream is closed here once it has been duplicated into the
+        // host relay.
+        if send_connection(channel, index, &stream).is_err() {
+            return;
+        }
+    }
+}
+
+fn run_host_relay(channel: RawFd, endpoints: &[SocketAddr]) {
+    loop {
+        match receive_connection(channel) {
+            Ok(Some((index, client))) => {
+                let Some(endpoint) = endpoints.get(index).copied() else {
+                    continue;
+                };
+                std::thread::spawn(move || relay_to_endpoint(client, endpoint));
+            }
+            // Every process in the sandbox that held the other end is gone.
+            Ok(None) => return,
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::Interrupted | io::ErrorKind::InvalidData
+                ) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+fn relay_to_endpoint(client: TcpStream, endpoint: SocketAddr) {
+    // Dropping `client` on failure resets the connection inside the sandbox.
+    let Ok(upstream) = TcpStream::connect(endpoint) else {
+        return;
+    };
+    let (Ok(client_reader), Ok(upstream_writer)) = (client.try_clone(), upstream.try_clone())
+    else {
+        return;
+    };
+
+    let forward = std::thread::spawn(move || {
+        let _ = io::copy(&mut &client_reader, &mut &upstream_writer);
+        let _ = upstream_writer.shutdown(Shutdown::Write);
+    });
+    let _ = io::copy(&mut &upstream, &mut &client);
+    let _ = client.shutdown(Shutdown::Write);
+    let _ = forward.join();
+}
+
+fn send_connection(channel: RawFd, index: u8, stream: &TcpStream) -> io::Result<()> {
+    let payload = [index];
+    let mut iov = libc::iovec {
+        iov_base: payload.as_ptr() as *mut libc::c_void,
+        iov_len: payload.len(),
+    };
+    let mut control: ControlBuffer = [0; 4];
+
+    // SAFETY: `msg` points at live buffers for the duration of `sendmsg`, and
+    // `control` is large and aligned enough for one fd-carrying cmsghdr.
+    let sent = unsafe {
+        let mut msg: libc::msghdr = std::mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr().cast();
+        msg.msg_controllen = libc::CMSG_SPACE(std::mem::size_of::<RawFd>() as u32) as _;
+
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(std::mem::size_of::<RawFd>() as u32) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast::<RawFd>(), stream.as_raw_fd());
+
+        libc::sendmsg(channel, &msg, libc::MSG_NOSIGNAL)
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Receive one connection from the namespace relay. Returns `Ok(None)` once
+/// the sandbox side of the socket pair has been closed.
+fn receive_connection(channel: RawFd) -> io::Result<Option<(usize, TcpStream)>> {
+    let mut payload = [0u8; 1];
+    let mut iov = libc::iovec {
+        iov_base: payload.as_mut_ptr().cast(),
+        iov_len: payload.len(),
+    };
+    let mut control: ControlBuffer = [0; 4];
+
+    // SAFETY: `msg` points at live buffers for the duration of `recvmsg`.
+    let (received, fd) = unsafe {
+        let mut msg: libc::msghdr = std::mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr().cast();
+        msg.msg_controllen = std::mem::size_of::<ControlBuffer>() as _;
+
+        let received = libc::recvmsg(channel, &mut msg, libc::MSG_CMSG_CLOEXEC);
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        let fd = if received > 0
+            && !cmsg.is_null()
+            && (*cmsg).cmsg_level == libc::SOL_SOCKET
+            && (*cmsg).cmsg_type == libc::SCM_RIGHTS
+        {
+            Some(std::ptr::read_unaligned(
+                libc::CMSG_DATA(cmsg).cast::<RawFd>(),
+            ))
+        } else {
+            None
+        };
+        (received, fd)
+    };
+
+    if received < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if received == 0 {
+        return Ok(None);
+    }
+    let Some(fd) = fd else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "network proxy relay message is missing a socket",
+        ));
+    };
+    // SAFETY: the kernel installed `fd` in this process for us to own.
+    let stream = unsafe { TcpStream::from_raw_fd(fd) };
+    Ok(Some((usize::from(payload[0]), stream)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Read;
+    use std::io::Write;
+
+    #[test]
+    fn passes_accepted_connections_across_the_socket_pair() {
+        let mut fds = [0; 2];
+        let res = unsafe {
+            libc::socketpair(
+                libc::AF_UNIX,
+                libc::SOCK_SEQPACKET | libc::SOCK_CLOEXEC,
+                0,
+                fds.as_mut_ptr(),
+            )
+        };
+        assert_eq!(res, 0);
+        let [host_fd, sandbox_fd] = fds;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let mut client = TcpStream::connect(listener.local_addr().expect("addr")).expect("connect");
+        let (accepted, _) = listener.accept().expect("accept");
+
+        send_connection(sandbox_fd, 1, &accepted).expect("send connection");
+        drop(accepted);
+        let (index, mut received) = receive_connection(host_fd)
+            .expect("receive connection")
+            .expect("connection present");
+        assert_eq!(index, 1);
+
+        client.write_all(b"ping").expect("write");
+        let mut buf = [0u8; 4];
+        received.read_exact(&mut buf).expect("read");
+        assert_eq!(&buf, b"ping");
+
+        close_fd_or_panic(sandbox_fd, "close sandbox end");
+        assert_eq!(
+            receive_connection(host_fd)
+                .expect("receive after close")
+                .is_none(),
+            true
+        );
+        close_fd_or_panic(host_fd, "close host end");
+    }
+}
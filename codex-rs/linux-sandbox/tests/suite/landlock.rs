@@ -13,6 +13,11 @@ use codex_core::sandboxing::SandboxPermissions;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpListener;
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
 
@@ -61,12 +66,22 @@ async fn run_cmd_output(
         .expect("sandboxed command should execute")
 }
 
-#[expect(clippy::expect_used)]
 async fn run_cmd_result_with_writable_roots(
     cmd: &[&str],
     writable_roots: &[PathBuf],
     timeout_ms: u64,
     use_bwrap_sandbox: bool,
+) -> Result<codex_core::exec::ExecToolCallOutput> {
+    run_cmd_result(cmd, writable_roots, &[], timeout_ms, use_bwrap_sandbox).await
+}
+
+#[expect(clippy::expect_used)]
+async fn run_cmd_result(
+    cmd: &[&str],
+    writable_roots: &[PathBuf],
+    denied_read_paths: &[AbsolutePathBuf],
+    timeout_ms: u64,
+    use_bwrap_sandbox: bool,
 ) -> Result<codex_core::exec::ExecToolCallOutput> {
     let cwd = std::env::current_dir().expect("cwd should exist");
     let sandbox_cwd = cwd.clone();
@@ -100,6 +115,7 @@ async fn run_cmd_result_with_writable_roots(
         params,
         &sandbox_policy,
         sandbox_cwd.as_path(),
+        denied_read_paths,
         &codex_linux_sandbox_exe,
         use_bwrap_sandbox,
         None,
@@ -244,6 +260,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         params,
         &sandbox_policy,
         sandbox_cwd.as_path(),
+        &[],
         &codex_linux_sandbox_exe,
         false,
         None,
@@ -380,6 +397,76 @@ async fn sandbox_blocks_codex_symlink_replacement_attack() {
     assert_ne!(codex_output.exit_code, 0);
 }
 
+/// Creates a fake home directory with an SSH key and returns the home
+/// directory, the key path, and the `.ssh` directory to deny.
+#[expect(clippy::expect_used)]
+fn create_fake_ssh_home() -> (tempfile::TempDir, PathBuf, AbsolutePathBuf) {
+    let home = tempfile::tempdir().expect("tempdir");
+    let dot_ssh = home.path().join(".ssh");
+    std::fs::create_dir_all(&dot_ssh).expect("create .ssh");
+    let id_rsa = dot_ssh.join("id_rsa");
+    std::fs::write(&id_rsa, "PRIVATE KEY").expect("write id_rsa");
+    let denied = AbsolutePathBuf::try_from(dot_ssh).expect("absolute .ssh");
+    (home, id_rsa, denied)
+}
+
+#[tokio::test]
+async fn sandbox_denies_reading_ssh_key_with_bwrap() {
+    if should_skip_bwrap_tests().await {
+        eprintln!("skipping bwrap test: vendored bwrap was not built in this environment");
+        return;
+    }
+
+    let (_home, id_rsa, denied) = create_fake_ssh_home();
+    let output = expect_denied(
+        run_cmd_result(
+            &["cat", &id_rsa.to_string_lossy()],
+            &[],
+            &[denied],
+            LONG_TIMEOUT_MS,
+            true,
+        )
+        .await,
+        "reading ~/.ssh/id_rsa should be denied under bubblewrap",
+    );
+    assert!(!output.stdout.text.contains("PRIVATE KEY"));
+}
+
+#[tokio::test]
+async fn sandbox_denies_reading_ssh_key_with_landlock() {
+    let (_home, id_rsa, denied) = create_fake_ssh_home();
+    let output = expect_denied(
+        run_cmd_result(
+            &["cat", &id_rsa.to_string_lossy()],
+            &[],
+            &[denied],
+            LONG_TIMEOUT_MS,
+            false,
+        )
+        .await,
+        "reading ~/.ssh/id_rsa should be denied under Landlock",
+    );
+    assert!(!output.stdout.text.contains("PRIVATE KEY"));
+}
+
+#[tokio::test]
+async fn sandbox_still_reads_files_next_to_denied_paths() {
+    let (home, _id_rsa, denied) = create_fake_ssh_home();
+    let readable = home.path().join("notes.txt");
+    std::fs::write(&readable, "hello").expect("write notes");
+    let output = run_cmd_result(
+        &["cat", &readable.to_string_lossy()],
+        &[],
+        &[denied],
+        LONG_TIMEOUT_MS,
+        false,
+    )
+    .await
+    .expect("sandboxed command should execute");
+    assert_eq!(output.exit_code, 0);
+    assert_eq!(output.stdout.text, "hello");
+}
+
 #[tokio::test]
 async fn sandbox_blocks_ssh() {
     // Force ssh to attempt a real TCP connection but fail quickly.  `BatchMode`
@@ -407,3 +494,92 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+/// A minimal forward proxy on host loopback standing in for
+/// `codex-network-proxy`: it answers for `allowed.test` itself and refuses
+/// every other host with 403, as the real proxy does outside its allowlist.
+#[expect(clippy::expect_used)]
+fn spawn_filtering_proxy() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind proxy");
+    let addr = listener.local_addr().expect("proxy addr");
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            loop {
+                let mut header = String::new();
+                match reader.read_line(&mut header) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) if header.trim().is_empty() => break,
+                    Ok(_) => {}
+                }
+            }
+            let response = if request_line.starts_with("GET http://allowed.test/ ") {
+                "HTTP/1.1 200 OK\r\nContent-Length: 7\r\nConnection: close\r\n\r\nallowed"
+            } else {
+                "HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    addr
+}
+
+/// Runs `curl <url>` under bubblewrap with its network routed through `proxy`,
+/// returning curl's stdout: the body followed by the HTTP status.
+#[expect(clippy::expect_used)]
+async fn run_proxied_curl(proxy: SocketAddr, url: &str) -> String {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let sandbox_policy = serde_json::to_string(&SandboxPolicy::new_read_only_policy())
+        .expect("serialize sandbox policy");
+    // The relay mirrors the proxy on the same loopback port inside the
+    // namespace, so the host address works unchanged.
+    let proxy_url = format!("http://{proxy}");
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .arg("--sandbox-policy-cwd")
+        .arg(&cwd)
+        .arg("--sandbox-policy")
+        .arg(sandbox_policy)
+        .arg("--network-proxy")
+        .arg(proxy.to_string())
+        .arg("--use-bwrap-sandbox")
+        .arg("--")
+        .args(["curl", "-sS", "--max-time", "5", "-w", " %{http_code}", url])
+        .env("HTTP_PROXY", &proxy_url)
+        .env("http_proxy", &proxy_url)
+        .env_remove("NO_PROXY")
+        .env_remove("no_proxy")
+        .output()
+        .await
+        .expect("run codex-linux-sandbox");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[tokio::test]
+async fn sandbox_routes_curl_through_network_proxy() {
+    if should_skip_bwrap_tests().await {
+        eprintln!("skipping bwrap test: vendored bwrap was not built in this environment");
+        return;
+    }
+    if std::process::Command::new("curl")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping network proxy test: curl is not installed");
+        return;
+    }
+
+    let proxy = spawn_filtering_proxy();
+    assert_eq!(
+        run_proxied_curl(proxy, "http://allowed.test/").await,
+        "allowed 200"
+    );
+    assert_eq!(run_proxied_curl(proxy, "http://denied.test/").await, " 403");
+}
@@ -1,6 +1,7 @@
 use crate::config::NetworkMode;
 use crate::responses::json_response;
 use crate::responses::text_response;
+use crate::state::HostAllowScope;
 use crate::state::NetworkProxyState;
 use anyhow::Context;
 use anyhow::Result;
@@ -14,6 +15,7 @@ use rama_http_backend::server::HttpServer;
 use rama_tcp::server::TcpListener;
 use serde::Deserialize;
 use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -21,8 +23,9 @@ use tracing::error;
 use tracing::info;
 
 pub async fn run_admin_api(state: Arc<NetworkProxyState>, addr: SocketAddr) -> Result<()> {
-    // Debug-only admin API (health/config/patterns/blocked + mode/reload). Policy is config-driven
-    // and constraint-enforced; this endpoint should not become a second policy/approval plane.
+    // Local admin API (health/config/patterns/blocked + mode/allow/reload). Policy is
+    // config-driven and constraint-enforced; `/allow` only records the user's runtime approvals
+    // of blocked hosts and cannot widen managed constraints.
     let listener = TcpListener::build()
         .bind(addr)
         .await
@@ -45,8 +48,6 @@ async fn handle_admin_request(
     state: Arc<NetworkProxyState>,
     req: Request,
 ) -> Result<Response, Infallible> {
-    const MODE_BODY_LIMIT: usize = 8 * 1024;
-
     let method = req.method().clone();
     let path = req.uri().path().to_string();
     let response = match (method.as_str(), path.as_str()) {
@@ -76,38 +77,9 @@ async fn handle_admin_request(
             }
         },
         ("POST", "/mode") => {
-            let mut body = req.into_body();
-            let mut buf: Vec<u8> = Vec::new();
-            loop {
-                let chunk = match body.chunk().await {
-                    Ok(chunk) => chunk,
-                    Err(err) => {
-                        error!("failed to read mode body: {err}");
-                        return Ok(text_response(StatusCode::BAD_REQUEST, "invalid body"));
-                    }
-                };
-                let Some(chunk) = chunk else {
-                    break;
-                };
-
-                if buf.len().saturating_add(chunk.len()) > MODE_BODY_LIMIT {
-                    return Ok(text_response(
-                        StatusCode::PAYLOAD_TOO_LARGE,
-                        "body too large",
-                    ));
-                }
-                buf.extend_from_slice(&chunk);
-            }
-
-            if buf.is_empty() {
-                return Ok(text_response(StatusCode::BAD_REQUEST, "missing body"));
-            }
-            let update: ModeUpdate = match serde_json::from_slice(&buf) {
+            let update: ModeUpdate = match read_json_body(req, "mode update").await {
                 Ok(update) => update,
-                Err(err) => {
-                    error!("failed to parse mode update: {err}");
-                    return Ok(text_response(StatusCode::BAD_REQUEST, "invalid json"));
-                }
+                Err(response) => return Ok(response),
             };
             match state.set_network_mode(update.mode).await {
                 Ok(()) => json_response(&ModeUpdateResponse {
@@ -120,6 +92,22 @@ async fn handle_admin_request(
                 }
             }
         }
+        ("POST", "/allow") => {
+            let update: AllowUpdate = match read_json_body(req, "allow update").await {
+                Ok(update) => update,
+                Err(response) => return Ok(response),
+            };
+            match state.allow_host(&update.host, update.scope).await {
+                Ok(()) => json_response(&AllowUpdateResponse {
+                    status: "ok",
+                    host: update.host,
+                }),
+                Err(err) => {
+                    error!("allow update failed: {err}");
+                    text_response(StatusCode::INTERNAL_SERVER_ERROR, "allow update failed")
+                }
+            }
+        }
         ("POST", "/reload") => match state.force_reload().await {
             Ok(()) => json_response(&ReloadResponse { status: "reloaded" }),
             Err(err) => {
@@ -132,11 +120,52 @@ async fn handle_admin_request(
     Ok(response)
 }
 
+async fn read_json_body<T: DeserializeOwned>(req: Request, what: &str) -> Result<T, Response> {
+    const BODY_LIMIT: usize = 8 * 1024;
+
+    let mut body = req.into_body();
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        let chunk = match body.chunk().await {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                error!("failed to read {what} body: {err}");
+                return Err(text_response(StatusCode::BAD_REQUEST, "invalid body"));
+            }
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
+
+        if buf.len().saturating_add(chunk.len()) > BODY_LIMIT {
+            return Err(text_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "body too large",
+            ));
+        }
+        buf.extend_from_slice(&chunk);
+    }
+
+    if buf.is_empty() {
+        return Err(text_response(StatusCode::BAD_REQUEST, "missing body"));
+    }
+    serde_json::from_slice(&buf).map_err(|err| {
+        error!("failed to parse {what}: {err}");
+        text_response(StatusCode::BAD_REQUEST, "invalid json")
+    })
+}
+
 #[derive(Deserialize)]
 struct ModeUpdate {
     mode: NetworkMode,
 }
 
+#[derive(Deserialize)]
+struct AllowUpdate {
+    host: String,
+    scope: HostAllowScope,
+}
+
 #[derive(Debug, Serialize)]
 struct PatternsResponse {
     allowed: Vec<String>,
@@ -154,6 +183,12 @@ struct ModeUpdateResponse {
     mode: NetworkMode,
 }
 
+#[derive(Debug, Serialize)]
+struct AllowUpdateResponse {
+    status: &'static str,
+    host: String,
+}
+
 #[derive(Debug, Serialize)]
 struct ReloadResponse {
     status: &'static str,
@@ -14,6 +14,7 @@ use anyhow::Context;
 use anyhow::Result;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use globset::GlobSet;
+use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -62,6 +63,16 @@ pub enum HostBlockDecision {
     Blocked(HostBlockReason),
 }
 
+/// How long a runtime approval from [`NetworkProxyState::allow_host`] lasts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostAllowScope {
+    /// Allow the next connection to the host only.
+    Once,
+    /// Allow the host until the proxy restarts.
+    Session,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct BlockedRequest {
     pub host: String,
@@ -113,6 +124,11 @@ pub(crate) struct ConfigState {
     pub(crate) layer_mtimes: Vec<LayerMtime>,
     pub(crate) cfg_path: PathBuf,
     pub(crate) blocked: VecDeque<BlockedRequest>,
+    /// Hosts approved at runtime for the lifetime of the proxy. Kept across
+    /// config reloads, like `blocked`.
+    pub(crate) session_allowed_hosts: HashSet<String>,
+    /// Hosts approved for a single connection; consumed on first use.
+    pub(crate) once_allowed_hosts: HashSet<String>,
 }
 
 #[derive(Clone)]
@@ -185,6 +201,8 @@ impl NetworkProxyState {
                 log_policy_changes(&previous_cfg, &new_state.config);
                 let mut guard = self.state.write().await;
                 new_state.blocked = guard.blocked.clone();
+                new_state.session_allowed_hosts = guard.session_allowed_hosts.clone();
+                new_state.once_allowed_hosts = guard.once_allowed_hosts.clone();
                 *guard = new_state;
                 let path = guard.cfg_path.display();
                 info!("reloaded config from {path}");
@@ -258,10 +276,55 @@ impl NetworkProxyState {
             }
         }
 
-        if allowed_domains_empty || !is_allowlisted {
-            Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowed))
-        } else {
+        if !allowed_domains_empty && is_allowlisted {
+            return Ok(HostBlockDecision::Allowed);
+        }
+
+        // Runtime approvals only ever lift `not_allowed`; denylist and local checks above still
+        // apply to approved hosts.
+        let mut guard = self.state.write().await;
+        if guard.session_allowed_hosts.contains(host_str)
+            || guard.once_allowed_hosts.remove(host_str)
+        {
             Ok(HostBlockDecision::Allowed)
+        } else {
+            Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowed))
+        }
+    }
+
+    /// Allow a host that is not on the allowlist without editing config, either for one
+    /// connection or until the proxy restarts. Fails when managed constraints pin
+    /// `allowed_domains` to a set that does not cover `host`.
+    pub async fn allow_host(&self, host: &str, scope: HostAllowScope) -> Result<()> {
+        let host = Host::parse(host).context("invalid host")?;
+        let host = host.as_str().to_string();
+        loop {
+            self.reload_if_needed().await?;
+            let (candidate, constraints) = {
+                let guard = self.state.read().await;
+                let mut candidate = guard.config.clone();
+                candidate.network.allowed_domains.push(host.clone());
+                (candidate, guard.constraints.clone())
+            };
+
+            validate_policy_against_constraints(&candidate, &constraints)
+                .context("network.allowed_domains constrained by managed config")?;
+
+            let mut guard = self.state.write().await;
+            if guard.constraints != constraints {
+                drop(guard);
+                continue;
+            }
+            match scope {
+                HostAllowScope::Once => {
+                    guard.once_allowed_hosts.insert(host.clone());
+                }
+                HostAllowScope::Session => {
+                    guard.session_allowed_hosts.insert(host.clone());
+                }
+            }
+            info!("allowed host {host} ({scope:?})");
+            return Ok(());
         }
     }
 
@@ -500,6 +563,8 @@ pub(crate) fn network_proxy_state_for_policy(
         layer_mtimes: Vec::new(),
         cfg_path: PathBuf::from("/nonexistent/config.toml"),
         blocked: VecDeque::new(),
+        session_allowed_hosts: HashSet::new(),
+        once_allowed_hosts: HashSet::new(),
     };
 
     NetworkProxyState {
@@ -551,6 +616,49 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn allow_host_once_is_consumed_and_session_persists() {
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            allowed_domains: vec!["example.com".to_string()],
+            denied_domains: vec!["8.8.4.4".to_string()],
+            ..NetworkProxySettings::default()
+        });
+
+        state
+            .allow_host("8.8.8.8", HostAllowScope::Once)
+            .await
+            .unwrap();
+        assert_eq!(
+            state.host_blocked("8.8.8.8", 80).await.unwrap(),
+            HostBlockDecision::Allowed
+        );
+        assert_eq!(
+            state.host_blocked("8.8.8.8", 80).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::NotAllowed)
+        );
+
+        state
+            .allow_host("1.1.1.1", HostAllowScope::Session)
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            assert_eq!(
+                state.host_blocked("1.1.1.1", 80).await.unwrap(),
+                HostBlockDecision::Allowed
+            );
+        }
+
+        // Runtime approvals never override the denylist.
+        state
+            .allow_host("8.8.4.4", HostAllowScope::Session)
+            .await
+            .unwrap();
+        assert_eq!(
+            state.host_blocked("8.8.4.4", 80).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::Denied)
+        );
+    }
+
     #[tokio::test]
     async fn host_blocked_subdomain_wildcards_exclude_apex() {
         let state = network_proxy_state_for_policy(NetworkProxySettings {
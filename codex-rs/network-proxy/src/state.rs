@@ -21,6 +21,7 @@ use std::collections::HashSet;
 
 pub use crate::runtime::BlockedRequest;
 pub use crate::runtime::BlockedRequestArgs;
+pub use crate::runtime::HostAllowScope;
 pub use crate::runtime::NetworkProxyState;
 #[cfg(test)]
 pub(crate) use crate::runtime::network_proxy_state_for_policy;
@@ -65,6 +66,8 @@ pub(crate) async fn build_config_state() -> Result<ConfigState> {
         layer_mtimes,
         cfg_path,
         blocked: std::collections::VecDeque::new(),
+        session_allowed_hosts: std::collections::HashSet::new(),
+        once_allowed_hosts: std::collections::HashSet::new(),
     })
 }
 
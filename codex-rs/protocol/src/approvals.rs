@@ -59,6 +59,39 @@ pub struct ExecApprovalRequestEvent {
     pub parsed_cmd: Vec<ParsedCommand>,
 }
 
+/// Request to let sandboxed commands reach a host that the network proxy
+/// blocked.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct NetworkApprovalRequestEvent {
+    /// Identifier to answer with `Op::NetworkApproval`.
+    pub id: String,
+    /// Host the command tried to reach.
+    pub host: String,
+    /// Why the proxy blocked the request (for example `not_allowed`).
+    pub reason: String,
+    /// Protocol of the blocked request (`http`, `https_connect`, `socks5`, ...).
+    pub protocol: String,
+    /// HTTP method of the blocked request, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub method: Option<String>,
+}
+
+/// User's decision in response to a [`NetworkApprovalRequestEvent`].
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkApprovalDecision {
+    /// Allow the next connection to the host.
+    AllowOnce,
+    /// Allow the host until the network proxy restarts.
+    AllowForSession,
+    /// Allow the host and add it to `[network] allowed_domains` in config.toml.
+    AllowAlways,
+    /// Keep the host blocked and do not ask again this session.
+    #[default]
+    Deny,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ElicitationRequestEvent {
     pub server_name: String,
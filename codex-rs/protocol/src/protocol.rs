@@ -53,6 +53,8 @@ pub use crate::approvals::ApplyPatchApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
+pub use crate::approvals::NetworkApprovalDecision;
+pub use crate::approvals::NetworkApprovalRequestEvent;
 pub use crate::request_user_input::RequestUserInputEvent;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
@@ -207,6 +209,14 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Answer a request to reach a host blocked by the network proxy.
+    NetworkApproval {
+        /// The id of the network approval request.
+        id: String,
+        /// The user's decision in response to the request.
+        decision: NetworkApprovalDecision,
+    },
+
     /// Resolve an MCP elicitation request.
     ResolveElicitation {
         /// Name of the MCP server that issued the request.
@@ -794,6 +804,10 @@ pub enum EventMsg {
 
     ElicitationRequest(ElicitationRequestEvent),
 
+    /// A sandboxed command was blocked from reaching a host by the network
+    /// proxy; the user may allow it.
+    NetworkApprovalRequest(NetworkApprovalRequestEvent),
+
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
     /// Notification advising the user that something they are using has been
@@ -840,6 +854,14 @@ pub enum EventMsg {
     /// Notification that skill data may have been updated and clients may want to reload.
     SkillsUpdateAvailable,
 
+    /// `config.toml` or `AGENTS.md` changed on disk. Accepted changes apply
+    /// from the next turn.
+    ConfigReloaded(ConfigReloadedEvent),
+
+    /// The session switched to a fallback model after the model it was using
+    /// kept failing, or returned to the configured model.
+    ModelFallback(ModelFallbackEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -1239,6 +1261,12 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Estimated cost of the thread so far in US dollars, from the configured
+    /// model prices. Absent when any usage was on a model without a known
+    /// price, so the estimate never undercounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub total_cost_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
@@ -1709,6 +1737,7 @@ pub enum RolloutItem {
     Compacted(CompactedItem),
     TurnContext(TurnContextItem),
     EventMsg(EventMsg),
+    ApprovalDecision(ApprovalDecisionItem),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, TS)]
@@ -1732,6 +1761,15 @@ impl From<CompactedItem> for ResponseItem {
     }
 }
 
+/// The user's answer to an approval prompt for a tool call. Recorded in the
+/// rollout so usage statistics can report approval rates.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, TS)]
+pub struct ApprovalDecisionItem {
+    pub call_id: String,
+    pub tool_name: String,
+    pub decision: ReviewDecision,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, TS)]
 pub struct TurnContextItem {
     pub cwd: PathBuf,
@@ -1810,6 +1848,18 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes between two revisions, e.g. a pull request's merge
+    /// base and head.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Range { from: String, to: String },
+
+    /// Review a patch file (e.g. a `.diff` received by mail) without applying
+    /// it. Relative paths are resolved against the session's cwd.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions provided by the user.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -1823,6 +1873,11 @@ pub struct ReviewRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub user_facing_hint: Option<String>,
+    /// Restricts the review to changes under these files or directories,
+    /// relative to the session's cwd. Empty reviews the whole target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(optional)]
+    pub paths: Vec<String>,
 }
 
 /// Structured review result produced by a child review session.
@@ -1989,6 +2044,39 @@ pub struct BackgroundEventEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ConfigReloadedEvent {
+    /// Changed settings the session applies from the next turn, as dotted
+    /// config keys (e.g. `mcp_servers.docs`), plus `AGENTS.md` when the
+    /// instructions changed.
+    pub changed: Vec<String>,
+    /// Changed settings that only take effect in new sessions.
+    pub restart_required: Vec<String>,
+    /// Why the new files were rejected. The session keeps its previous
+    /// settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ModelFallbackEvent {
+    pub from_model: String,
+    pub to_model: String,
+    /// Provider serving `to_model`.
+    pub model_provider_id: String,
+    /// Error that caused the switch. Absent when returning to the configured
+    /// model after the cooldown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub reason: Option<String>,
+    /// Unix timestamp (seconds) after which the session returns to the
+    /// configured model at the start of a turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "number")]
+    pub resume_primary_at: Option<i64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct DeprecationNoticeEvent {
     /// Concise summary of what is deprecated.
@@ -2385,6 +2473,10 @@ pub struct Chunk {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TurnAbortedEvent {
     pub reason: TurnAbortReason,
+    /// The budget that ran out, when `reason` is `budget_exceeded`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub budget: Option<SessionBudgetUsage>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
@@ -2393,6 +2485,44 @@ pub enum TurnAbortReason {
     Interrupted,
     Replaced,
     ReviewEnded,
+    BudgetExceeded,
+}
+
+/// A per-thread limit configured under `[budget]` in `config.toml`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionBudgetKind {
+    Turns,
+    TotalTokens,
+    ToolCalls,
+    Duration,
+}
+
+/// Consumption of one session budget. `limit` and `used` count turns,
+/// tokens, tool calls or seconds depending on `kind`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct SessionBudgetUsage {
+    pub kind: SessionBudgetKind,
+    #[ts(type = "number")]
+    pub limit: i64,
+    #[ts(type = "number")]
+    pub used: i64,
+}
+
+impl fmt::Display for SessionBudgetUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, unit) = match self.kind {
+            SessionBudgetKind::Turns => ("turn", "turns"),
+            SessionBudgetKind::TotalTokens => ("token", "tokens"),
+            SessionBudgetKind::ToolCalls => ("tool call", "tool calls"),
+            SessionBudgetKind::Duration => ("time", "seconds"),
+        };
+        write!(
+            f,
+            "{name} budget: {} of {} {unit} used",
+            self.used, self.limit
+        )
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
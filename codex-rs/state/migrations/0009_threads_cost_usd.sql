@@ -0,0 +1 @@
+ALTER TABLE threads ADD COLUMN cost_usd REAL NOT NULL DEFAULT 0;
@@ -0,0 +1,27 @@
+CREATE TABLE thread_turns (
+    thread_id TEXT NOT NULL,
+    turn_index INTEGER NOT NULL,
+    model TEXT NOT NULL,
+    tokens_at_start INTEGER NOT NULL,
+    tokens_used INTEGER NOT NULL,
+    cost_at_start REAL NOT NULL,
+    cost_usd REAL NOT NULL,
+    cost_known INTEGER NOT NULL,
+    started_at INTEGER NOT NULL,
+    PRIMARY KEY(thread_id, turn_index),
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE TABLE thread_tool_calls (
+    thread_id TEXT NOT NULL,
+    call_id TEXT NOT NULL,
+    turn_index INTEGER NOT NULL,
+    tool TEXT NOT NULL,
+    command TEXT,
+    approval TEXT,
+    exit_code INTEGER,
+    PRIMARY KEY(thread_id, call_id),
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_thread_tool_calls_tool ON thread_tool_calls(tool);
@@ -0,0 +1,27 @@
+CREATE TABLE thread_search_entries (
+    id INTEGER PRIMARY KEY,
+    thread_id TEXT NOT NULL,
+    turn_index INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    content TEXT NOT NULL,
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_thread_search_entries_thread ON thread_search_entries(thread_id, turn_index);
+
+CREATE VIRTUAL TABLE thread_search USING fts5(
+    content,
+    content = 'thread_search_entries',
+    content_rowid = 'id'
+);
+
+CREATE TRIGGER thread_search_entries_after_insert AFTER INSERT ON thread_search_entries BEGIN
+    INSERT INTO thread_search(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER thread_search_entries_after_delete AFTER DELETE ON thread_search_entries BEGIN
+    INSERT INTO thread_search(thread_search, rowid, content) VALUES ('delete', old.id, old.content);
+END;
+
+-- Re-run the rollout backfill so sessions recorded before this migration are indexed.
+UPDATE backfill_state SET status = 'pending', last_watermark = NULL;
@@ -0,0 +1,30 @@
+CREATE TABLE scheduled_jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    prompt TEXT NOT NULL,
+    cwd TEXT NOT NULL,
+    profile TEXT,
+    sandbox TEXT,
+    cron TEXT NOT NULL,
+    output TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    last_fired_at INTEGER
+);
+
+CREATE TABLE scheduled_job_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    job_id INTEGER NOT NULL,
+    scheduled_for INTEGER NOT NULL,
+    started_at INTEGER NOT NULL,
+    finished_at INTEGER,
+    status TEXT NOT NULL,
+    exit_code INTEGER,
+    thread_id TEXT,
+    rollout_path TEXT,
+    error TEXT,
+    FOREIGN KEY(job_id) REFERENCES scheduled_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_scheduled_job_runs_job ON scheduled_job_runs(job_id, started_at DESC);
@@ -22,7 +22,7 @@ pub fn apply_rollout_item(
         RolloutItem::TurnContext(turn_ctx) => apply_turn_context(metadata, turn_ctx),
         RolloutItem::EventMsg(event) => apply_event_msg(metadata, event),
         RolloutItem::ResponseItem(item) => apply_response_item(metadata, item),
-        RolloutItem::Compacted(_) => {}
+        RolloutItem::Compacted(_) | RolloutItem::ApprovalDecision(_) => {}
     }
     if metadata.model_provider.is_empty() {
         metadata.model_provider = default_provider.to_string();
@@ -65,6 +65,9 @@ fn apply_event_msg(metadata: &mut ThreadMetadata, event: &EventMsg) {
             if let Some(info) = token_count.info.as_ref() {
                 metadata.tokens_used = info.total_token_usage.total_tokens.max(0);
             }
+            if let Some(cost_usd) = token_count.total_cost_usd {
+                metadata.cost_usd = cost_usd;
+            }
         }
         EventMsg::UserMessage(user) => {
             if metadata.first_user_message.is_none() {
@@ -85,7 +88,7 @@ fn apply_response_item(_metadata: &mut ThreadMetadata, _item: &ResponseItem) {
     // Title and first_user_message are derived from EventMsg::UserMessage only.
 }
 
-fn strip_user_message_prefix(text: &str) -> &str {
+pub(crate) fn strip_user_message_prefix(text: &str) -> &str {
     match text.find(USER_MESSAGE_BEGIN) {
         Some(idx) => text[idx + USER_MESSAGE_BEGIN.len()..].trim(),
         None => text.trim(),
@@ -127,6 +130,9 @@ mod tests {
     use codex_protocol::models::ResponseItem;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::RolloutItem;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsage;
+    use codex_protocol::protocol::TokenUsageInfo;
     use codex_protocol::protocol::USER_MESSAGE_BEGIN;
     use codex_protocol::protocol::UserMessageEvent;
 
@@ -207,6 +213,28 @@ mod tests {
         assert_eq!(metadata.title, "");
     }
 
+    #[test]
+    fn token_count_events_set_tokens_and_cost() {
+        let mut metadata = metadata_for_test();
+        let item = RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: TokenUsage {
+                    total_tokens: 1_500,
+                    ..Default::default()
+                },
+                last_token_usage: TokenUsage::default(),
+                model_context_window: None,
+            }),
+            rate_limits: None,
+            total_cost_usd: Some(0.25),
+        }));
+
+        apply_rollout_item(&mut metadata, &item, "test-provider");
+
+        assert_eq!(metadata.tokens_used, 1_500);
+        assert_eq!(metadata.cost_usd, 0.25);
+    }
+
     fn metadata_for_test() -> ThreadMetadata {
         let id = ThreadId::from_string(&Uuid::from_u128(42).to_string()).expect("thread id");
         let created_at = DateTime::<Utc>::from_timestamp(1_735_689_600, 0).expect("timestamp");
@@ -223,6 +251,7 @@ mod tests {
             sandbox_policy: "read-only".to_string(),
             approval_mode: "on-request".to_string(),
             tokens_used: 1,
+            cost_usd: 0.0,
             first_user_message: None,
             archived_at: None,
             git_sha: None,
@@ -26,11 +26,29 @@ pub use model::BackfillState;
 pub use model::BackfillStats;
 pub use model::BackfillStatus;
 pub use model::ExtractionOutcome;
+pub use model::Memory;
+pub use model::MemoryScope;
+pub use model::MemorySource;
+pub use model::NamedCount;
+pub use model::NewMemory;
+pub use model::NewScheduledJob;
+pub use model::ScheduledJob;
+pub use model::ScheduledJobRun;
+pub use model::ScheduledRunOutcome;
+pub use model::ScheduledRunStatus;
 pub use model::SortKey;
 pub use model::ThreadMemory;
 pub use model::ThreadMetadata;
 pub use model::ThreadMetadataBuilder;
+pub use model::ThreadSearchMatch;
+pub use model::ThreadSearchMatchKind;
+pub use model::ThreadSearchQuery;
+pub use model::ThreadSearchResult;
 pub use model::ThreadsPage;
+pub use model::UsageStats;
+pub use model::UsageStatsGroup;
+pub use model::UsageStatsGroupBy;
+pub use model::UsageStatsQuery;
 pub use runtime::STATE_DB_FILENAME;
 pub use runtime::STATE_DB_VERSION;
 pub use runtime::state_db_filename;
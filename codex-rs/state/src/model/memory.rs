@@ -0,0 +1,138 @@
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Who a memory applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryScope {
+    /// Applies to every session of the user.
+    User,
+    /// Applies only to sessions in one repository.
+    Repo,
+}
+
+impl MemoryScope {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            MemoryScope::User => "user",
+            MemoryScope::Repo => "repo",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user" => Some(MemoryScope::User),
+            "repo" => Some(MemoryScope::Repo),
+            _ => None,
+        }
+    }
+}
+
+/// Who wrote a memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemorySource {
+    /// Saved by the agent with the `remember` tool.
+    Agent,
+    /// Added or edited by the user with `codex memory`.
+    User,
+}
+
+impl MemorySource {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            MemorySource::Agent => "agent",
+            MemorySource::User => "user",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "agent" => Some(MemorySource::Agent),
+            "user" => Some(MemorySource::User),
+            _ => None,
+        }
+    }
+}
+
+/// A durable fact carried across sessions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Memory {
+    pub id: i64,
+    pub scope: MemoryScope,
+    /// Repository key for [`MemoryScope::Repo`] memories.
+    pub repo: Option<String>,
+    pub content: String,
+    /// Provenance: who wrote the memory.
+    pub source: MemorySource,
+    /// Provenance: the thread the memory was saved from, if any.
+    pub thread_id: Option<ThreadId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A memory to insert with [`crate::StateRuntime::add_memory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewMemory {
+    pub scope: MemoryScope,
+    /// Repository key; required for [`MemoryScope::Repo`] and ignored otherwise.
+    pub repo: Option<String>,
+    pub content: String,
+    pub source: MemorySource,
+    pub thread_id: Option<ThreadId>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MemoryRow {
+    id: i64,
+    scope: String,
+    repo: String,
+    content: String,
+    source: String,
+    thread_id: Option<String>,
+    created_at: i64,
+    updated_at: i64,
+}
+
+impl MemoryRow {
+    pub(crate) fn try_from_row(row: &SqliteRow) -> Result<Self> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            scope: row.try_get("scope")?,
+            repo: row.try_get("repo")?,
+            content: row.try_get("content")?,
+            source: row.try_get("source")?,
+            thread_id: row.try_get("thread_id")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+impl TryFrom<MemoryRow> for Memory {
+    type Error = anyhow::Error;
+
+    fn try_from(row: MemoryRow) -> std::result::Result<Self, Self::Error> {
+        let scope = MemoryScope::parse(&row.scope)
+            .ok_or_else(|| anyhow::anyhow!("invalid memory scope: {}", row.scope))?;
+        let source = MemorySource::parse(&row.source)
+            .ok_or_else(|| anyhow::anyhow!("invalid memory source: {}", row.source))?;
+        Ok(Self {
+            id: row.id,
+            scope,
+            repo: (!row.repo.is_empty()).then_some(row.repo),
+            content: row.content,
+            source,
+            thread_id: row.thread_id.map(ThreadId::try_from).transpose()?,
+            created_at: epoch_seconds_to_datetime(row.created_at)?,
+            updated_at: epoch_seconds_to_datetime(row.updated_at)?,
+        })
+    }
+}
+
+fn epoch_seconds_to_datetime(secs: i64) -> Result<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp(secs, 0)
+        .ok_or_else(|| anyhow::anyhow!("invalid unix timestamp: {secs}"))
+}
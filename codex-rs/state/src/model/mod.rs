@@ -1,13 +1,26 @@
 mod backfill_state;
 mod log;
+mod memory;
+mod scheduled_job;
 mod thread_memory;
 mod thread_metadata;
+mod thread_search;
+mod usage_stats;
 
 pub use backfill_state::BackfillState;
 pub use backfill_state::BackfillStatus;
 pub use log::LogEntry;
 pub use log::LogQuery;
 pub use log::LogRow;
+pub use memory::Memory;
+pub use memory::MemoryScope;
+pub use memory::MemorySource;
+pub use memory::NewMemory;
+pub use scheduled_job::NewScheduledJob;
+pub use scheduled_job::ScheduledJob;
+pub use scheduled_job::ScheduledJobRun;
+pub use scheduled_job::ScheduledRunOutcome;
+pub use scheduled_job::ScheduledRunStatus;
 pub use thread_memory::ThreadMemory;
 pub use thread_metadata::Anchor;
 pub use thread_metadata::BackfillStats;
@@ -16,8 +29,26 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use thread_search::ThreadSearchMatch;
+pub use thread_search::ThreadSearchMatchKind;
+pub use thread_search::ThreadSearchQuery;
+pub use thread_search::ThreadSearchResult;
+pub use usage_stats::NamedCount;
+pub use usage_stats::UsageStats;
+pub use usage_stats::UsageStatsGroup;
+pub use usage_stats::UsageStatsGroupBy;
+pub use usage_stats::UsageStatsQuery;
 
+pub(crate) use memory::MemoryRow;
+pub(crate) use scheduled_job::ScheduledJobRow;
+pub(crate) use scheduled_job::ScheduledJobRunRow;
 pub(crate) use thread_memory::ThreadMemoryRow;
 pub(crate) use thread_metadata::ThreadRow;
 pub(crate) use thread_metadata::anchor_from_item;
 pub(crate) use thread_metadata::datetime_to_epoch_seconds;
+pub(crate) use thread_search::SearchEntry;
+pub(crate) use thread_search::fts_match_expression;
+pub(crate) use thread_search::search_entries;
+pub(crate) use usage_stats::ToolCallUpdate;
+pub(crate) use usage_stats::TurnUsageRow;
+pub(crate) use usage_stats::usage_delta;
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+use std::path::PathBuf;
+
+/// A headless run executed on a cron schedule by `codex schedule run-daemon`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledJob {
+    pub id: i64,
+    /// Unique, user-chosen name of the job.
+    pub name: String,
+    pub prompt: String,
+    /// Working directory the run starts in.
+    pub cwd: PathBuf,
+    /// Config profile to run with.
+    pub profile: Option<String>,
+    /// Sandbox mode to run with, as accepted by `codex exec --sandbox`.
+    pub sandbox: Option<String>,
+    /// Five-field cron expression or macro such as `@daily`.
+    pub cron: String,
+    /// File the last agent message of each run is written to.
+    pub output: Option<PathBuf>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// The schedule slot of the most recent run, if the job ever ran.
+    pub last_fired_at: Option<DateTime<Utc>>,
+}
+
+/// A job to insert with [`crate::StateRuntime::add_scheduled_job`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewScheduledJob {
+    pub name: String,
+    pub prompt: String,
+    pub cwd: PathBuf,
+    pub profile: Option<String>,
+    pub sandbox: Option<String>,
+    pub cron: String,
+    pub output: Option<PathBuf>,
+}
+
+/// State of one run of a scheduled job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduledRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl ScheduledRunStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ScheduledRunStatus::Running => "running",
+            ScheduledRunStatus::Succeeded => "succeeded",
+            ScheduledRunStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "running" => Some(ScheduledRunStatus::Running),
+            "succeeded" => Some(ScheduledRunStatus::Succeeded),
+            "failed" => Some(ScheduledRunStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One run of a scheduled job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledJobRun {
+    pub id: i64,
+    pub job_id: i64,
+    pub job_name: String,
+    /// The schedule slot this run was started for.
+    pub scheduled_for: DateTime<Utc>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub status: ScheduledRunStatus,
+    pub exit_code: Option<i32>,
+    /// The thread the run recorded, once known.
+    pub thread_id: Option<ThreadId>,
+    /// Rollout file of the thread, once known.
+    pub rollout_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+/// How a run ended, recorded with [`crate::StateRuntime::finish_scheduled_run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledRunOutcome {
+    pub status: ScheduledRunStatus,
+    pub exit_code: Option<i32>,
+    pub thread_id: Option<ThreadId>,
+    pub rollout_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ScheduledJobRow {
+    id: i64,
+    name: String,
+    prompt: String,
+    cwd: String,
+    profile: Option<String>,
+    sandbox: Option<String>,
+    cron: String,
+    output: Option<String>,
+    enabled: bool,
+    created_at: i64,
+    updated_at: i64,
+    last_fired_at: Option<i64>,
+}
+
+impl ScheduledJobRow {
+    pub(crate) fn try_from_row(row: &SqliteRow) -> Result<Self> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            prompt: row.try_get("prompt")?,
+            cwd: row.try_get("cwd")?,
+            profile: row.try_get("profile")?,
+            sandbox: row.try_get("sandbox")?,
+            cron: row.try_get("cron")?,
+            output: row.try_get("output")?,
+            enabled: row.try_get("enabled")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            last_fired_at: row.try_get("last_fired_at")?,
+        })
+    }
+}
+
+impl TryFrom<ScheduledJobRow> for ScheduledJob {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ScheduledJobRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            name: row.name,
+            prompt: row.prompt,
+            cwd: PathBuf::from(row.cwd),
+            profile: row.profile,
+            sandbox: row.sandbox,
+            cron: row.cron,
+            output: row.output.map(PathBuf::from),
+            enabled: row.enabled,
+            created_at: epoch_seconds_to_datetime(row.created_at)?,
+            updated_at: epoch_seconds_to_datetime(row.updated_at)?,
+            last_fired_at: row
+                .last_fired_at
+                .map(epoch_seconds_to_datetime)
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ScheduledJobRunRow {
+    id: i64,
+    job_id: i64,
+    job_name: String,
+    scheduled_for: i64,
+    started_at: i64,
+    finished_at: Option<i64>,
+    status: String,
+    exit_code: Option<i32>,
+    thread_id: Option<String>,
+    rollout_path: Option<String>,
+    error: Option<String>,
+}
+
+impl ScheduledJobRunRow {
+    pub(crate) fn try_from_row(row: &SqliteRow) -> Result<Self> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            job_id: row.try_get("job_id")?,
+            job_name: row.try_get("job_name")?,
+            scheduled_for: row.try_get("scheduled_for")?,
+            started_at: row.try_get("started_at")?,
+            finished_at: row.try_get("finished_at")?,
+            status: row.try_get("status")?,
+            exit_code: row.try_get("exit_code")?,
+            thread_id: row.try_get("thread_id")?,
+            rollout_path: row.try_get("rollout_path")?,
+            error: row.try_get("error")?,
+        })
+    }
+}
+
+impl TryFrom<ScheduledJobRunRow> for ScheduledJobRun {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ScheduledJobRunRow) -> std::result::Result<Self, Self::Error> {
+        let status = ScheduledRunStatus::parse(&row.status)
+            .ok_or_else(|| anyhow::anyhow!("invalid scheduled run status: {}", row.status))?;
+        Ok(Self {
+            id: row.id,
+            job_id: row.job_id,
+            job_name: row.job_name,
+            scheduled_for: epoch_seconds_to_datetime(row.scheduled_for)?,
+            started_at: epoch_seconds_to_datetime(row.started_at)?,
+            finished_at: row.finished_at.map(epoch_seconds_to_datetime).transpose()?,
+            status,
+            exit_code: row.exit_code,
+            thread_id: row.thread_id.map(ThreadId::try_from).transpose()?,
+            rollout_path: row.rollout_path.map(PathBuf::from),
+            error: row.error,
+        })
+    }
+}
+
+fn epoch_seconds_to_datetime(secs: i64) -> Result<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp(secs, 0)
+        .ok_or_else(|| anyhow::anyhow!("invalid unix timestamp: {secs}"))
+}
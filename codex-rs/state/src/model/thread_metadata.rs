@@ -30,7 +30,7 @@ pub struct Anchor {
 }
 
 /// A single page of thread metadata results.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ThreadsPage {
     /// The thread metadata items in this page.
     pub items: Vec<ThreadMetadata>,
@@ -41,7 +41,7 @@ pub struct ThreadsPage {
 }
 
 /// The outcome of extracting metadata from a rollout.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExtractionOutcome {
     /// The extracted thread metadata.
     pub metadata: ThreadMetadata,
@@ -50,7 +50,7 @@ pub struct ExtractionOutcome {
 }
 
 /// Canonical thread metadata derived from rollout files.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ThreadMetadata {
     /// The thread identifier.
     pub id: ThreadId,
@@ -76,6 +76,8 @@ pub struct ThreadMetadata {
     pub approval_mode: String,
     /// The last observed token usage.
     pub tokens_used: i64,
+    /// The last observed cost estimate, in US dollars.
+    pub cost_usd: f64,
     /// First user message observed for this thread, if any.
     pub first_user_message: Option<String>,
     /// The archive timestamp, if the thread is archived.
@@ -173,6 +175,7 @@ impl ThreadMetadataBuilder {
             sandbox_policy,
             approval_mode,
             tokens_used: 0,
+            cost_usd: 0.0,
             first_user_message: None,
             archived_at: self.archived_at.map(canonicalize_datetime),
             git_sha: self.git_sha.clone(),
@@ -222,6 +225,9 @@ impl ThreadMetadata {
         if self.tokens_used != other.tokens_used {
             diffs.push("tokens_used");
         }
+        if self.cost_usd != other.cost_usd {
+            diffs.push("cost_usd");
+        }
         if self.first_user_message != other.first_user_message {
             diffs.push("first_user_message");
         }
@@ -259,6 +265,7 @@ pub(crate) struct ThreadRow {
     sandbox_policy: String,
     approval_mode: String,
     tokens_used: i64,
+    cost_usd: f64,
     first_user_message: String,
     archived_at: Option<i64>,
     git_sha: Option<String>,
@@ -281,6 +288,7 @@ impl ThreadRow {
             sandbox_policy: row.try_get("sandbox_policy")?,
             approval_mode: row.try_get("approval_mode")?,
             tokens_used: row.try_get("tokens_used")?,
+            cost_usd: row.try_get("cost_usd")?,
             first_user_message: row.try_get("first_user_message")?,
             archived_at: row.try_get("archived_at")?,
             git_sha: row.try_get("git_sha")?,
@@ -307,6 +315,7 @@ impl TryFrom<ThreadRow> for ThreadMetadata {
             sandbox_policy,
             approval_mode,
             tokens_used,
+            cost_usd,
             first_user_message,
             archived_at,
             git_sha,
@@ -326,6 +335,7 @@ impl TryFrom<ThreadRow> for ThreadMetadata {
             sandbox_policy,
             approval_mode,
             tokens_used,
+            cost_usd,
             first_user_message: (!first_user_message.is_empty()).then_some(first_user_message),
             archived_at: archived_at.map(epoch_seconds_to_datetime).transpose()?,
             git_sha,
@@ -0,0 +1,274 @@
+use crate::extract::strip_user_message_prefix;
+use crate::model::ThreadMetadata;
+use crate::model::usage_stats::command_from_arguments;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use serde_json::Value;
+use std::path::PathBuf;
+
+const APPLY_PATCH_TOOL_NAME: &str = "apply_patch";
+const PATCH_FILE_MARKERS: [&str; 4] = [
+    "*** Add File: ",
+    "*** Update File: ",
+    "*** Delete File: ",
+    "*** Move to: ",
+];
+
+/// Filters for [`crate::StateRuntime::search_threads`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadSearchQuery {
+    /// Free-form search text; every word must appear in a match.
+    pub query: String,
+    /// Maximum number of threads to return.
+    pub limit: usize,
+    /// Session sources to include; empty means all.
+    pub allowed_sources: Vec<String>,
+    /// Model providers to include; `None` or empty means all.
+    pub model_providers: Option<Vec<String>>,
+    /// Only include threads whose working directory is exactly this path.
+    pub cwd: Option<PathBuf>,
+    /// Search archived threads instead of active ones.
+    pub archived: bool,
+}
+
+/// What part of a thread a search match came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadSearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    File,
+}
+
+impl ThreadSearchMatchKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ThreadSearchMatchKind::UserMessage => "user_message",
+            ThreadSearchMatchKind::AgentMessage => "agent_message",
+            ThreadSearchMatchKind::Command => "command",
+            ThreadSearchMatchKind::File => "file",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user_message" => Some(ThreadSearchMatchKind::UserMessage),
+            "agent_message" => Some(ThreadSearchMatchKind::AgentMessage),
+            "command" => Some(ThreadSearchMatchKind::Command),
+            "file" => Some(ThreadSearchMatchKind::File),
+            _ => None,
+        }
+    }
+}
+
+/// One matching message, command or file within a thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadSearchMatch {
+    /// Zero-based index of the turn (user message) the match belongs to.
+    pub turn_index: i64,
+    pub kind: ThreadSearchMatchKind,
+    /// Excerpt of the matching text around the search terms.
+    pub snippet: String,
+}
+
+/// A thread with at least one match, best matches first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadSearchResult {
+    pub thread: ThreadMetadata,
+    pub matches: Vec<ThreadSearchMatch>,
+}
+
+/// A row to insert into `thread_search_entries`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchEntry {
+    pub turn_index: i64,
+    pub kind: ThreadSearchMatchKind,
+    pub content: String,
+}
+
+/// Collect searchable text from a batch of rollout items.
+///
+/// `last_turn_index` is the latest turn already indexed for the thread; each
+/// user message starts the next turn.
+pub(crate) fn search_entries(
+    last_turn_index: Option<i64>,
+    items: &[RolloutItem],
+) -> Vec<SearchEntry> {
+    let mut entries = Vec::new();
+    let mut turn_index = last_turn_index;
+    for item in items {
+        let current_turn = turn_index.unwrap_or(0);
+        match item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(user)) => {
+                let next_turn = turn_index.map_or(0, |index| index + 1);
+                turn_index = Some(next_turn);
+                push_entry(
+                    &mut entries,
+                    next_turn,
+                    ThreadSearchMatchKind::UserMessage,
+                    strip_user_message_prefix(user.message.as_str()),
+                );
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(agent)) => push_entry(
+                &mut entries,
+                current_turn,
+                ThreadSearchMatchKind::AgentMessage,
+                agent.message.as_str(),
+            ),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                name, arguments, ..
+            }) => {
+                if name == APPLY_PATCH_TOOL_NAME {
+                    let patch = serde_json::from_str::<Value>(arguments)
+                        .ok()
+                        .and_then(|args| args.get("input")?.as_str().map(str::to_string))
+                        .unwrap_or_default();
+                    push_patch_files(&mut entries, current_turn, &patch);
+                } else if let Some(command) = command_from_arguments(arguments) {
+                    push_entry(
+                        &mut entries,
+                        current_turn,
+                        ThreadSearchMatchKind::Command,
+                        &command,
+                    );
+                }
+            }
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall { name, input, .. })
+                if name == APPLY_PATCH_TOOL_NAME =>
+            {
+                push_patch_files(&mut entries, current_turn, input);
+            }
+            RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+                action: LocalShellAction::Exec(exec),
+                ..
+            }) => push_entry(
+                &mut entries,
+                current_turn,
+                ThreadSearchMatchKind::Command,
+                &exec.command.join(" "),
+            ),
+            _ => {}
+        }
+    }
+    entries
+}
+
+fn push_entry(
+    entries: &mut Vec<SearchEntry>,
+    turn_index: i64,
+    kind: ThreadSearchMatchKind,
+    content: &str,
+) {
+    let content = content.trim();
+    if content.is_empty() {
+        return;
+    }
+    entries.push(SearchEntry {
+        turn_index,
+        kind,
+        content: content.to_string(),
+    });
+}
+
+fn push_patch_files(entries: &mut Vec<SearchEntry>, turn_index: i64, patch: &str) {
+    for line in patch.lines() {
+        if let Some(path) = PATCH_FILE_MARKERS
+            .iter()
+            .find_map(|marker| line.strip_prefix(marker))
+        {
+            push_entry(entries, turn_index, ThreadSearchMatchKind::File, path);
+        }
+    }
+}
+
+/// Turn free-form text into an FTS5 query that matches every word.
+///
+/// Each word is quoted so punctuation in the input cannot be read as query
+/// syntax, and the last word matches as a prefix so results update while the
+/// user is still typing.
+pub(crate) fn fts_match_expression(query: &str) -> Option<String> {
+    let words: Vec<String> = query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    let (last, rest) = words.split_last()?;
+    let mut expression = rest.join(" ");
+    if !expression.is_empty() {
+        expression.push(' ');
+    }
+    expression.push_str(last);
+    expression.push('*');
+    Some(expression)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    #[test]
+    fn indexes_messages_commands_and_patched_files_by_turn() {
+        let items = vec![
+            user_message("fix the flaky login test"),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["cargo","test","-p","login"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: src/login.rs\n@@\n-a\n+b\n*** End Patch"
+                    .to_string(),
+            }),
+            user_message("thanks"),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Done.".to_string(),
+            })),
+        ];
+        let entry = |turn_index, kind, content: &str| SearchEntry {
+            turn_index,
+            kind,
+            content: content.to_string(),
+        };
+        assert_eq!(
+            search_entries(Some(4), &items),
+            vec![
+                entry(
+                    5,
+                    ThreadSearchMatchKind::UserMessage,
+                    "fix the flaky login test"
+                ),
+                entry(5, ThreadSearchMatchKind::Command, "cargo test -p login"),
+                entry(5, ThreadSearchMatchKind::File, "src/login.rs"),
+                entry(6, ThreadSearchMatchKind::UserMessage, "thanks"),
+                entry(6, ThreadSearchMatchKind::AgentMessage, "Done."),
+            ]
+        );
+    }
+
+    #[test]
+    fn match_expression_quotes_words_and_prefixes_the_last() {
+        assert_eq!(
+            fts_match_expression(r#"login "test" fl"#),
+            Some(r#""login" """test""" "fl"*"#.to_string())
+        );
+        assert_eq!(fts_match_expression("   "), None);
+    }
+}
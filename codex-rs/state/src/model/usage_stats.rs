@@ -0,0 +1,500 @@
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::RolloutItem;
+use serde::Serialize;
+use serde_json::Value;
+
+/// How usage statistics are grouped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UsageStatsGroupBy {
+    /// A single group covering every thread.
+    #[default]
+    None,
+    /// Group by git origin URL, falling back to the working directory.
+    Repo,
+    /// Group by the model that served each turn.
+    Model,
+    /// Group by the UTC day each turn started, so a thread that spans several
+    /// days counts towards each of them.
+    Day,
+}
+
+/// Filters for [`crate::StateRuntime::usage_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageStatsQuery {
+    /// Only include threads created at or after this time, or turns started
+    /// then when grouping by day.
+    pub since: Option<DateTime<Utc>>,
+    /// Only include threads created before this time, or turns started
+    /// before then when grouping by day.
+    pub until: Option<DateTime<Utc>>,
+    /// How to group the results.
+    pub group_by: UsageStatsGroupBy,
+    /// Maximum number of entries in each per-tool and per-command list.
+    pub top: usize,
+}
+
+/// A named count, e.g. calls to one tool or denials of one command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NamedCount {
+    pub name: String,
+    pub count: i64,
+}
+
+/// Aggregated usage for one group of threads.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageStatsGroup {
+    /// The repo, model or `YYYY-MM-DD` day for this group; `None` when
+    /// results are not grouped.
+    pub key: Option<String>,
+    pub sessions: i64,
+    pub turns: i64,
+    pub tokens: i64,
+    /// `None` when any turn in the group ran on a model without a known price.
+    pub cost_usd: Option<f64>,
+    pub tool_calls: Vec<NamedCount>,
+    /// Tool calls that asked the user for approval.
+    pub approvals_requested: i64,
+    /// Approval prompts the user declined.
+    pub approvals_denied: i64,
+    pub denied_commands: Vec<NamedCount>,
+    /// Commands that ran and exited with a non-zero code.
+    pub failing_commands: Vec<NamedCount>,
+}
+
+impl UsageStatsGroup {
+    /// Share of approval prompts the user accepted, if any were shown.
+    pub fn approval_rate(&self) -> Option<f64> {
+        (self.approvals_requested > 0).then(|| {
+            (self.approvals_requested - self.approvals_denied) as f64
+                / self.approvals_requested as f64
+        })
+    }
+}
+
+/// Usage statistics over a date range.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageStats {
+    pub groups: Vec<UsageStatsGroup>,
+}
+
+/// A turn row as stored in `thread_turns`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TurnUsageRow {
+    pub turn_index: i64,
+    pub model: String,
+    pub tokens_at_start: i64,
+    pub tokens_used: i64,
+    pub cost_at_start: f64,
+    pub cost_usd: f64,
+    /// False once a token count in the turn arrived without a cost estimate.
+    pub cost_known: bool,
+}
+
+/// A change to a row in `thread_tool_calls`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ToolCallUpdate {
+    Started {
+        call_id: String,
+        turn_index: i64,
+        tool: String,
+        command: Option<String>,
+    },
+    Approval {
+        call_id: String,
+        turn_index: i64,
+        tool: String,
+        denied: bool,
+    },
+    Finished {
+        call_id: String,
+        exit_code: i64,
+    },
+}
+
+/// Rows to write after applying a batch of rollout items to a thread.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct UsageDelta {
+    /// Turns started or updated by the batch, in order.
+    pub turns: Vec<TurnUsageRow>,
+    pub tool_calls: Vec<ToolCallUpdate>,
+}
+
+/// Derive per-turn and per-tool-call rows from a batch of rollout items.
+///
+/// `last_turn` is the latest stored turn for the thread, and `tokens`/`cost`
+/// are the thread totals before the batch. A turn starts at each user message
+/// and takes its model from the turn context that follows it.
+pub(crate) fn usage_delta(
+    last_turn: Option<TurnUsageRow>,
+    tokens: i64,
+    cost: f64,
+    items: &[RolloutItem],
+) -> UsageDelta {
+    let mut delta = UsageDelta::default();
+    let mut current = last_turn;
+    let mut current_changed = false;
+    let mut total_tokens = tokens;
+    let mut total_cost = cost;
+    for item in items {
+        match item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(_)) => {
+                let (turn_index, model) = match current.take() {
+                    Some(turn) => {
+                        let next = (turn.turn_index + 1, turn.model.clone());
+                        if current_changed {
+                            delta.turns.push(turn);
+                        }
+                        next
+                    }
+                    None => (0, String::new()),
+                };
+                current = Some(TurnUsageRow {
+                    turn_index,
+                    model,
+                    tokens_at_start: total_tokens,
+                    tokens_used: 0,
+                    cost_at_start: total_cost,
+                    cost_usd: 0.0,
+                    cost_known: true,
+                });
+                current_changed = true;
+            }
+            RolloutItem::TurnContext(ctx) => {
+                if let Some(turn) = current.as_mut()
+                    && turn.model != ctx.model
+                {
+                    turn.model = ctx.model.clone();
+                    current_changed = true;
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::TokenCount(token_count)) => {
+                if let Some(info) = token_count.info.as_ref() {
+                    total_tokens = info.total_token_usage.total_tokens.max(0);
+                }
+                if let Some(cost_usd) = token_count.total_cost_usd {
+                    total_cost = cost_usd;
+                }
+                // Usage without an estimate means the model had no price.
+                let priced = token_count.total_cost_usd.is_some() || token_count.info.is_none();
+                if let Some(turn) = current.as_mut() {
+                    turn.tokens_used = (total_tokens - turn.tokens_at_start).max(0);
+                    turn.cost_usd = (total_cost - turn.cost_at_start).max(0.0);
+                    turn.cost_known &= priced;
+                    current_changed = true;
+                }
+            }
+            RolloutItem::ResponseItem(item) => {
+                let turn_index = current.as_ref().map_or(0, |turn| turn.turn_index);
+                if let Some(update) = tool_call_update(item, turn_index) {
+                    delta.tool_calls.push(update);
+                }
+            }
+            RolloutItem::ApprovalDecision(decision) => {
+                delta.tool_calls.push(ToolCallUpdate::Approval {
+                    call_id: decision.call_id.clone(),
+                    turn_index: current.as_ref().map_or(0, |turn| turn.turn_index),
+                    tool: decision.tool_name.clone(),
+                    denied: matches!(
+                        decision.decision,
+                        ReviewDecision::Denied | ReviewDecision::Abort
+                    ),
+                });
+            }
+            RolloutItem::SessionMeta(_) | RolloutItem::Compacted(_) | RolloutItem::EventMsg(_) => {}
+        }
+    }
+    if current_changed && let Some(turn) = current {
+        delta.turns.push(turn);
+    }
+    delta
+}
+
+fn tool_call_update(item: &ResponseItem, turn_index: i64) -> Option<ToolCallUpdate> {
+    match item {
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => Some(ToolCallUpdate::Started {
+            call_id: call_id.clone(),
+            turn_index,
+            tool: name.clone(),
+            command: command_from_arguments(arguments),
+        }),
+        ResponseItem::CustomToolCall { call_id, name, .. } => Some(ToolCallUpdate::Started {
+            call_id: call_id.clone(),
+            turn_index,
+            tool: name.clone(),
+            command: None,
+        }),
+        ResponseItem::LocalShellCall {
+            call_id: Some(call_id),
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => Some(ToolCallUpdate::Started {
+            call_id: call_id.clone(),
+            turn_index,
+            tool: "local_shell".to_string(),
+            command: Some(exec.command.join(" ")),
+        }),
+        ResponseItem::FunctionCallOutput { call_id, output } => {
+            let exit_code = exit_code_from_output(output.body.to_text()?.as_str())?;
+            Some(ToolCallUpdate::Finished {
+                call_id: call_id.clone(),
+                exit_code,
+            })
+        }
+        ResponseItem::CustomToolCallOutput { call_id, output } => {
+            let exit_code = exit_code_from_output(output)?;
+            Some(ToolCallUpdate::Finished {
+                call_id: call_id.clone(),
+                exit_code,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The shell command in a tool call's JSON arguments, if it has one.
+pub(crate) fn command_from_arguments(arguments: &str) -> Option<String> {
+    let arguments = serde_json::from_str::<Value>(arguments).ok()?;
+    match arguments.get("command").or_else(|| arguments.get("cmd"))? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(parts) => Some(
+            parts
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
+/// The exit code reported in a shell tool's output, in any of the formats the
+/// shell tools emit.
+fn exit_code_from_output(output: &str) -> Option<i64> {
+    if let Ok(value) = serde_json::from_str::<Value>(output) {
+        return value.get("metadata")?.get("exit_code")?.as_i64();
+    }
+    output.lines().find_map(|line| {
+        let line = line.trim();
+        line.strip_prefix("Exit code: ")
+            .or_else(|| line.strip_prefix("Process exited with code "))?
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::protocol::ApprovalDecisionItem;
+    use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::SandboxPolicy;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsage;
+    use codex_protocol::protocol::TokenUsageInfo;
+    use codex_protocol::protocol::TurnContextItem;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn user_message() -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: "hi".to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn turn_context(model: &str) -> RolloutItem {
+        RolloutItem::TurnContext(TurnContextItem {
+            cwd: PathBuf::from("/tmp"),
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            model: model.to_string(),
+            personality: None,
+            collaboration_mode: None,
+            effort: None,
+            summary: Default::default(),
+            user_instructions: None,
+            developer_instructions: None,
+            final_output_json_schema: None,
+            truncation_policy: None,
+        })
+    }
+
+    fn token_count(total_tokens: i64, cost_usd: impl Into<Option<f64>>) -> RolloutItem {
+        let usage = TokenUsage {
+            total_tokens,
+            ..Default::default()
+        };
+        RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+            total_cost_usd: cost_usd.into(),
+        }))
+    }
+
+    #[test]
+    fn user_messages_start_turns_and_token_counts_fill_them() {
+        let last_turn = TurnUsageRow {
+            turn_index: 3,
+            model: "gpt-5".to_string(),
+            tokens_at_start: 100,
+            tokens_used: 50,
+            cost_at_start: 1.0,
+            cost_usd: 0.5,
+            cost_known: true,
+        };
+        let delta = usage_delta(
+            Some(last_turn),
+            150,
+            1.5,
+            &[
+                token_count(200, 2.0),
+                user_message(),
+                turn_context("gpt-5.2-codex"),
+                token_count(260, 2.25),
+            ],
+        );
+        assert_eq!(
+            delta.turns,
+            vec![
+                TurnUsageRow {
+                    turn_index: 3,
+                    model: "gpt-5".to_string(),
+                    tokens_at_start: 100,
+                    tokens_used: 100,
+                    cost_at_start: 1.0,
+                    cost_usd: 1.0,
+                    cost_known: true,
+                },
+                TurnUsageRow {
+                    turn_index: 4,
+                    model: "gpt-5.2-codex".to_string(),
+                    tokens_at_start: 200,
+                    tokens_used: 60,
+                    cost_at_start: 2.0,
+                    cost_usd: 0.25,
+                    cost_known: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn usage_without_a_cost_estimate_marks_the_turn_unpriced() {
+        let delta = usage_delta(
+            None,
+            0,
+            0.0,
+            &[
+                user_message(),
+                turn_context("gpt-5.2-codex"),
+                token_count(100, 0.5),
+                user_message(),
+                turn_context("local-model"),
+                token_count(160, None),
+            ],
+        );
+        assert_eq!(
+            delta.turns,
+            vec![
+                TurnUsageRow {
+                    turn_index: 0,
+                    model: "gpt-5.2-codex".to_string(),
+                    tokens_at_start: 0,
+                    tokens_used: 100,
+                    cost_at_start: 0.0,
+                    cost_usd: 0.5,
+                    cost_known: true,
+                },
+                TurnUsageRow {
+                    turn_index: 1,
+                    model: "local-model".to_string(),
+                    tokens_at_start: 100,
+                    tokens_used: 60,
+                    cost_at_start: 0.5,
+                    cost_usd: 0.0,
+                    cost_known: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tool_calls_record_commands_approvals_and_exit_codes() {
+        let delta = usage_delta(
+            None,
+            0,
+            0.0,
+            &[
+                user_message(),
+                RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["cargo","test"]}"#.to_string(),
+                    call_id: "call-1".to_string(),
+                }),
+                RolloutItem::ApprovalDecision(ApprovalDecisionItem {
+                    call_id: "call-1".to_string(),
+                    tool_name: "shell".to_string(),
+                    decision: ReviewDecision::Approved,
+                }),
+                RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                    call_id: "call-1".to_string(),
+                    output: FunctionCallOutputPayload::from_text(
+                        r#"{"output":"boom","metadata":{"exit_code":101,"duration_seconds":1.0}}"#
+                            .to_string(),
+                    ),
+                }),
+                RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                    call_id: "call-2".to_string(),
+                    output: FunctionCallOutputPayload::from_text(
+                        "Chunk ID: 1\nProcess exited with code 2\nOutput:\n".to_string(),
+                    ),
+                }),
+            ],
+        );
+        assert_eq!(
+            delta.tool_calls,
+            vec![
+                ToolCallUpdate::Started {
+                    call_id: "call-1".to_string(),
+                    turn_index: 0,
+                    tool: "shell".to_string(),
+                    command: Some("cargo test".to_string()),
+                },
+                ToolCallUpdate::Approval {
+                    call_id: "call-1".to_string(),
+                    turn_index: 0,
+                    tool: "shell".to_string(),
+                    denied: false,
+                },
+                ToolCallUpdate::Finished {
+                    call_id: "call-1".to_string(),
+                    exit_code: 101,
+                },
+                ToolCallUpdate::Finished {
+                    call_id: "call-2".to_string(),
+                    exit_code: 2,
+                },
+            ]
+        );
+    }
+}
@@ -2,17 +2,43 @@ use crate::DB_ERROR_METRIC;
 use crate::LogEntry;
 use crate::LogQuery;
 use crate::LogRow;
+use crate::Memory;
+use crate::MemoryScope;
+use crate::NamedCount;
+use crate::NewMemory;
+use crate::NewScheduledJob;
+use crate::ScheduledJob;
+use crate::ScheduledJobRun;
+use crate::ScheduledRunOutcome;
+use crate::ScheduledRunStatus;
 use crate::SortKey;
 use crate::ThreadMemory;
 use crate::ThreadMetadata;
 use crate::ThreadMetadataBuilder;
+use crate::ThreadSearchMatch;
+use crate::ThreadSearchMatchKind;
+use crate::ThreadSearchQuery;
+use crate::ThreadSearchResult;
 use crate::ThreadsPage;
+use crate::UsageStats;
+use crate::UsageStatsGroup;
+use crate::UsageStatsGroupBy;
+use crate::UsageStatsQuery;
 use crate::apply_rollout_item;
 use crate::migrations::MIGRATOR;
+use crate::model::MemoryRow;
+use crate::model::ScheduledJobRow;
+use crate::model::ScheduledJobRunRow;
+use crate::model::SearchEntry;
 use crate::model::ThreadMemoryRow;
 use crate::model::ThreadRow;
+use crate::model::ToolCallUpdate;
+use crate::model::TurnUsageRow;
 use crate::model::anchor_from_item;
 use crate::model::datetime_to_epoch_seconds;
+use crate::model::fts_match_expression;
+use crate::model::search_entries;
+use crate::model::usage_delta;
 use crate::paths::file_modified_time_utc;
 use chrono::DateTime;
 use chrono::Utc;
@@ -31,6 +57,7 @@ use sqlx::sqlite::SqliteConnectOptions;
 use sqlx::sqlite::SqliteJournalMode;
 use sqlx::sqlite::SqlitePoolOptions;
 use sqlx::sqlite::SqliteSynchronous;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -41,6 +68,9 @@ pub const STATE_DB_FILENAME: &str = "state";
 pub const STATE_DB_VERSION: u32 = 4;
 
 const METRIC_DB_INIT: &str = "codex.db.init";
+/// Upper bound on matching entries scanned per search before grouping by thread.
+const SEARCH_MAX_MATCHES: i64 = 500;
+const SEARCH_MATCHES_PER_THREAD: usize = 3;
 
 #[derive(Clone)]
 pub struct StateRuntime {
@@ -182,6 +212,7 @@ SELECT
     sandbox_policy,
     approval_mode,
     tokens_used,
+    cost_usd,
     first_user_message,
     archived_at,
     git_sha,
@@ -301,6 +332,7 @@ SELECT
     sandbox_policy,
     approval_mode,
     tokens_used,
+    cost_usd,
     first_user_message,
     archived_at,
     git_sha,
@@ -456,13 +488,14 @@ INSERT INTO threads (
     sandbox_policy,
     approval_mode,
     tokens_used,
+    cost_usd,
     first_user_message,
     archived,
     archived_at,
     git_sha,
     git_branch,
     git_origin_url
-) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 ON CONFLICT(id) DO UPDATE SET
     rollout_path = excluded.rollout_path,
     created_at = excluded.created_at,
@@ -475,6 +508,7 @@ ON CONFLICT(id) DO UPDATE SET
     sandbox_policy = excluded.sandbox_policy,
     approval_mode = excluded.approval_mode,
     tokens_used = excluded.tokens_used,
+    cost_usd = excluded.cost_usd,
     first_user_message = excluded.first_user_message,
     archived = excluded.archived,
     archived_at = excluded.archived_at,
@@ -495,6 +529,7 @@ ON CONFLICT(id) DO UPDATE SET
         .bind(metadata.sandbox_policy.as_str())
         .bind(metadata.approval_mode.as_str())
         .bind(metadata.tokens_used)
+        .bind(metadata.cost_usd)
         .bind(metadata.first_user_message.as_deref().unwrap_or_default())
         .bind(metadata.archived_at.is_some())
         .bind(metadata.archived_at.map(datetime_to_epoch_seconds))
@@ -583,6 +618,407 @@ LIMIT ?
             .collect()
     }
 
+    /// Save a memory, or refresh `updated_at` on an identical one in the same
+    /// scope and repository.
+    pub async fn add_memory(&self, memory: &NewMemory) -> anyhow::Result<Memory> {
+        let content = memory.content.trim();
+        if content.is_empty() {
+            return Err(anyhow::anyhow!("memory content must not be empty"));
+        }
+        let repo = match memory.scope {
+            MemoryScope::User => "",
+            MemoryScope::Repo => memory
+                .repo
+                .as_deref()
+                .filter(|repo| !repo.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("repo-scoped memories need a repository"))?,
+        };
+        let now = Utc::now().timestamp();
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM memories WHERE scope = ? AND repo = ? AND content = ?",
+        )
+        .bind(memory.scope.as_str())
+        .bind(repo)
+        .bind(content)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        let id = match existing {
+            Some(id) => {
+                sqlx::query("UPDATE memories SET updated_at = ? WHERE id = ?")
+                    .bind(now)
+                    .bind(id)
+                    .execute(self.pool.as_ref())
+                    .await?;
+                id
+            }
+            None => sqlx::query(
+                r#"
+INSERT INTO memories (scope, repo, content, source, thread_id, created_at, updated_at)
+VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(memory.scope.as_str())
+            .bind(repo)
+            .bind(content)
+            .bind(memory.source.as_str())
+            .bind(memory.thread_id.map(|thread_id| thread_id.to_string()))
+            .bind(now)
+            .bind(now)
+            .execute(self.pool.as_ref())
+            .await?
+            .last_insert_rowid(),
+        };
+        self.get_memory(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("failed to load saved memory: {id}"))
+    }
+
+    /// Get a memory by id.
+    pub async fn get_memory(&self, id: i64) -> anyhow::Result<Option<Memory>> {
+        let row = sqlx::query(
+            r#"
+SELECT id, scope, repo, content, source, thread_id, created_at, updated_at
+FROM memories
+WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(|row| MemoryRow::try_from_row(&row).and_then(Memory::try_from))
+            .transpose()
+    }
+
+    /// List memories, most recently updated first.
+    ///
+    /// With `repo`, only user-scoped memories and memories for that repository
+    /// are returned, repository memories first.
+    pub async fn list_memories(&self, repo: Option<&str>) -> anyhow::Result<Vec<Memory>> {
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+SELECT id, scope, repo, content, source, thread_id, created_at, updated_at
+FROM memories
+            "#,
+        );
+        match repo {
+            Some(repo) => {
+                builder.push("WHERE scope = 'user' OR (scope = 'repo' AND repo = ");
+                builder.push_bind(repo);
+                builder.push(") ORDER BY scope = 'repo' DESC, updated_at DESC, id DESC");
+            }
+            None => {
+                builder.push("ORDER BY updated_at DESC, id DESC");
+            }
+        }
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| MemoryRow::try_from_row(&row).and_then(Memory::try_from))
+            .collect()
+    }
+
+    /// Replace the content of a memory. Edited memories are attributed to the
+    /// user. Returns `None` when no memory has the id.
+    pub async fn update_memory(&self, id: i64, content: &str) -> anyhow::Result<Option<Memory>> {
+        let content = content.trim();
+        if content.is_empty() {
+            return Err(anyhow::anyhow!("memory content must not be empty"));
+        }
+        let result = sqlx::query(
+            "UPDATE memories SET content = ?, source = 'user', updated_at = ? WHERE id = ?",
+        )
+        .bind(content)
+        .bind(Utc::now().timestamp())
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.get_memory(id).await
+    }
+
+    /// Delete a memory. Returns whether it existed.
+    pub async fn delete_memory(&self, id: i64) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM memories WHERE id = ?")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Add a scheduled job. Job names are unique.
+    pub async fn add_scheduled_job(
+        &self,
+        job: &NewScheduledJob,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<ScheduledJob> {
+        let name = job.name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("scheduled job name must not be empty"));
+        }
+        if self.get_scheduled_job(name).await?.is_some() {
+            return Err(anyhow::anyhow!(
+                "a scheduled job named {name} already exists"
+            ));
+        }
+        let now = datetime_to_epoch_seconds(now);
+        sqlx::query(
+            r#"
+INSERT INTO scheduled_jobs (
+    name,
+    prompt,
+    cwd,
+    profile,
+    sandbox,
+    cron,
+    output,
+    enabled,
+    created_at,
+    updated_at
+) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?)
+            "#,
+        )
+        .bind(name)
+        .bind(job.prompt.as_str())
+        .bind(job.cwd.display().to_string())
+        .bind(job.profile.as_deref())
+        .bind(job.sandbox.as_deref())
+        .bind(job.cron.as_str())
+        .bind(
+            job.output
+                .as_ref()
+                .map(|output| output.display().to_string()),
+        )
+        .bind(now)
+        .bind(now)
+        .execute(self.pool.as_ref())
+        .await?;
+        self.get_scheduled_job(name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("failed to load scheduled job: {name}"))
+    }
+
+    /// Get a scheduled job by name.
+    pub async fn get_scheduled_job(&self, name: &str) -> anyhow::Result<Option<ScheduledJob>> {
+        let row = sqlx::query(
+            r#"
+SELECT
+    id,
+    name,
+    prompt,
+    cwd,
+    profile,
+    sandbox,
+    cron,
+    output,
+    enabled,
+    created_at,
+    updated_at,
+    last_fired_at
+FROM scheduled_jobs
+WHERE name = ?
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(|row| ScheduledJobRow::try_from_row(&row).and_then(ScheduledJob::try_from))
+            .transpose()
+    }
+
+    /// List scheduled jobs by name.
+    pub async fn list_scheduled_jobs(&self) -> anyhow::Result<Vec<ScheduledJob>> {
+        let rows = sqlx::query(
+            r#"
+SELECT
+    id,
+    name,
+    prompt,
+    cwd,
+    profile,
+    sandbox,
+    cron,
+    output,
+    enabled,
+    created_at,
+    updated_at,
+    last_fired_at
+FROM scheduled_jobs
+ORDER BY name
+            "#,
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter()
+            .map(|row| ScheduledJobRow::try_from_row(&row).and_then(ScheduledJob::try_from))
+            .collect()
+    }
+
+    /// Delete a scheduled job and its run history. Returns whether it existed.
+    pub async fn delete_scheduled_job(&self, name: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM scheduled_jobs WHERE name = ?")
+            .bind(name)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Enable or disable a scheduled job. Returns whether it exists.
+    ///
+    /// Enabling a job treats `now` as its last run, so slots missed while it
+    /// was disabled are not run.
+    pub async fn set_scheduled_job_enabled(
+        &self,
+        name: &str,
+        enabled: bool,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let now = datetime_to_epoch_seconds(now);
+        let result = sqlx::query(
+            r#"
+UPDATE scheduled_jobs
+SET
+    last_fired_at = CASE WHEN ? AND enabled = 0 THEN ? ELSE last_fired_at END,
+    enabled = ?,
+    updated_at = ?
+WHERE name = ?
+            "#,
+        )
+        .bind(enabled)
+        .bind(now)
+        .bind(enabled)
+        .bind(now)
+        .bind(name)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record the start of a run for the schedule slot `scheduled_for` and
+    /// mark that slot as the job's last run. Returns the run id.
+    pub async fn start_scheduled_run(
+        &self,
+        job_id: i64,
+        scheduled_for: DateTime<Utc>,
+        started_at: DateTime<Utc>,
+    ) -> anyhow::Result<i64> {
+        let scheduled_for = datetime_to_epoch_seconds(scheduled_for);
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("UPDATE scheduled_jobs SET last_fired_at = ? WHERE id = ?")
+            .bind(scheduled_for)
+            .bind(job_id)
+            .execute(&mut *tx)
+            .await?;
+        let run_id = sqlx::query(
+            r#"
+INSERT INTO scheduled_job_runs (job_id, scheduled_for, started_at, status)
+VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(job_id)
+        .bind(scheduled_for)
+        .bind(datetime_to_epoch_seconds(started_at))
+        .bind(ScheduledRunStatus::Running.as_str())
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+        tx.commit().await?;
+        Ok(run_id)
+    }
+
+    /// Record how a run ended.
+    pub async fn finish_scheduled_run(
+        &self,
+        run_id: i64,
+        finished_at: DateTime<Utc>,
+        outcome: &ScheduledRunOutcome,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+UPDATE scheduled_job_runs
+SET
+    finished_at = ?,
+    status = ?,
+    exit_code = ?,
+    thread_id = ?,
+    rollout_path = ?,
+    error = ?
+WHERE id = ?
+            "#,
+        )
+        .bind(datetime_to_epoch_seconds(finished_at))
+        .bind(outcome.status.as_str())
+        .bind(outcome.exit_code)
+        .bind(outcome.thread_id.map(|thread_id| thread_id.to_string()))
+        .bind(
+            outcome
+                .rollout_path
+                .as_ref()
+                .map(|path| path.display().to_string()),
+        )
+        .bind(outcome.error.as_deref())
+        .bind(run_id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Mark runs still recorded as running as failed, e.g. after the daemon
+    /// that started them was killed. Returns the number of runs updated.
+    pub async fn fail_unfinished_scheduled_runs(
+        &self,
+        now: DateTime<Utc>,
+        error: &str,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            "UPDATE scheduled_job_runs SET status = ?, finished_at = ?, error = ? WHERE status = ?",
+        )
+        .bind(ScheduledRunStatus::Failed.as_str())
+        .bind(datetime_to_epoch_seconds(now))
+        .bind(error)
+        .bind(ScheduledRunStatus::Running.as_str())
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// List runs of scheduled jobs, most recent first, optionally only for
+    /// one job.
+    pub async fn list_scheduled_runs(
+        &self,
+        job_id: Option<i64>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ScheduledJobRun>> {
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+SELECT
+    r.id,
+    r.job_id,
+    j.name AS job_name,
+    r.scheduled_for,
+    r.started_at,
+    r.finished_at,
+    r.status,
+    r.exit_code,
+    r.thread_id,
+    r.rollout_path,
+    r.error
+FROM scheduled_job_runs AS r
+JOIN scheduled_jobs AS j ON j.id = r.job_id
+            "#,
+        );
+        if let Some(job_id) = job_id {
+            builder.push("WHERE r.job_id = ");
+            builder.push_bind(job_id);
+        }
+        builder.push(" ORDER BY r.started_at DESC, r.id DESC LIMIT ");
+        builder.push_bind(i64::try_from(limit).unwrap_or(i64::MAX));
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| ScheduledJobRunRow::try_from_row(&row).and_then(ScheduledJobRun::try_from))
+            .collect()
+    }
+
     /// Persist dynamic tools for a thread if none have been stored yet.
     ///
     /// Dynamic tools are defined at thread start and should not change afterward.
@@ -642,6 +1078,8 @@ ON CONFLICT(thread_id, position) DO NOTHING
             .await?
             .unwrap_or_else(|| builder.build(&self.default_provider));
         metadata.rollout_path = builder.rollout_path.clone();
+        let tokens_before = metadata.tokens_used;
+        let cost_before = metadata.cost_usd;
         for item in items {
             apply_rollout_item(&mut metadata, item, &self.default_provider);
         }
@@ -667,9 +1105,456 @@ ON CONFLICT(thread_id, position) DO NOTHING
             }
             return Err(err);
         }
+        if let Err(err) = self.index_thread_search(builder.id, items).await {
+            if let Some(otel) = otel {
+                otel.counter(DB_ERROR_METRIC, 1, &[("stage", "index_thread_search")]);
+            }
+            return Err(err);
+        }
+        if let Err(err) = self
+            .record_usage(builder.id, tokens_before, cost_before, items)
+            .await
+        {
+            if let Some(otel) = otel {
+                otel.counter(DB_ERROR_METRIC, 1, &[("stage", "record_usage")]);
+            }
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Add searchable text from a batch of rollout items to the thread's
+    /// full-text index.
+    async fn index_thread_search(
+        &self,
+        thread_id: ThreadId,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        let row = sqlx::query(
+            r#"
+SELECT MAX(turn_index) AS turn_index
+FROM thread_search_entries
+WHERE thread_id = ?
+            "#,
+        )
+        .bind(thread_id.to_string())
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        let last_turn_index: Option<i64> = row.try_get("turn_index")?;
+        let entries = search_entries(last_turn_index, items);
+        self.insert_search_entries(thread_id, &entries, false).await
+    }
+
+    /// Rebuild a thread's full-text index from its complete rollout.
+    pub async fn replace_thread_search(
+        &self,
+        thread_id: ThreadId,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        let entries = search_entries(None, items);
+        self.insert_search_entries(thread_id, &entries, true).await
+    }
+
+    async fn insert_search_entries(
+        &self,
+        thread_id: ThreadId,
+        entries: &[SearchEntry],
+        replace: bool,
+    ) -> anyhow::Result<()> {
+        if entries.is_empty() && !replace {
+            return Ok(());
+        }
+        let thread_id = thread_id.to_string();
+        let mut tx = self.pool.begin().await?;
+        if replace {
+            sqlx::query("DELETE FROM thread_search_entries WHERE thread_id = ?")
+                .bind(thread_id.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+        for entry in entries {
+            sqlx::query(
+                r#"
+INSERT INTO thread_search_entries (thread_id, turn_index, kind, content)
+VALUES (?, ?, ?, ?)
+                "#,
+            )
+            .bind(thread_id.as_str())
+            .bind(entry.turn_index)
+            .bind(entry.kind.as_str())
+            .bind(entry.content.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Full-text search over user and assistant messages, commands and patched
+    /// files, returning the best-matching threads first.
+    pub async fn search_threads(
+        &self,
+        query: &ThreadSearchQuery,
+    ) -> anyhow::Result<Vec<ThreadSearchResult>> {
+        let Some(expression) = fts_match_expression(query.query.as_str()) else {
+            return Ok(Vec::new());
+        };
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+SELECT
+    entries.thread_id,
+    entries.turn_index,
+    entries.kind,
+    snippet(thread_search, 0, '', '', '…', 16) AS snippet
+FROM thread_search
+JOIN thread_search_entries AS entries ON entries.id = thread_search.rowid
+JOIN threads ON threads.id = entries.thread_id
+WHERE thread_search MATCH "#,
+        );
+        builder.push_bind(expression);
+        builder.push(if query.archived {
+            " AND threads.archived = 1"
+        } else {
+            " AND threads.archived = 0"
+        });
+        if !query.allowed_sources.is_empty() {
+            builder.push(" AND threads.source IN (");
+            let mut separated = builder.separated(", ");
+            for source in &query.allowed_sources {
+                separated.push_bind(source);
+            }
+            separated.push_unseparated(")");
+        }
+        if let Some(model_providers) = query.model_providers.as_ref()
+            && !model_providers.is_empty()
+        {
+            builder.push(" AND threads.model_provider IN (");
+            let mut separated = builder.separated(", ");
+            for provider in model_providers {
+                separated.push_bind(provider);
+            }
+            separated.push_unseparated(")");
+        }
+        if let Some(cwd) = query.cwd.as_ref() {
+            builder
+                .push(" AND threads.cwd = ")
+                .push_bind(cwd.display().to_string());
+        }
+        builder
+            .push(" ORDER BY bm25(thread_search) LIMIT ")
+            .push_bind(SEARCH_MAX_MATCHES);
+
+        let mut results: Vec<(ThreadId, Vec<ThreadSearchMatch>)> = Vec::new();
+        for row in builder.build().fetch_all(self.pool.as_ref()).await? {
+            let thread_id = ThreadId::try_from(row.try_get::<String, _>("thread_id")?)?;
+            let kind: String = row.try_get("kind")?;
+            let Some(kind) = ThreadSearchMatchKind::parse(kind.as_str()) else {
+                continue;
+            };
+            let search_match = ThreadSearchMatch {
+                turn_index: row.try_get("turn_index")?,
+                kind,
+                snippet: row.try_get("snippet")?,
+            };
+            match results.iter_mut().find(|(id, _)| *id == thread_id) {
+                Some((_, matches)) => {
+                    if matches.len() < SEARCH_MATCHES_PER_THREAD {
+                        matches.push(search_match);
+                    }
+                }
+                None if results.len() < query.limit => {
+                    results.push((thread_id, vec![search_match]));
+                }
+                None => {}
+            }
+        }
+
+        let mut threads = Vec::with_capacity(results.len());
+        for (thread_id, matches) in results {
+            if let Some(thread) = self.get_thread(thread_id).await? {
+                threads.push(ThreadSearchResult { thread, matches });
+            }
+        }
+        Ok(threads)
+    }
+
+    /// Record per-turn and per-tool-call usage from a batch of rollout items.
+    ///
+    /// `tokens` and `cost_usd` are the thread totals before the batch was applied.
+    async fn record_usage(
+        &self,
+        thread_id: ThreadId,
+        tokens: i64,
+        cost_usd: f64,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        let thread_id = thread_id.to_string();
+        let last_turn = sqlx::query(
+            r#"
+SELECT turn_index, model, tokens_at_start, tokens_used, cost_at_start, cost_usd, cost_known
+FROM thread_turns
+WHERE thread_id = ?
+ORDER BY turn_index DESC
+LIMIT 1
+            "#,
+        )
+        .bind(thread_id.as_str())
+        .fetch_optional(self.pool.as_ref())
+        .await?
+        .map(|row| -> anyhow::Result<TurnUsageRow> {
+            Ok(TurnUsageRow {
+                turn_index: row.try_get("turn_index")?,
+                model: row.try_get("model")?,
+                tokens_at_start: row.try_get("tokens_at_start")?,
+                tokens_used: row.try_get("tokens_used")?,
+                cost_at_start: row.try_get("cost_at_start")?,
+                cost_usd: row.try_get("cost_usd")?,
+                cost_known: row.try_get("cost_known")?,
+            })
+        })
+        .transpose()?;
+        let delta = usage_delta(last_turn, tokens, cost_usd, items);
+        if delta.turns.is_empty() && delta.tool_calls.is_empty() {
+            return Ok(());
+        }
+        // `started_at` is when the turn was first recorded; it is kept on
+        // later updates so `UsageStatsGroupBy::Day` bills the turn to the day
+        // it started.
+        let now = Utc::now().timestamp();
+        let mut tx = self.pool.begin().await?;
+        for turn in &delta.turns {
+            sqlx::query(
+                r#"
+INSERT INTO thread_turns (
+    thread_id,
+    turn_index,
+    model,
+    tokens_at_start,
+    tokens_used,
+    cost_at_start,
+    cost_usd,
+    cost_known,
+    started_at
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT(thread_id, turn_index) DO UPDATE SET
+    model = excluded.model,
+    tokens_used = excluded.tokens_used,
+    cost_usd = excluded.cost_usd,
+    cost_known = excluded.cost_known
+                "#,
+            )
+            .bind(thread_id.as_str())
+            .bind(turn.turn_index)
+            .bind(turn.model.as_str())
+            .bind(turn.tokens_at_start)
+            .bind(turn.tokens_used)
+            .bind(turn.cost_at_start)
+            .bind(turn.cost_usd)
+            .bind(turn.cost_known)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for update in &delta.tool_calls {
+            match update {
+                ToolCallUpdate::Started {
+                    call_id,
+                    turn_index,
+                    tool,
+                    command,
+                } => {
+                    sqlx::query(
+                        r#"
+INSERT INTO thread_tool_calls (thread_id, call_id, turn_index, tool, command)
+VALUES (?, ?, ?, ?, ?)
+ON CONFLICT(thread_id, call_id) DO UPDATE SET
+    turn_index = excluded.turn_index,
+    tool = excluded.tool,
+    command = excluded.command
+                        "#,
+                    )
+                    .bind(thread_id.as_str())
+                    .bind(call_id.as_str())
+                    .bind(turn_index)
+                    .bind(tool.as_str())
+                    .bind(command.as_deref())
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                ToolCallUpdate::Approval {
+                    call_id,
+                    turn_index,
+                    tool,
+                    denied,
+                } => {
+                    let approval = if *denied { "denied" } else { "approved" };
+                    sqlx::query(
+                        r#"
+INSERT INTO thread_tool_calls (thread_id, call_id, turn_index, tool, approval)
+VALUES (?, ?, ?, ?, ?)
+ON CONFLICT(thread_id, call_id) DO UPDATE SET approval = excluded.approval
+                        "#,
+                    )
+                    .bind(thread_id.as_str())
+                    .bind(call_id.as_str())
+                    .bind(turn_index)
+                    .bind(tool.as_str())
+                    .bind(approval)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                ToolCallUpdate::Finished { call_id, exit_code } => {
+                    sqlx::query(
+                        "UPDATE thread_tool_calls SET exit_code = ? WHERE thread_id = ? AND call_id = ?",
+                    )
+                    .bind(exit_code)
+                    .bind(thread_id.as_str())
+                    .bind(call_id.as_str())
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+        tx.commit().await?;
         Ok(())
     }
 
+    /// Aggregate sessions, turns, tokens and tool calls for threads created in
+    /// the query's date range.
+    ///
+    /// Turn and tool-call figures only cover rollouts applied incrementally,
+    /// i.e. sessions recorded while the state database was enabled.
+    pub async fn usage_stats(&self, query: &UsageStatsQuery) -> anyhow::Result<UsageStats> {
+        let mut groups: BTreeMap<String, UsageStatsGroup> = BTreeMap::new();
+
+        let key = usage_group_key(query.group_by);
+        // Per-model and per-day totals come from turns; otherwise use the
+        // thread totals, which also cover sessions recorded before turns were
+        // tracked.
+        let (totals, from) = match query.group_by {
+            UsageStatsGroupBy::Model | UsageStatsGroupBy::Day => (
+                "COUNT(DISTINCT threads.id) AS sessions, SUM(turns.tokens_used) AS tokens, SUM(turns.cost_usd) AS cost_usd, MIN(turns.cost_known) AS cost_known",
+                " FROM thread_turns AS turns JOIN threads ON threads.id = turns.thread_id",
+            ),
+            UsageStatsGroupBy::None | UsageStatsGroupBy::Repo => (
+                "COUNT(*) AS sessions, SUM(threads.tokens_used) AS tokens, SUM(threads.cost_usd) AS cost_usd, MIN(NOT EXISTS (SELECT 1 FROM thread_turns AS unpriced WHERE unpriced.thread_id = threads.id AND unpriced.cost_known = 0)) AS cost_known",
+                " FROM threads",
+            ),
+        };
+        let mut builder = QueryBuilder::<Sqlite>::new(format!("SELECT {key} AS key, {totals}"));
+        builder.push(from);
+        push_usage_filters(&mut builder, query);
+        builder.push(" GROUP BY key");
+        for row in builder.build().fetch_all(self.pool.as_ref()).await? {
+            let group = usage_group(&mut groups, row.try_get("key")?);
+            group.sessions = row.try_get("sessions")?;
+            group.tokens = row.try_get::<Option<i64>, _>("tokens")?.unwrap_or(0);
+            let cost_usd = row.try_get::<Option<f64>, _>("cost_usd")?.unwrap_or(0.0);
+            let cost_known = row
+                .try_get::<Option<bool>, _>("cost_known")?
+                .unwrap_or(true);
+            group.cost_usd = cost_known.then_some(cost_usd);
+        }
+
+        let mut builder = QueryBuilder::<Sqlite>::new(format!(
+            "SELECT {key} AS key, COUNT(*) AS turns FROM thread_turns AS turns JOIN threads ON threads.id = turns.thread_id"
+        ));
+        push_usage_filters(&mut builder, query);
+        builder.push(" GROUP BY key");
+        for row in builder.build().fetch_all(self.pool.as_ref()).await? {
+            usage_group(&mut groups, row.try_get("key")?).turns = row.try_get("turns")?;
+        }
+
+        let mut builder = tool_calls_query(
+            &format!(
+                "SELECT {key} AS key, COUNT(calls.approval) AS requested, COALESCE(SUM(calls.approval = 'denied'), 0) AS denied"
+            ),
+            query,
+            None,
+        );
+        builder.push(" GROUP BY key");
+        for row in builder.build().fetch_all(self.pool.as_ref()).await? {
+            let group = usage_group(&mut groups, row.try_get("key")?);
+            group.approvals_requested = row.try_get("requested")?;
+            group.approvals_denied = row.try_get("denied")?;
+        }
+
+        for (key, count) in self.named_counts(query, "calls.tool", None).await? {
+            usage_group(&mut groups, key).tool_calls.push(count);
+        }
+        for (key, count) in self
+            .named_counts(
+                query,
+                "COALESCE(calls.command, calls.tool)",
+                Some("calls.approval = 'denied'"),
+            )
+            .await?
+        {
+            usage_group(&mut groups, key).denied_commands.push(count);
+        }
+        for (key, count) in self
+            .named_counts(
+                query,
+                "calls.command",
+                Some("calls.command IS NOT NULL AND calls.exit_code <> 0"),
+            )
+            .await?
+        {
+            usage_group(&mut groups, key).failing_commands.push(count);
+        }
+
+        let mut groups = groups
+            .into_iter()
+            .map(|(key, mut group)| {
+                group.key = (query.group_by != UsageStatsGroupBy::None).then_some(key);
+                group
+            })
+            .collect::<Vec<_>>();
+        if query.group_by == UsageStatsGroupBy::Day {
+            groups.sort_by(|a, b| a.key.cmp(&b.key));
+        } else {
+            groups.sort_by(|a, b| b.sessions.cmp(&a.sessions).then(b.tokens.cmp(&a.tokens)));
+        }
+        Ok(UsageStats { groups })
+    }
+
+    /// Count tool calls matching `condition` by `name`, keeping the `top`
+    /// most frequent names in each group.
+    async fn named_counts(
+        &self,
+        query: &UsageStatsQuery,
+        name: &str,
+        condition: Option<&str>,
+    ) -> anyhow::Result<Vec<(Option<String>, NamedCount)>> {
+        let key = usage_group_key(query.group_by);
+        let mut builder = tool_calls_query(
+            &format!("SELECT {key} AS key, {name} AS name, COUNT(*) AS count"),
+            query,
+            condition,
+        );
+        builder.push(" GROUP BY key, name ORDER BY key ASC, count DESC, name ASC");
+        let mut counts: Vec<(Option<String>, NamedCount)> = Vec::new();
+        let mut in_group = 0;
+        for row in builder.build().fetch_all(self.pool.as_ref()).await? {
+            let key: Option<String> = row.try_get("key")?;
+            if counts.last().is_some_and(|(last, _)| *last == key) {
+                in_group += 1;
+            } else {
+                in_group = 1;
+            }
+            if in_group <= query.top {
+                counts.push((
+                    key,
+                    NamedCount {
+                        name: row.try_get("name")?,
+                        count: row.try_get("count")?,
+                    },
+                ));
+            }
+        }
+        Ok(counts)
+    }
+
     /// Mark a thread as archived using the underlying database.
     pub async fn mark_archived(
         &self,
@@ -794,13 +1679,75 @@ fn push_like_filters<'a>(
     builder.push(")");
 }
 
+/// SQL expression naming the group a thread (or turn) belongs to.
+fn usage_group_key(group_by: UsageStatsGroupBy) -> &'static str {
+    match group_by {
+        UsageStatsGroupBy::None => "''",
+        UsageStatsGroupBy::Repo => "COALESCE(NULLIF(threads.git_origin_url, ''), threads.cwd)",
+        UsageStatsGroupBy::Model => "COALESCE(turns.model, '')",
+        UsageStatsGroupBy::Day => "date(turns.started_at, 'unixepoch')",
+    }
+}
+
+/// The time the date range applies to.
+fn usage_time_column(group_by: UsageStatsGroupBy) -> &'static str {
+    match group_by {
+        UsageStatsGroupBy::Day => "turns.started_at",
+        UsageStatsGroupBy::None | UsageStatsGroupBy::Repo | UsageStatsGroupBy::Model => {
+            "threads.created_at"
+        }
+    }
+}
+
+fn usage_group(
+    groups: &mut BTreeMap<String, UsageStatsGroup>,
+    key: Option<String>,
+) -> &mut UsageStatsGroup {
+    groups.entry(key.unwrap_or_default()).or_default()
+}
+
+fn tool_calls_query<'a>(
+    select: &str,
+    query: &'a UsageStatsQuery,
+    condition: Option<&str>,
+) -> QueryBuilder<'a, Sqlite> {
+    let mut builder = QueryBuilder::<Sqlite>::new(format!(
+        r#"{select}
+FROM thread_tool_calls AS calls
+JOIN threads ON threads.id = calls.thread_id
+LEFT JOIN thread_turns AS turns
+    ON turns.thread_id = calls.thread_id AND turns.turn_index = calls.turn_index"#
+    ));
+    push_usage_filters(&mut builder, query);
+    if let Some(condition) = condition {
+        builder.push(" AND ").push(condition);
+    }
+    builder
+}
+
+fn push_usage_filters<'a>(builder: &mut QueryBuilder<'a, Sqlite>, query: &'a UsageStatsQuery) {
+    let time = usage_time_column(query.group_by);
+    builder.push(" WHERE 1 = 1");
+    if let Some(since) = query.since {
+        builder
+            .push(format!(" AND {time} >= "))
+            .push_bind(datetime_to_epoch_seconds(since));
+    }
+    if let Some(until) = query.until {
+        builder
+            .push(format!(" AND {time} < "))
+            .push_bind(datetime_to_epoch_seconds(until));
+    }
+}
+
 fn extract_dynamic_tools(items: &[RolloutItem]) -> Option<Option<Vec<DynamicToolSpec>>> {
     items.iter().find_map(|item| match item {
         RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.dynamic_tools.clone()),
         RolloutItem::ResponseItem(_)
         | RolloutItem::Compacted(_)
         | RolloutItem::TurnContext(_)
-        | RolloutItem::EventMsg(_) => None,
+        | RolloutItem::EventMsg(_)
+        | RolloutItem::ApprovalDecision(_) => None,
     })
 }
 
@@ -967,12 +1914,21 @@ mod tests {
     use super::StateRuntime;
     use super::ThreadMetadata;
     use super::state_db_filename;
+    use crate::NamedCount;
+    use crate::ThreadSearchMatch;
+    use crate::ThreadSearchMatchKind;
+    use crate::ThreadSearchQuery;
+    use crate::UsageStatsGroup;
+    use crate::UsageStatsGroupBy;
+    use crate::UsageStatsQuery;
     use chrono::DateTime;
     use chrono::Utc;
     use codex_protocol::ThreadId;
     use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::RolloutItem;
     use codex_protocol::protocol::SandboxPolicy;
     use pretty_assertions::assert_eq;
+    use serde_json::json;
     use sqlx::Row;
     use std::path::Path;
     use std::path::PathBuf;
@@ -1328,6 +2284,209 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(codex_home).await;
     }
 
+    #[tokio::test]
+    async fn memories_are_scoped_to_user_or_repo() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home, "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+
+        let repo_memory = runtime
+            .add_memory(&NewMemory {
+                scope: MemoryScope::Repo,
+                repo: Some("/work/codex".to_string()),
+                content: "Run `just fmt` before committing.".to_string(),
+                source: crate::MemorySource::Agent,
+                thread_id: Some(thread_id),
+            })
+            .await
+            .expect("add repo memory");
+        let user_memory = runtime
+            .add_memory(&NewMemory {
+                scope: MemoryScope::User,
+                repo: None,
+                content: "Prefers concise answers.".to_string(),
+                source: crate::MemorySource::User,
+                thread_id: None,
+            })
+            .await
+            .expect("add user memory");
+        runtime
+            .add_memory(&NewMemory {
+                scope: MemoryScope::Repo,
+                repo: Some("/work/other".to_string()),
+                content: "Uses pnpm.".to_string(),
+                source: crate::MemorySource::Agent,
+                thread_id: None,
+            })
+            .await
+            .expect("add other repo memory");
+        let duplicate = runtime
+            .add_memory(&NewMemory {
+                scope: MemoryScope::Repo,
+                repo: Some("/work/codex".to_string()),
+                content: "Run `just fmt` before committing.  ".to_string(),
+                source: crate::MemorySource::Agent,
+                thread_id: None,
+            })
+            .await
+            .expect("add duplicate memory");
+        assert_eq!(duplicate.id, repo_memory.id);
+        assert_eq!(repo_memory.thread_id, Some(thread_id));
+
+        let ids = |memories: Vec<Memory>| memories.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(runtime
+                .list_memories(Some("/work/codex"))
+                .await
+                .expect("list memories")),
+            vec![repo_memory.id, user_memory.id]
+        );
+        assert_eq!(
+            runtime.list_memories(None).await.expect("list all").len(),
+            3
+        );
+
+        let edited = runtime
+            .update_memory(repo_memory.id, "Run `just fmt` and `just fix`.")
+            .await
+            .expect("update memory")
+            .expect("memory exists");
+        assert_eq!(edited.content, "Run `just fmt` and `just fix`.");
+        assert_eq!(edited.source, crate::MemorySource::User);
+
+        assert!(runtime.delete_memory(repo_memory.id).await.expect("delete"));
+        assert!(
+            !runtime
+                .delete_memory(repo_memory.id)
+                .await
+                .expect("delete again")
+        );
+        assert_eq!(
+            runtime
+                .get_memory(repo_memory.id)
+                .await
+                .expect("get memory"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn scheduled_runs_record_history_and_last_fired_slot() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home, "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        let created_at = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).expect("timestamp");
+        let job = runtime
+            .add_scheduled_job(
+                &crate::NewScheduledJob {
+                    name: "nightly".to_string(),
+                    prompt: "Triage new issues".to_string(),
+                    cwd: PathBuf::from("/work/codex"),
+                    profile: Some("ci".to_string()),
+                    sandbox: None,
+                    cron: "0 2 * * *".to_string(),
+                    output: Some(PathBuf::from("/tmp/nightly.md")),
+                },
+                created_at,
+            )
+            .await
+            .expect("add job");
+        assert_eq!(job.created_at, created_at);
+        assert_eq!(job.last_fired_at, None);
+        assert!(job.enabled);
+        assert!(
+            runtime
+                .add_scheduled_job(
+                    &crate::NewScheduledJob {
+                        name: "nightly".to_string(),
+                        prompt: "Again".to_string(),
+                        cwd: PathBuf::from("/work/codex"),
+                        profile: None,
+                        sandbox: None,
+                        cron: "@daily".to_string(),
+                        output: None,
+                    },
+                    created_at,
+                )
+                .await
+                .is_err()
+        );
+
+        let slot = created_at + chrono::Duration::hours(3);
+        let run_id = runtime
+            .start_scheduled_run(job.id, slot, slot + chrono::Duration::seconds(5))
+            .await
+            .expect("start run");
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        runtime
+            .finish_scheduled_run(
+                run_id,
+                slot + chrono::Duration::minutes(4),
+                &crate::ScheduledRunOutcome {
+                    status: crate::ScheduledRunStatus::Succeeded,
+                    exit_code: Some(0),
+                    thread_id: Some(thread_id),
+                    rollout_path: Some(PathBuf::from("/home/rollout.jsonl")),
+                    error: None,
+                },
+            )
+            .await
+            .expect("finish run");
+        let unfinished = runtime
+            .start_scheduled_run(job.id, slot + chrono::Duration::days(1), slot)
+            .await
+            .expect("start second run");
+        assert_eq!(
+            runtime
+                .fail_unfinished_scheduled_runs(slot, "interrupted")
+                .await
+                .expect("fail unfinished"),
+            1
+        );
+
+        let job = runtime
+            .get_scheduled_job("nightly")
+            .await
+            .expect("get job")
+            .expect("job exists");
+        assert_eq!(job.last_fired_at, Some(slot + chrono::Duration::days(1)));
+
+        let runs = runtime
+            .list_scheduled_runs(Some(job.id), 10)
+            .await
+            .expect("list runs");
+        assert_eq!(
+            runs.iter().map(|run| run.id).collect::<Vec<_>>(),
+            vec![run_id, unfinished]
+        );
+        assert_eq!(runs[0].job_name, "nightly");
+        assert_eq!(runs[0].status, crate::ScheduledRunStatus::Succeeded);
+        assert_eq!(runs[0].thread_id, Some(thread_id));
+        assert_eq!(
+            runs[0].rollout_path,
+            Some(PathBuf::from("/home/rollout.jsonl"))
+        );
+        assert_eq!(runs[1].status, crate::ScheduledRunStatus::Failed);
+        assert_eq!(runs[1].error.as_deref(), Some("interrupted"));
+
+        assert!(
+            runtime
+                .delete_scheduled_job("nightly")
+                .await
+                .expect("delete job")
+        );
+        assert_eq!(
+            runtime
+                .list_scheduled_runs(None, 10)
+                .await
+                .expect("list runs after delete"),
+            Vec::new()
+        );
+    }
+
     #[tokio::test]
     async fn deleting_thread_cascades_thread_memory() {
         let codex_home = unique_temp_dir();
@@ -1382,6 +2541,284 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(codex_home).await;
     }
 
+    #[tokio::test]
+    async fn usage_stats_aggregates_turns_and_tool_calls_by_model() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let metadata = test_thread_metadata(&codex_home, thread_id, codex_home.join("a"));
+        runtime
+            .upsert_thread(&metadata)
+            .await
+            .expect("upsert thread");
+
+        let items: Vec<RolloutItem> = serde_json::from_value(json!([
+            {"type": "event_msg", "payload": {"type": "user_message", "message": "fix it"}},
+            {"type": "turn_context", "payload": {
+                "cwd": "/repo",
+                "approval_policy": "on-request",
+                "sandbox_policy": {"type": "read-only"},
+                "model": "gpt-5.2-codex",
+                "summary": "auto"
+            }},
+            {"type": "response_item", "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": "{\"command\":[\"rm\",\"-rf\",\"target\"]}",
+                "call_id": "call-1"
+            }},
+            {"type": "approval_decision", "payload": {
+                "call_id": "call-1",
+                "tool_name": "shell",
+                "decision": "denied"
+            }},
+            {"type": "response_item", "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": "{\"command\":[\"cargo\",\"test\"]}",
+                "call_id": "call-2"
+            }},
+            {"type": "response_item", "payload": {
+                "type": "function_call_output",
+                "call_id": "call-2",
+                "output": "Exit code: 101\nOutput:\nfailed"
+            }},
+            {"type": "event_msg", "payload": {
+                "type": "token_count",
+                "info": {
+                    "total_token_usage": {
+                        "input_tokens": 900,
+                        "cached_input_tokens": 0,
+                        "output_tokens": 100,
+                        "reasoning_output_tokens": 0,
+                        "total_tokens": 1000
+                    },
+                    "last_token_usage": {
+                        "input_tokens": 900,
+                        "cached_input_tokens": 0,
+                        "output_tokens": 100,
+                        "reasoning_output_tokens": 0,
+                        "total_tokens": 1000
+                    },
+                    "model_context_window": null
+                },
+                "rate_limits": null
+            }}
+        ]))
+        .expect("rollout items");
+        runtime
+            .record_usage(thread_id, 0, 0.0, &items)
+            .await
+            .expect("record usage");
+
+        let stats = runtime
+            .usage_stats(&UsageStatsQuery {
+                group_by: UsageStatsGroupBy::Model,
+                top: 5,
+                ..Default::default()
+            })
+            .await
+            .expect("usage stats");
+        assert_eq!(
+            stats.groups,
+            vec![UsageStatsGroup {
+                key: Some("gpt-5.2-codex".to_string()),
+                sessions: 1,
+                turns: 1,
+                tokens: 1000,
+                cost_usd: None,
+                tool_calls: vec![NamedCount {
+                    name: "shell".to_string(),
+                    count: 2,
+                }],
+                approvals_requested: 1,
+                approvals_denied: 1,
+                denied_commands: vec![NamedCount {
+                    name: "rm -rf target".to_string(),
+                    count: 1,
+                }],
+                failing_commands: vec![NamedCount {
+                    name: "cargo test".to_string(),
+                    count: 1,
+                }],
+            }]
+        );
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
+    #[tokio::test]
+    async fn usage_stats_by_day_bills_turns_to_the_day_they_started() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let metadata = test_thread_metadata(&codex_home, thread_id, codex_home.join("a"));
+        runtime
+            .upsert_thread(&metadata)
+            .await
+            .expect("upsert thread");
+
+        let turn_context = json!({"type": "turn_context", "payload": {
+            "cwd": "/repo",
+            "approval_policy": "on-request",
+            "sandbox_policy": {"type": "read-only"},
+            "model": "gpt-5.2-codex",
+            "summary": "auto"
+        }});
+        let token_count = |total_tokens: i64, total_cost_usd: f64| {
+            let usage = json!({
+                "input_tokens": total_tokens,
+                "cached_input_tokens": 0,
+                "output_tokens": 0,
+                "reasoning_output_tokens": 0,
+                "total_tokens": total_tokens
+            });
+            json!({"type": "event_msg", "payload": {
+                "type": "token_count",
+                "info": {
+                    "total_token_usage": usage,
+                    "last_token_usage": usage,
+                    "model_context_window": null
+                },
+                "rate_limits": null,
+                "total_cost_usd": total_cost_usd
+            }})
+        };
+        let user_message =
+            json!({"type": "event_msg", "payload": {"type": "user_message", "message": "go on"}});
+        let items: Vec<RolloutItem> = serde_json::from_value(json!([
+            user_message,
+            turn_context,
+            token_count(100, 0.5),
+            user_message,
+            turn_context,
+            token_count(160, 1.25)
+        ]))
+        .expect("rollout items");
+        runtime
+            .record_usage(thread_id, 0, 0.0, &items)
+            .await
+            .expect("record usage");
+
+        // The thread spans midnight: its second turn starts the next day.
+        for (turn_index, started_at) in [(0, "2026-03-01T23:30:00Z"), (1, "2026-03-02T00:15:00Z")] {
+            let started_at = DateTime::parse_from_rfc3339(started_at)
+                .expect("timestamp")
+                .timestamp();
+            sqlx::query(
+                "UPDATE thread_turns SET started_at = ? WHERE thread_id = ? AND turn_index = ?",
+            )
+            .bind(started_at)
+            .bind(thread_id.to_string())
+            .bind(turn_index)
+            .execute(runtime.pool.as_ref())
+            .await
+            .expect("set started_at");
+        }
+
+        let day = |key: &str, tokens: i64, cost_usd: f64| UsageStatsGroup {
+            key: Some(key.to_string()),
+            sessions: 1,
+            turns: 1,
+            tokens,
+            cost_usd: Some(cost_usd),
+            ..Default::default()
+        };
+        let query = UsageStatsQuery {
+            group_by: UsageStatsGroupBy::Day,
+            top: 5,
+            ..Default::default()
+        };
+        let stats = runtime.usage_stats(&query).await.expect("usage stats");
+        assert_eq!(
+            stats.groups,
+            vec![day("2026-03-01", 100, 0.5), day("2026-03-02", 60, 0.75)]
+        );
+
+        let stats = runtime
+            .usage_stats(&UsageStatsQuery {
+                since: Some(
+                    DateTime::parse_from_rfc3339("2026-03-02T00:00:00Z")
+                        .expect("timestamp")
+                        .with_timezone(&Utc),
+                ),
+                ..query
+            })
+            .await
+            .expect("usage stats since");
+        assert_eq!(stats.groups, vec![day("2026-03-02", 60, 0.75)]);
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
+    #[tokio::test]
+    async fn search_threads_returns_snippets_and_turns() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let metadata = test_thread_metadata(&codex_home, thread_id, codex_home.join("a"));
+        runtime
+            .upsert_thread(&metadata)
+            .await
+            .expect("upsert thread");
+
+        let items: Vec<RolloutItem> = serde_json::from_value(json!([
+            {"type": "event_msg", "payload": {"type": "user_message", "message": "hello"}},
+            {"type": "event_msg", "payload": {"type": "user_message", "message": "why does the websocket reconnect loop spin"}},
+            {"type": "event_msg", "payload": {"type": "agent_message", "message": "The backoff resets on every error."}}
+        ]))
+        .expect("rollout items");
+        runtime
+            .index_thread_search(thread_id, &items[..1])
+            .await
+            .expect("index first batch");
+        runtime
+            .index_thread_search(thread_id, &items[1..])
+            .await
+            .expect("index second batch");
+
+        let results = runtime
+            .search_threads(&ThreadSearchQuery {
+                query: "websocket reconn".to_string(),
+                limit: 10,
+                ..Default::default()
+            })
+            .await
+            .expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].thread.id, thread_id);
+        assert_eq!(
+            results[0].matches,
+            vec![ThreadSearchMatch {
+                turn_index: 1,
+                kind: ThreadSearchMatchKind::UserMessage,
+                snippet: "why does the websocket reconnect loop spin".to_string(),
+            }]
+        );
+
+        runtime
+            .replace_thread_search(thread_id, &items[..1])
+            .await
+            .expect("replace index");
+        let results = runtime
+            .search_threads(&ThreadSearchQuery {
+                query: "websocket".to_string(),
+                limit: 10,
+                ..Default::default()
+            })
+            .await
+            .expect("search after replace");
+        assert_eq!(results, Vec::new());
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
     fn test_thread_metadata(
         codex_home: &Path,
         thread_id: ThreadId,
@@ -1401,6 +2838,7 @@ mod tests {
             sandbox_policy: crate::extract::enum_to_string(&SandboxPolicy::ReadOnly),
             approval_mode: crate::extract::enum_to_string(&AskForApproval::OnRequest),
             tokens_used: 0,
+            cost_usd: 0.0,
             first_user_message: Some("hello".to_string()),
             archived_at: None,
             git_sha: None,
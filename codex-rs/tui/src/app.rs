@@ -2204,6 +2204,15 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::OpenReviewRangePrompt => {
+                self.chat_widget.show_review_range_prompt();
+            }
+            AppEvent::OpenReviewPatchFilePrompt => {
+                self.chat_widget.show_review_patch_file_prompt();
+            }
+            AppEvent::OpenReviewPathsPrompt => {
+                self.chat_widget.show_review_paths_prompt();
+            }
             AppEvent::SubmitUserMessageWithMode {
                 text,
                 collaboration_mode,
@@ -2249,6 +2258,27 @@ impl App {
                         "E L I C I T A T I O N".to_string(),
                     ));
                 }
+                ApprovalRequest::Network {
+                    host,
+                    protocol,
+                    method,
+                    ..
+                } => {
+                    let _ = tui.enter_alt_screen();
+                    let request = match method {
+                        Some(method) => format!("{method} ({protocol})"),
+                        None => protocol,
+                    };
+                    let paragraph = Paragraph::new(vec![
+                        Line::from(vec!["Host: ".into(), host.bold()]),
+                        Line::from(vec!["Request: ".into(), request.into()]),
+                    ])
+                    .wrap(Wrap { trim: false });
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![Box::new(paragraph)],
+                        "N E T W O R K".to_string(),
+                    ));
+                }
             },
             AppEvent::StatusLineSetup { items } => {
                 let ids = items.iter().map(ToString::to_string).collect::<Vec<_>>();
@@ -271,6 +271,15 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Open the commit range prompt from the review popup.
+    OpenReviewRangePrompt,
+
+    /// Open the patch file prompt from the review popup.
+    OpenReviewPatchFilePrompt,
+
+    /// Open the file/directory prompt from the review popup.
+    OpenReviewPathsPrompt,
+
     /// Submit a user message with an explicit collaboration mask.
     SubmitUserMessageWithMode {
         text: String,
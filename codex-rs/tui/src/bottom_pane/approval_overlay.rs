@@ -20,6 +20,7 @@ use codex_core::features::Features;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::NetworkApprovalDecision;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use codex_protocol::mcp::RequestId;
@@ -55,6 +56,12 @@ pub(crate) enum ApprovalRequest {
         request_id: RequestId,
         message: String,
     },
+    Network {
+        id: String,
+        host: String,
+        protocol: String,
+        method: Option<String>,
+    },
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
@@ -122,6 +129,10 @@ impl ApprovalOverlay {
                 elicitation_options(),
                 format!("{server_name} needs your approval."),
             ),
+            ApprovalVariant::Network { host, .. } => (
+                network_options(host),
+                format!("Allow sandboxed commands to reach {host}?"),
+            ),
         };
 
         let header = Box::new(ColumnRenderable::with([
@@ -182,6 +193,9 @@ impl ApprovalOverlay {
                 ) => {
                     self.handle_elicitation_decision(server_name, request_id, *decision);
                 }
+                (ApprovalVariant::Network { id, .. }, ApprovalDecision::Network(decision)) => {
+                    self.handle_network_decision(id, *decision);
+                }
                 _ => {}
             }
         }
@@ -220,6 +234,14 @@ impl ApprovalOverlay {
             }));
     }
 
+    fn handle_network_decision(&self, id: &str, decision: NetworkApprovalDecision) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::NetworkApproval {
+                id: id.to_string(),
+                decision,
+            }));
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -295,6 +317,9 @@ impl BottomPaneView for ApprovalOverlay {
                         ElicitationAction::Cancel,
                     );
                 }
+                ApprovalVariant::Network { id, .. } => {
+                    self.handle_network_decision(id, NetworkApprovalDecision::Deny);
+                }
             }
         }
         self.queue.clear();
@@ -313,6 +338,58 @@ impl BottomPaneView for ApprovalOverlay {
         self.enqueue_request(request);
         None
     }
+
+    fn apply_recorded_decision(&mut self, op: &Op) -> bool {
+        if self.current_complete {
+            return false;
+        }
+        let decision = match (self.current_variant.as_ref(), op) {
+            (
+                Some(ApprovalVariant::Exec { id, .. }),
+                Op::ExecApproval {
+                    id: answered,
+                    decision,
+                },
+            )
+            | (
+                Some(ApprovalVariant::ApplyPatch { id }),
+                Op::PatchApproval {
+                    id: answered,
+                    decision,
+                },
+            ) if id == answered => ApprovalDecision::Review(decision.clone()),
+            (
+                Some(ApprovalVariant::McpElicitation {
+                    server_name,
+                    request_id,
+                }),
+                Op::ResolveElicitation {
+                    server_name: answered_server,
+                    request_id: answered_request,
+                    decision,
+                },
+            ) if server_name == answered_server && request_id == answered_request => {
+                ApprovalDecision::McpElicitation(*decision)
+            }
+            (
+                Some(ApprovalVariant::Network { id, .. }),
+                Op::NetworkApproval {
+                    id: answered,
+                    decision,
+                },
+            ) if id == answered => ApprovalDecision::Network(*decision),
+            _ => return false,
+        };
+        let Some(idx) = self
+            .options
+            .iter()
+            .position(|option| option.decision == decision)
+        else {
+            return false;
+        };
+        self.apply_selection(idx);
+        true
+    }
 }
 
 impl Renderable for ApprovalOverlay {
@@ -404,6 +481,31 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     header: Box::new(header),
                 }
             }
+            ApprovalRequest::Network {
+                id,
+                host,
+                protocol,
+                method,
+            } => {
+                let request = match method {
+                    Some(method) => format!("{method} ({protocol})"),
+                    None => protocol,
+                };
+                let header = Paragraph::new(vec![
+                    Line::from(vec!["Host: ".into(), host.clone().bold()]),
+                    Line::from(vec!["Request: ".into(), request.into()]),
+                    Line::from(""),
+                    Line::from(
+                        "The network proxy blocked this request. Re-run the command after allowing the host."
+                            .dim(),
+                    ),
+                ])
+                .wrap(Wrap { trim: false });
+                Self {
+                    variant: ApprovalVariant::Network { id, host },
+                    header: Box::new(header),
+                }
+            }
         }
     }
 }
@@ -422,12 +524,17 @@ enum ApprovalVariant {
         server_name: String,
         request_id: RequestId,
     },
+    Network {
+        id: String,
+        host: String,
+    },
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    Network(NetworkApprovalDecision),
 }
 
 #[derive(Clone)]
@@ -526,6 +633,35 @@ fn elicitation_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn network_options(host: &str) -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, allow once".to_string(),
+            decision: ApprovalDecision::Network(NetworkApprovalDecision::AllowOnce),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: format!("Yes, allow {host} for this session"),
+            decision: ApprovalDecision::Network(NetworkApprovalDecision::AllowForSession),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+        ApprovalOption {
+            label: format!("Yes, always allow {host} (adds it to config.toml)"),
+            decision: ApprovalDecision::Network(NetworkApprovalDecision::AllowAlways),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
+        },
+        ApprovalOption {
+            label: "No, keep it blocked".to_string(),
+            decision: ApprovalDecision::Network(NetworkApprovalDecision::Deny),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -553,6 +689,40 @@ mod tests {
         assert!(view.is_complete());
     }
 
+    #[test]
+    fn recorded_decision_answers_matching_request() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(make_exec_request(), tx, Features::with_defaults());
+        view.enqueue_request(make_exec_request());
+
+        assert!(!view.apply_recorded_decision(&Op::ExecApproval {
+            id: "other".to_string(),
+            decision: ReviewDecision::Approved,
+        }));
+        assert!(view.apply_recorded_decision(&Op::ExecApproval {
+            id: "test".to_string(),
+            decision: ReviewDecision::Approved,
+        }));
+        assert!(!view.is_complete());
+        assert!(view.apply_recorded_decision(&Op::ExecApproval {
+            id: "test".to_string(),
+            decision: ReviewDecision::Abort,
+        }));
+        assert!(view.is_complete());
+
+        let mut decisions = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { decision, .. }) = ev {
+                decisions.push(decision);
+            }
+        }
+        assert_eq!(
+            decisions,
+            vec![ReviewDecision::Approved, ReviewDecision::Abort]
+        );
+    }
+
     #[test]
     fn shortcut_triggers_selection() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -609,6 +779,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn network_session_shortcut_emits_network_approval() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::Network {
+                id: "network-1".to_string(),
+                host: "registry.npmjs.org".to_string(),
+                protocol: "https_connect".to_string(),
+                method: None,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+
+        let mut approval = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::NetworkApproval { id, decision }) = ev {
+                approval = Some((id, decision));
+                break;
+            }
+        }
+        assert_eq!(
+            approval,
+            Some((
+                "network-1".to_string(),
+                NetworkApprovalDecision::AllowForSession
+            ))
+        );
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
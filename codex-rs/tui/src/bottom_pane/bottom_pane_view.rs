@@ -1,5 +1,6 @@
 use crate::bottom_pane::ApprovalRequest;
 use crate::render::renderable::Renderable;
+use codex_core::protocol::Op;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 use crossterm::event::KeyEvent;
 
@@ -66,4 +67,10 @@ pub(crate) trait BottomPaneView: Renderable {
     ) -> Option<RequestUserInputEvent> {
         Some(request)
     }
+
+    /// Apply an answer recorded in a session log as if the user had picked
+    /// it in this view. Return true if the answer was for this view.
+    fn apply_recorded_decision(&mut self, _op: &Op) -> bool {
+        false
+    }
 }
@@ -27,6 +27,7 @@ use crate::render::renderable::RenderableItem;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
 use codex_core::features::Features;
+use codex_core::protocol::Op;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
 use codex_protocol::request_user_input::RequestUserInputEvent;
@@ -465,6 +466,11 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_placeholder_text(&mut self, placeholder: String) {
+        self.composer.set_placeholder_text(placeholder);
+        self.request_redraw();
+    }
+
     #[allow(dead_code)]
     pub(crate) fn set_composer_input_enabled(
         &mut self,
@@ -729,6 +735,23 @@ impl BottomPane {
         self.push_view(Box::new(modal));
     }
 
+    /// Answer the active modal with a decision recorded in a session log, as
+    /// if the user had picked it. Returns true if the modal consumed it.
+    pub(crate) fn apply_recorded_decision(&mut self, op: &Op) -> bool {
+        let Some(view) = self.view_stack.last_mut() else {
+            return false;
+        };
+        if !view.apply_recorded_decision(op) {
+            return false;
+        }
+        if view.is_complete() {
+            self.view_stack.clear();
+            self.on_active_view_complete();
+        }
+        self.request_redraw();
+        true
+    }
+
     /// Called when the agent requests user input.
     pub fn push_user_input_request(&mut self, request: RequestUserInputEvent) {
         let request = if let Some(view) = self.view_stack.last_mut() {
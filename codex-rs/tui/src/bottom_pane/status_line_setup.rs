@@ -14,7 +14,7 @@
 //! - Git information (branch name)
 //! - Context usage (remaining %, used %, window size)
 //! - Usage limits (5-hour, weekly)
-//! - Session info (ID, tokens used)
+//! - Session info (ID, tokens used, estimated cost)
 //! - Application version
 
 use ratatui::buffer::Buffer;
@@ -89,6 +89,9 @@ pub(crate) enum StatusLineItem {
     /// Total output tokens generated.
     TotalOutputTokens,
 
+    /// Estimated cost of the current session.
+    SessionCost,
+
     /// Full session UUID.
     SessionId,
 }
@@ -121,6 +124,9 @@ impl StatusLineItem {
             StatusLineItem::UsedTokens => "Total tokens used in session (omitted when zero)",
             StatusLineItem::TotalInputTokens => "Total input tokens used in session",
             StatusLineItem::TotalOutputTokens => "Total output tokens used in session",
+            StatusLineItem::SessionCost => {
+                "Estimated cost of the session (omitted when the model has no price)"
+            }
             StatusLineItem::SessionId => {
                 "Current session identifier (omitted until session starts)"
             }
@@ -147,6 +153,7 @@ impl StatusLineItem {
             StatusLineItem::UsedTokens => "27.3K used",
             StatusLineItem::TotalInputTokens => "17,588 in",
             StatusLineItem::TotalOutputTokens => "265 out",
+            StatusLineItem::SessionCost => "$0.42",
             StatusLineItem::SessionId => "019c19bd-ceb6-73b0-adc8-8ec0397b85cf",
         }
     }
@@ -39,6 +39,7 @@ use std::time::Instant;
 use crate::bottom_pane::StatusLineItem;
 use crate::bottom_pane::StatusLineSetupView;
 use crate::status::RateLimitWindowDisplay;
+use crate::status::SessionCost;
 use crate::status::format_directory_display;
 use crate::status::format_tokens_compact;
 use crate::text_formatting::proper_join;
@@ -67,6 +68,7 @@ use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::ConfigReloadedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -86,6 +88,8 @@ use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelFallbackEvent;
+use codex_core::protocol::NetworkApprovalRequestEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
@@ -509,6 +513,8 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    /// Estimated cost of the thread so far, as last reported by core.
+    session_cost: Option<SessionCost>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
@@ -1807,6 +1813,14 @@ impl ChatWidget {
         );
     }
 
+    fn on_network_approval_request(&mut self, ev: NetworkApprovalRequestEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_network_approval(ev),
+            |s| s.handle_network_approval_now(ev2),
+        );
+    }
+
     fn on_request_user_input(&mut self, ev: RequestUserInputEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
@@ -2101,6 +2115,62 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_config_reloaded(&mut self, event: ConfigReloadedEvent) {
+        let ConfigReloadedEvent {
+            changed,
+            restart_required,
+            error,
+        } = event;
+        if let Some(error) = error {
+            self.on_warning(format!(
+                "Ignored config changes; keeping the previous settings: {error}"
+            ));
+            return;
+        }
+        let hint = (!restart_required.is_empty()).then(|| {
+            format!(
+                "Start a new session to apply {}",
+                restart_required.join(", ")
+            )
+        });
+        if changed.is_empty() {
+            if let Some(hint) = hint {
+                self.add_info_message("Config changed".to_string(), Some(hint));
+            }
+            return;
+        }
+        self.add_info_message(format!("Reloaded {} from disk", changed.join(", ")), hint);
+    }
+
+    fn on_model_fallback(&mut self, event: ModelFallbackEvent) {
+        let ModelFallbackEvent {
+            from_model,
+            to_model,
+            model_provider_id,
+            reason,
+            resume_primary_at,
+        } = event;
+        let Some(reason) = reason else {
+            self.add_info_message(
+                format!("Switched back from {from_model} to {to_model}"),
+                None,
+            );
+            return;
+        };
+        let resume = resume_primary_at
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+            .map(|at| {
+                format!(
+                    "; returning to {from_model} after {}",
+                    at.with_timezone(&Local).format("%H:%M")
+                )
+            })
+            .unwrap_or_default();
+        self.on_warning(format!(
+            "{from_model} is unavailable ({reason}). Switched to {to_model} on {model_provider_id}{resume}."
+        ));
+    }
+
     fn on_background_event(&mut self, message: String) {
         debug!("BackgroundEvent: {message}");
         self.bottom_pane.ensure_status_indicator();
@@ -2410,6 +2480,24 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn handle_network_approval_now(&mut self, ev: NetworkApprovalRequestEvent) {
+        self.flush_answer_stream_with_separator();
+
+        self.notify(Notification::NetworkApprovalRequested {
+            host: ev.host.clone(),
+        });
+
+        let request = ApprovalRequest::Network {
+            id: ev.id,
+            host: ev.host,
+            protocol: ev.protocol,
+            method: ev.method,
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
     pub(crate) fn handle_request_user_input_now(&mut self, ev: RequestUserInputEvent) {
         self.flush_answer_stream_with_separator();
         self.bottom_pane.push_user_input_request(ev);
@@ -2602,6 +2690,7 @@ impl ChatWidget {
             session_header: SessionHeader::new(header_model),
             initial_user_message,
             token_info: None,
+            session_cost: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -2765,6 +2854,7 @@ impl ChatWidget {
             session_header: SessionHeader::new(header_model),
             initial_user_message,
             token_info: None,
+            session_cost: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -2917,6 +3007,7 @@ impl ChatWidget {
             session_header: SessionHeader::new(header_model),
             initial_user_message,
             token_info: None,
+            session_cost: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -3513,6 +3604,7 @@ impl ChatWidget {
                             instructions: prepared_args,
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 });
                 self.bottom_pane.drain_pending_submission_state();
@@ -3873,6 +3965,18 @@ impl ChatWidget {
         self.dispatch_event_msg(None, msg, true);
     }
 
+    /// Answer the active approval modal with a decision recorded in a session
+    /// log. Used by `codex replay`, where no agent is listening for the op.
+    pub(crate) fn apply_recorded_decision(&mut self, op: &Op) {
+        if self.bottom_pane.apply_recorded_decision(op) {
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn set_placeholder_text(&mut self, placeholder: String) {
+        self.bottom_pane.set_placeholder_text(placeholder);
+    }
+
     /// Dispatch a protocol `EventMsg` to the appropriate handler.
     ///
     /// `id` is `Some` for live events and `None` for replayed events from
@@ -3918,6 +4022,10 @@ impl ChatWidget {
                 self.on_task_complete(last_agent_message, from_replay)
             }
             EventMsg::TokenCount(ev) => {
+                if ev.info.is_some() {
+                    self.session_cost =
+                        SessionCost::from_token_count(ev.total_cost_usd, ev.info.as_ref());
+                }
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
@@ -3954,6 +4062,10 @@ impl ChatWidget {
                 TurnAbortReason::ReviewEnded => {
                     self.on_interrupted_turn(ev.reason);
                 }
+                TurnAbortReason::BudgetExceeded => self.on_error(match &ev.budget {
+                    Some(budget) => format!("Turn stopped: session {budget}"),
+                    None => "Turn stopped: session budget exceeded".to_owned(),
+                }),
             },
             EventMsg::PlanUpdate(update) => self.on_plan_update(update),
             EventMsg::ExecApprovalRequest(ev) => {
@@ -3966,6 +4078,9 @@ impl ChatWidget {
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
+            EventMsg::NetworkApprovalRequest(ev) => {
+                self.on_network_approval_request(ev);
+            }
             EventMsg::RequestUserInput(ev) => {
                 self.on_request_user_input(ev);
             }
@@ -3994,6 +4109,8 @@ impl ChatWidget {
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
+            EventMsg::ConfigReloaded(ev) => self.on_config_reloaded(ev),
+            EventMsg::ModelFallback(ev) => self.on_model_fallback(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
@@ -4212,6 +4329,7 @@ impl ChatWidget {
             self.auth_manager.as_ref(),
             token_info,
             total_usage,
+            self.session_cost,
             &self.thread_id,
             self.thread_name.clone(),
             self.forked_from,
@@ -4390,6 +4508,7 @@ impl ChatWidget {
                 "{} out",
                 format_tokens_compact(self.status_line_total_usage().output_tokens)
             )),
+            StatusLineItem::SessionCost => self.session_cost.map(SessionCost::display),
             StatusLineItem::SessionId => self.thread_id.map(|id| id.to_string()),
         }
     }
@@ -6695,6 +6814,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         target: ReviewTarget::UncommittedChanges,
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
@@ -6724,6 +6844,35 @@ impl ChatWidget {
             ..Default::default()
         });
 
+        items.push(SelectionItem {
+            name: "Review a commit range".to_string(),
+            description: Some("(FROM..TO)".into()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewRangePrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        items.push(SelectionItem {
+            name: "Review a patch file".to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewPatchFilePrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        items.push(SelectionItem {
+            name: "Review specific files".to_string(),
+            description: Some("(uncommitted changes)".into()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewPathsPrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
         self.bottom_pane.show_selection_view(SelectionViewParams {
             title: Some("Select a review preset".into()),
             footer_hint: Some(standard_popup_hint_line()),
@@ -6750,6 +6899,7 @@ impl ChatWidget {
                                 branch: branch.clone(),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -6788,6 +6938,7 @@ impl ChatWidget {
                                 title: Some(subject.clone()),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -6824,6 +6975,87 @@ impl ChatWidget {
                             instructions: trimmed,
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_review_range_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Review a commit range".to_string(),
+            "Type a range like origin/main..HEAD and press Enter".to_string(),
+            None,
+            Box::new(move |range: String| {
+                if range.trim().is_empty() {
+                    return;
+                }
+                let Some((from, to)) = codex_core::review_prompts::parse_revision_range(&range)
+                else {
+                    tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_error_event(format!(
+                            "Invalid range `{}`; expected FROM..TO",
+                            range.trim()
+                        )),
+                    )));
+                    return;
+                };
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::Range { from, to },
+                        user_facing_hint: None,
+                        paths: Vec::new(),
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_review_patch_file_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Review a patch file".to_string(),
+            "Type the path to a .diff or .patch file and press Enter".to_string(),
+            None,
+            Box::new(move |path: String| {
+                let trimmed = path.trim();
+                if trimmed.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::PatchFile {
+                            path: PathBuf::from(trimmed),
+                        },
+                        user_facing_hint: None,
+                        paths: Vec::new(),
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_review_paths_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Review specific files".to_string(),
+            "Type files or directories separated by spaces and press Enter".to_string(),
+            None,
+            Box::new(move |input: String| {
+                let paths: Vec<String> = input.split_whitespace().map(str::to_string).collect();
+                if paths.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::UncommittedChanges,
+                        user_facing_hint: None,
+                        paths,
                     },
                 }));
             }),
@@ -6894,6 +7126,7 @@ impl ChatWidget {
 
     pub(crate) fn clear_token_usage(&mut self) {
         self.token_info = None;
+        self.session_cost = None;
     }
 
     fn as_renderable(&self) -> RenderableItem<'_> {
@@ -6946,6 +7179,7 @@ enum Notification {
     ExecApprovalRequested { command: String },
     EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
     ElicitationRequested { server_name: String },
+    NetworkApprovalRequested { host: String },
 }
 
 impl Notification {
@@ -6972,6 +7206,9 @@ impl Notification {
             Notification::ElicitationRequested { server_name } => {
                 format!("Approval requested by {server_name}")
             }
+            Notification::NetworkApprovalRequested { host } => {
+                format!("Network access requested: {host}")
+            }
         }
     }
 
@@ -6980,7 +7217,8 @@ impl Notification {
             Notification::AgentTurnComplete { .. } => "agent-turn-complete",
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
-            | Notification::ElicitationRequested { .. } => "approval-requested",
+            | Notification::ElicitationRequested { .. }
+            | Notification::NetworkApprovalRequested { .. } => "approval-requested",
         }
     }
 
@@ -7088,6 +7326,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                             title: Some(subject.clone()),
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
@@ -583,6 +583,7 @@ async fn interrupted_turn_restores_queued_messages_with_images_and_elements() {
         id: "interrupt".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -646,6 +647,7 @@ async fn interrupted_turn_restore_keeps_active_mode_for_resubmission() {
         id: "interrupt".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -805,6 +807,7 @@ async fn entered_review_mode_uses_request_hint() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -824,6 +827,7 @@ async fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         }),
     });
 
@@ -847,6 +851,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_review_tokens, context_window)),
             rate_limits: None,
+            total_cost_usd: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -858,6 +863,7 @@ async fn review_restores_context_window_indicator() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -866,6 +872,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(review_tokens, context_window)),
             rate_limits: None,
+            total_cost_usd: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(97));
@@ -895,6 +902,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_compact_tokens, context_window)),
             rate_limits: None,
+            total_cost_usd: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -904,6 +912,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: None,
             rate_limits: None,
+            total_cost_usd: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), None);
@@ -934,6 +943,7 @@ async fn context_indicator_shows_used_tokens_when_window_unknown() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(token_info),
             rate_limits: None,
+            total_cost_usd: None,
         }),
     });
 
@@ -1055,6 +1065,7 @@ async fn make_chatwidget_manual(
         session_header: SessionHeader::new(resolved_model.clone()),
         initial_user_message: None,
         token_info: None,
+        session_cost: None,
         rate_limit_snapshot: None,
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
@@ -3443,6 +3454,60 @@ async fn custom_prompt_submit_sends_review_op() {
                         instructions: "please audit dependencies".to_string(),
                     },
                     user_facing_hint: None,
+                    paths: Vec::new(),
+                }
+            );
+        }
+        other => panic!("unexpected app event: {other:?}"),
+    }
+}
+
+/// Submitting the paths prompt view sends Op::Review limited to those paths.
+#[tokio::test]
+async fn paths_prompt_submit_sends_review_op() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.show_review_paths_prompt();
+    chat.handle_paste(" src/lib.rs  docs/ ".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let evt = rx.try_recv().expect("expected one app event");
+    match evt {
+        AppEvent::CodexOp(Op::Review { review_request }) => {
+            assert_eq!(
+                review_request,
+                ReviewRequest {
+                    target: ReviewTarget::UncommittedChanges,
+                    user_facing_hint: None,
+                    paths: vec!["src/lib.rs".to_string(), "docs/".to_string()],
+                }
+            );
+        }
+        other => panic!("unexpected app event: {other:?}"),
+    }
+}
+
+/// Submitting the range prompt view sends Op::Review with a Range target.
+#[tokio::test]
+async fn range_prompt_submit_sends_review_op() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.show_review_range_prompt();
+    chat.handle_paste(" origin/main..HEAD ".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let evt = rx.try_recv().expect("expected one app event");
+    match evt {
+        AppEvent::CodexOp(Op::Review { review_request }) => {
+            assert_eq!(
+                review_request,
+                ReviewRequest {
+                    target: ReviewTarget::Range {
+                        from: "origin/main".to_string(),
+                        to: "HEAD".to_string(),
+                    },
+                    user_facing_hint: None,
+                    paths: Vec::new(),
                 }
             );
         }
@@ -3497,6 +3562,7 @@ async fn interrupt_exec_marks_failed_snapshot() {
         id: "call-int".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -3531,6 +3597,7 @@ async fn interrupted_turn_error_message_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -4457,6 +4524,7 @@ async fn interrupt_restores_queued_messages_into_composer() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -4495,6 +4563,7 @@ async fn interrupt_prepends_queued_messages_before_existing_composer_text() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -4523,6 +4592,7 @@ async fn interrupt_clears_unified_exec_processes() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -4550,6 +4620,7 @@ async fn interrupt_clears_unified_exec_wait_streak_snapshot() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -5326,6 +5397,68 @@ async fn warning_event_adds_warning_history_cell() {
     );
 }
 
+#[tokio::test]
+async fn config_reloaded_event_lists_settings_that_need_a_new_session() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let approval_policy = chat.config.approval_policy.value();
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::ConfigReloaded(ConfigReloadedEvent {
+            changed: vec!["mcp_servers.docs".to_string()],
+            restart_required: vec!["approval_policy".to_string()],
+            error: None,
+        }),
+    });
+
+    assert_eq!(chat.config.approval_policy.value(), approval_policy);
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one info history cell");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Reloaded mcp_servers.docs") && rendered.contains("approval_policy"),
+        "config reload cell missing content: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn model_fallback_event_reports_switch_and_return() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::ModelFallback(ModelFallbackEvent {
+            from_model: "gpt-5.1-codex".to_string(),
+            to_model: "gpt-oss:20b".to_string(),
+            model_provider_id: "ollama".to_string(),
+            reason: Some("exceeded retry limit".to_string()),
+            resume_primary_at: None,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-2".into(),
+        msg: EventMsg::ModelFallback(ModelFallbackEvent {
+            from_model: "gpt-oss:20b".to_string(),
+            to_model: "gpt-5.1-codex".to_string(),
+            model_provider_id: "openai".to_string(),
+            reason: None,
+            resume_primary_at: None,
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 2, "expected a warning and an info cell");
+    let switched = lines_to_single_string(&cells[0]);
+    assert!(
+        switched.contains("Switched to gpt-oss:20b on ollama")
+            && switched.contains("exceeded retry limit"),
+        "fallback cell missing content: {switched}"
+    );
+    let restored = lines_to_single_string(&cells[1]);
+    assert!(
+        restored.contains("Switched back from gpt-oss:20b to gpt-5.1-codex"),
+        "restore cell missing content: {restored}"
+    );
+}
+
 #[tokio::test]
 async fn status_line_invalid_items_warn_once() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -5397,6 +5530,7 @@ async fn status_line_branch_refreshes_after_interrupt() {
         id: "turn-1".into(),
         msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
             reason: TurnAbortReason::Interrupted,
+            budget: None,
         }),
     });
 
@@ -5854,6 +5988,7 @@ async fn review_queues_user_messages_snapshot() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: Some("current changes".to_string()),
+            paths: Vec::new(),
         }),
     });
     let _ = drain_insert_history(&mut rx);
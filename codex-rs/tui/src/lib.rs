@@ -93,6 +93,7 @@ mod render;
 mod resume_picker;
 mod selection_list;
 mod session_log;
+mod session_replay;
 mod shimmer;
 mod skills_helpers;
 mod slash_command;
@@ -122,6 +123,8 @@ pub use cli::Cli;
 pub use markdown_render::render_markdown_text;
 pub use public_widgets::composer_input::ComposerAction;
 pub use public_widgets::composer_input::ComposerInput;
+pub use session_replay::ReplayOptions;
+pub use session_replay::run_replay;
 // (tests access modules directly within the crate)
 
 pub async fn run_main(
@@ -14,6 +14,9 @@ use codex_core::ThreadSortKey;
 use codex_core::ThreadsPage;
 use codex_core::find_thread_names_by_ids;
 use codex_core::path_utils;
+use codex_core::state_db::ThreadSearchResult;
+use codex_core::state_db::open_if_present;
+use codex_core::state_db::search_threads_db;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -39,6 +42,7 @@ use codex_protocol::ThreadId;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const SEARCH_LIMIT: usize = 50;
 #[derive(Debug, Clone)]
 pub enum SessionSelection {
     StartFresh,
@@ -88,12 +92,25 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+#[derive(Clone)]
+struct SearchRequest {
+    query: String,
+    default_provider: String,
+    filter_cwd: Option<PathBuf>,
+}
+
+type SearchLoader = Arc<dyn Fn(SearchRequest) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    SearchLoaded {
+        query: String,
+        rows: Vec<Row>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -112,6 +129,10 @@ enum BackgroundEvent {
 /// 1. Provider and source filtering at the backend (only interactive CLI sessions
 ///    for the current model provider).
 /// 2. Working-directory filtering at the picker (unless `--all` is passed).
+///
+/// When the state database exists, typing a query also runs a full-text search
+/// over message bodies, commands and edited files, so sessions can be found by
+/// more than their first message.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     codex_home: &Path,
@@ -184,6 +205,38 @@ async fn run_session_picker(
         });
     });
 
+    let search_loader: Option<SearchLoader> = open_if_present(codex_home, &default_provider)
+        .await
+        .map(|state_db| {
+            let search_tx = bg_tx.clone();
+            Arc::new(move |request: SearchRequest| {
+                let tx = search_tx.clone();
+                let state_db = state_db.clone();
+                tokio::spawn(async move {
+                    let provider_filter = vec![request.default_provider];
+                    let results = search_threads_db(
+                        Some(state_db.as_ref()),
+                        &request.query,
+                        SEARCH_LIMIT,
+                        INTERACTIVE_SESSION_SOURCES,
+                        Some(provider_filter.as_slice()),
+                        request.filter_cwd.as_deref(),
+                        false,
+                    )
+                    .await;
+                    match results {
+                        Ok(results) => {
+                            let _ = tx.send(BackgroundEvent::SearchLoaded {
+                                query: request.query,
+                                rows: results.iter().map(search_result_to_row).collect(),
+                            });
+                        }
+                        Err(err) => tracing::warn!("session search failed: {err}"),
+                    }
+                });
+            }) as SearchLoader
+        });
+
     let mut state = PickerState::new(
         codex_home.to_path_buf(),
         alt.tui.frame_requester(),
@@ -193,6 +246,7 @@ async fn run_session_picker(
         filter_cwd,
         action,
     );
+    state.search_loader = search_loader;
     state.start_initial_load();
     state.request_frame();
 
@@ -273,6 +327,10 @@ struct PickerState {
     next_request_token: usize,
     next_search_token: usize,
     page_loader: PageLoader,
+    /// Full-text search over the state database, when it is available.
+    search_loader: Option<SearchLoader>,
+    /// Full-text matches for the current query, best first.
+    search_results: Vec<Row>,
     view_rows: Option<usize>,
     default_provider: String,
     show_all: bool,
@@ -341,6 +399,8 @@ struct Row {
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    /// Excerpt of the full-text match that made this row match the query.
+    search_snippet: Option<String>,
 }
 
 impl Row {
@@ -390,6 +450,8 @@ impl PickerState {
             next_request_token: 0,
             next_search_token: 0,
             page_loader,
+            search_loader: None,
+            search_results: Vec::new(),
             view_rows: None,
             default_provider,
             show_all,
@@ -532,6 +594,13 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::SearchLoaded { query, rows } => {
+                if query != self.query {
+                    return Ok(());
+                }
+                self.search_results = rows;
+                self.apply_filter();
+            }
         }
         Ok(())
     }
@@ -618,7 +687,32 @@ impl PickerState {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter.filter(|r| r.matches_query(&q)).cloned().collect();
+            let snippets: HashMap<&Path, &str> = self
+                .search_results
+                .iter()
+                .filter_map(|row| Some((row.path.as_path(), row.search_snippet.as_deref()?)))
+                .collect();
+            let mut filtered_rows: Vec<Row> = base_iter
+                .filter_map(|row| {
+                    if row.matches_query(&q) {
+                        return Some(row.clone());
+                    }
+                    let snippet = snippets.get(row.path.as_path())?;
+                    Some(Row {
+                        search_snippet: Some((*snippet).to_string()),
+                        ..row.clone()
+                    })
+                })
+                .collect();
+            // Full-text matches from sessions that have not been paged in yet.
+            filtered_rows.extend(
+                self.search_results
+                    .iter()
+                    .filter(|row| !self.seen_paths.contains(&row.path))
+                    .filter(|row| self.row_matches_filter(row))
+                    .cloned(),
+            );
+            self.filtered_rows = filtered_rows;
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -649,6 +743,20 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.search_results.clear();
+        if !self.query.is_empty()
+            && let Some(search_loader) = self.search_loader.as_ref()
+        {
+            search_loader(SearchRequest {
+                query: self.query.clone(),
+                default_provider: self.default_provider.clone(),
+                filter_cwd: if self.show_all {
+                    None
+                } else {
+                    self.filter_cwd.clone()
+                },
+            });
+        }
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
@@ -837,6 +945,36 @@ fn head_to_row(item: &ThreadItem) -> Row {
         updated_at,
         cwd: item.cwd.clone(),
         git_branch: item.git_branch.clone(),
+        search_snippet: None,
+    }
+}
+
+fn search_result_to_row(result: &ThreadSearchResult) -> Row {
+    let thread = &result.thread;
+    let preview = thread
+        .first_user_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| String::from("(no message yet)"));
+    let thread_name = Some(thread.title.trim())
+        .filter(|title| !title.is_empty() && *title != preview)
+        .map(str::to_string);
+
+    Row {
+        path: thread.rollout_path.clone(),
+        preview,
+        thread_id: Some(thread.id),
+        thread_name,
+        created_at: Some(thread.created_at),
+        updated_at: Some(thread.updated_at),
+        cwd: Some(thread.cwd.clone()),
+        git_branch: thread.git_branch.clone(),
+        search_snippet: result
+            .matches
+            .first()
+            .map(|search_match| search_match.snippet.replace('\n', " ")),
     }
 }
 
@@ -1017,7 +1155,15 @@ fn render_list(
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
-        let preview = truncate_text(row.display_preview(), preview_width);
+        // Leave room for the full-text match when the preview itself did not match.
+        let snippet_width = match row.search_snippet {
+            Some(_) => preview_width / 2,
+            None => 0,
+        };
+        let preview = truncate_text(
+            row.display_preview(),
+            preview_width.saturating_sub(snippet_width),
+        );
         let mut spans: Vec<Span> = vec![marker];
         if let Some(created) = created_span {
             spans.push(created);
@@ -1039,6 +1185,12 @@ fn render_list(
             spans.push("  ".into());
         }
         spans.push(preview.into());
+        if let Some(snippet) = row.search_snippet.as_deref()
+            && snippet_width > 2
+        {
+            spans.push("  ".into());
+            spans.push(truncate_text(snippet, snippet_width - 2).dim().italic());
+        }
 
         let line: Line = spans.into();
         let rect = Rect::new(area.x, y, area.width, 1);
@@ -1577,6 +1729,7 @@ mod tests {
             updated_at: None,
             cwd: None,
             git_branch: None,
+            search_snippet: None,
         };
 
         assert_eq!(row.display_preview(), "My session");
@@ -1611,6 +1764,7 @@ mod tests {
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1621,6 +1775,7 @@ mod tests {
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
@@ -1631,6 +1786,7 @@ mod tests {
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1888,6 +2044,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(2)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1898,6 +2055,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(3)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -2313,4 +2471,95 @@ mod tests {
         assert!(!state.search_state.is_active());
         assert!(state.pagination.reached_scan_cap);
     }
+
+    #[tokio::test]
+    async fn full_text_matches_extend_filtered_rows_with_snippets() {
+        let loader: PageLoader = Arc::new(|_| {});
+        let searched_queries: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let query_sink = searched_queries.clone();
+        let search_loader: SearchLoader = Arc::new(move |req: SearchRequest| {
+            query_sink.lock().unwrap().push(req.query);
+        });
+
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+        state.search_loader = Some(search_loader);
+        state.reset_pagination();
+        state.ingest_page(page(
+            vec![
+                make_item("/tmp/loaded.jsonl", "2025-01-02T00:00:00Z", "fix ci"),
+                make_item("/tmp/other.jsonl", "2025-01-01T00:00:00Z", "write docs"),
+            ],
+            None,
+            2,
+            false,
+        ));
+
+        state.set_query("flaky".to_string());
+        assert_eq!(*searched_queries.lock().unwrap(), vec!["flaky".to_string()]);
+        assert!(state.filtered_rows.is_empty());
+
+        let search_row = |path: &str, preview: &str, snippet: &str| Row {
+            path: PathBuf::from(path),
+            preview: preview.to_string(),
+            thread_id: None,
+            thread_name: None,
+            created_at: None,
+            updated_at: None,
+            cwd: None,
+            git_branch: None,
+            search_snippet: Some(snippet.to_string()),
+        };
+        state
+            .handle_background_event(BackgroundEvent::SearchLoaded {
+                query: "flaky".to_string(),
+                rows: vec![
+                    search_row("/tmp/loaded.jsonl", "fix ci", "the flaky login test"),
+                    search_row("/tmp/older.jsonl", "debug", "flaky retries"),
+                ],
+            })
+            .await
+            .unwrap();
+
+        let visible: Vec<_> = state
+            .filtered_rows
+            .iter()
+            .map(|row| (row.path.clone(), row.search_snippet.clone()))
+            .collect();
+        assert_eq!(
+            visible,
+            vec![
+                (
+                    PathBuf::from("/tmp/loaded.jsonl"),
+                    Some("the flaky login test".to_string())
+                ),
+                (
+                    PathBuf::from("/tmp/older.jsonl"),
+                    Some("flaky retries".to_string())
+                ),
+            ]
+        );
+
+        // Results for a stale query are ignored.
+        state.set_query("docs".to_string());
+        state
+            .handle_background_event(BackgroundEvent::SearchLoaded {
+                query: "flaky".to_string(),
+                rows: vec![search_row("/tmp/older.jsonl", "debug", "flaky retries")],
+            })
+            .await
+            .unwrap();
+        assert_eq!(state.filtered_rows.len(), 1);
+        assert_eq!(
+            state.filtered_rows[0].path,
+            PathBuf::from("/tmp/other.jsonl")
+        );
+    }
 }
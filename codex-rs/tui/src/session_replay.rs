@@ -0,0 +1,731 @@
+//! Playback of session logs recorded with `CODEX_TUI_RECORD_SESSION=1`.
+//!
+//! A log written by [`crate::session_log`] contains every `Event` the TUI
+//! received from core and every `Op` it sent back. Replay feeds the events
+//! into a `ChatWidget` whose op channel is not connected to an agent, so the
+//! session is re-rendered exactly as it was shown without contacting a model.
+//! Recorded approval answers are applied to the approval modal they resolved;
+//! typed input is not replayed because the resulting user messages are
+//! already part of the event stream.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+use chrono::DateTime;
+use codex_core::AuthManager;
+use codex_core::config::Config;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SessionSource;
+use codex_otel::OtelManager;
+use codex_protocol::ThreadId;
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::text::Line;
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::time::Instant;
+use tokio_stream::StreamExt;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::FeedbackAudience;
+use crate::chatwidget::ChatWidget;
+use crate::chatwidget::ChatWidgetInit;
+use crate::render::renderable::Renderable;
+use crate::tui;
+use crate::tui::FrameRequester;
+use crate::tui::Tui;
+use crate::tui::TuiEvent;
+
+/// Longest pause replay keeps between two recorded steps, before applying
+/// the speed factor. Keeps idle stretches of a recording from stalling demos.
+const MAX_STEP_DELAY: Duration = Duration::from_secs(2);
+
+/// Upper bound on commit ticks used to flush streamed output once the log
+/// ends, in case a recording stops in the middle of a stream.
+const MAX_FINISH_TICKS: usize = 10_000;
+
+/// How `codex replay` paces the recorded steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplayOptions {
+    /// Playback speed relative to the recording; `2.0` plays twice as fast.
+    pub speed: f64,
+    /// Start paused and advance one step per key press.
+    pub step: bool,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            step: false,
+        }
+    }
+}
+
+/// One replayable record of a session log.
+#[derive(Debug)]
+pub(crate) enum ReplayStep {
+    /// An event core sent to the TUI.
+    Event(Event),
+    /// The user's answer to an approval modal.
+    Decision(Op),
+    /// The user started a new session with `/new`.
+    NewSession,
+}
+
+/// The replayable part of a session log.
+#[derive(Debug, Default)]
+pub(crate) struct SessionRecording {
+    /// Model from the `session_start` header, if the log has one.
+    pub(crate) model: Option<String>,
+    /// Steps paired with their offset from the first step.
+    pub(crate) steps: Vec<(Duration, ReplayStep)>,
+    /// `codex_event` records whose payload does not parse as an `Event`,
+    /// typically because the log was recorded by a different Codex version.
+    pub(crate) skipped_events: usize,
+}
+
+impl SessionRecording {
+    pub(crate) fn parse(contents: &str) -> Result<Self> {
+        let mut recording = Self::default();
+        let mut start = None;
+        let mut offset = Duration::ZERO;
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Value = serde_json::from_str(line)
+                .wrap_err_with(|| format!("line {} is not valid JSON", index + 1))?;
+            let step = match record.get("kind").and_then(Value::as_str) {
+                Some("session_start") => {
+                    recording.model = record
+                        .get("model")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                    None
+                }
+                Some("codex_event") => {
+                    match record.get("payload").cloned().map(serde_json::from_value) {
+                        Some(Ok(event)) => Some(ReplayStep::Event(event)),
+                        _ => {
+                            recording.skipped_events += 1;
+                            None
+                        }
+                    }
+                }
+                Some("op") => record
+                    .get("payload")
+                    .cloned()
+                    .and_then(|payload| serde_json::from_value::<Op>(payload).ok())
+                    .filter(|op| {
+                        matches!(
+                            op,
+                            Op::ExecApproval { .. }
+                                | Op::PatchApproval { .. }
+                                | Op::ResolveElicitation { .. }
+                                | Op::NetworkApproval { .. }
+                        )
+                    })
+                    .map(ReplayStep::Decision),
+                Some("new_session") => Some(ReplayStep::NewSession),
+                _ => None,
+            };
+            let Some(step) = step else {
+                continue;
+            };
+            if let Some(ts) = record
+                .get("ts")
+                .and_then(Value::as_str)
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            {
+                let start = *start.get_or_insert(ts);
+                offset = (ts - start).to_std().unwrap_or(offset).max(offset);
+            }
+            recording.steps.push((offset, step));
+        }
+        Ok(recording)
+    }
+}
+
+/// Time to wait before a step recorded at `next`, when the previous step was
+/// recorded at `previous`.
+fn step_delay(previous: Duration, next: Duration, speed: f64) -> Duration {
+    next.saturating_sub(previous)
+        .min(MAX_STEP_DELAY)
+        .div_f64(speed)
+}
+
+/// Feeds recorded steps into a `ChatWidget` that is not connected to an
+/// agent and collects the history lines it would have inserted.
+pub(crate) struct ReplayRenderer {
+    config: Config,
+    model: Option<String>,
+    frame_requester: FrameRequester,
+    auth_manager: Arc<AuthManager>,
+    models_manager: Arc<ModelsManager>,
+    otel_manager: OtelManager,
+    placeholder: String,
+    chat_widget: ChatWidget,
+    app_event_rx: UnboundedReceiver<AppEvent>,
+    _op_rx: UnboundedReceiver<Op>,
+    commit_animation_running: bool,
+    has_emitted_history_lines: bool,
+}
+
+impl ReplayRenderer {
+    pub(crate) fn new(
+        config: Config,
+        model: Option<String>,
+        frame_requester: FrameRequester,
+        placeholder: String,
+    ) -> Self {
+        let auth_manager = AuthManager::shared(
+            config.codex_home.clone(),
+            false,
+            config.cli_auth_credentials_store_mode,
+        );
+        let models_manager = Arc::new(ModelsManager::new(
+            config.codex_home.clone(),
+            auth_manager.clone(),
+        ));
+        let model_slug = model
+            .clone()
+            .unwrap_or_else(|| ModelsManager::get_model_offline(config.model.as_deref()));
+        let otel_manager = OtelManager::new(
+            ThreadId::new(),
+            model_slug.as_str(),
+            model_slug.as_str(),
+            None,
+            None,
+            None,
+            codex_core::default_client::originator().value,
+            false,
+            codex_core::terminal::user_agent(),
+            SessionSource::Cli,
+        );
+        let (chat_widget, app_event_rx, op_rx) = Self::new_chat_widget(
+            &config,
+            model.clone(),
+            frame_requester.clone(),
+            auth_manager.clone(),
+            models_manager.clone(),
+            otel_manager.clone(),
+            placeholder.clone(),
+        );
+        Self {
+            config,
+            model,
+            frame_requester,
+            auth_manager,
+            models_manager,
+            otel_manager,
+            placeholder,
+            chat_widget,
+            app_event_rx,
+            _op_rx: op_rx,
+            commit_animation_running: false,
+            has_emitted_history_lines: false,
+        }
+    }
+
+    fn new_chat_widget(
+        config: &Config,
+        model: Option<String>,
+        frame_requester: FrameRequester,
+        auth_manager: Arc<AuthManager>,
+        models_manager: Arc<ModelsManager>,
+        otel_manager: OtelManager,
+        placeholder: String,
+    ) -> (
+        ChatWidget,
+        UnboundedReceiver<AppEvent>,
+        UnboundedReceiver<Op>,
+    ) {
+        let (app_event_tx, app_event_rx) = unbounded_channel();
+        let (op_tx, op_rx) = unbounded_channel();
+        let init = ChatWidgetInit {
+            config: config.clone(),
+            frame_requester,
+            app_event_tx: AppEventSender::new(app_event_tx),
+            initial_user_message: None,
+            enhanced_keys_supported: false,
+            auth_manager,
+            models_manager,
+            feedback: codex_feedback::CodexFeedback::new(),
+            is_first_run: false,
+            feedback_audience: FeedbackAudience::External,
+            model: model.or_else(|| config.model.clone()),
+            status_line_invalid_items_warned: Arc::new(AtomicBool::new(false)),
+            otel_manager,
+        };
+        let mut chat_widget = ChatWidget::new_with_op_sender(init, op_tx);
+        chat_widget.set_placeholder_text(placeholder);
+        (chat_widget, app_event_rx, op_rx)
+    }
+
+    /// Applies one step and returns the history lines it produced at `width`.
+    ///
+    /// Streamed output advances by one commit tick per step rather than on a
+    /// timer, so the rendering depends only on the recorded event order.
+    pub(crate) fn apply(&mut self, step: ReplayStep, width: u16) -> Vec<Line<'static>> {
+        match step {
+            ReplayStep::Event(event) => match event.msg {
+                EventMsg::ShutdownComplete => {}
+                // Live sessions render the prompt when it is submitted, which
+                // replay does not see; the replay path renders it from the event.
+                EventMsg::UserMessage(_) => self.chat_widget.handle_codex_event_replay(event),
+                _ => self.chat_widget.handle_codex_event(event),
+            },
+            ReplayStep::Decision(op) => self.chat_widget.apply_recorded_decision(&op),
+            ReplayStep::NewSession => {
+                let (chat_widget, app_event_rx, op_rx) = Self::new_chat_widget(
+                    &self.config,
+                    self.model.clone(),
+                    self.frame_requester.clone(),
+                    self.auth_manager.clone(),
+                    self.models_manager.clone(),
+                    self.otel_manager.clone(),
+                    self.placeholder.clone(),
+                );
+                self.chat_widget = chat_widget;
+                self.app_event_rx = app_event_rx;
+                self._op_rx = op_rx;
+                self.commit_animation_running = false;
+            }
+        }
+        let mut lines = self.drain_app_events(width);
+        if self.commit_animation_running {
+            self.chat_widget.on_commit_tick();
+            lines.extend(self.drain_app_events(width));
+        }
+        lines
+    }
+
+    /// Flushes output that was still streaming when the log ended.
+    pub(crate) fn finish(&mut self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for _ in 0..MAX_FINISH_TICKS {
+            if !self.commit_animation_running {
+                break;
+            }
+            self.chat_widget.on_commit_tick();
+            lines.extend(self.drain_app_events(width));
+        }
+        lines
+    }
+
+    /// Handles the app events a live `App` would act on while replaying and
+    /// returns the history lines to insert, separated the same way.
+    fn drain_app_events(&mut self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        while let Ok(event) = self.app_event_rx.try_recv() {
+            match event {
+                AppEvent::InsertHistoryCell(cell) => {
+                    let mut display = cell.display_lines(width);
+                    if display.is_empty() {
+                        continue;
+                    }
+                    if !cell.is_stream_continuation() {
+                        if self.has_emitted_history_lines {
+                            display.insert(0, Line::from(""));
+                        } else {
+                            self.has_emitted_history_lines = true;
+                        }
+                    }
+                    lines.extend(display);
+                }
+                AppEvent::StartCommitAnimation => self.commit_animation_running = true,
+                AppEvent::StopCommitAnimation => self.commit_animation_running = false,
+                _ => {}
+            }
+        }
+        lines
+    }
+
+    pub(crate) fn chat_widget(&self) -> &ChatWidget {
+        &self.chat_widget
+    }
+}
+
+/// Replays the session log at `log_path` in the terminal.
+///
+/// Space pauses and resumes playback, Enter or → applies the next step while
+/// paused, and `q` or Esc exits.
+pub async fn run_replay(
+    config: Config,
+    log_path: &Path,
+    options: ReplayOptions,
+) -> std::io::Result<()> {
+    if !options.speed.is_finite() || options.speed <= 0.0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "replay speed must be a positive number, got {}",
+                options.speed
+            ),
+        ));
+    }
+    let contents = std::fs::read_to_string(log_path)?;
+    let recording = SessionRecording::parse(&contents).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("failed to parse {}: {err}", log_path.display()),
+        )
+    })?;
+    let skipped_events = recording.skipped_events;
+    let name = log_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| log_path.display().to_string());
+
+    let mut terminal = tui::init()?;
+    terminal.clear()?;
+    let mut tui = Tui::new(terminal);
+    let result = replay_in_tui(&mut tui, config, recording, options, &name).await;
+    crate::restore();
+    if skipped_events > 0 {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!(
+                "Skipped {skipped_events} recorded events that this version of Codex cannot read."
+            );
+        }
+    }
+    result.map_err(|err| std::io::Error::other(err.to_string()))
+}
+
+async fn replay_in_tui(
+    tui: &mut Tui,
+    config: Config,
+    recording: SessionRecording,
+    options: ReplayOptions,
+    name: &str,
+) -> Result<()> {
+    let mut renderer = ReplayRenderer::new(
+        config,
+        recording.model,
+        tui.frame_requester(),
+        format!("Replaying {name} · space pause · enter step · q quit"),
+    );
+    let mut steps = recording.steps.into_iter().peekable();
+    let mut previous = Duration::ZERO;
+    let mut paused = options.step;
+    let mut next_at = None;
+
+    let events = tui.event_stream();
+    tokio::pin!(events);
+    tui.frame_requester().schedule_frame();
+
+    loop {
+        if next_at.is_none()
+            && !paused
+            && let Some((offset, _)) = steps.peek()
+        {
+            next_at = Some(Instant::now() + step_delay(previous, *offset, options.speed));
+        }
+
+        let mut advance = false;
+        tokio::select! {
+            event = events.next() => match event {
+                Some(TuiEvent::Key(key_event)) => match replay_key(key_event) {
+                    Some(ReplayKey::Quit) => break,
+                    Some(ReplayKey::TogglePause) => {
+                        paused = !paused;
+                        next_at = None;
+                    }
+                    Some(ReplayKey::Step) if paused => advance = true,
+                    Some(ReplayKey::Step) | None => {}
+                },
+                Some(TuiEvent::Draw) => {
+                    let width = tui.terminal.size()?.width;
+                    let chat_widget = renderer.chat_widget();
+                    tui.draw(chat_widget.desired_height(width), |frame| {
+                        chat_widget.render(frame.area(), frame.buffer);
+                    })?;
+                }
+                Some(TuiEvent::Paste(_)) => {}
+                None => break,
+            },
+            _ = tokio::time::sleep_until(next_at.unwrap_or_else(Instant::now)), if next_at.is_some() => {
+                advance = true;
+            }
+        }
+
+        if advance && let Some((offset, step)) = steps.next() {
+            next_at = None;
+            previous = offset;
+            let width = tui.terminal.size()?.width;
+            let mut lines = renderer.apply(step, width);
+            if steps.peek().is_none() {
+                lines.extend(renderer.finish(width));
+            }
+            tui.insert_history_lines(lines);
+            tui.frame_requester().schedule_frame();
+        }
+    }
+    tui.terminal.clear()?;
+    Ok(())
+}
+
+enum ReplayKey {
+    Quit,
+    TogglePause,
+    Step,
+}
+
+fn replay_key(key_event: KeyEvent) -> Option<ReplayKey> {
+    if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+        return None;
+    }
+    match key_event.code {
+        KeyCode::Char('q') | KeyCode::Esc => Some(ReplayKey::Quit),
+        KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(ReplayKey::Quit)
+        }
+        KeyCode::Char(' ') => Some(ReplayKey::TogglePause),
+        KeyCode::Enter | KeyCode::Right | KeyCode::Char('n') => Some(ReplayKey::Step),
+        _ => None,
+    }
+}
+
+/// Replays `recording` into a VT100 terminal of the given size and
+/// returns the final screen, so a session log attached to a bug report
+/// can be turned into a snapshot test.
+#[cfg(test)]
+pub(crate) async fn replay_to_screen(
+    recording: SessionRecording,
+    width: u16,
+    height: u16,
+) -> String {
+    use crate::custom_terminal::Terminal;
+    use crate::insert_history::insert_history_lines;
+    use crate::test_backend::VT100Backend;
+    use codex_core::config::ConfigBuilder;
+    use ratatui::layout::Rect;
+
+    let codex_home = tempfile::tempdir().expect("tempdir");
+    let config = ConfigBuilder::default()
+        .codex_home(codex_home.path().to_path_buf())
+        .build()
+        .await
+        .expect("config");
+    let mut renderer = ReplayRenderer::new(
+        config,
+        recording.model,
+        FrameRequester::test_dummy(),
+        "Replaying session".to_string(),
+    );
+    let mut history = Vec::new();
+    for (_, step) in recording.steps {
+        history.extend(renderer.apply(step, width));
+    }
+    history.extend(renderer.finish(width));
+
+    let ui_height = renderer.chat_widget().desired_height(width).min(height);
+    let mut term = Terminal::with_options(VT100Backend::new(width, height)).expect("terminal");
+    term.set_viewport_area(Rect::new(0, height - ui_height, width, ui_height));
+    insert_history_lines(&mut term, history).expect("insert history");
+    term.draw(|f| renderer.chat_widget().render(f.area(), f.buffer_mut()))
+        .expect("draw");
+    term.backend().vt100().screen().contents()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageDeltaEvent;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::AskForApproval;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use codex_core::protocol::ReviewDecision;
+    use codex_core::protocol::SandboxPolicy;
+    use codex_core::protocol::SessionConfiguredEvent;
+    use codex_core::protocol::TurnCompleteEvent;
+    use codex_core::protocol::TurnStartedEvent;
+    use codex_core::protocol::UserMessageEvent;
+    use codex_protocol::config_types::ModeKind;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn record(ts: &str, dir: &str, kind: &str, payload: impl serde::Serialize) -> String {
+        json!({ "ts": ts, "dir": dir, "kind": kind, "payload": payload }).to_string()
+    }
+
+    fn event(msg: EventMsg) -> Event {
+        Event {
+            id: "turn-1".to_string(),
+            msg,
+        }
+    }
+
+    fn session_log() -> String {
+        let configured = SessionConfiguredEvent {
+            session_id: ThreadId::new(),
+            forked_from_id: None,
+            thread_name: None,
+            model: "gpt-test".to_string(),
+            model_provider_id: "test-provider".to_string(),
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            cwd: PathBuf::from("/home/user/project"),
+            reasoning_effort: None,
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: None,
+            rollout_path: None,
+        };
+        let lines = [
+            json!({
+                "ts": "2026-01-05T10:00:00.000Z",
+                "dir": "meta",
+                "kind": "session_start",
+                "model": "gpt-test",
+            })
+            .to_string(),
+            record(
+                "2026-01-05T10:00:00.100Z",
+                "to_tui",
+                "codex_event",
+                event(EventMsg::SessionConfigured(configured)),
+            ),
+            json!({
+                "ts": "2026-01-05T10:00:00.200Z",
+                "dir": "to_tui",
+                "kind": "app_event",
+                "variant": "RequestRedraw",
+            })
+            .to_string(),
+            record("2026-01-05T10:00:03.000Z", "from_tui", "op", Op::Interrupt),
+            record(
+                "2026-01-05T10:00:03.100Z",
+                "to_tui",
+                "codex_event",
+                event(EventMsg::UserMessage(UserMessageEvent {
+                    message: "say hi from the log".to_string(),
+                    images: None,
+                    text_elements: Vec::new(),
+                    local_images: Vec::new(),
+                })),
+            ),
+            record(
+                "2026-01-05T10:00:03.200Z",
+                "to_tui",
+                "codex_event",
+                event(EventMsg::TurnStarted(TurnStartedEvent {
+                    model_context_window: None,
+                    collaboration_mode_kind: ModeKind::Default,
+                })),
+            ),
+            record(
+                "2026-01-05T10:00:04.000Z",
+                "to_tui",
+                "codex_event",
+                event(EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                    call_id: "call-1".to_string(),
+                    turn_id: "turn-1".to_string(),
+                    command: vec!["echo".to_string(), "hi".to_string()],
+                    cwd: PathBuf::from("/home/user/project"),
+                    reason: None,
+                    proposed_execpolicy_amendment: None,
+                    parsed_cmd: Vec::new(),
+                })),
+            ),
+            record(
+                "2026-01-05T10:00:09.000Z",
+                "from_tui",
+                "op",
+                Op::ExecApproval {
+                    id: "turn-1".to_string(),
+                    decision: ReviewDecision::Approved,
+                },
+            ),
+            record(
+                "2026-01-05T10:00:09.500Z",
+                "to_tui",
+                "codex_event",
+                json!({ "id": "turn-1", "msg": { "type": "event_from_the_future" } }),
+            ),
+            record(
+                "2026-01-05T10:00:10.000Z",
+                "to_tui",
+                "codex_event",
+                event(EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                    delta: "Replayed answer\n".to_string(),
+                })),
+            ),
+            record(
+                "2026-01-05T10:00:10.100Z",
+                "to_tui",
+                "codex_event",
+                event(EventMsg::AgentMessage(AgentMessageEvent {
+                    message: "Replayed answer".to_string(),
+                })),
+            ),
+            record(
+                "2026-01-05T10:00:10.200Z",
+                "to_tui",
+                "codex_event",
+                event(EventMsg::TurnComplete(TurnCompleteEvent {
+                    last_agent_message: Some("Replayed answer".to_string()),
+                })),
+            ),
+        ];
+        lines.join("\n")
+    }
+
+    #[test]
+    fn parses_replayable_records_with_offsets() {
+        let recording = SessionRecording::parse(&session_log()).expect("parse");
+
+        assert_eq!(recording.model.as_deref(), Some("gpt-test"));
+        assert_eq!(recording.skipped_events, 1);
+        let offsets: Vec<u128> = recording
+            .steps
+            .iter()
+            .map(|(offset, _)| offset.as_millis())
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![0, 3000, 3100, 3900, 8900, 9900, 10_000, 10_100]
+        );
+        assert!(matches!(
+            recording.steps[4].1,
+            ReplayStep::Decision(Op::ExecApproval { .. })
+        ));
+    }
+
+    #[test]
+    fn step_delay_caps_idle_gaps_and_applies_speed() {
+        let previous = Duration::from_millis(500);
+        assert_eq!(
+            step_delay(previous, Duration::from_millis(1500), 2.0),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            step_delay(previous, Duration::from_secs(60), 1.0),
+            MAX_STEP_DELAY
+        );
+        assert_eq!(step_delay(previous, previous, 1.0), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn replays_log_into_vt100_terminal() {
+        let recording = SessionRecording::parse(&session_log()).expect("parse");
+
+        let screen = replay_to_screen(recording, 80, 40).await;
+
+        insta::assert_snapshot!(screen);
+    }
+}
@@ -26,6 +26,7 @@ use super::format::FieldFormatter;
 use super::format::line_display_width;
 use super::format::push_label;
 use super::format::truncate_line_to_width;
+use super::helpers::SessionCost;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
 use super::helpers::compose_model_display;
@@ -54,6 +55,7 @@ pub(crate) struct StatusTokenUsageData {
     total: i64,
     input: i64,
     output: i64,
+    cost: Option<SessionCost>,
     context_window: Option<StatusContextWindowData>,
 }
 
@@ -81,6 +83,7 @@ pub(crate) fn new_status_output(
     auth_manager: &AuthManager,
     token_info: Option<&TokenUsageInfo>,
     total_usage: &TokenUsage,
+    session_cost: Option<SessionCost>,
     session_id: &Option<ThreadId>,
     thread_name: Option<String>,
     forked_from: Option<ThreadId>,
@@ -97,6 +100,7 @@ pub(crate) fn new_status_output(
         auth_manager,
         token_info,
         total_usage,
+        session_cost,
         session_id,
         thread_name,
         forked_from,
@@ -118,6 +122,7 @@ impl StatusHistoryCell {
         auth_manager: &AuthManager,
         token_info: Option<&TokenUsageInfo>,
         total_usage: &TokenUsage,
+        session_cost: Option<SessionCost>,
         session_id: &Option<ThreadId>,
         thread_name: Option<String>,
         forked_from: Option<ThreadId>,
@@ -187,6 +192,7 @@ impl StatusHistoryCell {
             total: total_usage.blended_total(),
             input: total_usage.non_cached_input(),
             output: total_usage.output_tokens,
+            cost: session_cost,
             context_window,
         };
         let rate_limits = compose_rate_limit_data(rate_limits, now);
@@ -403,6 +409,9 @@ impl HistoryCell for StatusHistoryCell {
             push_label(&mut labels, &mut seen, "Collaboration mode");
         }
         push_label(&mut labels, &mut seen, "Token usage");
+        if self.token_usage.cost.is_some() {
+            push_label(&mut labels, &mut seen, "Estimated cost");
+        }
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
@@ -470,6 +479,9 @@ impl HistoryCell for StatusHistoryCell {
         // Hide token usage only for ChatGPT subscribers
         if !matches!(self.account, Some(StatusAccountDisplay::ChatGpt { .. })) {
             lines.push(formatter.line("Token usage", self.token_usage_spans()));
+            if let Some(cost) = self.token_usage.cost {
+                lines.push(formatter.line("Estimated cost", vec![Span::from(cost.display())]));
+            }
         }
 
         if let Some(spans) = self.context_window_spans() {
@@ -7,6 +7,7 @@ use codex_core::auth::AuthMode as CoreAuthMode;
 use codex_core::config::Config;
 use codex_core::project_doc::discover_project_doc_paths;
 use codex_protocol::account::PlanType;
+use codex_protocol::protocol::TokenUsageInfo;
 use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
@@ -143,6 +144,43 @@ pub(crate) fn format_tokens_compact(value: i64) -> String {
     format!("{formatted}{suffix}")
 }
 
+/// A thread's estimated cost, as reported by core.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SessionCost {
+    Known(f64),
+    /// Some of the thread's usage was on a model without a price.
+    Unknown,
+}
+
+impl SessionCost {
+    /// Interpret a token count update. Core leaves the estimate out once any
+    /// usage was on a model without a price, so a thread that used tokens but
+    /// has no estimate has an unknown cost.
+    pub(crate) fn from_token_count(
+        total_cost_usd: Option<f64>,
+        info: Option<&TokenUsageInfo>,
+    ) -> Option<Self> {
+        match total_cost_usd {
+            Some(cost_usd) => Some(SessionCost::Known(cost_usd)),
+            None if info.is_some_and(|info| info.total_token_usage.total_tokens > 0) => {
+                Some(SessionCost::Unknown)
+            }
+            None => None,
+        }
+    }
+
+    /// Format the cost in US dollars, keeping small amounts visible.
+    pub(crate) fn display(self) -> String {
+        match self {
+            SessionCost::Known(cost_usd) if cost_usd > 0.0 && cost_usd < 0.01 => {
+                "<$0.01".to_string()
+            }
+            SessionCost::Known(cost_usd) => format!("${cost_usd:.2}"),
+            SessionCost::Unknown => "price unknown".to_string(),
+        }
+    }
+}
+
 pub(crate) fn format_directory_display(directory: &Path, max_width: Option<usize>) -> String {
     let formatted = if let Some(rel) = relativize_to_home(directory) {
         if rel.as_os_str().is_empty() {
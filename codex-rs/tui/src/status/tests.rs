@@ -1,3 +1,4 @@
+use super::SessionCost;
 use super::new_status_output;
 use super::rate_limit_snapshot_display;
 use crate::history_cell::HistoryCell;
@@ -18,6 +19,7 @@ use codex_protocol::ThreadId;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::openai_models::ReasoningEffort;
 use insta::assert_snapshot;
+use pretty_assertions::assert_eq;
 use ratatui::prelude::*;
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -146,6 +148,7 @@ async fn status_snapshot_includes_reasoning_details() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -200,6 +203,7 @@ async fn status_snapshot_includes_forked_from() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &Some(session_id),
         None,
         Some(forked_from),
@@ -260,6 +264,7 @@ async fn status_snapshot_includes_monthly_limit() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -308,6 +313,7 @@ async fn status_snapshot_shows_unlimited_credits() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -355,6 +361,7 @@ async fn status_snapshot_shows_positive_credits() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -402,6 +409,7 @@ async fn status_snapshot_hides_zero_credits() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -447,6 +455,7 @@ async fn status_snapshot_hides_when_has_no_credits_flag() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -492,6 +501,7 @@ async fn status_card_token_usage_excludes_cached_tokens() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -510,6 +520,107 @@ async fn status_card_token_usage_excludes_cached_tokens() {
     );
 }
 
+#[tokio::test]
+async fn status_card_shows_estimated_cost() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage {
+        input_tokens: 1_200,
+        cached_input_tokens: 200,
+        output_tokens: 900,
+        reasoning_output_tokens: 0,
+        total_tokens: 2_100,
+    };
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        .single()
+        .expect("timestamp");
+
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let token_info = token_info_for(&model_slug, &config, &usage);
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        Some(&token_info),
+        &usage,
+        Some(SessionCost::Known(1.234)),
+        &None,
+        None,
+        None,
+        None,
+        None,
+        now,
+        &model_slug,
+        None,
+        None,
+    );
+    let rendered = render_lines(&composite.display_lines(120));
+
+    assert!(
+        rendered
+            .iter()
+            .any(|line| line.contains("Estimated cost") && line.contains("$1.23")),
+        "estimated cost should be displayed, got: {rendered:?}"
+    );
+}
+
+#[tokio::test]
+async fn status_card_reports_unknown_price() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("local-model".to_string());
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage {
+        input_tokens: 1_200,
+        cached_input_tokens: 0,
+        output_tokens: 900,
+        reasoning_output_tokens: 0,
+        total_tokens: 2_100,
+    };
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        .single()
+        .expect("timestamp");
+
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let token_info = token_info_for(&model_slug, &config, &usage);
+    // Core omits the estimate once any usage was on an unpriced model.
+    let session_cost = SessionCost::from_token_count(None, Some(&token_info));
+    assert_eq!(session_cost, Some(SessionCost::Unknown));
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        Some(&token_info),
+        &usage,
+        session_cost,
+        &None,
+        None,
+        None,
+        None,
+        None,
+        now,
+        &model_slug,
+        None,
+        None,
+    );
+    let rendered = render_lines(&composite.display_lines(120));
+
+    assert!(
+        rendered
+            .iter()
+            .any(|line| line.contains("Estimated cost") && line.contains("price unknown")),
+        "unknown price should be displayed, got: {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn status_snapshot_truncates_in_narrow_terminal() {
     let temp_home = TempDir::new().expect("temp home");
@@ -552,6 +663,7 @@ async fn status_snapshot_truncates_in_narrow_terminal() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -601,6 +713,7 @@ async fn status_snapshot_shows_missing_limits_message() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -668,6 +781,7 @@ async fn status_snapshot_includes_credits_and_limits() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -723,6 +837,7 @@ async fn status_snapshot_shows_empty_limits_message() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -787,6 +902,7 @@ async fn status_snapshot_shows_stale_limits_message() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -855,6 +971,7 @@ async fn status_snapshot_cached_limits_hide_credits_without_flag() {
         &auth_manager,
         Some(&token_info),
         &usage,
+        None,
         &None,
         None,
         None,
@@ -913,6 +1030,7 @@ async fn status_context_window_uses_last_usage() {
         &auth_manager,
         Some(&token_info),
         &total_usage,
+        None,
         &None,
         None,
         None,
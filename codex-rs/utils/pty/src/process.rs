@@ -38,6 +38,7 @@ pub struct ProcessHandle {
     wait_handle: StdMutex<Option<JoinHandle<()>>>,
     exit_status: Arc<AtomicBool>,
     exit_code: Arc<StdMutex<Option<i32>>>,
+    pid: Option<u32>,
     // PtyHandles must be preserved because the process will receive Control+C if the
     // slave is closed
     _pty_handles: StdMutex<Option<PtyHandles>>,
@@ -62,6 +63,7 @@ impl ProcessHandle {
         wait_handle: JoinHandle<()>,
         exit_status: Arc<AtomicBool>,
         exit_code: Arc<StdMutex<Option<i32>>>,
+        pid: Option<u32>,
         pty_handles: Option<PtyHandles>,
     ) -> (Self, broadcast::Receiver<Vec<u8>>) {
         (
@@ -75,6 +77,7 @@ impl ProcessHandle {
                 wait_handle: StdMutex::new(Some(wait_handle)),
                 exit_status,
                 exit_code,
+                pid,
                 _pty_handles: StdMutex::new(pty_handles),
             },
             initial_output_rx,
@@ -101,6 +104,11 @@ impl ProcessHandle {
         self.exit_code.lock().ok().and_then(|guard| *guard)
     }
 
+    /// Returns the OS process id of the child, if the platform reports one.
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
     /// Attempts to kill the child and abort helper tasks.
     pub fn terminate(&self) {
         if let Ok(mut killer_opt) = self.killer.lock() {
@@ -86,6 +86,7 @@ pub async fn spawn_process(
     }
 
     let mut child = pair.slave.spawn_command(command_builder)?;
+    let pid = child.process_id();
     let killer = child.clone_killer();
 
     let (writer_tx, mut writer_rx) = mpsc::channel::<Vec<u8>>(128);
@@ -163,6 +164,7 @@ pub async fn spawn_process(
         wait_handle,
         exit_status,
         exit_code,
+        pid,
         Some(handles),
     );
 
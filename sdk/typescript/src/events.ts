@@ -25,6 +25,8 @@ export type Usage = {
   cached_input_tokens: number;
   /** The number of output tokens used during the turn. */
   output_tokens: number;
+  /** Estimated cost of the thread so far in US dollars. Absent when any model used in the thread has no known price. */
+  cost_usd?: number;
 };
 
 /** Emitted when a turn is completed. Typically right after the assistant's response. */
@@ -33,10 +35,21 @@ export type TurnCompletedEvent = {
   usage: Usage;
 };
 
+/** A `[budget]` limit from `config.toml` that ran out. */
+export type BudgetExceeded = {
+  kind: "turns" | "total_tokens" | "tool_calls" | "duration";
+  /** The configured limit, in turns, tokens, tool calls or seconds. */
+  limit: number;
+  /** The amount used when the turn stopped, in the same unit. */
+  used: number;
+};
+
 /** Indicates that a turn failed with an error. */
 export type TurnFailedEvent = {
   type: "turn.failed";
   error: ThreadError;
+  /** The session budget that stopped the turn, when that is why it failed. */
+  budget?: BudgetExceeded;
 };
 
 /** Emitted when a new item is added to the thread. Typically the item is initially "in progress". */
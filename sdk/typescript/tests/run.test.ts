@@ -44,6 +44,7 @@ describe("Codex", () => {
         cached_input_tokens: 12,
         input_tokens: 42,
         output_tokens: 5,
+        cost_usd: expect.any(Number),
       });
       expect(thread.id).toEqual(expect.any(String));
     } finally {
@@ -55,6 +55,7 @@ describe("Codex", () => {
             cached_input_tokens: 12,
             input_tokens: 42,
             output_tokens: 5,
+            cost_usd: expect.any(Number),
           },
         },
       ]);